@@ -3,14 +3,14 @@
 //! Run with: cargo run --example basic_usage
 
 use chasm_cli::models::ChatSession;
-use chasm_cli::workspace::{discover_workspaces, find_workspace_by_path};
+use chasm_cli::workspace::{discover_workspaces, find_workspace_by_path, DiscoveryOptions};
 
 fn main() -> anyhow::Result<()> {
     println!("=== CSM Basic Usage Examples ===\n");
 
     // Example 1: Discover all workspaces
     println!("1. Discovering all VS Code workspaces...");
-    let workspaces = discover_workspaces()?;
+    let workspaces = discover_workspaces(&DiscoveryOptions::default())?;
     println!("   Found {} workspaces", workspaces.len());
 
     // Show first 5 workspaces
@@ -46,7 +46,7 @@ fn main() -> anyhow::Result<()> {
     println!("\n3. Finding workspace by exact path...");
     if let Some(first_ws) = workspaces.first() {
         if let Some(path) = &first_ws.project_path {
-            match find_workspace_by_path(path) {
+            match find_workspace_by_path(path, &DiscoveryOptions::default()) {
                 Ok(Some((hash, _, _))) => {
                     println!("   Found workspace: {}...", &hash[..16]);
                 }
@@ -4,6 +4,7 @@
 
 use chasm_cli::workspace::{
     discover_workspaces, find_all_workspaces_for_project, get_chat_sessions_from_workspace,
+    DiscoveryOptions,
 };
 use std::collections::HashMap;
 
@@ -12,7 +13,8 @@ fn main() -> anyhow::Result<()> {
 
     // Example 1: Find all workspaces for a project (across renames/moves)
     println!("1. Finding all workspaces for 'copilot_chat_relink'...");
-    let project_workspaces = find_all_workspaces_for_project("copilot_chat_relink")?;
+    let project_workspaces =
+        find_all_workspaces_for_project("copilot_chat_relink", &DiscoveryOptions::default())?;
 
     println!(
         "   Found {} workspace instance(s):",
@@ -53,7 +55,7 @@ fn main() -> anyhow::Result<()> {
 
     // Example 3: Analyze workspace distribution
     println!("\n3. Analyzing workspace distribution...");
-    let all_workspaces = discover_workspaces()?;
+    let all_workspaces = discover_workspaces(&DiscoveryOptions::default())?;
 
     let mut by_session_count: HashMap<usize, usize> = HashMap::new();
     for ws in &all_workspaces {
@@ -73,7 +75,7 @@ fn main() -> anyhow::Result<()> {
     // Example 4: Find workspaces with most sessions
     println!("\n4. Top workspaces by session count...");
     let mut sorted_workspaces = all_workspaces.clone();
-    sorted_workspaces.sort_by(|a, b| b.chat_session_count.cmp(&a.chat_session_count));
+    sorted_workspaces.sort_by_key(|ws| std::cmp::Reverse(ws.chat_session_count));
 
     for ws in sorted_workspaces.iter().take(5) {
         if ws.chat_session_count > 0 {
@@ -17,7 +17,7 @@ use chasm_cli::providers::openai_compat::OpenAICompatProvider;
 use chasm_cli::providers::{
     ChatProvider, CsmConfig, GenericMessage, GenericSession, ProviderConfig, ProviderType,
 };
-use chasm_cli::workspace::discover_workspaces;
+use chasm_cli::workspace::{discover_workspaces, DiscoveryOptions};
 
 fn main() -> anyhow::Result<()> {
     println!("=== CSM Local Provider Examples ===\n");
@@ -85,7 +85,7 @@ fn main() -> anyhow::Result<()> {
     // ========================================================================
     println!("\n3. VS Code Copilot Chat sessions:");
 
-    let workspaces = discover_workspaces()?;
+    let workspaces = discover_workspaces(&DiscoveryOptions::default())?;
     let mut total_sessions = 0;
 
     for ws in workspaces.iter().take(5) {
@@ -225,6 +225,10 @@ fn main() -> anyhow::Result<()> {
             lmstudio_cfg,
         ],
         auto_discover: true,
+        editor_cmd: None,
+        redact_patterns: Vec::new(),
+        exclude_globs: Vec::new(),
+        saved_searches: Default::default(),
     };
 
     println!("   Default provider: {:?}", config.default_provider);
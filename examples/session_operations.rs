@@ -3,7 +3,9 @@
 //! Run with: cargo run --example session_operations
 
 use chasm_cli::models::{ChatMessage, ChatRequest, ChatSession};
-use chasm_cli::workspace::{find_workspace_by_path, get_chat_sessions_from_workspace};
+use chasm_cli::workspace::{
+    find_workspace_by_path, get_chat_sessions_from_workspace, DiscoveryOptions,
+};
 use uuid::Uuid;
 
 fn main() -> anyhow::Result<()> {
@@ -29,7 +31,7 @@ fn main() -> anyhow::Result<()> {
     println!("\n3. Reading sessions from workspace...");
     // Try to find a workspace with sessions
     let test_path = std::env::current_dir()?;
-    match find_workspace_by_path(&test_path.to_string_lossy()) {
+    match find_workspace_by_path(&test_path.to_string_lossy(), &DiscoveryOptions::default()) {
         Ok(Some((hash, ws_dir, _))) => {
             println!("   Found workspace: {}...", &hash[..16]);
             let sessions = get_chat_sessions_from_workspace(&ws_dir)?;
@@ -121,6 +123,8 @@ fn create_sample_session() -> ChatSession {
             code_citations: None,
             response_markdown_info: None,
             source_session: None,
+            branches: Vec::new(),
+            selected_branch: None,
         }],
     }
 }
@@ -148,6 +152,8 @@ fn create_session_with_requests(title: &str, messages: Vec<(&str, i64)>) -> Chat
             code_citations: None,
             response_markdown_info: None,
             source_session: None,
+            branches: Vec::new(),
+            selected_branch: None,
         })
         .collect();
 
@@ -3,7 +3,7 @@
 //! Run with: cargo run --example storage_operations
 
 use chasm_cli::storage::{is_vscode_running, read_chat_session_index};
-use chasm_cli::workspace::discover_workspaces;
+use chasm_cli::workspace::{discover_workspaces, DiscoveryOptions};
 use std::path::PathBuf;
 
 fn main() -> anyhow::Result<()> {
@@ -25,7 +25,7 @@ fn main() -> anyhow::Result<()> {
 
     // Example 3: Read session index from database
     println!("\n3. Reading session index from workspace database...");
-    let workspaces = discover_workspaces()?;
+    let workspaces = discover_workspaces(&DiscoveryOptions::default())?;
 
     // Find a workspace with sessions
     if let Some(ws) = workspaces.iter().find(|w| w.chat_session_count > 0) {
@@ -108,8 +108,12 @@ fn lookup_workspace_path(workspace_hash: &str) -> Option<String> {
     if workspace_storage.exists() {
         if let Ok(content) = std::fs::read_to_string(&workspace_storage) {
             if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                // Extract folder path from workspace.json
-                if let Some(folder) = json.get("folder").and_then(|f| f.as_str()) {
+                // Extract folder path (or multi-root .code-workspace file) from workspace.json
+                if let Some(folder) = json
+                    .get("folder")
+                    .or_else(|| json.get("workspace"))
+                    .and_then(|f| f.as_str())
+                {
                     // Decode file:// URL
                     let path = folder
                         .strip_prefix("file:///")
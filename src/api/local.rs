@@ -0,0 +1,193 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Read-only JSON API over VS Code chat storage (`serve`)
+//!
+//! Unlike the database-backed server in this module, nothing is harvested
+//! first: every request runs workspace discovery and reads session files
+//! straight from disk, so responses always match what `list` and `show` see.
+
+use actix_web::http::StatusCode;
+use actix_web::{middleware, web, App, HttpResponse, HttpServer};
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::net::IpAddr;
+
+use crate::commands::find_session_workspace;
+use crate::error::CsmError;
+use crate::providers::session_format::TemplateContext;
+use crate::storage::parse_session_json;
+use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace, DiscoveryOptions};
+
+/// Address `serve` listens on, and how it discovers workspaces
+#[derive(Debug, Clone)]
+pub struct LocalServerConfig {
+    pub host: String,
+    pub port: u16,
+    pub discovery: DiscoveryOptions,
+}
+
+/// Whether `host` only accepts connections from this machine
+pub fn is_loopback(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Label a session's workspace the way exports do: project path, else hash
+fn workspace_label(project_path: Option<&str>, hash: &str) -> String {
+    project_path.unwrap_or(hash).to_string()
+}
+
+fn error(status: StatusCode, message: impl std::fmt::Display) -> (StatusCode, Value) {
+    (status, json!({ "error": message.to_string() }))
+}
+
+/// A 500 response, with the error logged since the client only sees the message
+fn internal_error(e: impl std::fmt::Display) -> (StatusCode, Value) {
+    eprintln!("[ERROR] {:#}", e);
+    error(StatusCode::INTERNAL_SERVER_ERROR, e)
+}
+
+/// `GET /workspaces`
+fn workspaces_json(discovery: &DiscoveryOptions) -> Result<Value> {
+    let workspaces: Vec<Value> = discover_workspaces(discovery)?
+        .into_iter()
+        .map(|ws| {
+            json!({
+                "hash": ws.hash,
+                "project_path": ws.project_path,
+                "session_count": ws.chat_session_count,
+                "last_modified": ws.last_modified.map(|t| t.to_rfc3339()),
+            })
+        })
+        .collect();
+    Ok(Value::Array(workspaces))
+}
+
+/// `GET /workspaces/{hash}/sessions`
+fn workspace_sessions_json(hash: &str, discovery: &DiscoveryOptions) -> Result<Option<Value>> {
+    let Some(ws) = discover_workspaces(discovery)?
+        .into_iter()
+        .find(|ws| ws.hash == hash)
+    else {
+        return Ok(None);
+    };
+    let sessions: Vec<Value> = get_chat_sessions_from_workspace(&ws.workspace_path)?
+        .into_iter()
+        .map(|s| {
+            json!({
+                "session_id": s.session.session_id,
+                "title": s.session.title(),
+                "messages": s.session.request_count(),
+                "last_message_date": s.session.last_message_date,
+                "file": s.path.file_name().map(|n| n.to_string_lossy().to_string()),
+            })
+        })
+        .collect();
+    Ok(Some(Value::Array(sessions)))
+}
+
+/// `GET /sessions/{id}`: the normalized session, as `export --dump-context` prints it
+fn session_json(session_id: &str, discovery: &DiscoveryOptions) -> Result<Value> {
+    let (_, ws, path) = find_session_workspace(session_id, discovery)?;
+    let content = std::fs::read_to_string(&path)?;
+    let session = parse_session_json(&content)
+        .with_context(|| format!("Failed to parse session: {}", path.display()))?;
+    let workspace = workspace_label(ws.project_path.as_deref(), &ws.hash);
+    Ok(serde_json::to_value(TemplateContext::new(
+        &session, &workspace,
+    ))?)
+}
+
+/// Run a disk-bound lookup off the async workers and turn it into a response
+async fn respond<F>(lookup: F) -> HttpResponse
+where
+    F: FnOnce() -> (StatusCode, Value) + Send + 'static,
+{
+    let (status, body) = web::block(lookup).await.unwrap_or_else(internal_error);
+    HttpResponse::build(status).json(body)
+}
+
+async fn list_workspaces(discovery: web::Data<DiscoveryOptions>) -> HttpResponse {
+    respond(move || match workspaces_json(&discovery) {
+        Ok(body) => (StatusCode::OK, body),
+        Err(e) => internal_error(e),
+    })
+    .await
+}
+
+async fn list_workspace_sessions(
+    path: web::Path<String>,
+    discovery: web::Data<DiscoveryOptions>,
+) -> HttpResponse {
+    let hash = path.into_inner();
+    respond(move || match workspace_sessions_json(&hash, &discovery) {
+        Ok(Some(body)) => (StatusCode::OK, body),
+        Ok(None) => error(
+            StatusCode::NOT_FOUND,
+            format!("Workspace not found: {}", hash),
+        ),
+        Err(e) => internal_error(e),
+    })
+    .await
+}
+
+async fn get_session(
+    path: web::Path<String>,
+    discovery: web::Data<DiscoveryOptions>,
+) -> HttpResponse {
+    let session_id = path.into_inner();
+    respond(move || match session_json(&session_id, &discovery) {
+        Ok(body) => (StatusCode::OK, body),
+        Err(e) if matches!(e.downcast_ref(), Some(CsmError::SessionNotFound(_))) => {
+            error(StatusCode::NOT_FOUND, e)
+        }
+        Err(e) => internal_error(e),
+    })
+    .await
+}
+
+fn configure_local_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/workspaces", web::get().to(list_workspaces))
+        .route(
+            "/workspaces/{hash}/sessions",
+            web::get().to(list_workspace_sessions),
+        )
+        .route("/sessions/{id}", web::get().to(get_session));
+}
+
+/// Serve the read-only JSON API until interrupted
+pub async fn start_local_server(config: LocalServerConfig) -> Result<()> {
+    use colored::Colorize;
+
+    if !is_loopback(&config.host) {
+        eprintln!(
+            "{} Listening on {}: anyone who can reach this port can read your chat history",
+            "[!]".yellow(),
+            config.host
+        );
+    }
+
+    let discovery = web::Data::new(config.discovery.clone());
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(discovery.clone())
+            .wrap(middleware::Logger::default())
+            .configure(configure_local_routes)
+    })
+    .bind((config.host.as_str(), config.port))
+    .with_context(|| format!("Failed to bind {}:{}", config.host, config.port))?;
+
+    println!(
+        "{} Serving sessions at http://{}:{}",
+        "[*]".blue(),
+        config.host,
+        config.port
+    );
+    println!("   GET /workspaces");
+    println!("   GET /workspaces/:hash/sessions");
+    println!("   GET /sessions/:id");
+    println!("Press Ctrl+C to stop the server...");
+
+    server.run().await?;
+    Ok(())
+}
@@ -8,6 +8,7 @@
 mod auth;
 mod handlers_simple;
 mod handlers_swe;
+mod local;
 mod state;
 mod sync;
 
@@ -17,6 +18,7 @@ mod sync;
 pub use auth::{
     configure_auth_routes, require_tier, AuthenticatedUser, SubscriptionFeatures, SubscriptionTier,
 };
+pub use local::{start_local_server, LocalServerConfig};
 pub use state::AppState;
 pub use sync::{configure_sync_routes, create_sync_state};
 
@@ -10,7 +10,10 @@ use colored::Colorize;
 use rusqlite::{Connection, OpenFlags};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 // Suppress dead code warnings for fields used in debugging
 #[allow(dead_code)]
@@ -37,49 +40,24 @@ impl BrowserType {
         }
     }
 
-    /// Get the default profile path for this browser
+    /// Whether this browser is built on Chromium (shares the profile/cookie layout)
+    pub fn is_chromium(&self) -> bool {
+        !matches!(self, BrowserType::Firefox)
+    }
+
+    /// Get the Chromium "User Data" directory that holds every profile and `Local State`
     #[cfg(windows)]
-    pub fn profile_path(&self) -> Option<PathBuf> {
+    pub fn user_data_dir(&self) -> Option<PathBuf> {
         let local_app_data = dirs::data_local_dir()?;
         let roaming_app_data = dirs::data_dir()?;
 
         let path = match self {
-            BrowserType::Chrome => local_app_data.join("Google/Chrome/User Data/Default"),
-            BrowserType::Edge => local_app_data.join("Microsoft/Edge/User Data/Default"),
-            BrowserType::Brave => {
-                local_app_data.join("BraveSoftware/Brave-Browser/User Data/Default")
-            }
-            BrowserType::Vivaldi => local_app_data.join("Vivaldi/User Data/Default"),
+            BrowserType::Chrome => local_app_data.join("Google/Chrome/User Data"),
+            BrowserType::Edge => local_app_data.join("Microsoft/Edge/User Data"),
+            BrowserType::Brave => local_app_data.join("BraveSoftware/Brave-Browser/User Data"),
+            BrowserType::Vivaldi => local_app_data.join("Vivaldi/User Data"),
             BrowserType::Opera => roaming_app_data.join("Opera Software/Opera Stable"),
-            BrowserType::Firefox => {
-                // Firefox uses random profile directories
-                // Select the profile with the largest cookies.sqlite (most likely active)
-                let profiles_dir = roaming_app_data.join("Mozilla/Firefox/Profiles");
-                if profiles_dir.exists() {
-                    if let Ok(entries) = fs::read_dir(&profiles_dir) {
-                        let mut best_profile: Option<(PathBuf, u64)> = None;
-
-                        for entry in entries.flatten() {
-                            let profile_path = entry.path();
-                            let cookies_path = profile_path.join("cookies.sqlite");
-
-                            if cookies_path.exists() {
-                                if let Ok(metadata) = fs::metadata(&cookies_path) {
-                                    let size = metadata.len();
-                                    if best_profile.as_ref().map_or(true, |(_, s)| size > *s) {
-                                        best_profile = Some((profile_path, size));
-                                    }
-                                }
-                            }
-                        }
-
-                        if let Some((path, _)) = best_profile {
-                            return Some(path);
-                        }
-                    }
-                }
-                return None;
-            }
+            BrowserType::Firefox => return None,
         };
 
         if path.exists() {
@@ -90,59 +68,28 @@ impl BrowserType {
     }
 
     #[cfg(not(windows))]
-    pub fn profile_path(&self) -> Option<PathBuf> {
+    pub fn user_data_dir(&self) -> Option<PathBuf> {
         let home = dirs::home_dir()?;
 
+        #[cfg(target_os = "macos")]
         let path = match self {
-            BrowserType::Chrome => {
-                #[cfg(target_os = "macos")]
-                {
-                    home.join("Library/Application Support/Google/Chrome/Default")
-                }
-                #[cfg(target_os = "linux")]
-                {
-                    home.join(".config/google-chrome/Default")
-                }
-            }
-            BrowserType::Edge => {
-                #[cfg(target_os = "macos")]
-                {
-                    home.join("Library/Application Support/Microsoft Edge/Default")
-                }
-                #[cfg(target_os = "linux")]
-                {
-                    home.join(".config/microsoft-edge/Default")
-                }
-            }
-            BrowserType::Firefox => {
-                #[cfg(target_os = "macos")]
-                let profiles_dir = home.join("Library/Application Support/Firefox/Profiles");
-                #[cfg(target_os = "linux")]
-                let profiles_dir = home.join(".mozilla/firefox");
-
-                if profiles_dir.exists() {
-                    if let Ok(entries) = fs::read_dir(&profiles_dir) {
-                        for entry in entries.flatten() {
-                            let name = entry.file_name().to_string_lossy().to_string();
-                            if name.ends_with(".default-release") || name.ends_with(".default") {
-                                return Some(entry.path());
-                            }
-                        }
-                    }
-                }
-                return None;
-            }
+            BrowserType::Chrome => home.join("Library/Application Support/Google/Chrome"),
+            BrowserType::Edge => home.join("Library/Application Support/Microsoft Edge"),
             BrowserType::Brave => {
-                #[cfg(target_os = "macos")]
-                {
-                    home.join("Library/Application Support/BraveSoftware/Brave-Browser/Default")
-                }
-                #[cfg(target_os = "linux")]
-                {
-                    home.join(".config/BraveSoftware/Brave-Browser/Default")
-                }
+                home.join("Library/Application Support/BraveSoftware/Brave-Browser")
             }
-            _ => return None,
+            BrowserType::Vivaldi => home.join("Library/Application Support/Vivaldi"),
+            BrowserType::Opera => home.join("Library/Application Support/com.operasoftware.Opera"),
+            BrowserType::Firefox => return None,
+        };
+        #[cfg(not(target_os = "macos"))]
+        let path = match self {
+            BrowserType::Chrome => home.join(".config/google-chrome"),
+            BrowserType::Edge => home.join(".config/microsoft-edge"),
+            BrowserType::Brave => home.join(".config/BraveSoftware/Brave-Browser"),
+            BrowserType::Vivaldi => home.join(".config/vivaldi"),
+            BrowserType::Opera => home.join(".config/opera"),
+            BrowserType::Firefox => return None,
         };
 
         if path.exists() {
@@ -152,54 +99,60 @@ impl BrowserType {
         }
     }
 
-    /// Get the cookie database path for Chromium-based browsers
-    pub fn cookies_path(&self) -> Option<PathBuf> {
-        let profile = self.profile_path()?;
+    /// Get the default profile path for this browser
+    #[allow(dead_code)]
+    pub fn profile_path(&self) -> Option<PathBuf> {
+        if self.is_chromium() {
+            return self
+                .profiles()
+                .into_iter()
+                .find(|p| p.dir_name == "Default")
+                .or_else(|| self.profiles().into_iter().next())
+                .map(|p| p.path);
+        }
 
-        match self {
-            BrowserType::Firefox => {
-                let path = profile.join("cookies.sqlite");
-                if path.exists() {
-                    Some(path)
-                } else {
-                    None
-                }
-            }
-            _ => {
-                // Chromium-based browsers store cookies in Network/Cookies (newer) or Cookies (older)
-                let network_path = profile.join("Network/Cookies");
-                if network_path.exists() {
-                    return Some(network_path);
-                }
-                let old_path = profile.join("Cookies");
-                if old_path.exists() {
-                    Some(old_path)
-                } else {
-                    None
-                }
-            }
+        firefox_profile_path()
+    }
+
+    /// Enumerate all profiles for this browser.
+    ///
+    /// Chromium browsers list their profiles in `Local State` under
+    /// `profile.info_cache`; Firefox and single-profile browsers return one entry.
+    pub fn profiles(&self) -> Vec<BrowserProfile> {
+        if !self.is_chromium() {
+            return firefox_profile_path()
+                .map(|path| {
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "default".to_string());
+                    vec![BrowserProfile {
+                        browser: *self,
+                        dir_name: name.clone(),
+                        name,
+                        path,
+                    }]
+                })
+                .unwrap_or_default();
+        }
+
+        match self.user_data_dir() {
+            Some(dir) => chromium_profiles_in(*self, &dir),
+            None => Vec::new(),
         }
     }
 
+    /// Get the cookie database path for the default profile
+    #[allow(dead_code)]
+    pub fn cookies_path(&self) -> Option<PathBuf> {
+        let profile = self.profile_path()?;
+        BrowserProfile::cookies_path_in(*self, &profile)
+    }
+
     /// Get the Local State file path (contains encryption key for Chromium browsers)
-    /// Reserved for future cookie decryption implementation
-    #[cfg(windows)]
     #[allow(dead_code)]
     pub fn local_state_path(&self) -> Option<PathBuf> {
-        let local_app_data = dirs::data_local_dir()?;
-        let roaming_app_data = dirs::data_dir()?;
-
-        let path = match self {
-            BrowserType::Chrome => local_app_data.join("Google/Chrome/User Data/Local State"),
-            BrowserType::Edge => local_app_data.join("Microsoft/Edge/User Data/Local State"),
-            BrowserType::Brave => {
-                local_app_data.join("BraveSoftware/Brave-Browser/User Data/Local State")
-            }
-            BrowserType::Vivaldi => local_app_data.join("Vivaldi/User Data/Local State"),
-            BrowserType::Opera => roaming_app_data.join("Opera Software/Opera Stable/Local State"),
-            BrowserType::Firefox => return None, // Firefox doesn't use this
-        };
-
+        let path = self.user_data_dir()?.join("Local State");
         if path.exists() {
             Some(path)
         } else {
@@ -208,6 +161,151 @@ impl BrowserType {
     }
 }
 
+/// A single browser profile (e.g. Brave "Default" or Edge "Profile 1")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserProfile {
+    pub browser: BrowserType,
+    /// Directory name inside the user data dir ("Default", "Profile 1", ...)
+    pub dir_name: String,
+    /// Display name from `Local State` (falls back to the directory name)
+    pub name: String,
+    /// Full path to the profile directory
+    pub path: PathBuf,
+}
+
+impl BrowserProfile {
+    /// Get the cookie database for this profile, if it exists
+    pub fn cookies_path(&self) -> Option<PathBuf> {
+        Self::cookies_path_in(self.browser, &self.path)
+    }
+
+    fn cookies_path_in(browser: BrowserType, profile: &Path) -> Option<PathBuf> {
+        let candidates = if browser.is_chromium() {
+            // Chromium-based browsers store cookies in Network/Cookies (newer) or Cookies (older)
+            vec![profile.join("Network/Cookies"), profile.join("Cookies")]
+        } else {
+            vec![profile.join("cookies.sqlite")]
+        };
+
+        candidates.into_iter().find(|p| p.exists())
+    }
+
+    /// Label used in scan output, e.g. "Brave (Work)"
+    pub fn label(&self) -> String {
+        format!("{} ({})", self.browser.name(), self.name)
+    }
+}
+
+/// Parse `profile.info_cache` from a Chromium `Local State` file into
+/// `(directory name, display name)` pairs, sorted by directory name.
+pub fn parse_local_state_profiles(local_state: &str) -> Vec<(String, String)> {
+    let value: serde_json::Value = match serde_json::from_str(local_state) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut profiles: Vec<(String, String)> = value
+        .get("profile")
+        .and_then(|p| p.get("info_cache"))
+        .and_then(|c| c.as_object())
+        .map(|cache| {
+            cache
+                .iter()
+                .map(|(dir, info)| {
+                    let name = info
+                        .get("name")
+                        .and_then(|n| n.as_str())
+                        .filter(|n| !n.is_empty())
+                        .unwrap_or(dir)
+                        .to_string();
+                    (dir.clone(), name)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    profiles.sort();
+    profiles
+}
+
+/// Enumerate Chromium profiles inside a user data directory
+fn chromium_profiles_in(browser: BrowserType, user_data_dir: &Path) -> Vec<BrowserProfile> {
+    let listed = fs::read_to_string(user_data_dir.join("Local State"))
+        .map(|content| parse_local_state_profiles(&content))
+        .unwrap_or_default();
+
+    // Without a usable Local State, fall back to the conventional default profile
+    let listed = if listed.is_empty() {
+        vec![("Default".to_string(), "Default".to_string())]
+    } else {
+        listed
+    };
+
+    // Opera keeps its single profile directly in the user data dir
+    if browser == BrowserType::Opera && !user_data_dir.join("Default").exists() {
+        return vec![BrowserProfile {
+            browser,
+            dir_name: "Default".to_string(),
+            name: "Default".to_string(),
+            path: user_data_dir.to_path_buf(),
+        }];
+    }
+
+    listed
+        .into_iter()
+        .map(|(dir_name, name)| BrowserProfile {
+            browser,
+            path: user_data_dir.join(&dir_name),
+            dir_name,
+            name,
+        })
+        .filter(|p| p.path.exists())
+        .collect()
+}
+
+/// Locate the active Firefox profile directory
+#[cfg(windows)]
+fn firefox_profile_path() -> Option<PathBuf> {
+    // Firefox uses random profile directories
+    // Select the profile with the largest cookies.sqlite (most likely active)
+    let profiles_dir = dirs::data_dir()?.join("Mozilla/Firefox/Profiles");
+    let entries = fs::read_dir(&profiles_dir).ok()?;
+    let mut best_profile: Option<(PathBuf, u64)> = None;
+
+    for entry in entries.flatten() {
+        let profile_path = entry.path();
+        let cookies_path = profile_path.join("cookies.sqlite");
+
+        if let Ok(metadata) = fs::metadata(&cookies_path) {
+            let size = metadata.len();
+            if best_profile.as_ref().map_or(true, |(_, s)| size > *s) {
+                best_profile = Some((profile_path, size));
+            }
+        }
+    }
+
+    best_profile.map(|(path, _)| path)
+}
+
+#[cfg(not(windows))]
+fn firefox_profile_path() -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+
+    #[cfg(target_os = "macos")]
+    let profiles_dir = home.join("Library/Application Support/Firefox/Profiles");
+    #[cfg(not(target_os = "macos"))]
+    let profiles_dir = home.join(".mozilla/firefox");
+
+    fs::read_dir(&profiles_dir)
+        .ok()?
+        .flatten()
+        .find(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.ends_with(".default-release") || name.ends_with(".default")
+        })
+        .map(|entry| entry.path())
+}
+
 /// Web LLM provider authentication info
 #[derive(Debug, Clone)]
 pub struct ProviderAuth {
@@ -321,25 +419,31 @@ pub const WEB_LLM_PROVIDERS: &[ProviderAuth] = &[
 #[derive(Debug, Clone)]
 pub struct BrowserAuthResult {
     pub browser: BrowserType,
+    /// Display name of the browser profile the cookies came from
+    pub profile: String,
     pub provider: String,
     pub authenticated: bool,
     #[allow(dead_code)]
     pub cookies_found: Vec<String>,
 }
 
+impl BrowserAuthResult {
+    /// Browser and profile the result came from, e.g. "Edge (Work)"
+    pub fn source(&self) -> String {
+        if self.profile.is_empty() {
+            self.browser.name().to_string()
+        } else {
+            format!("{} ({})", self.browser.name(), self.profile)
+        }
+    }
+}
+
 /// Scan browsers for authenticated LLM providers
 pub fn scan_browser_auth() -> Vec<BrowserAuthResult> {
     scan_browser_auth_internal(false)
 }
 
-/// Scan browsers for authenticated LLM providers with verbose output
-pub fn scan_browser_auth_verbose() -> Vec<BrowserAuthResult> {
-    scan_browser_auth_internal(true)
-}
-
 fn scan_browser_auth_internal(verbose: bool) -> Vec<BrowserAuthResult> {
-    let mut results = Vec::new();
-
     let browsers = [
         BrowserType::Edge,
         BrowserType::Chrome,
@@ -349,37 +453,115 @@ fn scan_browser_auth_internal(verbose: bool) -> Vec<BrowserAuthResult> {
         BrowserType::Opera,
     ];
 
-    for browser in browsers {
-        if let Some(cookies_path) = browser.cookies_path() {
-            if verbose {
-                println!(
-                    "      {} {} cookies: {}",
-                    "->".dimmed(),
-                    browser.name(),
-                    cookies_path.display()
-                );
-            }
-            match scan_browser_cookies_internal(&browser, &cookies_path, verbose) {
-                Ok(browser_results) => results.extend(browser_results),
-                Err(e) => {
-                    if verbose {
-                        println!("        {} Direct access failed: {}", "!".yellow(), e);
-                        println!("        {} Trying copy method...", "->".dimmed());
-                    }
-                    // Browser might be open and locking the database
-                    // Try copying to temp file
-                    match scan_browser_cookies_with_copy_internal(&browser, &cookies_path, verbose)
-                    {
-                        Ok(browser_results) => results.extend(browser_results),
-                        Err(e2) => {
-                            if verbose {
-                                println!("        {} Copy method also failed: {}", "x".red(), e2);
-                            }
+    browsers
+        .into_iter()
+        .flat_map(|browser| scan_browser_profiles(browser, verbose))
+        .collect()
+}
+
+/// Browser scan bounded by a deadline
+#[derive(Debug, Default)]
+pub struct BrowserScan {
+    /// Results from the browsers that finished in time
+    pub results: Vec<BrowserAuthResult>,
+    /// Browsers still scanning at the deadline, in scan order
+    pub timed_out: Vec<BrowserType>,
+}
+
+/// Scan installed browsers for authenticated LLM providers, one thread per
+/// browser, abandoning any browser that hasn't finished within `timeout`
+///
+/// A locked or slow cookie database then only costs that browser's results.
+pub fn scan_browser_auth_with_timeout(verbose: bool, timeout: Duration) -> BrowserScan {
+    let jobs = get_installed_browsers()
+        .into_iter()
+        .map(|browser| (browser, move || scan_browser_profiles(browser, verbose)))
+        .collect();
+    let (finished, timed_out) = run_with_deadline(jobs, timeout);
+    BrowserScan {
+        results: finished.into_iter().flatten().collect(),
+        timed_out,
+    }
+}
+
+/// Run each job on its own thread and collect results as they finish.
+/// Returns the finished results (in completion order) and the keys of jobs
+/// still running at the deadline; those threads are left to finish on their own.
+fn run_with_deadline<K, T, F>(jobs: Vec<(K, F)>, timeout: Duration) -> (Vec<T>, Vec<K>)
+where
+    K: Copy + PartialEq + Send + 'static,
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let deadline = Instant::now() + timeout;
+    let (tx, rx) = mpsc::channel();
+    let mut pending = Vec::with_capacity(jobs.len());
+    for (key, job) in jobs {
+        let tx = tx.clone();
+        pending.push(key);
+        thread::spawn(move || {
+            // The receiver is gone once the deadline has passed
+            let _ = tx.send((key, job()));
+        });
+    }
+    drop(tx);
+
+    let mut finished = Vec::new();
+    while !pending.is_empty() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        // Timed out, or every remaining job panicked
+        let Ok((key, value)) = rx.recv_timeout(remaining) else {
+            break;
+        };
+        pending.retain(|k| *k != key);
+        finished.push(value);
+    }
+    (finished, pending)
+}
+
+/// Scan every profile of one browser that has a cookie database
+fn scan_browser_profiles(browser: BrowserType, verbose: bool) -> Vec<BrowserAuthResult> {
+    let mut results = Vec::new();
+
+    for profile in browser.profiles() {
+        // Profiles that never stored cookies are skipped rather than treated as errors
+        let Some(cookies_path) = profile.cookies_path() else {
+            continue;
+        };
+        if verbose {
+            println!(
+                "      {} {} cookies: {}",
+                "->".dimmed(),
+                profile.label(),
+                cookies_path.display()
+            );
+        }
+        let profile_results = match scan_browser_cookies_internal(&browser, &cookies_path, verbose)
+        {
+            Ok(browser_results) => browser_results,
+            Err(e) => {
+                if verbose {
+                    println!("        {} Direct access failed: {}", "!".yellow(), e);
+                    println!("        {} Trying copy method...", "->".dimmed());
+                }
+                // Browser might be open and locking the database
+                // Try copying to temp file
+                match scan_browser_cookies_with_copy_internal(&browser, &cookies_path, verbose) {
+                    Ok(browser_results) => browser_results,
+                    Err(e2) => {
+                        if verbose {
+                            println!("        {} Copy method also failed: {}", "x".red(), e2);
                         }
+                        continue;
                     }
                 }
             }
-        }
+        };
+
+        results.extend(profile_results.into_iter().map(|mut r| {
+            r.profile = profile.name.clone();
+            r
+        }));
     }
 
     results
@@ -398,7 +580,7 @@ pub fn get_installed_browsers() -> Vec<BrowserType> {
 
     browsers
         .into_iter()
-        .filter(|b| b.profile_path().is_some())
+        .filter(|b| !b.profiles().is_empty())
         .collect()
 }
 
@@ -520,6 +702,7 @@ fn scan_browser_cookies_internal(
 
         results.push(BrowserAuthResult {
             browser: *browser,
+            profile: String::new(),
             provider: provider.name.to_string(),
             authenticated,
             cookies_found: found_auth_cookies,
@@ -685,7 +868,7 @@ pub fn extract_provider_cookies(provider_name: &str) -> Option<ProviderCredentia
     ];
 
     for browser in browsers {
-        if let Some(cookies_path) = browser.cookies_path() {
+        for cookies_path in browser.profiles().iter().filter_map(|p| p.cookies_path()) {
             for domain in &domains_to_try {
                 // Try to extract cookies
                 if let Ok(cookies) = extract_cookies_for_domain(&browser, &cookies_path, domain) {
@@ -1053,6 +1236,79 @@ mod tests {
         assert_eq!(BrowserType::Firefox.name(), "Firefox");
     }
 
+    #[test]
+    fn test_parse_local_state_profiles() {
+        let local_state = r#"{
+            "profile": {
+                "info_cache": {
+                    "Profile 1": { "name": "Work" },
+                    "Default": { "name": "Personal" },
+                    "Profile 2": {}
+                }
+            }
+        }"#;
+
+        let profiles = parse_local_state_profiles(local_state);
+        assert_eq!(
+            profiles,
+            vec![
+                ("Default".to_string(), "Personal".to_string()),
+                ("Profile 1".to_string(), "Work".to_string()),
+                ("Profile 2".to_string(), "Profile 2".to_string()),
+            ]
+        );
+        assert!(parse_local_state_profiles("not json").is_empty());
+    }
+
+    #[test]
+    fn test_chromium_profiles_skip_missing_cookie_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let user_data = dir.path();
+        fs::write(
+            user_data.join("Local State"),
+            r#"{"profile":{"info_cache":{"Default":{"name":"Personal"},"Profile 1":{"name":"Work"},"Profile 3":{"name":"Gone"}}}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(user_data.join("Default")).unwrap();
+        fs::create_dir_all(user_data.join("Profile 1/Network")).unwrap();
+        fs::write(user_data.join("Profile 1/Network/Cookies"), b"").unwrap();
+
+        // Profile 3 is listed but has no directory on disk
+        let profiles = chromium_profiles_in(BrowserType::Brave, user_data);
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[1].label(), "Brave (Work)");
+
+        // Only Profile 1 has a cookie database
+        let with_cookies: Vec<_> = profiles.iter().filter_map(|p| p.cookies_path()).collect();
+        assert_eq!(
+            with_cookies,
+            vec![user_data.join("Profile 1/Network/Cookies")]
+        );
+    }
+
+    #[test]
+    fn test_slow_browser_does_not_block_others() {
+        let slow = || {
+            // Stands in for a cookie database held locked by a running browser
+            thread::sleep(Duration::from_secs(5));
+            vec!["slow"]
+        };
+        let jobs: Vec<(BrowserType, Box<dyn FnOnce() -> Vec<&'static str> + Send>)> = vec![
+            (BrowserType::Chrome, Box::new(|| vec!["chrome"])),
+            (BrowserType::Firefox, Box::new(slow)),
+            (BrowserType::Edge, Box::new(|| vec!["edge"])),
+        ];
+
+        let started = Instant::now();
+        let (finished, timed_out) = run_with_deadline(jobs, Duration::from_millis(300));
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let mut finished: Vec<_> = finished.into_iter().flatten().collect();
+        finished.sort();
+        assert_eq!(finished, vec!["chrome", "edge"]);
+        assert_eq!(timed_out, vec![BrowserType::Firefox]);
+    }
+
     #[test]
     fn test_get_installed_browsers() {
         let browsers = get_installed_browsers();
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 //! CLI argument definitions using clap derive macros
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 
 /// Chat System Manager (csm) - Manage and merge chat sessions across workspaces
 #[derive(Parser)]
@@ -11,10 +11,108 @@ use clap::{Parser, Subcommand};
 #[command(version)]
 #[command(about = "Manage and merge chat sessions across workspaces", long_about = None)]
 pub struct Cli {
+    /// Also search this many nested directory levels below the workspace
+    /// storage root (set CSM_STORAGE_PATH to scan a tree of user profiles)
+    #[arg(long, global = true, default_value = "0", value_name = "N")]
+    pub depth: usize,
+
+    /// Descend into directory symlinks during workspace discovery (each
+    /// target is walked once, so link cycles are safe); symlinked workspaces
+    /// are listed either way
+    #[arg(long, global = true)]
+    pub follow_symlinks: bool,
+
+    /// VS Code builds to read: stable, insiders, or all (Insiders keeps its
+    /// own `Code - Insiders` workspaceStorage)
+    #[arg(long, global = true, default_value = "all", value_name = "CHANNEL")]
+    pub channel: crate::workspace::ChannelFilter,
+
+    /// Skip workspaces whose project path matches this glob (repeatable;
+    /// adds to `exclude_globs` in config), e.g. --exclude '/tmp/*'
+    #[arg(long, global = true, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// When to color output: auto, always, or never (auto honors NO_COLOR
+    /// and only colors a terminal)
+    #[arg(long, global = true, default_value = "auto", value_name = "WHEN")]
+    pub color: ColorChoice,
+
+    /// Print only a command's primary output (tables, JSON, counts), leaving
+    /// out summaries, footers, hints and progress lines
+    #[arg(long, short = 'q', global = true)]
+    pub quiet: bool,
+
+    /// Indent `--json` output for reading
+    #[arg(long, global = true, conflicts_with = "compact")]
+    pub pretty: bool,
+
+    /// Print `--json` output on one line (the default)
+    #[arg(long, global = true)]
+    pub compact: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+/// `--color` setting, applied once for every subcommand (and the TUI)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Color a terminal unless NO_COLOR, CLICOLOR=0 or TERM=dumb say otherwise
+    #[default]
+    Auto,
+    /// Always color, even when piped (e.g. into `less -R`)
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorChoice {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["auto", "always", "never"];
+
+    /// Install this choice as the global `colored` override
+    pub fn apply(self) {
+        match self {
+            ColorChoice::Always => colored::control::set_override(true),
+            ColorChoice::Never => colored::control::set_override(false),
+            // colored already honors NO_COLOR, CLICOLOR(_FORCE) and TTY detection
+            ColorChoice::Auto => {
+                if std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+                    colored::control::set_override(false);
+                }
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "unknown color choice '{}' (valid choices: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     // ============================================================================
@@ -36,6 +134,48 @@ pub enum Commands {
         command: Option<FindCommands>,
     },
 
+    // ============================================================================
+    // Tag Commands
+    // ============================================================================
+    /// Annotate sessions with local tags
+    Tag {
+        #[command(subcommand)]
+        command: TagCommands,
+    },
+
+    // ============================================================================
+    // Link Commands
+    // ============================================================================
+    /// Link a session to the git repository it belongs to (used by `git
+    /// snapshot`, `git track` and `git diff --session`)
+    Link {
+        /// Session ID (or unique prefix)
+        #[arg(required_unless_present = "list")]
+        session_id: Option<String>,
+
+        /// Path inside the git repository
+        #[arg(required_unless_present = "list")]
+        repo_path: Option<String>,
+
+        /// Show every session to repository link
+        #[arg(long, conflicts_with_all = ["session_id", "repo_path"])]
+        list: bool,
+
+        /// Show the change to the link index without saving it
+        #[arg(long, conflicts_with = "list")]
+        dry_run: bool,
+    },
+
+    /// Remove a session's repository link
+    Unlink {
+        /// Session ID (or unique prefix)
+        session_id: String,
+
+        /// Show the change to the link index without saving it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     // ============================================================================
     // Show Commands
     // ============================================================================
@@ -46,11 +186,106 @@ pub enum Commands {
         command: Option<ShowCommands>,
     },
 
+    // ============================================================================
+    // Stats Command
+    // ============================================================================
+    /// Show when a session was active: first/last message and longest gap
+    Stats {
+        /// Session ID or filename (partial match supported)
+        session_id: String,
+
+        /// Also chart messages per day
+        #[arg(long)]
+        by_day: bool,
+    },
+
+    /// Show a calendar of messages per day across all sessions
+    Timeline {
+        /// Calendar year to show (default: the current year)
+        #[arg(long)]
+        year: Option<i32>,
+    },
+
+    // ============================================================================
+    // Open Commands
+    // ============================================================================
+    /// Open a session's workspace in VS Code
+    Open {
+        #[command(subcommand)]
+        command: OpenCommands,
+    },
+
+    // ============================================================================
+    // Purge Commands
+    // ============================================================================
+    /// Bulk-remove sessions (dry run unless --delete or --yes is given)
+    Purge {
+        /// Purge empty-window sessions (not tied to any workspace)
+        #[arg(long, required = true)]
+        empty: bool,
+
+        /// Only purge sessions last active before this date (YYYY-MM-DD, or relative: 30d, 2w, 3mo, 1y)
+        #[arg(long)]
+        older_than: Option<String>,
+
+        /// Delete the listed sessions after a confirmation prompt
+        #[arg(long)]
+        delete: bool,
+
+        /// Delete the listed sessions without prompting
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Only list the files that would be removed (the default without --delete or --yes)
+        #[arg(long, conflicts_with_all = ["delete", "yes"])]
+        dry_run: bool,
+    },
+
+    /// Find sessions duplicated across workspaces (dry run unless --remove is given)
+    Dedupe {
+        /// Keep the most recently active copy of each duplicate set and
+        /// delete the rest, after a confirmation prompt
+        #[arg(long)]
+        remove: bool,
+
+        /// With --remove, delete without prompting
+        #[arg(long, short = 'y', requires = "remove")]
+        yes: bool,
+    },
+
+    /// Break a session into consecutive parts, each a new session in the
+    /// same workspace (the original is kept unless --in-place is given)
+    Split {
+        /// Session ID or filename (partial match supported)
+        session_id: String,
+
+        /// Turns that end a part, comma-separated: `--at 10,25` writes turns
+        /// 1-10, 11-25 and 26 onwards
+        #[arg(long, required = true, value_delimiter = ',', value_name = "N")]
+        at: Vec<usize>,
+
+        /// Rewrite the original to hold only the first part (backed up first)
+        #[arg(long)]
+        in_place: bool,
+
+        /// Register the parts in VS Code's index even while VS Code is running
+        #[arg(long)]
+        force: bool,
+
+        /// Show the files that would be written without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     // ============================================================================
     // Fetch Commands
     // ============================================================================
     /// Fetch chat sessions from workspaces, sessions, or paths
     Fetch {
+        /// Show the files that would be copied without copying them
+        #[arg(long, global = true)]
+        dry_run: bool,
+
         #[command(subcommand)]
         command: Option<FetchCommands>,
     },
@@ -60,6 +295,10 @@ pub enum Commands {
     // ============================================================================
     /// Merge chat sessions from workspaces, sessions, or paths
     Merge {
+        /// Show the files a merge would create and modify without writing them
+        #[arg(long, global = true)]
+        dry_run: bool,
+
         #[command(subcommand)]
         command: Option<MergeCommands>,
     },
@@ -69,6 +308,68 @@ pub enum Commands {
     // ============================================================================
     /// Export chat sessions from workspaces, sessions, or paths
     Export {
+        /// Output format: json, md, html, txt
+        #[arg(long, global = true, default_value = "json")]
+        format: crate::commands::ExportFormat,
+
+        /// Replace API keys, tokens and email addresses with [REDACTED]
+        #[arg(long, global = true)]
+        redact: bool,
+
+        /// Replace your home directory, username and the session's project
+        /// path with $HOME, $USER and $PROJECT
+        #[arg(long, global = true)]
+        anonymize: bool,
+
+        /// Render each session with this template instead of --format
+        /// (Tera syntax subset; output extension comes from the file name, e.g. docs.md.tera)
+        #[arg(long, global = true, value_name = "FILE")]
+        template: Option<String>,
+
+        /// Print the JSON context a --template receives for this session
+        #[arg(long, value_name = "SESSION_ID")]
+        dump_context: Option<String>,
+
+        /// Export the sessions whose IDs are piped in, one per line (e.g.
+        /// `find session ... --json | jq -r '.[].session_id'`); IDs that
+        /// don't resolve are reported on stderr
+        #[arg(long, requires = "output", conflicts_with = "dump_context")]
+        stdin_ids: bool,
+
+        /// Directory --stdin-ids exports into
+        #[arg(long, value_name = "DIR", requires = "stdin_ids")]
+        output: Option<String>,
+
+        /// Only export these turns of each session, counting from 1: 5, 3-7 or 10-
+        #[arg(long, global = true, value_name = "RANGE")]
+        messages: Option<crate::commands::MessageRange>,
+
+        /// Turns that were edited or regenerated: latest renders the selected
+        /// version, all renders every version marked [branch i/n]
+        /// (md, html, txt or --template; JSON keeps the branches as recorded)
+        #[arg(long, global = true, value_name = "MODE", default_value = "latest")]
+        flatten_threads: crate::commands::FlattenThreads,
+
+        /// Directory structure: flat, by-workspace (<workspace>/<session>)
+        /// or by-date (YYYY/MM/<session>, from the last message date)
+        #[arg(long, global = true, default_value = "flat")]
+        layout: crate::commands::ExportLayout,
+
+        /// Write each fenced code block to <destination>/code/<session>-<n>.<ext>
+        /// (extension from the fence language, else .txt) and refer to it
+        /// from the transcript (md, html, txt or --template)
+        #[arg(long, global = true)]
+        split_by_code: bool,
+
+        /// Gzip each exported file, writing e.g. <session>.md.gz
+        #[arg(long, global = true, conflicts_with = "archive")]
+        gzip: bool,
+
+        /// Write one gzipped tarball instead of a directory: the destination
+        /// gets .tar.gz unless it ends in .tar.gz or .tgz
+        #[arg(long, global = true)]
+        archive: bool,
+
         #[command(subcommand)]
         command: Option<ExportCommands>,
     },
@@ -78,6 +379,10 @@ pub enum Commands {
     // ============================================================================
     /// Import session files from external directories into a workspace
     Import {
+        /// Show the files that would be copied without copying them
+        #[arg(long, global = true)]
+        dry_run: bool,
+
         #[command(subcommand)]
         command: Option<ImportCommands>,
     },
@@ -88,6 +393,10 @@ pub enum Commands {
     /// Move chat sessions between workspaces
     #[command(visible_alias = "mv")]
     Move {
+        /// Show the files that would be moved without moving them
+        #[arg(long, global = true)]
+        dry_run: bool,
+
         #[command(subcommand)]
         command: Option<MoveCommands>,
     },
@@ -143,6 +452,10 @@ pub enum Commands {
     /// Add on-disk sessions to VS Code's database index (makes orphaned sessions visible)
     #[command(visible_alias = "sync")]
     Register {
+        /// Show the changes to VS Code's index without writing them
+        #[arg(long, global = true)]
+        dry_run: bool,
+
         #[command(subcommand)]
         command: RegisterCommands,
     },
@@ -160,12 +473,22 @@ pub enum Commands {
     // API Server Commands
     // ============================================================================
     /// Start the HTTP API server for the web frontend
-    #[command(visible_alias = "serve")]
     Api {
         #[command(subcommand)]
         command: ApiCommands,
     },
 
+    /// Serve workspaces and sessions from VS Code storage as a read-only JSON API
+    Serve {
+        /// Host to bind to; anything but localhost exposes your chat history
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to listen on
+        #[arg(short, long, default_value = "8080")]
+        port: u16,
+    },
+
     // ============================================================================
     // Agency Commands
     // ============================================================================
@@ -175,6 +498,19 @@ pub enum Commands {
         command: AgencyCommands,
     },
 
+    // ============================================================================
+    // Schema Command
+    // ============================================================================
+    /// Print the JSON Schema of session files and the normalized export shape
+    Schema,
+
+    // ============================================================================
+    // Doctor Command
+    // ============================================================================
+    /// Check that csm can find VS Code storage, git and providers
+    /// (exits 1 if a critical check fails)
+    Doctor,
+
     // ============================================================================
     // Easter Egg
     // ============================================================================
@@ -191,7 +527,32 @@ pub enum Commands {
 pub enum ListCommands {
     /// List all VS Code workspaces
     #[command(visible_alias = "ws")]
-    Workspaces,
+    Workspaces {
+        /// Sort by: path, sessions, or modified
+        #[arg(long, default_value = "path")]
+        sort: crate::commands::WorkspaceSort,
+
+        /// Reverse the sort order
+        #[arg(long)]
+        reverse: bool,
+
+        /// Show the disk usage of each workspace's chat sessions
+        #[arg(long)]
+        size: bool,
+
+        /// Log each workspace directory that couldn't be read, and why, to stderr
+        #[arg(long, short)]
+        verbose: bool,
+
+        /// Output format: table or csv (a header row, then one row per workspace)
+        #[arg(long, default_value = "table")]
+        format: crate::commands::OutputFormat,
+
+        /// CSV fields in order, comma-separated: root, hash, channel, host, path,
+        /// sessions, has_chats, size
+        #[arg(long, value_delimiter = ',', value_name = "KEYS")]
+        columns: Vec<String>,
+    },
 
     /// List all chat sessions
     #[command(visible_alias = "s")]
@@ -199,6 +560,28 @@ pub enum ListCommands {
         /// Filter by project path
         #[arg(long)]
         project_path: Option<String>,
+
+        /// Also list empty-window sessions (VS Code's "ALL SESSIONS"); ignored
+        /// with --project-path
+        #[arg(long)]
+        include_empty: bool,
+        /// Log each session file that couldn't be read or parsed, and why, to stderr
+        #[arg(long, short)]
+        verbose: bool,
+
+        /// Output format: table or csv (a header row, then one row per session)
+        #[arg(long, default_value = "table")]
+        format: crate::commands::OutputFormat,
+
+        /// CSV fields in order, comma-separated: workspace, file, session_id,
+        /// title, modified, messages, model, tags, preview
+        #[arg(long, value_delimiter = ',', value_name = "KEYS")]
+        columns: Vec<String>,
+
+        /// Add a column with the first ~60 characters of each session's
+        /// first prompt
+        #[arg(long)]
+        with_preview: bool,
     },
 
     /// List sessions for a specific project path
@@ -235,6 +618,10 @@ pub enum ListCommands {
 // Find Subcommands
 // ============================================================================
 
+/// Exit status contract shared by `find session` and `find path`
+const FIND_EXIT_STATUS: &str =
+    "Exit status: 0 if any session matched, 1 if none matched, 2 on error.";
+
 #[derive(Subcommand)]
 pub enum FindCommands {
     /// Search workspaces by name pattern (defaults to current directory name)
@@ -242,15 +629,27 @@ pub enum FindCommands {
     Workspace {
         /// Text pattern to match (case-insensitive, defaults to current directory name)
         pattern: Option<String>,
+
+        /// Log each workspace directory that couldn't be read, and why, to stderr
+        #[arg(long, short)]
+        verbose: bool,
     },
 
     /// Search sessions by title, content, or ID pattern
-    #[command(visible_alias = "s")]
+    #[command(visible_alias = "s", after_help = FIND_EXIT_STATUS)]
     Session {
-        /// Text pattern to match (case-insensitive, defaults to current directory name)
-        pattern: Option<String>,
+        /// Text patterns to match (case-insensitive, defaults to current directory name)
+        patterns: Vec<String>,
 
-        /// Filter by project path or workspace name
+        /// Require every pattern to match
+        #[arg(long, conflicts_with = "any")]
+        all: bool,
+
+        /// Match sessions containing any of the patterns (default)
+        #[arg(long)]
+        any: bool,
+
+        /// Filter by project path or workspace name (`.` for the current directory)
         #[arg(long, short = 'w')]
         workspace: Option<String>,
 
@@ -262,20 +661,130 @@ pub enum FindCommands {
         #[arg(long, short = 'c')]
         content: bool,
 
-        /// Filter sessions modified after this date (YYYY-MM-DD)
+        /// Filter sessions modified after this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
         #[arg(long)]
         after: Option<String>,
 
-        /// Filter sessions modified before this date (YYYY-MM-DD)
+        /// Filter sessions modified before this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
         #[arg(long)]
         before: Option<String>,
 
         /// Limit number of results
         #[arg(long, short = 'n', default_value = "50")]
         limit: usize,
+
+        /// Print only the number of matching sessions (ignores --limit)
+        #[arg(long)]
+        count: bool,
+
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Output one JSON object per result per line (NDJSON), for
+        /// line-oriented tools
+        #[arg(long, conflicts_with_all = ["json", "count", "group_by", "context", "format"])]
+        ndjson: bool,
+
+        /// Only include sessions carrying this tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Only include sessions with at least N messages (forces accurate counting, slower)
+        #[arg(long, value_name = "N")]
+        min_messages: Option<usize>,
+
+        /// Only include sessions with at most N messages (forces accurate counting, slower)
+        #[arg(long, value_name = "N")]
+        max_messages: Option<usize>,
+
+        /// Log each unreadable or unparseable session file and workspace
+        /// directory to stderr
+        #[arg(long, short)]
+        verbose: bool,
+
+        /// Also search empty-window sessions (VS Code's "ALL SESSIONS");
+        /// ignored with --workspace
+        #[arg(long)]
+        include_empty: bool,
+
+        /// Print one table per workspace instead of a combined one: workspace
+        #[arg(long, value_name = "FIELD", conflicts_with_all = ["json", "count"])]
+        group_by: Option<crate::commands::FindGroupBy>,
+        /// Print N lines of transcript around each content match beneath the
+        /// results (requires --content)
+        #[arg(
+            long,
+            value_name = "N",
+            requires = "content",
+            conflicts_with_all = ["json", "count", "title_only"]
+        )]
+        context: Option<usize>,
+
+        /// Skip the content search of session files larger than N bytes
+        /// (ID and title still match; 0 for no limit)
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = crate::commands::DEFAULT_MAX_SESSION_BYTES
+        )]
+        max_bytes: u64,
+
+        /// Match patterns with their case as given (default: ignore case)
+        #[arg(long)]
+        case_sensitive: bool,
+
+        /// Only match patterns as whole words (`IO` doesn't match "audio")
+        #[arg(long)]
+        word: bool,
+
+        /// Order results by: modified (newest first) or relevance (title
+        /// hits, repeated and whole-word matches rank higher; adds a Score
+        /// column)
+        #[arg(long, default_value = "modified")]
+        sort: crate::commands::FindSort,
+
+        /// Output format: table or csv (a header row, then one row per match)
+        #[arg(
+            long,
+            default_value = "table",
+            conflicts_with_all = ["json", "count", "group_by", "context"]
+        )]
+        format: crate::commands::OutputFormat,
+
+        /// CSV fields in order, comma-separated: session_id, title,
+        /// workspace, modified, messages, match, score, path
+        #[arg(long, value_delimiter = ',', value_name = "KEYS")]
+        columns: Vec<String>,
+
+        /// Only include sessions modified since the last `harvest run` (read
+        /// from the harvest database; see CSM_HARVEST_DB)
+        #[arg(long)]
+        updated_since_harvest: bool,
+
+        /// Drop sessions whose content also contains PATTERN (repeatable;
+        /// requires --content)
+        #[arg(
+            long,
+            value_name = "PATTERN",
+            requires = "content",
+            conflicts_with = "title_only"
+        )]
+        exclude_content: Vec<String>,
+
+        /// Rank other sessions by how many words they share with this one
+        /// (weighted TF-IDF style), with a similarity score, instead of
+        /// matching patterns
+        #[arg(
+            long,
+            value_name = "SESSION_ID",
+            conflicts_with_all = ["patterns", "all", "title_only", "context"]
+        )]
+        similar: Option<String>,
     },
 
     /// Search sessions within a specific project path
+    #[command(after_help = FIND_EXIT_STATUS)]
     Path {
         /// Search pattern (case-insensitive, defaults to current directory name)
         pattern: Option<String>,
@@ -284,6 +793,152 @@ pub enum FindCommands {
         #[arg(long)]
         project_path: Option<String>,
     },
+
+    /// Save a session search under a name (replacing one of the same name)
+    Save {
+        /// Name to run the search by
+        name: String,
+
+        #[command(flatten)]
+        query: SavedSearchArgs,
+    },
+
+    /// Run a saved session search; flags given here override the saved ones
+    #[command(after_help = FIND_EXIT_STATUS)]
+    Run {
+        /// Name of the saved search
+        name: String,
+
+        #[command(flatten)]
+        overrides: SavedSearchArgs,
+
+        /// Print only the number of matching sessions (ignores --limit)
+        #[arg(long)]
+        count: bool,
+
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List saved session searches
+    Saved,
+}
+
+/// The `find session` flags a saved search keeps
+#[derive(Args, Debug, Clone, Default)]
+pub struct SavedSearchArgs {
+    /// Text patterns to match (none matches every session the flags allow)
+    pub patterns: Vec<String>,
+
+    /// Filter by project path or workspace name
+    #[arg(long, short = 'w')]
+    pub workspace: Option<String>,
+
+    /// Only search in session titles
+    #[arg(long, short = 't')]
+    pub title_only: bool,
+
+    /// Include message content in search
+    #[arg(long, short = 'c')]
+    pub content: bool,
+
+    /// Filter sessions modified after this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
+    #[arg(long)]
+    pub after: Option<String>,
+
+    /// Filter sessions modified before this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
+    #[arg(long)]
+    pub before: Option<String>,
+
+    /// Limit number of results (default: 50)
+    #[arg(long, short = 'n')]
+    pub limit: Option<usize>,
+
+    /// Only include sessions carrying this tag
+    #[arg(long)]
+    pub tag: Option<String>,
+
+    /// Require every pattern to match
+    #[arg(long)]
+    pub all: bool,
+
+    /// Match patterns with their case as given
+    #[arg(long)]
+    pub case_sensitive: bool,
+
+    /// Only match patterns as whole words
+    #[arg(long)]
+    pub word: bool,
+}
+
+impl SavedSearchArgs {
+    /// The flags as a stored search
+    pub fn into_saved_search(self) -> crate::providers::SavedSearch {
+        crate::providers::SavedSearch {
+            patterns: self.patterns,
+            workspace: self.workspace,
+            title_only: self.title_only,
+            content: self.content,
+            after: self.after,
+            before: self.before,
+            limit: self.limit,
+            tag: self.tag,
+            all: self.all,
+            case_sensitive: self.case_sensitive,
+            word: self.word,
+        }
+    }
+}
+
+// ============================================================================
+// Tag Subcommands
+// ============================================================================
+
+#[derive(Subcommand)]
+pub enum TagCommands {
+    /// Add one or more tags to a session
+    Add {
+        /// Session ID (or unique prefix)
+        session_id: String,
+
+        /// Tags to add
+        #[arg(required = true, num_args = 1..)]
+        tags: Vec<String>,
+
+        /// Show the change to the tag index without saving it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Remove one or more tags from a session
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Session ID (or unique prefix)
+        session_id: String,
+
+        /// Tags to remove
+        #[arg(required = true, num_args = 1..)]
+        tags: Vec<String>,
+
+        /// Show the change to the tag index without saving it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List all tags, or the tags of a single session
+    #[command(visible_alias = "ls")]
+    List {
+        /// Session ID (or unique prefix)
+        session_id: Option<String>,
+    },
+
+    /// Remove tags whose sessions no longer exist
+    Gc {
+        /// Only list orphaned tags and the planned change without removing them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 // ============================================================================
@@ -297,6 +952,9 @@ pub enum ShowCommands {
     Workspace {
         /// Workspace name or hash
         workspace: String,
+        /// Log each session file that couldn't be read or parsed, and why, to stderr
+        #[arg(long, short)]
+        verbose: bool,
     },
 
     /// Show session details
@@ -308,6 +966,47 @@ pub enum ShowCommands {
         /// Project path to search in
         #[arg(long)]
         project_path: Option<String>,
+
+        /// Print undecorated `key: value` lines for scripts
+        #[arg(long, conflicts_with = "json")]
+        plain: bool,
+
+        /// Output session details as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Preview the first N turns
+        #[arg(
+            long = "first",
+            value_name = "N",
+            default_value = "3",
+            alias = "preview-count"
+        )]
+        preview_count: usize,
+
+        /// Preview the last N turns instead (3 if N is omitted)
+        #[arg(
+            long,
+            value_name = "N",
+            num_args = 0..=1,
+            default_missing_value = "3",
+            conflicts_with = "preview_count"
+        )]
+        last: Option<usize>,
+
+        /// Preview these turns instead, counting from 1: 5, 3-7 or 10-
+        #[arg(long, value_name = "RANGE", conflicts_with_all = ["preview_count", "last"])]
+        messages: Option<crate::commands::MessageRange>,
+
+        /// Print the complete, untruncated text of the previewed turns
+        /// (prompt, tool calls and response) with no other decoration
+        #[arg(long, conflicts_with_all = ["plain", "json"])]
+        full: bool,
+
+        /// Turns that were edited or regenerated: latest previews the
+        /// selected version, all every version marked [branch i/n]
+        #[arg(long, value_name = "MODE", default_value = "latest")]
+        flatten_threads: crate::commands::FlattenThreads,
     },
 
     /// Show chat history timeline for a project path
@@ -317,6 +1016,24 @@ pub enum ShowCommands {
     },
 }
 
+// ============================================================================
+// Open Subcommands
+// ============================================================================
+
+#[derive(Subcommand)]
+pub enum OpenCommands {
+    /// Reopen the workspace that owns a session
+    #[command(visible_alias = "s")]
+    Session {
+        /// Session ID (or unique prefix)
+        session_id: String,
+
+        /// Editor command to launch (default: `editor_cmd` from config, then `code`)
+        #[arg(long)]
+        editor_cmd: Option<String>,
+    },
+}
+
 // ============================================================================
 // Fetch Subcommands
 // ============================================================================
@@ -434,9 +1151,18 @@ pub enum MergeCommands {
     #[command(visible_alias = "s")]
     Sessions {
         /// Session IDs or filenames (comma-separated or space-separated)
-        #[arg(required = true, num_args = 1..)]
+        #[arg(required_unless_present = "from_file", num_args = 1..)]
         sessions: Vec<String>,
 
+        /// Merge each line of this file (a group of session IDs separated by
+        /// spaces or commas) into its own session; `#` starts a comment
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["sessions", "output"])]
+        from_file: Option<String>,
+
+        /// With --from-file, merge nothing unless every listed ID resolves
+        #[arg(long, requires = "from_file", conflicts_with = "sessions")]
+        atomic: bool,
+
         /// Title for the merged session
         #[arg(short, long)]
         title: Option<String>,
@@ -445,6 +1171,11 @@ pub enum MergeCommands {
         #[arg(long)]
         target_path: Option<String>,
 
+        /// Write the merged session to this file, or under this new session
+        /// ID in the target workspace (never overwrites; sources are left as is)
+        #[arg(short, long, value_name = "FILE_OR_ID")]
+        output: Option<String>,
+
         /// Skip VS Code running check
         #[arg(long)]
         force: bool,
@@ -454,6 +1185,22 @@ pub enum MergeCommands {
         no_backup: bool,
     },
 
+    /// Show the session `merge sessions` would create, without writing anything
+    Preview {
+        /// Session IDs or filenames; the first is the base for --format diff
+        #[arg(required = true, num_args = 2..)]
+        sessions: Vec<String>,
+
+        /// Title for the merged session
+        #[arg(short, long)]
+        title: Option<String>,
+
+        /// Rendering: plain (`key: value` lines), diff (turns added to the
+        /// first session marked with +), md, txt, or json (the file as written)
+        #[arg(long, default_value = "plain")]
+        format: crate::commands::MergePreviewFormat,
+    },
+
     /// Merge all sessions for a project path into one unified chat
     Path {
         /// Path to the project (default: current directory)
@@ -559,11 +1306,38 @@ pub enum ExportCommands {
     /// Export sessions from a workspace by hash
     #[command(visible_alias = "ws")]
     Workspace {
-        /// Destination directory for exported sessions
+        /// Destination directory for exported sessions (the output file with --combine)
         destination: String,
 
         /// Source workspace hash
         hash: String,
+
+        /// Only export sessions last active on or after this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only export sessions last active on or before this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Write one md, html or txt document with a section per session,
+        /// oldest first, under a linked table of contents
+        #[arg(long)]
+        combine: bool,
+    },
+
+    /// Export sessions from every workspace
+    All {
+        /// Destination directory for exported sessions
+        destination: String,
+
+        /// Only export sessions last active on or after this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only export sessions last active on or before this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
+        #[arg(long)]
+        until: Option<String>,
     },
 
     /// Export specific sessions by ID
@@ -581,13 +1355,36 @@ pub enum ExportCommands {
         project_path: Option<String>,
     },
 
+    /// Export every session into an Obsidian vault: one note per session,
+    /// wiki-linked to sessions citing the same files, plus a map-of-content
+    /// note per workspace (--format, --layout, --template, --gzip and
+    /// --archive don't apply)
+    Obsidian {
+        /// Vault directory to write into
+        #[arg(long, value_name = "VAULT")]
+        output: String,
+    },
+
     /// Export chat sessions from a project path
     Path {
-        /// Destination directory for exported sessions
+        /// Destination directory for exported sessions (the output file with --combine)
         destination: String,
 
         /// Source project path (default: current directory)
         project_path: Option<String>,
+
+        /// Only export sessions last active on or after this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only export sessions last active on or before this date (YYYY-MM-DD, or relative: 7d, 2w, 3mo, 1y)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Write one md, html or txt document with a section per session,
+        /// oldest first, under a linked table of contents
+        #[arg(long)]
+        combine: bool,
     },
 }
 
@@ -701,8 +1498,19 @@ pub enum GitCommands {
 
     /// Initialize git versioning for chat sessions
     Init {
-        /// Project path
-        path: String,
+        /// Project path (with --link-session, defaults to the session's
+        /// workspace folder)
+        #[arg(required_unless_present = "link_session")]
+        path: Option<String>,
+
+        /// Instead, run `git init` in the project and link this session (ID
+        /// or prefix) to the new repository; fails if it is already a repo
+        #[arg(long, value_name = "ID")]
+        link_session: Option<String>,
+
+        /// With --link-session, commit the project as `[CSM: <id>] initial`
+        #[arg(long, requires = "link_session")]
+        commit: bool,
     },
 
     /// Add chat sessions to git (stage and optionally commit)
@@ -722,7 +1530,13 @@ pub enum GitCommands {
     /// Show git status of chat sessions
     Status {
         /// Project path
+        #[arg(default_value = ".")]
         path: String,
+
+        /// Show working-tree changes in the repository linked to this session
+        /// (ID or prefix), next to its last stamped commit
+        #[arg(long)]
+        session: Option<String>,
     },
 
     /// Create a git tag snapshot of chat sessions
@@ -737,6 +1551,16 @@ pub enum GitCommands {
         /// Snapshot message
         #[arg(short, long)]
         message: Option<String>,
+
+        /// Session to stamp into the commit subject as `[CSM: <id>]`
+        /// (default: the session linked to this repo by `csm link` or
+        /// `harvest git --auto-link`)
+        #[arg(long)]
+        session: Option<String>,
+
+        /// Also attach a git note with the session title and message count
+        #[arg(long)]
+        note: bool,
     },
 
     /// Track chat sessions together with associated file changes
@@ -759,6 +1583,21 @@ pub enum GitCommands {
         /// Create a tag for this tracked state
         #[arg(long)]
         tag: Option<String>,
+
+        /// Session to stamp into the commit subject as `[CSM: <id>]`
+        /// (default: the session linked to this repo by `csm link` or
+        /// `harvest git --auto-link`)
+        #[arg(long)]
+        session: Option<String>,
+
+        /// Stamp the last commit instead of making one: prepend `[CSM: <id>]`
+        /// to its message, keeping the rest of it and its changes
+        #[arg(long, conflicts_with_all = ["message", "all", "files", "tag"])]
+        amend: bool,
+
+        /// With --amend, amend even a commit already pushed to its upstream
+        #[arg(long, requires = "amend")]
+        force: bool,
     },
 
     /// Show history of chat session commits with associated file changes
@@ -773,6 +1612,10 @@ pub enum GitCommands {
         /// Show only commits that include chat session changes
         #[arg(long)]
         sessions_only: bool,
+
+        /// Show only snapshots stamped with this session ID (or prefix)
+        #[arg(long)]
+        session: Option<String>,
     },
 
     /// Diff chat sessions between commits or current state
@@ -791,6 +1634,10 @@ pub enum GitCommands {
         /// Show associated file changes alongside chat diffs
         #[arg(long)]
         with_files: bool,
+
+        /// Combined diff of all commits stamped with this session (ID or prefix)
+        #[arg(long, conflicts_with_all = ["from", "to", "with_files"])]
+        session: Option<String>,
     },
 
     /// Restore chat sessions from a specific commit
@@ -853,7 +1700,11 @@ pub enum MigrationCommands {
 #[derive(Subcommand)]
 pub enum RunCommands {
     /// Launch interactive TUI (Text User Interface)
-    Tui,
+    Tui {
+        /// Reload automatically when sessions change on disk
+        #[arg(long)]
+        watch: bool,
+    },
 }
 
 // ============================================================================
@@ -863,12 +1714,68 @@ pub enum RunCommands {
 #[derive(Subcommand)]
 pub enum ProviderCommands {
     /// List all discovered LLM providers
-    List,
+    List {
+        /// Ping each provider endpoint and show reachability and latency
+        #[arg(long)]
+        probe: bool,
 
-    /// Show detailed info about a specific provider
+        /// Timeout in seconds for each endpoint probe (default: 5)
+        #[arg(long, default_value = "5", requires = "probe")]
+        timeout: u64,
+
+        /// Retry a failed probe up to N times, waiting 250 ms, 500 ms, 1 s, ...
+        /// in between; each provider takes at most (N + 1) x --timeout
+        #[arg(long, default_value = "0", value_name = "N", requires = "probe")]
+        retries: u32,
+    },
+
+    /// Add a custom OpenAI-compatible provider
+    Add {
+        /// Provider name (must not collide with a built-in provider)
+        name: String,
+
+        /// Base URL of the OpenAI-compatible API (e.g. http://gpu-box:8000)
+        #[arg(long)]
+        base_url: String,
+
+        /// Environment variable holding the API key
+        #[arg(long)]
+        api_key_env: Option<String>,
+    },
+
+    /// Remove a custom provider
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Provider name
+        name: String,
+    },
+
+    /// Show detailed info about a provider, or every known provider
     Info {
-        /// Provider name (copilot, cursor, ollama, vllm, foundry, lm-studio, etc.)
-        provider: String,
+        /// Provider name (copilot, cursor, ollama, vllm, foundry, lm-studio, etc.);
+        /// omit to show every provider
+        provider: Option<String>,
+
+        /// Show every known provider, built-in and user-defined (the default
+        /// when no name is given)
+        #[arg(long, conflicts_with = "provider")]
+        all: bool,
+
+        /// Ping each provider's endpoint (in parallel) and show whether it answered
+        #[arg(long)]
+        probe: bool,
+
+        /// Timeout in seconds for each endpoint probe (default: 5)
+        #[arg(long, default_value = "5", requires = "probe")]
+        timeout: u64,
+
+        /// Retry a failed probe up to N times
+        #[arg(long, default_value = "0", value_name = "N", requires = "probe")]
+        retries: u32,
+
+        /// Output as JSON (an array with --all)
+        #[arg(long)]
+        json: bool,
     },
 
     /// Configure a provider
@@ -913,6 +1820,16 @@ pub enum ProviderCommands {
         /// Provider name
         provider: String,
     },
+
+    /// List the models a provider serves (Ollama, LM Studio, vLLM, custom, etc.)
+    Models {
+        /// Provider name (built-in or added with 'provider add')
+        provider: String,
+
+        /// Timeout in seconds for the model-listing request (default: 5)
+        #[arg(long, default_value = "5")]
+        timeout: u64,
+    },
 }
 
 // ============================================================================
@@ -930,8 +1847,31 @@ pub enum DetectCommands {
     /// Detect available providers
     Providers {
         /// Only show providers with sessions
-        #[arg(long)]
+        #[arg(long, conflicts_with = "json")]
         with_sessions: bool,
+
+        /// Ping every provider and print `{name, kind, endpoint, reachable,
+        /// latency_ms}` for each as a JSON array
+        #[arg(long)]
+        json: bool,
+
+        /// Exit non-zero unless this provider is reachable (repeatable or
+        /// comma-separated, e.g. ollama,lm-studio)
+        #[arg(long, value_name = "NAME", value_delimiter = ',')]
+        require: Vec<String>,
+
+        /// Timeout in seconds for each endpoint probe
+        #[arg(long, default_value = "5")]
+        timeout: u64,
+
+        /// Retry a failed probe up to N times, waiting 250 ms, 500 ms, 1 s, ...
+        /// in between; each provider takes at most (N + 1) x --timeout
+        #[arg(long, default_value = "0", value_name = "N")]
+        retries: u32,
+
+        /// Show how many probe attempts each --require provider took
+        #[arg(long, short)]
+        verbose: bool,
     },
 
     /// Detect which provider a session belongs to
@@ -1024,13 +1964,18 @@ pub enum HarvestCommands {
         #[arg(long)]
         web: bool,
 
-        /// Timeout in seconds for web provider checks (default: 5)
+        /// Timeout in seconds for web provider checks and each browser cookie scan (default: 5)
         #[arg(long, default_value = "5")]
         timeout: u64,
 
         /// Show verbose debug output for browser scanning
         #[arg(long, short)]
         verbose: bool,
+
+        /// Print the scan as JSON: providers, workspace and session counts,
+        /// and with --web the endpoint probes and browser logins
+        #[arg(long, conflicts_with = "verbose")]
+        json: bool,
     },
 
     /// Run the harvest to collect sessions from all providers
@@ -1051,6 +1996,11 @@ pub enum HarvestCommands {
         #[arg(long)]
         incremental: bool,
 
+        /// Only harvest sessions active after this git ref's commit date
+        /// (resolved in the current directory's repository)
+        #[arg(long, value_name = "REF")]
+        since_commit: Option<String>,
+
         /// Auto-commit changes to git after harvest
         #[arg(long)]
         commit: bool,
@@ -1088,8 +2038,17 @@ pub enum HarvestCommands {
 
     /// Export sessions from the harvest database
     Export {
-        /// Output file path
-        output: String,
+        /// Output file path (`-` or none for stdout; gzipped if it ends in .gz)
+        output: Option<String>,
+
+        /// Write every discovered session into this SQLite database instead
+        /// (workspaces, sessions and messages tables; re-running updates it)
+        #[arg(
+            long,
+            value_name = "DB",
+            conflicts_with_all = ["output", "path", "format", "provider", "sessions"]
+        )]
+        to: Option<String>,
 
         /// Path to the harvest database
         #[arg(long)]
@@ -1106,6 +2065,10 @@ pub enum HarvestCommands {
         /// Export specific sessions by ID (comma-separated)
         #[arg(long, value_delimiter = ',')]
         sessions: Option<Vec<String>>,
+
+        /// Replace API keys, tokens and email addresses with [REDACTED]
+        #[arg(long)]
+        redact: bool,
     },
 
     /// Import a shared chat session from a URL
@@ -1204,9 +2167,19 @@ pub enum HarvestCommands {
     },
 
     /// Git operations for the harvest database
+    #[command(
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true,
+        arg_required_else_help = true
+    )]
     Git {
+        /// Link every workspace session to the git repository containing its project
+        /// (required unless a subcommand is given)
+        #[arg(long, required = true)]
+        auto_link: bool,
+
         #[command(subcommand)]
-        command: HarvestGitCommands,
+        command: Option<HarvestGitCommands>,
     },
 }
 
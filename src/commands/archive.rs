@@ -0,0 +1,299 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! gzip and tar writers for `export --gzip`, `export --archive` and `.gz`
+//! output paths
+//!
+//! Only the write side is needed, so these are small in-tree encoders rather
+//! than a compression dependency: DEFLATE (RFC 1951) as one block of fixed
+//! Huffman codes over a hash-chain LZ77 matcher, the gzip member around it
+//! (RFC 1952), and ustar headers (POSIX.1-1988). Any gzip or tar reads the
+//! output; files come out somewhat larger than with `gzip`, whose Huffman
+//! codes are built for each block's data.
+//!
+//! These are a stopgap: swap them for `flate2` and `tar` once the crate can
+//! take on those dependencies.
+
+use anyhow::Result;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// CRC-32 (IEEE 802.3, reflected) lookup table
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// The CRC-32 gzip stores for `data`
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// First match length of each length code (257..=285) and its extra bits
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+/// First distance of each distance code (0..=29) and its extra bits
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+const WINDOW: usize = 32 * 1024;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+/// Candidates tried per position; more finds longer matches, slower
+const MAX_CHAIN: usize = 64;
+const HASH_BITS: u32 = 15;
+
+/// Bits packed least significant first, as DEFLATE stores them
+#[derive(Default)]
+struct BitWriter {
+    out: Vec<u8>,
+    bits: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    fn put(&mut self, value: u32, len: u32) {
+        self.bits |= (value as u64) << self.count;
+        self.count += len;
+        while self.count >= 8 {
+            self.out.push(self.bits as u8);
+            self.bits >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// A Huffman code, which DEFLATE stores most significant bit first
+    fn put_code(&mut self, code: u32, len: u32) {
+        self.put(code.reverse_bits() >> (32 - len), len);
+    }
+
+    /// A literal/length symbol in the fixed code
+    fn put_symbol(&mut self, symbol: u32) {
+        match symbol {
+            0..=143 => self.put_code(0x30 + symbol, 8),
+            144..=255 => self.put_code(0x190 + symbol - 144, 9),
+            256..=279 => self.put_code(symbol - 256, 7),
+            _ => self.put_code(0xC0 + symbol - 280, 8),
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.out.push(self.bits as u8);
+        }
+        self.out
+    }
+}
+
+/// Index of the last entry of `bases` that is at most `value`
+fn code_index(bases: &[u16], value: usize) -> usize {
+    bases.partition_point(|&base| base as usize <= value) - 1
+}
+
+fn hash(bytes: &[u8]) -> usize {
+    let key = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+    (key.wrapping_mul(0x9E37_79B1) >> (32 - HASH_BITS)) as usize
+}
+
+/// LZ77 match finder: hash chains over the last [`WINDOW`] bytes
+struct Matcher {
+    /// Most recent position per hash of three bytes
+    head: Vec<usize>,
+    /// The position before each one with the same hash, by position % WINDOW
+    prev: Vec<usize>,
+}
+
+impl Matcher {
+    fn new() -> Self {
+        Self {
+            head: vec![usize::MAX; 1 << HASH_BITS],
+            prev: vec![usize::MAX; WINDOW],
+        }
+    }
+
+    fn insert(&mut self, data: &[u8], pos: usize) {
+        if pos + MIN_MATCH <= data.len() {
+            let h = hash(&data[pos..]);
+            self.prev[pos % WINDOW] = self.head[h];
+            self.head[h] = pos;
+        }
+    }
+
+    /// Length and distance of the longest earlier match for `pos`; a
+    /// length below [`MIN_MATCH`] means none
+    fn longest(&self, data: &[u8], pos: usize) -> (usize, usize) {
+        let (mut best_len, mut best_dist) = (0, 0);
+        if pos + MIN_MATCH > data.len() {
+            return (best_len, best_dist);
+        }
+        let limit = MAX_MATCH.min(data.len() - pos);
+        let mut candidate = self.head[hash(&data[pos..])];
+        let mut chain = MAX_CHAIN;
+        while candidate < pos && pos - candidate <= WINDOW && chain > 0 {
+            let len = data[candidate..]
+                .iter()
+                .zip(&data[pos..pos + limit])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if len > best_len {
+                (best_len, best_dist) = (len, pos - candidate);
+                if len == limit {
+                    break;
+                }
+            }
+            candidate = self.prev[candidate % WINDOW];
+            chain -= 1;
+        }
+        (best_len, best_dist)
+    }
+}
+
+/// Raw DEFLATE stream of `data`: one final block with fixed Huffman codes
+pub fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter::default();
+    out.put(1, 1); // BFINAL
+    out.put(1, 2); // BTYPE 01, fixed Huffman codes
+
+    let mut matcher = Matcher::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        // Search before inserting `pos`, which may evict the oldest candidate
+        let (len, dist) = matcher.longest(data, pos);
+        if len >= MIN_MATCH {
+            let i = code_index(&LENGTH_BASE, len);
+            out.put_symbol(257 + i as u32);
+            out.put(
+                (len - LENGTH_BASE[i] as usize) as u32,
+                LENGTH_EXTRA[i] as u32,
+            );
+            let i = code_index(&DISTANCE_BASE, dist);
+            out.put_code(i as u32, 5);
+            out.put(
+                (dist - DISTANCE_BASE[i] as usize) as u32,
+                DISTANCE_EXTRA[i] as u32,
+            );
+            for p in pos..pos + len {
+                matcher.insert(data, p);
+            }
+            pos += len;
+        } else {
+            out.put_symbol(data[pos] as u32);
+            matcher.insert(data, pos);
+            pos += 1;
+        }
+    }
+    out.put_symbol(256); // end of block
+    out.finish()
+}
+
+/// `data` as a gzip file: header without a name or timestamp, DEFLATE
+/// stream, then the CRC-32 and length of `data`
+pub fn gzip(data: &[u8]) -> Vec<u8> {
+    // Magic, CM 8 (deflate), no flags, MTIME 0, XFL 0, OS 255 (unknown)
+    let mut out = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 255];
+    out.extend(deflate(data));
+    out.extend(crc32(data).to_le_bytes());
+    out.extend((data.len() as u32).to_le_bytes());
+    out
+}
+
+/// An uncompressed ustar archive of regular files, built in memory
+pub struct TarBuilder {
+    data: Vec<u8>,
+    /// Modification time given to every entry, in seconds since the epoch
+    mtime: u64,
+}
+
+impl Default for TarBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TarBuilder {
+    /// An empty archive whose entries are stamped with the current time
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            mtime: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+
+    /// Add a file at `path` (`/`-separated, relative) holding `contents`
+    pub fn append(&mut self, path: &str, contents: &[u8]) -> Result<()> {
+        let (prefix, name) = split_ustar_path(path)
+            .ok_or_else(|| anyhow::anyhow!("path too long for a tar archive: {}", path))?;
+        let mut header = [0u8; 512];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        put_octal(&mut header[100..108], 0o644);
+        put_octal(&mut header[108..116], 0);
+        put_octal(&mut header[116..124], 0);
+        put_octal(&mut header[124..136], contents.len() as u64);
+        put_octal(&mut header[136..148], self.mtime);
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+        // The checksum is summed with its own field as spaces
+        header[148..156].fill(b' ');
+        let checksum: u32 = header.iter().map(|&b| b as u32).sum();
+        put_octal(&mut header[148..155], checksum as u64);
+
+        self.data.extend_from_slice(&header);
+        self.data.extend_from_slice(contents);
+        let padding = (512 - contents.len() % 512) % 512;
+        self.data.resize(self.data.len() + padding, 0);
+        Ok(())
+    }
+
+    /// The archive, closed by its two zero blocks
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        self.data.resize(self.data.len() + 1024, 0);
+        self.data
+    }
+}
+
+/// Split `path` into ustar's prefix (up to 155 bytes) and name (up to 100)
+/// at a `/`, if it fits
+fn split_ustar_path(path: &str) -> Option<(&str, &str)> {
+    if path.len() <= 100 {
+        return Some(("", path));
+    }
+    path.match_indices('/')
+        .map(|(i, _)| (&path[..i], &path[i + 1..]))
+        .find(|(prefix, name)| prefix.len() <= 155 && name.len() <= 100 && !name.is_empty())
+}
+
+/// Zero-padded octal, NUL-terminated, filling `field`
+fn put_octal(field: &mut [u8], value: u64) {
+    let digits = format!("{:0width$o}\0", value, width = field.len() - 1);
+    field.copy_from_slice(&digits.as_bytes()[digits.len() - field.len()..]);
+}
@@ -0,0 +1,146 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! `--format csv` for `list workspaces`, `list sessions` and `find session`
+//!
+//! Rows are written with the `csv` crate, so titles holding commas, quotes or
+//! line breaks stay in one cell, and every cell is stripped of ANSI escapes
+//! whatever the color setting. `--columns` picks and orders the fields by key.
+
+use anyhow::Result;
+use std::fmt;
+use std::str::FromStr;
+
+/// How `list` and `find session` print their results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// ASCII table with a summary footer
+    #[default]
+    Table,
+    /// Comma-separated values with a header row, and nothing else
+    Csv,
+}
+
+impl OutputFormat {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["table", "csv"];
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "unknown output format '{}' (valid formats: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Csv => "csv",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A field `--format csv` can write for rows of type `T`
+pub struct CsvColumn<T> {
+    /// Name given to `--columns`
+    pub key: &'static str,
+    /// Header cell, as the table names the column
+    pub header: &'static str,
+    pub value: fn(&T) -> String,
+}
+
+/// The columns `selected` names, in that order, or `defaults` when it is empty
+pub fn select_columns<'a, T>(
+    available: &'a [CsvColumn<T>],
+    defaults: &[&str],
+    selected: &[String],
+) -> Result<Vec<&'a CsvColumn<T>>> {
+    let keys: Vec<&str> = if selected.is_empty() {
+        defaults.to_vec()
+    } else {
+        selected.iter().map(|k| k.trim()).collect()
+    };
+    keys.iter()
+        .map(|key| {
+            available
+                .iter()
+                .find(|c| c.key.eq_ignore_ascii_case(key))
+                .ok_or_else(|| {
+                    let valid: Vec<&str> = available.iter().map(|c| c.key).collect();
+                    anyhow::anyhow!(
+                        "unknown column '{}' (valid columns: {})",
+                        key,
+                        valid.join(", ")
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Fail when `--columns` is given without `--format csv`, which is the only
+/// output it shapes
+pub fn check_columns(format: OutputFormat, columns: &[String]) -> Result<()> {
+    if format != OutputFormat::Csv && !columns.is_empty() {
+        anyhow::bail!("--columns only applies to --format csv");
+    }
+    Ok(())
+}
+
+/// `rows` as CSV under a header row
+pub fn to_csv<T>(rows: &[T], columns: &[&CsvColumn<T>]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(columns.iter().map(|c| c.header))?;
+    for row in rows {
+        writer.write_record(columns.iter().map(|c| strip_ansi(&(c.value)(row))))?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// `text` without ANSI escape sequences (CSI such as colors, and OSC such as
+/// hyperlinks)
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters and intermediates up to a final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: up to BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Other two-character escapes
+            _ => {}
+        }
+    }
+    out
+}
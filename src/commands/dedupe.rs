@@ -0,0 +1,260 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! `dedupe`: find sessions VS Code copied into more than one workspace
+//!
+//! Every session file is hashed on its conversation alone (the prompt and
+//! response text of each turn, with line endings and surrounding whitespace
+//! normalized), so copies whose IDs, titles or metadata drifted still group
+//! together. Listing is the default; `--remove` keeps the most recently
+//! active copy of each set and deletes the others after a prompt.
+
+use anyhow::Result;
+use blake2::{Blake2s256, Digest};
+use chrono::{TimeZone, Utc};
+use colored::*;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::commands::{confirm, format_bytes};
+use crate::models::{ChatSession, Workspace};
+use crate::providers::session_format::extract_response_text;
+use crate::storage::{parse_session_json, remove_session_from_index};
+use crate::workspace::{discover_workspaces, list_session_files, DiscoveryOptions};
+
+/// One copy of a duplicated session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionCopy {
+    pub path: PathBuf,
+    /// Project path of the workspace, or its hash when it has none
+    pub workspace: String,
+    /// Workspace directory, whose `state.vscdb` indexes the session
+    pub workspace_dir: PathBuf,
+    /// Newest request timestamp, else `lastMessageDate` (ms)
+    pub last_activity: i64,
+    pub size: u64,
+}
+
+/// Sessions with the same conversation, newest copy first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateSet {
+    /// Hex digest of the normalized conversation
+    pub hash: String,
+    pub title: String,
+    pub copies: Vec<SessionCopy>,
+}
+
+/// Text as hashed: `\r\n` line endings folded to `\n`, outer whitespace trimmed
+fn normalize_text(text: &str) -> String {
+    text.replace("\r\n", "\n").trim().to_string()
+}
+
+/// BLAKE2s-256 digest (hex) of a session's prompt and response texts
+///
+/// `None` for sessions with no text at all, which would otherwise all count
+/// as copies of each other (`purge --empty` deals with those).
+pub fn session_content_hash(session: &ChatSession) -> Option<String> {
+    let mut hasher = Blake2s256::new();
+    let mut has_text = false;
+    for request in &session.requests {
+        let user = request
+            .message
+            .as_ref()
+            .and_then(|m| m.text.as_deref())
+            .map(normalize_text)
+            .unwrap_or_default();
+        let assistant = request
+            .response
+            .as_ref()
+            .and_then(extract_response_text)
+            .map(|text| normalize_text(&text))
+            .unwrap_or_default();
+        has_text |= !user.is_empty() || !assistant.is_empty();
+        // Separators keep "ab" + "c" from hashing like "a" + "bc"
+        hasher.update(user.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(assistant.as_bytes());
+        hasher.update([0x1eu8]);
+    }
+    has_text.then(|| {
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    })
+}
+
+/// Hash every session file across all workspaces in parallel, and return
+/// the groups with more than one copy
+pub fn find_duplicate_sessions(discovery: &DiscoveryOptions) -> Result<Vec<DuplicateSet>> {
+    let workspaces: Vec<Workspace> = discover_workspaces(discovery)?
+        .into_iter()
+        .filter(|ws| ws.has_chat_sessions)
+        .collect();
+    let files: Vec<(PathBuf, &Workspace)> = workspaces
+        .iter()
+        .flat_map(|ws| {
+            list_session_files(&ws.chat_sessions_path)
+                .into_iter()
+                .map(move |path| (path, ws))
+        })
+        .collect();
+
+    let hashed: Vec<(String, String, SessionCopy)> = files
+        .par_iter()
+        .filter_map(|(path, ws)| {
+            let content = std::fs::read_to_string(path).ok()?;
+            let session = parse_session_json(&content).ok()?;
+            let hash = session_content_hash(&session)?;
+            let copy = SessionCopy {
+                path: path.clone(),
+                workspace: ws.project_path.clone().unwrap_or_else(|| ws.hash.clone()),
+                workspace_dir: ws.workspace_path.clone(),
+                last_activity: session
+                    .timestamp_range()
+                    .map(|(_, last)| last)
+                    .unwrap_or(session.last_message_date),
+                size: content.len() as u64,
+            };
+            Some((hash, session.title(), copy))
+        })
+        .collect();
+
+    let mut groups: BTreeMap<String, (String, Vec<SessionCopy>)> = BTreeMap::new();
+    for (hash, title, copy) in hashed {
+        groups
+            .entry(hash)
+            .or_insert_with(|| (title, Vec::new()))
+            .1
+            .push(copy);
+    }
+
+    let mut sets: Vec<DuplicateSet> = groups
+        .into_iter()
+        .filter(|(_, (_, copies))| copies.len() > 1)
+        .map(|(hash, (title, mut copies))| {
+            // Newest first; equal times fall back to the path for a stable pick
+            copies.sort_by(|a, b| {
+                b.last_activity
+                    .cmp(&a.last_activity)
+                    .then_with(|| modified(&b.path).cmp(&modified(&a.path)))
+                    .then_with(|| a.path.cmp(&b.path))
+            });
+            DuplicateSet {
+                hash,
+                title,
+                copies,
+            }
+        })
+        .collect();
+    sets.sort_by(|a, b| a.title.cmp(&b.title).then_with(|| a.hash.cmp(&b.hash)));
+    Ok(sets)
+}
+
+fn modified(path: &Path) -> Option<std::time::SystemTime> {
+    path.metadata().and_then(|m| m.modified()).ok()
+}
+
+fn format_activity(timestamp: i64) -> String {
+    (timestamp > 0)
+        .then(|| Utc.timestamp_millis_opt(timestamp).single())
+        .flatten()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// List duplicate sessions and, with `remove`, delete all but the newest
+/// copy of each (prompting first unless `yes`)
+pub fn dedupe_sessions(remove: bool, yes: bool, discovery: &DiscoveryOptions) -> Result<()> {
+    let sets = find_duplicate_sessions(discovery)?;
+    if sets.is_empty() {
+        println!("{} No duplicate sessions found", "[OK]".green());
+        return Ok(());
+    }
+
+    for set in &sets {
+        println!(
+            "\n{} {} copies of \"{}\" ({})",
+            "[=]".blue(),
+            set.copies.len(),
+            set.title.bold(),
+            &set.hash[..12]
+        );
+        for (i, copy) in set.copies.iter().enumerate() {
+            let marker = if i == 0 {
+                "[keep]".green()
+            } else {
+                "[-]".red()
+            };
+            println!(
+                "   {} {} ({}, {})",
+                marker,
+                copy.path.display(),
+                copy.workspace,
+                format_activity(copy.last_activity)
+            );
+        }
+    }
+
+    let redundant: Vec<&SessionCopy> = sets.iter().flat_map(|s| &s.copies[1..]).collect();
+    let total_size: u64 = redundant.iter().map(|c| c.size).sum();
+    println!(
+        "\n{} Found {} duplicate set(s) with {} redundant copy(ies) ({})",
+        "[!]".yellow(),
+        sets.len(),
+        redundant.len(),
+        format_bytes(total_size)
+    );
+
+    if !remove {
+        println!(
+            "{} DRY RUN - Nothing was deleted. Run with --remove to keep only the newest copy of each.",
+            "[!]".yellow()
+        );
+        return Ok(());
+    }
+    if !yes && !confirm(&format!("\nDelete {} older copy(ies)?", redundant.len()))? {
+        println!("{} Aborted, nothing was deleted", "[i]".cyan());
+        return Ok(());
+    }
+
+    let mut removed = 0;
+    let mut reclaimed = 0;
+    for copy in redundant {
+        if let Err(e) = std::fs::remove_file(&copy.path) {
+            println!(
+                "{} Failed to remove {}: {}",
+                "[X]".red(),
+                copy.path.display(),
+                e
+            );
+            continue;
+        }
+        removed += 1;
+        reclaimed += copy.size;
+
+        // Drop the workspace's index entry too, so VS Code doesn't list a
+        // session it can no longer open
+        let db_path = copy.workspace_dir.join("state.vscdb");
+        let session_id = copy.path.file_stem().map(|s| s.to_string_lossy());
+        if let (true, Some(id)) = (db_path.exists(), session_id) {
+            if let Err(e) = remove_session_from_index(&db_path, &id) {
+                println!(
+                    "{} Removed {} but could not update its index: {}",
+                    "[!]".yellow(),
+                    copy.path.display(),
+                    e
+                );
+            }
+        }
+    }
+
+    println!(
+        "\n{} Removed {} duplicate session(s), reclaimed {}",
+        "[OK]".green(),
+        removed,
+        format_bytes(reclaimed)
+    );
+    Ok(())
+}
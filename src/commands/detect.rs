@@ -5,14 +5,16 @@
 use anyhow::Result;
 use colored::*;
 
+use crate::commands::{probe_providers, ProviderProbe};
 use crate::models::Workspace;
+use crate::providers::openai_compat::CUSTOM_PROBE_TIMEOUT;
 use crate::providers::{ProviderRegistry, ProviderType};
 use crate::workspace::{
-    discover_workspaces, find_workspace_by_path, get_chat_sessions_from_workspace,
+    discover_workspaces, find_workspace_by_path, get_chat_sessions_from_workspace, DiscoveryOptions,
 };
 
 /// Detect workspace information for a given path
-pub fn detect_workspace(path: Option<&str>) -> Result<()> {
+pub fn detect_workspace(path: Option<&str>, discovery: &DiscoveryOptions) -> Result<()> {
     let project_path = path.map(|p| p.to_string()).unwrap_or_else(|| {
         std::env::current_dir()
             .map(|p| p.to_string_lossy().to_string())
@@ -23,7 +25,7 @@ pub fn detect_workspace(path: Option<&str>) -> Result<()> {
     println!("{}", "=".repeat(60));
     println!("{} Path: {}", "[*]".blue(), project_path.cyan());
 
-    match find_workspace_by_path(&project_path)? {
+    match find_workspace_by_path(&project_path, discovery)? {
         Some((ws_id, ws_dir, ws_name)) => {
             println!("\n{} Workspace Found!", "[+]".green().bold());
             println!("   {} ID: {}", "[*]".blue(), &ws_id[..16.min(ws_id.len())]);
@@ -59,7 +61,7 @@ pub fn detect_workspace(path: Option<&str>) -> Result<()> {
             );
 
             // Check if there are similar workspaces
-            let all_workspaces = discover_workspaces()?;
+            let all_workspaces = discover_workspaces(discovery)?;
             let path_lower = project_path.to_lowercase();
             let similar: Vec<&Workspace> = all_workspaces
                 .iter()
@@ -162,6 +164,36 @@ pub fn detect_providers(with_sessions: bool) -> Result<()> {
         }
     }
 
+    // User-defined providers (`provider add`), probed here rather than
+    // when the registry is built
+    for provider in registry.custom_providers() {
+        if with_sessions {
+            continue;
+        }
+        found_count += 1;
+
+        let status = match provider.fetch_models(CUSTOM_PROBE_TIMEOUT) {
+            Some(Ok(models)) => {
+                format!(
+                    "{} ({} models)",
+                    "+".green(),
+                    models.len().to_string().cyan()
+                )
+            }
+            _ => format!("{} not available", "x".red()),
+        };
+        println!(
+            "   {} {} {}: {}",
+            "[*]".blue(),
+            provider.name().bold(),
+            "(custom)".dimmed(),
+            status
+        );
+        if let Some(endpoint) = provider.endpoint() {
+            println!("      {} Endpoint: {}", "`".dimmed(), endpoint.dimmed());
+        }
+    }
+
     println!("\n{} Summary:", "[*]".green().bold());
     println!("   {} providers available", found_count.to_string().cyan());
     println!(
@@ -172,8 +204,81 @@ pub fn detect_providers(with_sessions: bool) -> Result<()> {
     Ok(())
 }
 
+/// Probe every provider for `detect providers --json` / `--require`
+///
+/// With `json`, prints an array of [`ProviderProbe`]s; otherwise reports
+/// each required provider. Fails if a `require`d provider wasn't discovered
+/// or didn't answer within `timeout_secs`, so CI can gate on it.
+pub fn check_providers(
+    json: bool,
+    require: &[String],
+    timeout_secs: u64,
+    retries: u32,
+    verbose: bool,
+) -> Result<()> {
+    let registry = ProviderRegistry::new();
+    let probes = probe_providers(
+        &registry,
+        std::time::Duration::from_secs(timeout_secs),
+        retries,
+    );
+
+    if json {
+        println!("{}", crate::commands::to_json(&probes)?);
+    } else if !require.is_empty() {
+        println!("\n{} Required Providers:", "[*]".green().bold());
+    }
+
+    let mut missing = Vec::new();
+    for name in require {
+        let probe = probes.iter().find(|p| p.matches(name));
+        if !json {
+            print_required_provider(name, probe, verbose);
+        }
+        if !probe.is_some_and(|p| p.reachable) {
+            missing.push(name.as_str());
+        }
+    }
+    if !missing.is_empty() {
+        anyhow::bail!("Required provider(s) unreachable: {}", missing.join(", "));
+    }
+    Ok(())
+}
+
+/// One `--require` line; `verbose` adds how many probes were sent
+fn print_required_provider(name: &str, probe: Option<&ProviderProbe>, verbose: bool) {
+    let attempts = |probe: &ProviderProbe| match probe.attempts {
+        n if verbose && n > 0 => format!(", {} attempt(s)", n),
+        _ => String::new(),
+    };
+    match probe {
+        Some(probe) if probe.reachable => match probe.latency_ms {
+            Some(ms) => println!(
+                "   {} {} reachable ({} ms{})",
+                "[OK]".green(),
+                name,
+                ms,
+                attempts(probe)
+            ),
+            None => println!("   {} {} found", "[OK]".green(), name),
+        },
+        Some(probe) => println!(
+            "   {} {} unreachable ({}{})",
+            "[X]".red(),
+            name,
+            probe.error.as_deref().unwrap_or("no data found"),
+            attempts(probe)
+        ),
+        None => println!("   {} {} not found", "[X]".red(), name),
+    }
+}
+
 /// Detect which provider a session belongs to
-pub fn detect_session(session_id: &str, path: Option<&str>) -> Result<()> {
+pub fn detect_session(
+    session_id: &str,
+    path: Option<&str>,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
     println!("\n{} Detecting Session Provider", "[D]".blue().bold());
     println!("{}", "=".repeat(60));
     println!("{} Session: {}", "[*]".blue(), session_id.cyan());
@@ -190,7 +295,7 @@ pub fn detect_session(session_id: &str, path: Option<&str>) -> Result<()> {
     });
 
     // Check in VS Code/Copilot workspaces
-    if let Ok(Some((_ws_id, ws_dir, ws_name))) = find_workspace_by_path(&project_path) {
+    if let Ok(Some((_ws_id, ws_dir, ws_name))) = find_workspace_by_path(&project_path, discovery) {
         if let Ok(sessions) = get_chat_sessions_from_workspace(&ws_dir) {
             for swp in &sessions {
                 let sid = swp
@@ -289,7 +394,7 @@ pub fn detect_session(session_id: &str, path: Option<&str>) -> Result<()> {
 }
 
 /// Detect everything (workspace, providers, sessions) for a path
-pub fn detect_all(path: Option<&str>, verbose: bool) -> Result<()> {
+pub fn detect_all(path: Option<&str>, verbose: bool, discovery: &DiscoveryOptions) -> Result<()> {
     let project_path = path.map(|p| p.to_string()).unwrap_or_else(|| {
         std::env::current_dir()
             .map(|p| p.to_string_lossy().to_string())
@@ -303,7 +408,7 @@ pub fn detect_all(path: Option<&str>, verbose: bool) -> Result<()> {
 
     // 1. Workspace Detection
     println!("{} Workspace", "---".dimmed());
-    let workspace_info = find_workspace_by_path(&project_path)?;
+    let workspace_info = find_workspace_by_path(&project_path, discovery)?;
 
     match &workspace_info {
         Some((ws_id, ws_dir, ws_name)) => {
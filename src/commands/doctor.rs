@@ -0,0 +1,338 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Environment and storage diagnostics
+
+use anyhow::Result;
+use colored::*;
+
+use crate::providers::{CsmConfig, ProviderRegistry};
+use crate::workspace::{
+    channel_storage_path, default_workspace_storage_path, get_workspace_storage_path,
+    remote_storage_paths, scan_workspaces, DiscoveryOptions, VsCodeChannel, STORAGE_PATH_ENV,
+};
+
+/// Outcome of a single `doctor` check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but something is likely missing or off
+    Warn,
+    /// Critical: chat history can't be read; `doctor` exits non-zero
+    Fail,
+}
+
+/// A named `doctor` check and what it found
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub status: CheckStatus,
+    pub name: &'static str,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(status: CheckStatus, name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            name,
+            detail: detail.into(),
+        }
+    }
+
+    fn print(&self) {
+        let marker = match self.status {
+            CheckStatus::Pass => "[OK]".green(),
+            CheckStatus::Warn => "[!]".yellow(),
+            CheckStatus::Fail => "[X]".red(),
+        };
+        println!("{} {}: {}", marker, self.name.bold(), self.detail);
+    }
+}
+
+/// Whether `CSM_STORAGE_PATH` replaces VS Code's default storage location
+fn check_storage_override() -> Check {
+    let default = default_workspace_storage_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    match std::env::var_os(STORAGE_PATH_ENV).filter(|p| !p.is_empty()) {
+        Some(path) => Check::new(
+            CheckStatus::Pass,
+            "Storage override",
+            format!(
+                "{} is set to {} (default: {})",
+                STORAGE_PATH_ENV,
+                path.to_string_lossy(),
+                default
+            ),
+        ),
+        None => Check::new(
+            CheckStatus::Pass,
+            "Storage override",
+            format!("{} not set, using {}", STORAGE_PATH_ENV, default),
+        ),
+    }
+}
+
+/// Whether the workspaceStorage directory exists and can be listed
+fn check_storage_path(discovery: &DiscoveryOptions) -> Check {
+    let name = "Storage path";
+    let path = match get_workspace_storage_path() {
+        Ok(path) => path,
+        Err(e) => return Check::new(CheckStatus::Fail, name, e.to_string()),
+    };
+    let overridden = std::env::var_os(STORAGE_PATH_ENV).is_some_and(|p| !p.is_empty());
+    let insiders_only = !overridden
+        && discovery.channel.includes(VsCodeChannel::Insiders)
+        && channel_storage_path(VsCodeChannel::Insiders).is_ok_and(|p| p.exists());
+    if !path.exists() && insiders_only {
+        return Check::new(
+            CheckStatus::Warn,
+            name,
+            format!(
+                "{} does not exist; only VS Code Insiders storage is read",
+                path.display()
+            ),
+        );
+    }
+    // On the remote host of a Remote - SSH session only the server has storage
+    let remote_only = !overridden
+        && remote_storage_paths(discovery.channel)
+            .iter()
+            .any(|p| p.exists());
+    if !path.exists() && remote_only {
+        return Check::new(
+            CheckStatus::Warn,
+            name,
+            format!(
+                "{} does not exist; only VS Code Server storage is read",
+                path.display()
+            ),
+        );
+    }
+    if !path.exists() {
+        return Check::new(
+            CheckStatus::Fail,
+            name,
+            format!(
+                "{} does not exist (is VS Code installed, or should {} point elsewhere?)",
+                path.display(),
+                STORAGE_PATH_ENV
+            ),
+        );
+    }
+    match std::fs::read_dir(&path) {
+        Ok(_) => Check::new(CheckStatus::Pass, name, path.display().to_string()),
+        Err(e) => Check::new(
+            CheckStatus::Fail,
+            name,
+            format!("{} is not readable: {}", path.display(), e),
+        ),
+    }
+}
+
+/// Whether VS Code Insiders keeps its own workspaceStorage, and whether
+/// `--channel` reads it
+fn check_insiders_storage(discovery: &DiscoveryOptions) -> Check {
+    let name = "Insiders storage";
+    if std::env::var_os(STORAGE_PATH_ENV).is_some_and(|p| !p.is_empty()) {
+        return Check::new(
+            CheckStatus::Pass,
+            name,
+            format!("not checked, {} is set", STORAGE_PATH_ENV),
+        );
+    }
+    let path = match channel_storage_path(VsCodeChannel::Insiders) {
+        Ok(path) => path,
+        Err(e) => return Check::new(CheckStatus::Warn, name, e.to_string()),
+    };
+    if !path.exists() {
+        return Check::new(
+            CheckStatus::Pass,
+            name,
+            format!("none ({} does not exist)", path.display()),
+        );
+    }
+    if discovery.channel.includes(VsCodeChannel::Insiders) {
+        Check::new(
+            CheckStatus::Pass,
+            name,
+            format!("{} (read alongside Stable)", path.display()),
+        )
+    } else {
+        Check::new(
+            CheckStatus::Warn,
+            name,
+            format!(
+                "{} exists but --channel leaves its sessions out",
+                path.display()
+            ),
+        )
+    }
+}
+
+/// Whether a VS Code Server (or Cursor server) keeps workspaceStorage here,
+/// as on the remote host of a Remote - SSH session
+fn check_remote_storage(discovery: &DiscoveryOptions) -> Check {
+    let name = "Remote storage";
+    if std::env::var_os(STORAGE_PATH_ENV).is_some_and(|p| !p.is_empty()) {
+        return Check::new(
+            CheckStatus::Pass,
+            name,
+            format!("not checked, {} is set", STORAGE_PATH_ENV),
+        );
+    }
+    let found: Vec<String> = remote_storage_paths(discovery.channel)
+        .into_iter()
+        .filter(|p| p.exists())
+        .map(|p| p.display().to_string())
+        .collect();
+    let detail = if found.is_empty() {
+        "none (no VS Code Server storage in the home directory)".to_string()
+    } else {
+        format!("{} (read as remote)", found.join(", "))
+    };
+    Check::new(CheckStatus::Pass, name, detail)
+}
+
+/// How many workspaces and sessions discovery finds
+fn check_workspaces(discovery: &DiscoveryOptions) -> Check {
+    let name = "Workspaces";
+    let scan = match scan_workspaces(discovery) {
+        Ok(scan) => scan,
+        Err(e) => return Check::new(CheckStatus::Fail, name, e.to_string()),
+    };
+    let with_chats = scan
+        .workspaces
+        .iter()
+        .filter(|ws| ws.has_chat_sessions)
+        .count();
+    let sessions: usize = scan.workspaces.iter().map(|ws| ws.chat_session_count).sum();
+
+    let mut detail = format!(
+        "{} found, {} with chats, {} session(s)",
+        scan.workspaces.len(),
+        with_chats,
+        sessions
+    );
+    if scan.excluded > 0 {
+        detail.push_str(&format!(", {} excluded", scan.excluded));
+    }
+    if !scan.errors.is_empty() {
+        detail.push_str(&format!(
+            "; {} dir(s) could not be fully read (see `list workspaces --verbose`)",
+            scan.errors.len()
+        ));
+    }
+
+    let status = if sessions == 0 || !scan.errors.is_empty() {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Pass
+    };
+    Check::new(status, name, detail)
+}
+
+/// Whether `git` is on PATH, for the `git` commands
+fn check_git() -> Check {
+    let name = "git";
+    match std::process::Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => Check::new(
+            CheckStatus::Pass,
+            name,
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ),
+        Ok(output) => Check::new(
+            CheckStatus::Warn,
+            name,
+            format!("`git --version` failed ({})", output.status),
+        ),
+        Err(_) => Check::new(
+            CheckStatus::Warn,
+            name,
+            "not found on PATH (needed by the `git` commands)",
+        ),
+    }
+}
+
+/// Whether the config file parses, and which providers it and discovery found
+fn check_providers() -> Check {
+    let name = "Providers";
+    let config = match CsmConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            let path = CsmConfig::config_path()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|_| "config".to_string());
+            return Check::new(
+                CheckStatus::Fail,
+                name,
+                format!("{} is invalid: {}", path, e),
+            );
+        }
+    };
+
+    let configured: Vec<String> = config
+        .providers
+        .iter()
+        .filter(|p| p.enabled)
+        .map(|p| p.display_name())
+        .collect();
+    let registry = ProviderRegistry::new();
+    let available: Vec<&str> = registry
+        .available_providers()
+        .into_iter()
+        .map(|p| p.name())
+        .collect();
+
+    let list = |names: &[&str]| {
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    };
+    let configured: Vec<&str> = configured.iter().map(String::as_str).collect();
+    let detail = format!(
+        "configured: {}; available: {}",
+        list(&configured),
+        list(&available)
+    );
+    let status = if available.is_empty() {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Pass
+    };
+    Check::new(status, name, detail)
+}
+
+/// Run every `doctor` check, in display order
+pub fn doctor_checks(discovery: &DiscoveryOptions) -> Vec<Check> {
+    vec![
+        check_storage_override(),
+        check_storage_path(discovery),
+        check_insiders_storage(discovery),
+        check_remote_storage(discovery),
+        check_workspaces(discovery),
+        check_git(),
+        check_providers(),
+    ]
+}
+
+/// Diagnose the environment and storage, printing each check. Returns
+/// whether every critical check passed.
+pub fn run_doctor(discovery: &DiscoveryOptions) -> Result<bool> {
+    let checks = doctor_checks(discovery);
+    for check in &checks {
+        check.print();
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    let failed = count(CheckStatus::Fail);
+    println!(
+        "\n{} {} passed, {} warning(s), {} failed",
+        "[=]".blue(),
+        count(CheckStatus::Pass),
+        count(CheckStatus::Warn),
+        failed
+    );
+    Ok(failed == 0)
+}
@@ -3,60 +3,1226 @@
 //! Export and import commands
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use colored::*;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::commands::{
+    ensure_dir, export_dir, export_status, flatten_threads, gzip, is_stdout_target,
+    parse_date_range, print_dry_run, FlattenThreads, OutputSink, PlannedAction, TarBuilder,
+};
+use crate::models::{ChatSession, Workspace};
+use crate::providers::session_format::{
+    code_extension, extract_response_text, session_to_html, session_to_markdown, session_to_text,
+    sessions_to_combined_html, sessions_to_combined_markdown, sessions_to_combined_text,
+    set_response_text, split_code_blocks, TemplateContext,
+};
+use crate::providers::CsmConfig;
+use crate::redact::{Anonymizer, Redactor};
+use crate::storage::{copy_file_atomic, parse_session_json};
+use crate::template::Template;
+use crate::workspace::{
+    discover_workspaces, get_workspace_by_hash, get_workspace_by_path, DiscoveryOptions,
+};
+
+/// Output format for exported sessions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Raw VS Code session JSON (copied as-is)
+    #[default]
+    Json,
+    /// Markdown transcript
+    Markdown,
+    /// Standalone HTML document
+    Html,
+    /// Plain, un-styled text transcript
+    Text,
+}
 
-use crate::models::Workspace;
-use crate::workspace::{get_workspace_by_hash, get_workspace_by_path};
+impl ExportFormat {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["json", "md", "html", "txt"];
+
+    /// File extension for this format (without the dot)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Text => "txt",
+        }
+    }
 
-/// Export chat sessions from a workspace
-pub fn export_sessions(destination: &str, hash: Option<&str>, path: Option<&str>) -> Result<()> {
-    let workspace = if let Some(h) = hash {
-        get_workspace_by_hash(h)?.context(format!("Workspace not found with hash: {}", h))?
-    } else if let Some(p) = path {
-        get_workspace_by_path(p)?.context(format!("Workspace not found for path: {}", p))?
+    /// Transcript renderer of this format; `None` for JSON, which is written
+    /// from the session file itself
+    fn renderer(&self) -> Option<fn(&ChatSession) -> String> {
+        match self {
+            ExportFormat::Json => None,
+            ExportFormat::Markdown => Some(session_to_markdown),
+            ExportFormat::Html => Some(session_to_html),
+            ExportFormat::Text => Some(session_to_text),
+        }
+    }
+
+    /// Render a session file in this format, keeping only the `turns`
+    /// selected and scrubbing secrets with `redactor` and local paths with
+    /// `anonymizer` if given
+    ///
+    /// JSON keeps every branch of a turn as recorded, whatever the
+    /// `--flatten-threads` mode.
+    pub fn render_file(
+        &self,
+        src_path: &Path,
+        redactor: Option<&Redactor>,
+        anonymizer: Option<&Anonymizer>,
+        turns: TurnSelection,
+    ) -> Result<Vec<u8>> {
+        let Some(renderer) = self.renderer() else {
+            if redactor.is_none() && anonymizer.is_none() && turns.messages.is_none() {
+                return Ok(std::fs::read(src_path)?);
+            }
+            // Edit the raw JSON so fields the model doesn't know survive, and
+            // redact string values only, so the output is still valid JSON
+            let content = std::fs::read_to_string(src_path)?;
+            let mut value: serde_json::Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse session: {}", src_path.display()))?;
+            if let Some(range) = turns.messages {
+                if let Some(requests) = value.get_mut("requests").and_then(|r| r.as_array_mut()) {
+                    range.apply(requests, src_path);
+                }
+            }
+            if let Some(redactor) = redactor {
+                redactor.redact_json(&mut value);
+            }
+            if let Some(anonymizer) = anonymizer {
+                anonymizer.anonymize_json(&mut value);
+            }
+            return Ok(serde_json::to_string_pretty(&value)?.into_bytes());
+        };
+
+        let content = std::fs::read_to_string(src_path)?;
+        let mut session = parse_session_json(&content)
+            .with_context(|| format!("Failed to parse session: {}", src_path.display()))?;
+        turns.apply(&mut session, src_path);
+        Ok(scrub(renderer(&session), redactor, anonymizer).into_bytes())
+    }
+}
+
+/// A 1-based, inclusive range of turns for `export --messages`: `5`, `3-7` or `10-`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageRange {
+    pub start: usize,
+    /// Last turn to keep; `None` runs to the end of the session
+    pub end: Option<usize>,
+}
+
+impl MessageRange {
+    /// Keep only this range of `items`, clamping (with a warning naming
+    /// `src_path`) when it runs past the end of the session
+    pub fn apply<T>(&self, items: &mut Vec<T>, src_path: &Path) {
+        let len = items.len();
+        let end = self.end.unwrap_or(len);
+        if self.start > len || end > len {
+            eprintln!(
+                "{} {}: --messages {} is past the last turn ({}), clamping",
+                "[!]".yellow(),
+                src_path.display(),
+                self,
+                len
+            );
+        }
+        let end = end.min(len);
+        let start = (self.start - 1).min(end);
+        items.truncate(end);
+        items.drain(..start);
+    }
+}
+
+impl FromStr for MessageRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid message range '{}' (expected N, N-M or N-, counting from 1)",
+                s
+            )
+        };
+        let index = |v: &str| match v.trim().parse::<usize>() {
+            Ok(n) if n >= 1 => Ok(n),
+            _ => Err(invalid()),
+        };
+
+        let (start, end) = match s.split_once('-') {
+            None => {
+                let n = index(s)?;
+                (n, Some(n))
+            }
+            Some((start, "")) => (index(start)?, None),
+            Some((start, end)) => (index(start)?, Some(index(end)?)),
+        };
+        if end.is_some_and(|end| end < start) {
+            return Err(invalid());
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl fmt::Display for MessageRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.end {
+            Some(end) if end == self.start => write!(f, "{}", self.start),
+            Some(end) => write!(f, "{}-{}", self.start, end),
+            None => write!(f, "{}-", self.start),
+        }
+    }
+}
+
+/// The turns of each session an export renders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TurnSelection {
+    /// `--messages`: only this range of turns
+    pub messages: Option<MessageRange>,
+    /// `--flatten-threads`: which versions of a branched turn
+    pub threads: FlattenThreads,
+}
+
+impl TurnSelection {
+    /// Keep the `messages` range of `session`'s turns, counted as recorded,
+    /// then flatten the branches of those left
+    pub fn apply(&self, session: &mut ChatSession, src_path: &Path) {
+        if let Some(range) = self.messages {
+            range.apply(&mut session.requests, src_path);
+        }
+        flatten_threads(session, self.threads);
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "html" | "htm" => Ok(ExportFormat::Html),
+            "txt" | "text" => Ok(ExportFormat::Text),
+            _ => Err(format!(
+                "unknown export format '{}' (valid formats: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// How batch exports arrange files under the destination directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportLayout {
+    /// Every file directly in the destination
+    #[default]
+    Flat,
+    /// `<workspace-name>/<session>.<ext>`
+    ByWorkspace,
+    /// `YYYY/MM/<session>.<ext>`, from the session's last activity date
+    ByDate,
+}
+
+impl ExportLayout {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["flat", "by-workspace", "by-date"];
+}
+
+impl FromStr for ExportLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "flat" => Ok(ExportLayout::Flat),
+            "by-workspace" | "workspace" => Ok(ExportLayout::ByWorkspace),
+            "by-date" | "date" => Ok(ExportLayout::ByDate),
+            _ => Err(format!(
+                "unknown export layout '{}' (valid layouts: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ExportLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ExportLayout::Flat => "flat",
+            ExportLayout::ByWorkspace => "by-workspace",
+            ExportLayout::ByDate => "by-date",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Turn a workspace name into a single safe path component
+pub fn sanitize_dir_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let sanitized = sanitized.trim().trim_matches('.');
+    if sanitized.is_empty() {
+        "workspace".to_string()
     } else {
-        anyhow::bail!("Must specify either --hash or --path");
+        sanitized.to_string()
+    }
+}
+
+/// Date of a session's last activity, used by `export --since/--until`
+///
+/// Uses the newest request or `lastMessageDate` timestamp from the session
+/// content, falling back to the file modification time.
+pub fn session_activity_date(src_path: &Path) -> Option<NaiveDate> {
+    let from_content = std::fs::read_to_string(src_path)
+        .ok()
+        .and_then(|content| parse_session_json(&content).ok())
+        .and_then(|session| session_last_activity(&session))
+        .and_then(|ms| Utc.timestamp_millis_opt(ms).single());
+
+    from_content
+        .or_else(|| {
+            let modified = src_path.metadata().ok()?.modified().ok()?;
+            Some(modified.into())
+        })
+        .map(|t: DateTime<Utc>| t.date_naive())
+}
+
+/// Newest request or `lastMessageDate` timestamp of a session (ms), if it
+/// records any
+pub fn session_last_activity(session: &ChatSession) -> Option<i64> {
+    let newest = session
+        .timestamp_range()
+        .map_or(session.last_message_date, |(_, max)| {
+            max.max(session.last_message_date)
+        });
+    (newest > 0).then_some(newest)
+}
+
+/// A user-supplied `--template`, used in place of `--format`
+pub struct ExportTemplate {
+    template: Template,
+    /// Output extension, from the template name with any `.tera` suffix removed
+    extension: String,
+}
+
+impl ExportTemplate {
+    /// Load and validate a template file
+    pub fn load(path: &Path) -> Result<Self> {
+        let template = Template::load(path)?;
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string());
+        let name = name.as_deref().unwrap_or_default();
+        let extension = Path::new(name.strip_suffix(".tera").unwrap_or(name))
+            .extension()
+            .map(|e| e.to_string_lossy().to_string())
+            .unwrap_or_else(|| "txt".to_string());
+        Ok(Self {
+            template,
+            extension,
+        })
+    }
+
+    /// Render a session file, keeping only the `turns` selected and
+    /// scrubbing secrets with `redactor` and local paths with `anonymizer`
+    /// if given
+    pub fn render_file(
+        &self,
+        src_path: &Path,
+        workspace: &str,
+        redactor: Option<&Redactor>,
+        anonymizer: Option<&Anonymizer>,
+        turns: TurnSelection,
+    ) -> Result<Vec<u8>> {
+        let content = std::fs::read_to_string(src_path)?;
+        let mut session = parse_session_json(&content)
+            .with_context(|| format!("Failed to parse session: {}", src_path.display()))?;
+        turns.apply(&mut session, src_path);
+        let rendered = self.render(&session, workspace, src_path)?;
+        Ok(scrub(rendered, redactor, anonymizer).into_bytes())
+    }
+
+    /// Render a parsed session, read from `src_path`
+    fn render(&self, session: &ChatSession, workspace: &str, src_path: &Path) -> Result<String> {
+        let context = serde_json::to_value(TemplateContext::new(session, workspace))?;
+        self.template
+            .render(&context)
+            .with_context(|| format!("Failed to render {}", src_path.display()))
+    }
+}
+
+/// Load the `--template` file, if one was given
+pub fn load_template(path: Option<&str>) -> Result<Option<ExportTemplate>> {
+    path.map(|p| ExportTemplate::load(Path::new(p))).transpose()
+}
+
+/// Label used for `workspace` in template contexts
+fn workspace_label(workspace: &Workspace) -> String {
+    workspace
+        .project_path
+        .clone()
+        .unwrap_or_else(|| workspace.hash.clone())
+}
+
+/// Print the JSON context a `--template` sees for a session
+pub fn export_dump_context(session_id: &str, discovery: &DiscoveryOptions) -> Result<()> {
+    let (_, workspace, path) = crate::commands::find_session_workspace(session_id, discovery)?;
+    let content = std::fs::read_to_string(&path)?;
+    let session = parse_session_json(&content)
+        .with_context(|| format!("Failed to parse session: {}", path.display()))?;
+    let context = TemplateContext::new(&session, &workspace_label(&workspace));
+    println!("{}", serde_json::to_string_pretty(&context)?);
+    Ok(())
+}
+
+/// Build the redactor for `--redact`: built-in patterns plus config `redact_patterns`
+pub fn load_redactor(redact: bool) -> Result<Option<Redactor>> {
+    if !redact {
+        return Ok(None);
+    }
+    let config = CsmConfig::load()?;
+    let redactor =
+        Redactor::new(&config.redact_patterns).context("Check `redact_patterns` in config")?;
+    Ok(Some(redactor))
+}
+
+/// Apply `--redact`, then `--anonymize`, to rendered output
+pub(crate) fn scrub(
+    rendered: String,
+    redactor: Option<&Redactor>,
+    anonymizer: Option<&Anonymizer>,
+) -> String {
+    let rendered = match redactor {
+        Some(redactor) => redactor.redact(&rendered),
+        None => rendered,
     };
+    match anonymizer {
+        Some(anonymizer) => anonymizer.anonymize(&rendered),
+        None => rendered,
+    }
+}
 
-    if !workspace.has_chat_sessions {
-        println!("No chat sessions to export.");
-        return Ok(());
+/// How `export` renders each session
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Output format, unless `template` is set
+    pub format: ExportFormat,
+    /// Scrub secrets (see [`load_redactor`])
+    pub redact: bool,
+    /// Replace the home directory, username and project path with placeholders
+    pub anonymize: bool,
+    /// Template file to render with instead of `format`
+    pub template: Option<String>,
+    /// Only export this range of turns from each session
+    pub messages: Option<MessageRange>,
+    /// Which versions of edited or regenerated turns to render
+    pub threads: FlattenThreads,
+    /// Directory structure under the destination
+    pub layout: ExportLayout,
+    /// Move fenced code blocks into `code/` files the transcript refers to
+    pub split_by_code: bool,
+    /// Gzip each file written, adding `.gz` to its name
+    pub gzip: bool,
+    /// Write one `.tar.gz` at the destination instead of a directory
+    pub archive: bool,
+}
+
+impl ExportOptions {
+    /// The turns of each session to render
+    pub fn turns(&self) -> TurnSelection {
+        TurnSelection {
+            messages: self.messages,
+            threads: self.threads,
+        }
+    }
+}
+
+/// Where an [`ExportWriter`] puts its files
+enum ExportOutput {
+    /// Plain files under the root directory
+    Files,
+    /// `--gzip`: gzipped files under the root directory, named `<file>.gz`
+    Gzip,
+    /// `--archive`: entries under the root of a tarball that
+    /// [`ExportWriter::finish`] writes, gzipped, to `path`
+    Archive { path: PathBuf, tar: TarBuilder },
+}
+
+/// Writes exported sessions under a destination directory (or into an
+/// archive) following an [`ExportLayout`], keeping file and workspace
+/// directory names unique
+struct ExportWriter {
+    /// The destination directory, or for `--archive`, the directory its
+    /// entries sit under
+    root: PathBuf,
+    output: ExportOutput,
+    layout: ExportLayout,
+    format: ExportFormat,
+    template: Option<ExportTemplate>,
+    redactor: Option<Redactor>,
+    /// Current user's anonymizer; each session adds its workspace's project path
+    anonymizer: Option<Anonymizer>,
+    turns: TurnSelection,
+    /// Directory name per workspace hash, for `by-workspace`
+    workspace_dirs: HashMap<String, String>,
+    /// Files written so far, relative to `root`, so later sessions don't
+    /// overwrite them
+    written: HashSet<PathBuf>,
+    /// Sessions written per directory, relative to `root`
+    counts: BTreeMap<PathBuf, usize>,
+    split_by_code: bool,
+    /// File name stems used under `code/`, so sessions don't share one
+    code_stems: HashSet<String>,
+    /// Code blocks written under `code/`
+    code_blocks: usize,
+}
+
+impl ExportWriter {
+    fn new(destination: &str, options: &ExportOptions) -> Result<Self> {
+        if options.split_by_code
+            && options.template.is_none()
+            && options.format.renderer().is_none()
+        {
+            anyhow::bail!(
+                "--split-by-code rewrites the transcript: use --format md, html or txt, or a --template"
+            );
+        }
+        if options.split_by_code && options.gzip {
+            anyhow::bail!(
+                "--split-by-code can't be used with --gzip, since the transcript's \
+                 code references would miss the .gz; use --archive instead"
+            );
+        }
+        let (root, output) = if options.archive {
+            let (path, root) = archive_target(destination)?;
+            let tar = TarBuilder::new();
+            (root, ExportOutput::Archive { path, tar })
+        } else {
+            let root = export_dir(destination)?.to_path_buf();
+            std::fs::create_dir_all(&root)?;
+            let output = if options.gzip {
+                ExportOutput::Gzip
+            } else {
+                ExportOutput::Files
+            };
+            (root, output)
+        };
+        Ok(Self {
+            root,
+            output,
+            layout: options.layout,
+            format: options.format,
+            template: load_template(options.template.as_deref())?,
+            redactor: load_redactor(options.redact)?,
+            anonymizer: options.anonymize.then(Anonymizer::for_current_user),
+            turns: options.turns(),
+            workspace_dirs: HashMap::new(),
+            written: HashSet::new(),
+            counts: BTreeMap::new(),
+            split_by_code: options.split_by_code,
+            code_stems: HashSet::new(),
+            code_blocks: 0,
+        })
+    }
+
+    fn extension(&self) -> &str {
+        match &self.template {
+            Some(template) => &template.extension,
+            None => self.format.extension(),
+        }
+    }
+
+    /// Format description for the summary line
+    fn format_label(&self) -> String {
+        let format = match &self.template {
+            Some(template) => format!("template, .{}", template.extension),
+            None => self.format.to_string(),
+        };
+        match self.output {
+            ExportOutput::Files => format,
+            ExportOutput::Gzip => format!("{}, gzip", format),
+            ExportOutput::Archive { .. } => format!("{}, tar.gz", format),
+        }
+    }
+
+    /// Where the export goes, for messages: the directory or the archive
+    fn target(&self) -> String {
+        match &self.output {
+            ExportOutput::Archive { path, .. } => path.display().to_string(),
+            _ => self.root.display().to_string(),
+        }
+    }
+
+    /// Write `contents` to `file`, a path below the root, as a plain file,
+    /// a gzipped one or an archive entry
+    fn put(&mut self, file: &Path, contents: Vec<u8>) -> Result<()> {
+        let path = self.root.join(file);
+        let (path, contents) = match &mut self.output {
+            ExportOutput::Files => (path, contents),
+            ExportOutput::Gzip => {
+                let mut name = path.into_os_string();
+                name.push(".gz");
+                (PathBuf::from(name), gzip(&contents))
+            }
+            ExportOutput::Archive { tar, .. } => {
+                let entry: Vec<_> = path
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect();
+                return tar.append(&entry.join("/"), &contents);
+            }
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Write out the archive, for `--archive`; files are already written
+    fn finish(&mut self) -> Result<()> {
+        if let ExportOutput::Archive { path, tar } = &mut self.output {
+            let archive = gzip(&std::mem::take(tar).into_bytes());
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&*path, archive)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Directory for a workspace under `by-workspace`: the sanitized project
+    /// folder name, with `-2`, `-3`, ... for other workspaces of the same name
+    fn workspace_dir(&mut self, workspace: &Workspace) -> String {
+        if let Some(dir) = self.workspace_dirs.get(&workspace.hash) {
+            return dir.clone();
+        }
+        let name = workspace
+            .project_path
+            .as_deref()
+            .and_then(|p| p.trim_end_matches(['/', '\\']).rsplit(['/', '\\']).next())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| &workspace.hash[..8.min(workspace.hash.len())]);
+        let base = sanitize_dir_name(name);
+        let taken = |dir: &String| {
+            self.workspace_dirs
+                .values()
+                .any(|d| d.eq_ignore_ascii_case(dir))
+        };
+        let mut dir = base.clone();
+        let mut n = 2;
+        while taken(&dir) {
+            dir = format!("{}-{}", base, n);
+            n += 1;
+        }
+        self.workspace_dirs
+            .insert(workspace.hash.clone(), dir.clone());
+        dir
     }
 
-    // Create destination directory
-    let dest_path = Path::new(destination);
-    std::fs::create_dir_all(dest_path)?;
+    /// Export one session file from `workspace`
+    fn write(&mut self, src_path: &Path, workspace: &Workspace) -> Result<()> {
+        let dir = match self.layout {
+            ExportLayout::Flat => PathBuf::new(),
+            ExportLayout::ByWorkspace => PathBuf::from(self.workspace_dir(workspace)),
+            ExportLayout::ByDate => match session_activity_date(src_path) {
+                Some(date) => {
+                    PathBuf::from(date.format("%Y").to_string()).join(date.format("%m").to_string())
+                }
+                None => PathBuf::from("undated"),
+            },
+        };
+        let stem = src_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "session".to_string());
+        let extension = self.extension().to_string();
+        let mut dest_file = dir.join(format!("{}.{}", stem, extension));
+        let mut n = 2;
+        while self.written.contains(&dest_file) {
+            dest_file = dir.join(format!("{}-{}.{}", stem, n, extension));
+            n += 1;
+        }
+
+        let anonymizer = self
+            .anonymizer
+            .as_ref()
+            .map(|a| a.with_project(workspace.project_path.as_deref()));
+        let contents = match &self.template {
+            _ if self.split_by_code => {
+                // Needs `&mut self` to record the code files it writes
+                self.split(src_path, &dest_file, &dir, workspace, anonymizer.as_ref())?
+            }
+            Some(template) => template.render_file(
+                src_path,
+                &workspace_label(workspace),
+                self.redactor.as_ref(),
+                anonymizer.as_ref(),
+                self.turns,
+            )?,
+            None => self.format.render_file(
+                src_path,
+                self.redactor.as_ref(),
+                anonymizer.as_ref(),
+                self.turns,
+            )?,
+        };
+        self.put(&dest_file, contents)?;
+
+        self.written.insert(dest_file);
+        *self.counts.entry(dir).or_default() += 1;
+        Ok(())
+    }
 
-    // Copy all session files
+    /// `--split-by-code`: render the transcript of `src_path` with each
+    /// fenced code block, in prompts and responses, replaced by a reference
+    /// to `code/<session>-<n>.<ext>` under the root, where the block's body
+    /// is written. `dir` is where `dest_file` sits below the root.
+    fn split(
+        &mut self,
+        src_path: &Path,
+        dest_file: &Path,
+        dir: &Path,
+        workspace: &Workspace,
+        anonymizer: Option<&Anonymizer>,
+    ) -> Result<Vec<u8>> {
+        let content = std::fs::read_to_string(src_path)?;
+        let mut session = parse_session_json(&content)
+            .with_context(|| format!("Failed to parse session: {}", src_path.display()))?;
+        self.turns.apply(&mut session, src_path);
+
+        let base = dest_file
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "session".to_string());
+        let mut stem = base.clone();
+        let mut n = 2;
+        while self.code_stems.contains(&stem) {
+            stem = format!("{}-{}", base, n);
+            n += 1;
+        }
+        // References are relative to the transcript
+        let prefix = format!("{}code/", "../".repeat(dir.components().count()));
+
+        let mut blocks: Vec<(String, String)> = Vec::new();
+        let mut extract = |text: &str| {
+            split_code_blocks(text, |language, body| {
+                let name = format!("{}-{}.{}", stem, blocks.len() + 1, code_extension(language));
+                let reference = format!("[code: {}{}]", prefix, name);
+                blocks.push((name, format!("{}\n", body)));
+                reference
+            })
+        };
+        for request in &mut session.requests {
+            if let Some(text) = request.message.as_mut().and_then(|m| m.text.as_mut()) {
+                *text = extract(text);
+            }
+            if let Some(response) = request.response.as_mut() {
+                if let Some(text) = extract_response_text(response) {
+                    let split = extract(&text);
+                    if split != text {
+                        set_response_text(response, split);
+                    }
+                }
+            }
+        }
+
+        let rendered = match (&self.template, self.format.renderer()) {
+            (Some(template), _) => {
+                template.render(&session, &workspace_label(workspace), src_path)?
+            }
+            (None, Some(renderer)) => renderer(&session),
+            (None, None) => unreachable!("checked when the writer was created"),
+        };
+
+        if !blocks.is_empty() {
+            self.code_blocks += blocks.len();
+            for (name, body) in blocks {
+                let body = scrub(body, self.redactor.as_ref(), anonymizer);
+                self.put(&Path::new("code").join(name), body.into_bytes())?;
+            }
+            self.code_stems.insert(stem);
+        }
+        Ok(scrub(rendered, self.redactor.as_ref(), anonymizer).into_bytes())
+    }
+
+    /// Print the directories written to and their session counts; a flat
+    /// export has nothing to show
+    fn print_tree(&self) {
+        if self.layout == ExportLayout::Flat || self.counts.is_empty() {
+            return;
+        }
+        println!("{} {}/", "[=]".blue(), self.root.display());
+        let mut previous: Vec<String> = Vec::new();
+        for (dir, count) in &self.counts {
+            let parts: Vec<String> = dir
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            let shared = previous
+                .iter()
+                .zip(&parts)
+                .take_while(|(a, b)| a == b)
+                .count();
+            for (depth, part) in parts.iter().enumerate().skip(shared) {
+                let indent = "  ".repeat(depth + 2);
+                if depth + 1 == parts.len() {
+                    println!("{}{}/ ({})", indent, part, count);
+                } else {
+                    println!("{}{}/", indent, part);
+                }
+            }
+            previous = parts;
+        }
+    }
+}
+
+/// The file `export --archive` writes for `destination`, which gets
+/// `.tar.gz` unless it ends in `.tar.gz` or `.tgz`, and the directory its
+/// entries sit under: the file name without that suffix
+fn archive_target(destination: &str) -> Result<(PathBuf, PathBuf)> {
+    if destination == "-" {
+        anyhow::bail!(
+            "--archive writes a file, so it can't go to `-` (stdout); give a path such as chats.tar.gz"
+        );
+    }
+    let lower = destination.to_ascii_lowercase();
+    let path = if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+        PathBuf::from(destination)
+    } else {
+        PathBuf::from(format!(
+            "{}.tar.gz",
+            destination.trim_end_matches(['/', '\\'])
+        ))
+    };
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let suffix = if name.to_ascii_lowercase().ends_with(".tgz") {
+        ".tgz".len()
+    } else {
+        ".tar.gz".len()
+    };
+    let root = match &name[..name.len().saturating_sub(suffix)] {
+        "" => "export",
+        stem => stem,
+    };
+    Ok((path, PathBuf::from(root)))
+}
+
+/// Whether a session's last activity falls in an inclusive date range
+fn in_date_range(src_path: &Path, since: Option<NaiveDate>, until: Option<NaiveDate>) -> bool {
+    if since.is_none() && until.is_none() {
+        return true;
+    }
+    session_activity_date(src_path).is_some_and(|date| {
+        since.map_or(true, |since| date >= since) && until.map_or(true, |until| date <= until)
+    })
+}
+
+/// Export every session of a workspace in the date range, returning how
+/// many were exported and how many were skipped by date
+fn export_workspace_sessions(
+    writer: &mut ExportWriter,
+    workspace: &Workspace,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+) -> Result<(usize, usize)> {
     let mut exported_count = 0;
+    let mut skipped_by_date = 0;
     for entry in std::fs::read_dir(&workspace.chat_sessions_path)? {
-        let entry = entry?;
-        let src_path = entry.path();
-
+        let src_path = entry?.path();
         if src_path.extension().map(|e| e == "json").unwrap_or(false) {
-            let dest_file = dest_path.join(entry.file_name());
-            std::fs::copy(&src_path, &dest_file)?;
+            if !in_date_range(&src_path, since, until) {
+                skipped_by_date += 1;
+                continue;
+            }
+            writer.write(&src_path, workspace)?;
             exported_count += 1;
         }
     }
+    Ok((exported_count, skipped_by_date))
+}
+
+/// Print the summary line (and layout tree) after a batch export
+fn print_export_summary(
+    writer: &ExportWriter,
+    exported_count: usize,
+    skipped_by_date: Option<usize>,
+) {
+    match skipped_by_date {
+        Some(skipped) => println!(
+            "{} Exported {} chat session(s) to {} ({}), skipped {} by date",
+            "[OK]".green(),
+            exported_count,
+            writer.target(),
+            writer.format_label(),
+            skipped
+        ),
+        None => println!(
+            "{} Exported {} chat session(s) to {} ({})",
+            "[OK]".green(),
+            exported_count,
+            writer.target(),
+            writer.format_label()
+        ),
+    }
+    writer.print_tree();
+    if writer.code_blocks > 0 {
+        println!(
+            "{} Extracted {} code block(s) to {}",
+            "[i]".cyan(),
+            writer.code_blocks,
+            writer.root.join("code").display()
+        );
+    }
+}
 
-    println!(
-        "{} Exported {} chat session(s) to {}",
-        "[OK]".green(),
+/// Export chat sessions from a workspace, optionally limited to an
+/// inclusive `since`/`until` range of last-activity dates
+pub fn export_sessions(
+    destination: &str,
+    hash: Option<&str>,
+    path: Option<&str>,
+    since: Option<&str>,
+    until: Option<&str>,
+    options: &ExportOptions,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let (since_date, until_date) = parse_date_range(("--since", since), ("--until", until))?;
+    let date_filtered = since_date.is_some() || until_date.is_some();
+
+    let workspace = resolve_export_workspace(hash, path, discovery)?;
+    if !workspace.has_chat_sessions {
+        println!("No chat sessions to export.");
+        return Ok(());
+    }
+
+    let mut writer = ExportWriter::new(destination, options)?;
+    let (exported_count, skipped_by_date) =
+        export_workspace_sessions(&mut writer, &workspace, since_date, until_date)?;
+    writer.finish()?;
+    print_export_summary(
+        &writer,
+        exported_count,
+        date_filtered.then_some(skipped_by_date),
+    );
+
+    Ok(())
+}
+
+/// The workspace an export names by hash or by project path
+fn resolve_export_workspace(
+    hash: Option<&str>,
+    path: Option<&str>,
+    discovery: &DiscoveryOptions,
+) -> Result<Workspace> {
+    if let Some(h) = hash {
+        get_workspace_by_hash(h, discovery)?
+            .context(format!("Workspace not found with hash: {}", h))
+    } else if let Some(p) = path {
+        get_workspace_by_path(p, discovery)?.context(format!("Workspace not found for path: {}", p))
+    } else {
+        anyhow::bail!("Must specify either --hash or --path");
+    }
+}
+
+/// `export workspace|path --combine`: a workspace's sessions in the date
+/// range as one document at `output`, oldest first, under a table of contents
+pub fn export_combined(
+    output: &str,
+    hash: Option<&str>,
+    path: Option<&str>,
+    since: Option<&str>,
+    until: Option<&str>,
+    options: &ExportOptions,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    if options.split_by_code {
+        anyhow::bail!("--combine can't be used with --split-by-code");
+    }
+    if options.gzip || options.archive {
+        anyhow::bail!(
+            "--combine writes one document: end the output path in .gz to compress it \
+             instead of using --gzip or --archive"
+        );
+    }
+    let render = match (options.format, &options.template) {
+        (_, Some(_)) => anyhow::bail!("--combine can't be used with --template"),
+        (ExportFormat::Json, None) => {
+            anyhow::bail!("--combine writes a document: use --format md, html or txt")
+        }
+        (ExportFormat::Markdown, None) => sessions_to_combined_markdown,
+        (ExportFormat::Html, None) => sessions_to_combined_html,
+        (ExportFormat::Text, None) => sessions_to_combined_text,
+    };
+    let (since_date, until_date) = parse_date_range(("--since", since), ("--until", until))?;
+    let workspace = resolve_export_workspace(hash, path, discovery)?;
+
+    let mut sessions = Vec::new();
+    if workspace.has_chat_sessions {
+        for entry in std::fs::read_dir(&workspace.chat_sessions_path)? {
+            let src_path = entry?.path();
+            if src_path.extension().map(|e| e == "json").unwrap_or(false)
+                && in_date_range(&src_path, since_date, until_date)
+            {
+                let content = std::fs::read_to_string(&src_path)?;
+                let mut session = parse_session_json(&content)
+                    .with_context(|| format!("Failed to parse session: {}", src_path.display()))?;
+                options.turns().apply(&mut session, &src_path);
+                sessions.push(session);
+            }
+        }
+    }
+    if sessions.is_empty() {
+        export_status(
+            is_stdout_target(Some(output)),
+            "No chat sessions to export.",
+        );
+        return Ok(());
+    }
+    sessions.sort_by_key(|s| {
+        s.timestamp_range()
+            .map(|(first, _)| first)
+            .unwrap_or(s.creation_date)
+    });
+
+    let title = workspace
+        .project_path
+        .as_deref()
+        .and_then(|p| p.trim_end_matches(['/', '\\']).rsplit(['/', '\\']).next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| &workspace.hash[..8.min(workspace.hash.len())])
+        .to_string();
+    let intro = format!(
+        "{} session(s) from {}",
+        sessions.len(),
+        workspace_label(&workspace)
+    );
+    let redactor = load_redactor(options.redact)?;
+    let anonymizer = options
+        .anonymize
+        .then(|| Anonymizer::for_current_user().with_project(workspace.project_path.as_deref()));
+    let document = scrub(
+        render(&title, Some(&intro), &sessions),
+        redactor.as_ref(),
+        anonymizer.as_ref(),
+    );
+
+    let sink = OutputSink::open(Some(output))?;
+    let destination = sink.describe();
+    let to_stdout = sink.is_stdout();
+    sink.write_all_and_finish(&document)?;
+    export_status(
+        to_stdout,
+        format!(
+            "{} Exported {} chat session(s) to {} ({}, combined)",
+            "[OK]".green(),
+            sessions.len(),
+            destination,
+            options.format
+        ),
+    );
+    Ok(())
+}
+
+/// Export chat sessions from every workspace, optionally limited to an
+/// inclusive `since`/`until` range of last-activity dates
+pub fn export_all_sessions(
+    destination: &str,
+    since: Option<&str>,
+    until: Option<&str>,
+    options: &ExportOptions,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let (since_date, until_date) = parse_date_range(("--since", since), ("--until", until))?;
+    let date_filtered = since_date.is_some() || until_date.is_some();
+
+    let workspaces: Vec<_> = discover_workspaces(discovery)?
+        .into_iter()
+        .filter(|ws| ws.has_chat_sessions)
+        .collect();
+    if workspaces.is_empty() {
+        println!("No chat sessions to export.");
+        return Ok(());
+    }
+
+    let mut writer = ExportWriter::new(destination, options)?;
+    let mut exported_count = 0;
+    let mut skipped_by_date = 0;
+    for workspace in &workspaces {
+        let (exported, skipped) =
+            export_workspace_sessions(&mut writer, workspace, since_date, until_date)?;
+        exported_count += exported;
+        skipped_by_date += skipped;
+    }
+    writer.finish()?;
+    print_export_summary(
+        &writer,
         exported_count,
-        destination
+        date_filtered.then_some(skipped_by_date),
     );
 
     Ok(())
 }
 
+/// Session IDs listed one per line, as piped from `find session --json | jq`.
+/// Blank lines are skipped, and surrounding quotes (`jq` without `-r`) and a
+/// `.json` extension are dropped.
+pub fn parse_session_id_list(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .map(|line| line.trim().trim_matches('"').trim())
+        .filter(|line| !line.is_empty())
+        .map(|id| match id.len().checked_sub(5) {
+            Some(stem) if id[stem..].eq_ignore_ascii_case(".json") => id[..stem].to_string(),
+            _ => id.to_string(),
+        })
+        .collect()
+}
+
+/// `export --stdin-ids --output <dir>`: export the sessions whose IDs are
+/// piped in, one per line
+pub fn export_stdin_ids(
+    destination: &str,
+    options: &ExportOptions,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    use std::io::{IsTerminal, Read};
+
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        anyhow::bail!(
+            "--stdin-ids reads session IDs from a pipe, e.g.\n  \
+             csm find session docker --json | jq -r '.[].session_id' | \
+             csm export --stdin-ids --output ./out"
+        );
+    }
+    let mut input = String::new();
+    stdin.read_to_string(&mut input)?;
+    export_session_ids(
+        destination,
+        &parse_session_id_list(&input),
+        options,
+        discovery,
+    )
+}
+
+/// Export sessions by ID (a session file's name, or a unique prefix of one)
+/// across every workspace. IDs that match no session, or several, are
+/// reported on stderr and skipped.
+pub fn export_session_ids(
+    destination: &str,
+    ids: &[String],
+    options: &ExportOptions,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    use crate::workspace::list_session_files;
+
+    if ids.is_empty() {
+        anyhow::bail!("No session IDs given on stdin");
+    }
+    let workspaces: Vec<Workspace> = discover_workspaces(discovery)?
+        .into_iter()
+        .filter(|ws| ws.has_chat_sessions)
+        .collect();
+    let files: Vec<(String, PathBuf, &Workspace)> = workspaces
+        .iter()
+        .flat_map(|ws| {
+            list_session_files(&ws.chat_sessions_path)
+                .into_iter()
+                .map(move |path| {
+                    let stem = path
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_lowercase())
+                        .unwrap_or_default();
+                    (stem, path, ws)
+                })
+        })
+        .collect();
+
+    let mut writer = ExportWriter::new(destination, options)?;
+    let mut exported = HashSet::new();
+    let mut unresolved = 0;
+    for id in ids {
+        let wanted = id.to_lowercase();
+        let exact: Vec<_> = files.iter().filter(|(stem, ..)| *stem == wanted).collect();
+        let matches = if exact.is_empty() {
+            files
+                .iter()
+                .filter(|(stem, ..)| stem.starts_with(&wanted))
+                .collect()
+        } else {
+            exact
+        };
+        match matches.as_slice() {
+            [(_, path, ws)] => {
+                if exported.insert(path.clone()) {
+                    writer.write(path, ws)?;
+                }
+            }
+            [] => {
+                eprintln!("{} No session with ID '{}'", "[!]".yellow(), id);
+                unresolved += 1;
+            }
+            several => {
+                eprintln!(
+                    "{} '{}' matches {} sessions; give more of the ID",
+                    "[!]".yellow(),
+                    id,
+                    several.len()
+                );
+                unresolved += 1;
+            }
+        }
+    }
+
+    if exported.is_empty() {
+        anyhow::bail!("None of the {} session ID(s) matched a session", ids.len());
+    }
+    writer.finish()?;
+    print_export_summary(&writer, exported.len(), None);
+    if unresolved > 0 {
+        eprintln!(
+            "{} {} of {} ID(s) not exported",
+            "[!]".yellow(),
+            unresolved,
+            ids.len()
+        );
+    }
+    Ok(())
+}
+
 /// Import chat sessions into a workspace
 pub fn import_sessions(
     source: &str,
     hash: Option<&str>,
     path: Option<&str>,
     force: bool,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
     let src_path = Path::new(source);
     if !src_path.exists() {
@@ -64,15 +1230,18 @@ pub fn import_sessions(
     }
 
     let workspace = if let Some(h) = hash {
-        get_workspace_by_hash(h)?.context(format!("Workspace not found with hash: {}", h))?
+        get_workspace_by_hash(h, discovery)?
+            .context(format!("Workspace not found with hash: {}", h))?
     } else if let Some(p) = path {
-        get_workspace_by_path(p)?.context(format!("Workspace not found for path: {}", p))?
+        get_workspace_by_path(p, discovery)?
+            .context(format!("Workspace not found for path: {}", p))?
     } else {
         anyhow::bail!("Must specify either --hash or --path");
     };
 
     // Create chatSessions directory if it doesn't exist
-    std::fs::create_dir_all(&workspace.chat_sessions_path)?;
+    let mut actions = Vec::new();
+    ensure_dir(&workspace.chat_sessions_path, dry_run, &mut actions)?;
 
     // Import all JSON files
     let mut imported_count = 0;
@@ -87,18 +1256,27 @@ pub fn import_sessions(
 
             if dest_file.exists() && !force {
                 skipped_count += 1;
+            } else if dry_run {
+                actions.push(PlannedAction::Copy {
+                    from: src_file,
+                    to: dest_file,
+                });
             } else {
-                std::fs::copy(&src_file, &dest_file)?;
+                copy_file_atomic(&src_file, &dest_file)?;
                 imported_count += 1;
             }
         }
     }
 
-    println!(
-        "{} Imported {} chat session(s)",
-        "[OK]".green(),
-        imported_count
-    );
+    if dry_run {
+        print_dry_run(&actions);
+    } else {
+        println!(
+            "{} Imported {} chat session(s)",
+            "[OK]".green(),
+            imported_count
+        );
+    }
     if skipped_count > 0 {
         println!(
             "{} Skipped {} existing session(s). Use --force to overwrite.",
@@ -112,11 +1290,15 @@ pub fn import_sessions(
 
 /// Move chat sessions from one workspace to another (by path lookup)
 #[allow(dead_code)]
-pub fn move_sessions(source_hash: &str, target_path: &str) -> Result<()> {
-    let source_ws = get_workspace_by_hash(source_hash)?
+pub fn move_sessions(
+    source_hash: &str,
+    target_path: &str,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let source_ws = get_workspace_by_hash(source_hash, discovery)?
         .context(format!("Source workspace not found: {}", source_hash))?;
 
-    let target_ws = get_workspace_by_path(target_path)?.context(format!(
+    let target_ws = get_workspace_by_path(target_path, discovery)?.context(format!(
         "Target workspace not found for path: {}",
         target_path
     ))?;
@@ -130,16 +1312,20 @@ pub fn move_sessions(source_hash: &str, target_path: &str) -> Result<()> {
         return Ok(());
     }
 
-    move_sessions_internal(&source_ws, &target_ws, target_path)
+    move_sessions_internal(&source_ws, &target_ws, target_path, false)
 }
 
 /// Move chat sessions from one workspace to another (with explicit target workspace)
-fn move_sessions_to_workspace(source_ws: &Workspace, target_ws: &Workspace) -> Result<()> {
+fn move_sessions_to_workspace(
+    source_ws: &Workspace,
+    target_ws: &Workspace,
+    dry_run: bool,
+) -> Result<()> {
     let target_path: &str = target_ws
         .project_path
         .as_deref()
         .unwrap_or("target workspace");
-    move_sessions_internal(source_ws, target_ws, target_path)
+    move_sessions_internal(source_ws, target_ws, target_path, dry_run)
 }
 
 /// Internal function to move sessions between workspaces
@@ -147,6 +1333,7 @@ fn move_sessions_internal(
     source_ws: &Workspace,
     target_ws: &Workspace,
     display_path: &str,
+    dry_run: bool,
 ) -> Result<()> {
     if !source_ws.has_chat_sessions {
         println!("No chat sessions to move.");
@@ -163,7 +1350,8 @@ fn move_sessions_internal(
     }
 
     // Create chatSessions directory in target if needed
-    std::fs::create_dir_all(&target_ws.chat_sessions_path)?;
+    let mut actions = Vec::new();
+    ensure_dir(&target_ws.chat_sessions_path, dry_run, &mut actions)?;
 
     // Move all session files
     let mut moved_count = 0;
@@ -181,17 +1369,28 @@ fn move_sessions_internal(
                 continue;
             }
 
+            if dry_run {
+                actions.push(PlannedAction::Move {
+                    from: src_file,
+                    to: dest_file,
+                });
+                continue;
+            }
             std::fs::rename(&src_file, &dest_file)?;
             moved_count += 1;
         }
     }
 
-    println!(
-        "{} Moved {} chat session(s) to {}",
-        "[OK]".green(),
-        moved_count,
-        display_path
-    );
+    if dry_run {
+        print_dry_run(&actions);
+    } else {
+        println!(
+            "{} Moved {} chat session(s) to {}",
+            "[OK]".green(),
+            moved_count,
+            display_path
+        );
+    }
 
     if skipped_count > 0 {
         println!(
@@ -209,13 +1408,14 @@ pub fn export_specific_sessions(
     destination: &str,
     session_ids: &[String],
     project_path: Option<&str>,
+    options: &ExportOptions,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
-    use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace, normalize_path};
+    use crate::workspace::{get_chat_sessions_from_workspace, normalize_path};
 
-    let dest_path = Path::new(destination);
-    std::fs::create_dir_all(dest_path)?;
+    let mut writer = ExportWriter::new(destination, options)?;
 
-    let workspaces = discover_workspaces()?;
+    let workspaces = discover_workspaces(discovery)?;
 
     // Filter workspaces by project path if provided
     let filtered: Vec<_> = if let Some(path) = project_path {
@@ -264,14 +1464,7 @@ pub fn export_specific_sessions(
             });
 
             if matches && !found_ids.contains(&session_id) {
-                let filename = session
-                    .path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_default();
-
-                let dest_file = dest_path.join(&filename);
-                std::fs::copy(&session.path, &dest_file)?;
+                writer.write(&session.path, &ws)?;
                 exported_count += 1;
                 found_ids.push(session_id);
                 println!(
@@ -283,12 +1476,14 @@ pub fn export_specific_sessions(
         }
     }
 
+    writer.finish()?;
     println!(
         "\n{} Exported {} session(s) to {}",
         "[OK]".green().bold(),
         exported_count,
-        destination
+        writer.target()
     );
+    writer.print_tree();
 
     Ok(())
 }
@@ -298,16 +1493,20 @@ pub fn import_specific_sessions(
     session_files: &[String],
     target_path: Option<&str>,
     force: bool,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
     let target_ws = if let Some(path) = target_path {
-        get_workspace_by_path(path)?.context(format!("Workspace not found for path: {}", path))?
+        get_workspace_by_path(path, discovery)?
+            .context(format!("Workspace not found for path: {}", path))?
     } else {
         let cwd = std::env::current_dir()?;
-        get_workspace_by_path(cwd.to_str().unwrap_or(""))?
+        get_workspace_by_path(cwd.to_str().unwrap_or(""), discovery)?
             .context("Current directory is not a VS Code workspace")?
     };
 
-    std::fs::create_dir_all(&target_ws.chat_sessions_path)?;
+    let mut actions = Vec::new();
+    ensure_dir(&target_ws.chat_sessions_path, dry_run, &mut actions)?;
 
     let mut imported_count = 0;
     let mut skipped_count = 0;
@@ -330,18 +1529,27 @@ pub fn import_specific_sessions(
         if dest_file.exists() && !force {
             println!("   {} Skipping (exists): {}", "[!]".yellow(), filename);
             skipped_count += 1;
+        } else if dry_run {
+            actions.push(PlannedAction::Copy {
+                from: src_path.to_path_buf(),
+                to: dest_file,
+            });
         } else {
-            std::fs::copy(src_path, &dest_file)?;
+            copy_file_atomic(src_path, &dest_file)?;
             imported_count += 1;
             println!("   {} Imported: {}", "[OK]".green(), filename);
         }
     }
 
-    println!(
-        "\n{} Imported {} session(s)",
-        "[OK]".green().bold(),
-        imported_count
-    );
+    if dry_run {
+        print_dry_run(&actions);
+    } else {
+        println!(
+            "\n{} Imported {} session(s)",
+            "[OK]".green().bold(),
+            imported_count
+        );
+    }
     if skipped_count > 0 {
         println!(
             "{} Skipped {} existing. Use --force to overwrite.",
@@ -354,30 +1562,41 @@ pub fn import_specific_sessions(
 }
 
 /// Move all sessions from one workspace to another (by hash)
-pub fn move_workspace(source_hash: &str, target: &str) -> Result<()> {
+pub fn move_workspace(
+    source_hash: &str,
+    target: &str,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
     // Get source workspace
-    let source_ws = get_workspace_by_hash(source_hash)?
+    let source_ws = get_workspace_by_hash(source_hash, discovery)?
         .context(format!("Source workspace not found: {}", source_hash))?;
 
     // Try target as hash first, then as path
     // This prevents ambiguity when multiple workspaces share the same path
-    let target_ws = get_workspace_by_hash(target)?
-        .or_else(|| get_workspace_by_path(target).ok().flatten())
+    let target_ws = get_workspace_by_hash(target, discovery)?
+        .or_else(|| get_workspace_by_path(target, discovery).ok().flatten())
         .context(format!("Target workspace not found: {}", target))?;
 
-    move_sessions_to_workspace(&source_ws, &target_ws)
+    move_sessions_to_workspace(&source_ws, &target_ws, dry_run)
 }
 
 /// Move specific sessions by ID
-pub fn move_specific_sessions(session_ids: &[String], target_path: &str) -> Result<()> {
+pub fn move_specific_sessions(
+    session_ids: &[String],
+    target_path: &str,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
     use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace, normalize_path};
 
-    let target_ws = get_workspace_by_path(target_path)?
+    let target_ws = get_workspace_by_path(target_path, discovery)?
         .context(format!("Target workspace not found: {}", target_path))?;
 
-    std::fs::create_dir_all(&target_ws.chat_sessions_path)?;
+    let mut actions = Vec::new();
+    ensure_dir(&target_ws.chat_sessions_path, dry_run, &mut actions)?;
 
-    let workspaces = discover_workspaces()?;
+    let workspaces = discover_workspaces(discovery)?;
 
     let normalized_ids: Vec<String> = session_ids
         .iter()
@@ -427,14 +1646,25 @@ pub fn move_specific_sessions(session_ids: &[String], target_path: &str) -> Resu
                     .unwrap_or_default();
 
                 let dest_file = target_ws.chat_sessions_path.join(&filename);
+                found_ids.push(session_id);
+                if dry_run {
+                    actions.push(PlannedAction::Move {
+                        from: session.path,
+                        to: dest_file,
+                    });
+                    continue;
+                }
                 std::fs::rename(&session.path, &dest_file)?;
                 moved_count += 1;
-                found_ids.push(session_id);
                 println!("   {} Moved: {}", "[OK]".green(), session.session.title());
             }
         }
     }
 
+    if dry_run {
+        print_dry_run(&actions);
+        return Ok(());
+    }
     println!(
         "\n{} Moved {} session(s) to {}",
         "[OK]".green().bold(),
@@ -446,11 +1676,16 @@ pub fn move_specific_sessions(session_ids: &[String], target_path: &str) -> Resu
 }
 
 /// Move sessions from one path to another
-pub fn move_by_path(source_path: &str, target_path: &str) -> Result<()> {
-    let source_ws = get_workspace_by_path(source_path)?
+pub fn move_by_path(
+    source_path: &str,
+    target_path: &str,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let source_ws = get_workspace_by_path(source_path, discovery)?
         .context(format!("Source workspace not found: {}", source_path))?;
 
-    let target_ws = get_workspace_by_path(target_path)?
+    let target_ws = get_workspace_by_path(target_path, discovery)?
         .context(format!("Target workspace not found: {}", target_path))?;
 
     if !source_ws.has_chat_sessions {
@@ -458,7 +1693,8 @@ pub fn move_by_path(source_path: &str, target_path: &str) -> Result<()> {
         return Ok(());
     }
 
-    std::fs::create_dir_all(&target_ws.chat_sessions_path)?;
+    let mut actions = Vec::new();
+    ensure_dir(&target_ws.chat_sessions_path, dry_run, &mut actions)?;
 
     let mut moved_count = 0;
     for entry in std::fs::read_dir(&source_ws.chat_sessions_path)? {
@@ -467,11 +1703,23 @@ pub fn move_by_path(source_path: &str, target_path: &str) -> Result<()> {
 
         if src_file.extension().map(|e| e == "json").unwrap_or(false) {
             let dest_file = target_ws.chat_sessions_path.join(entry.file_name());
+            if dry_run {
+                actions.push(PlannedAction::Move {
+                    from: src_file,
+                    to: dest_file,
+                });
+                continue;
+            }
             std::fs::rename(&src_file, &dest_file)?;
             moved_count += 1;
         }
     }
 
+    if dry_run {
+        print_dry_run(&actions);
+        return Ok(());
+    }
+
     println!(
         "{} Moved {} chat session(s) from {} to {}",
         "[OK]".green(),
@@ -4,10 +4,65 @@
 
 use anyhow::{Context, Result};
 use colored::*;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::workspace::get_workspace_by_path;
+use crate::links::{find_git_root, LinkIndex};
+use crate::storage::parse_session_json;
+use crate::workspace::{get_workspace_by_path, DiscoveryOptions};
+
+/// Commit subject marker linking a snapshot to its session, e.g. `[CSM: <id>]`
+const SESSION_MARKER: &str = "[CSM: ";
+
+/// Pick the session a snapshot belongs to: `--session` (a full ID or local
+/// prefix), or else the one session linked to the enclosing repository
+fn snapshot_session_id(
+    project_dir: &Path,
+    session: Option<&str>,
+    discovery: &DiscoveryOptions,
+) -> Result<Option<String>> {
+    if let Some(session) = session {
+        // Expand a prefix to the full ID when the session is on this machine
+        let full_id = crate::commands::find_session_workspace(session, discovery)
+            .map(|(id, _, _)| id)
+            .unwrap_or_else(|_| session.to_string());
+        return Ok(Some(full_id));
+    }
+    let Some(repo) = find_git_root(project_dir) else {
+        return Ok(None);
+    };
+    let index = LinkIndex::load()?;
+    match index.sessions_for_repo(&repo).as_slice() {
+        [only] => Ok(Some(only.to_string())),
+        [] => Ok(None),
+        linked => {
+            println!(
+                "{} {} sessions are linked to this repo; pass --session to stamp one",
+                "[i]".cyan(),
+                linked.len()
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Note body for a snapshot: session ID, title and message count when the
+/// session file can be found locally
+fn snapshot_note(session_id: &str, discovery: &DiscoveryOptions) -> String {
+    let details = crate::commands::find_session_workspace(session_id, discovery)
+        .ok()
+        .and_then(|(_, _, path)| std::fs::read_to_string(path).ok())
+        .and_then(|content| parse_session_json(&content).ok());
+    match details {
+        Some(session) => format!(
+            "Session: {}\nTitle: {}\nMessages: {}",
+            session_id,
+            session.title(),
+            session.request_count()
+        ),
+        None => format!("Session: {}", session_id),
+    }
+}
 
 /// Configure git settings for chat sessions
 pub fn git_config(name: Option<&str>, email: Option<&str>, path: Option<&str>) -> Result<()> {
@@ -89,8 +144,8 @@ pub fn git_config(name: Option<&str>, email: Option<&str>, path: Option<&str>) -
 }
 
 /// Initialize git versioning for chat sessions
-pub fn git_init(project_path: &str) -> Result<()> {
-    let workspace = get_workspace_by_path(project_path)?
+pub fn git_init(project_path: &str, discovery: &DiscoveryOptions) -> Result<()> {
+    let workspace = get_workspace_by_path(project_path, discovery)?
         .context(format!("Workspace not found for path: {}", project_path))?;
 
     let project_dir = Path::new(project_path);
@@ -129,6 +184,90 @@ pub fn git_init(project_path: &str) -> Result<()> {
     Ok(())
 }
 
+/// `git init --link-session`: create a repository in a session's project
+/// folder (or `project_path`), link the session to it, and with `commit`
+/// record the folder in a first commit stamped `[CSM: <id>] initial`
+pub fn git_init_linked(
+    project_path: Option<&str>,
+    session: &str,
+    commit: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let (session_id, workspace, _) = crate::commands::find_session_workspace(session, discovery)?;
+    let project_dir = match project_path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let folder = PathBuf::from(workspace.project_path.as_deref().context(format!(
+                "Session {} has no project folder; pass the path to initialize",
+                session_id
+            ))?);
+            // A relative folder would resolve against the current directory
+            if !folder.is_absolute() {
+                anyhow::bail!(
+                    "Project folder of session {} is not an absolute path: {}; pass the path to initialize",
+                    session_id,
+                    folder.display()
+                );
+            }
+            folder
+        }
+    };
+    if !project_dir.is_dir() {
+        anyhow::bail!("Not a directory: {}", project_dir.display());
+    }
+    let project_dir = project_dir.canonicalize().unwrap_or(project_dir);
+
+    if let Some(root) = find_git_root(&project_dir) {
+        anyhow::bail!(
+            "{} is already a git repository{}; to link the session without \
+             initializing, run: csm link {} {}",
+            project_dir.display(),
+            if root == project_dir {
+                String::new()
+            } else {
+                format!(" (inside {})", root.display())
+            },
+            session_id,
+            project_dir.display()
+        );
+    }
+
+    git_output(&project_dir, &["init", "-q"])?;
+    println!(
+        "{} Initialized git repository in {}",
+        "[OK]".green(),
+        project_dir.display()
+    );
+
+    let repo = project_dir.to_string_lossy().to_string();
+    let mut index = LinkIndex::load()?;
+    index.link(&session_id, &repo);
+    index.save()?;
+    println!(
+        "{} Linked {} to {}",
+        "[OK]".green(),
+        session_id.cyan(),
+        repo
+    );
+
+    if commit {
+        let subject = format!("{}{}] initial", SESSION_MARKER, session_id);
+        git_output(&project_dir, &["add", "-A"])?;
+        git_output(
+            &project_dir,
+            &["commit", "-q", "--allow-empty", "-m", &subject],
+        )?;
+        println!("{} Committed: {}", "[OK]".green(), subject);
+    } else {
+        println!("\nNext steps:");
+        println!(
+            "  csm git track {} -m 'initial' (commits are stamped with the session)",
+            project_dir.display()
+        );
+    }
+    Ok(())
+}
+
 /// Add chat sessions to git
 pub fn git_add(project_path: &str, commit: bool, message: Option<&str>) -> Result<()> {
     let project_dir = Path::new(project_path);
@@ -188,7 +327,7 @@ pub fn git_add(project_path: &str, commit: bool, message: Option<&str>) -> Resul
 }
 
 /// Show git status of chat sessions
-pub fn git_status(project_path: &str) -> Result<()> {
+pub fn git_status(project_path: &str, discovery: &DiscoveryOptions) -> Result<()> {
     let project_dir = Path::new(project_path);
 
     // Check if it's a git repo
@@ -199,7 +338,7 @@ pub fn git_status(project_path: &str) -> Result<()> {
     let versioning_enabled = chat_sessions_path.exists();
 
     // Get workspace info
-    let workspace = get_workspace_by_path(project_path)?;
+    let workspace = get_workspace_by_path(project_path, discovery)?;
     let session_count = workspace.map(|w| w.chat_session_count).unwrap_or(0);
 
     println!("Project: {}", project_path);
@@ -256,8 +395,16 @@ pub fn git_status(project_path: &str) -> Result<()> {
     Ok(())
 }
 
-/// Create a git tag snapshot of chat sessions
-pub fn git_snapshot(project_path: &str, tag: Option<&str>, message: Option<&str>) -> Result<()> {
+/// Create a git tag snapshot of chat sessions, stamping the commit subject
+/// with the session it belongs to (see [`SESSION_MARKER`])
+pub fn git_snapshot(
+    project_path: &str,
+    tag: Option<&str>,
+    message: Option<&str>,
+    session: Option<&str>,
+    note: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
     let project_dir = Path::new(project_path);
     let chat_sessions_path = project_dir.join(".vscode").join("chat-sessions");
 
@@ -275,6 +422,11 @@ pub fn git_snapshot(project_path: &str, tag: Option<&str>, message: Option<&str>
         .unwrap_or_else(|| format!("chat-snapshot-{}", timestamp));
 
     let msg = message.unwrap_or("Chat session snapshot");
+    let session_id = snapshot_session_id(project_dir, session, discovery)?;
+    let subject = match &session_id {
+        Some(id) => format!("Snapshot: {} {}{}]", msg, SESSION_MARKER, id),
+        None => format!("Snapshot: {}", msg),
+    };
 
     // Stage and commit
     let _ = Command::new("git")
@@ -284,9 +436,30 @@ pub fn git_snapshot(project_path: &str, tag: Option<&str>, message: Option<&str>
 
     let _ = Command::new("git")
         .current_dir(project_dir)
-        .args(["commit", "-m", &format!("Snapshot: {}", msg)])
+        .args(["commit", "-m", &subject])
         .output()?;
 
+    if let (true, Some(id)) = (note, &session_id) {
+        let output = Command::new("git")
+            .current_dir(project_dir)
+            .args([
+                "notes",
+                "add",
+                "-f",
+                "-m",
+                &snapshot_note(id, discovery),
+                "HEAD",
+            ])
+            .output()?;
+        if !output.status.success() {
+            println!(
+                "{} Failed to add git note: {}",
+                "[!]".yellow(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+
     // Create tag
     let output = Command::new("git")
         .current_dir(project_dir)
@@ -311,17 +484,23 @@ pub fn git_snapshot(project_path: &str, tag: Option<&str>, message: Option<&str>
     println!("{} Created snapshot", "[OK]".green());
     println!("   Tag: {}", tag_name);
     println!("   Commit: {}", hash);
+    if let Some(id) = &session_id {
+        println!("   Session: {}", id);
+    }
 
     Ok(())
 }
 
-/// Track chat sessions together with associated file changes
+/// Track chat sessions together with associated file changes, stamping the
+/// commit with the session like [`git_snapshot`] does
 pub fn git_track(
     project_path: &str,
     message: Option<&str>,
     all: bool,
     files: Option<&[String]>,
     tag: Option<&str>,
+    session: Option<&str>,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
     let project_dir = Path::new(project_path);
     let chat_sessions_path = project_dir.join(".vscode").join("chat-sessions");
@@ -399,12 +578,16 @@ pub fn git_track(
     // Generate commit message
     let timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M").to_string();
     let default_msg = format!("Track chat sessions with changes ({})", timestamp);
-    let commit_msg = message.unwrap_or(&default_msg);
+    let msg = message.unwrap_or(&default_msg);
+    let commit_msg = match snapshot_session_id(project_dir, session, discovery)? {
+        Some(id) => format!("{} {}{}]", msg, SESSION_MARKER, id),
+        None => msg.to_string(),
+    };
 
     // Create commit
     let output = Command::new("git")
         .current_dir(project_dir)
-        .args(["commit", "-m", commit_msg])
+        .args(["commit", "-m", &commit_msg])
         .output()?;
 
     if !output.status.success() {
@@ -429,7 +612,7 @@ pub fn git_track(
     if let Some(tag_name) = tag {
         let output = Command::new("git")
             .current_dir(project_dir)
-            .args(["tag", "-a", tag_name, "-m", commit_msg])
+            .args(["tag", "-a", tag_name, "-m", &commit_msg])
             .output()?;
 
         if output.status.success() {
@@ -440,8 +623,83 @@ pub fn git_track(
     Ok(())
 }
 
-/// Show history of chat session commits with associated file changes
-pub fn git_log(project_path: &str, count: usize, sessions_only: bool) -> Result<()> {
+/// `git track --amend`: stamp the last commit with its session after the
+/// fact, prepending `[CSM: <id>]` to its message
+///
+/// Only the message changes (`git commit --amend --only`), so anything
+/// staged stays staged. A commit its upstream already contains is left alone
+/// unless `force`, since rewriting it would diverge from what was pushed.
+pub fn git_track_amend(
+    project_path: &str,
+    session: Option<&str>,
+    force: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let project_dir = Path::new(project_path);
+    if find_git_root(project_dir).is_none() {
+        anyhow::bail!("Not a git repository: {}", project_path);
+    }
+    let Some(session_id) = snapshot_session_id(project_dir, session, discovery)? else {
+        anyhow::bail!("No session to stamp: pass --session <id>");
+    };
+    let message =
+        git_output(project_dir, &["log", "-1", "--format=%B"]).context("No commit to amend")?;
+    let marker = format!("{}{}]", SESSION_MARKER, session_id);
+    if message.contains(&marker) {
+        println!(
+            "{} The last commit is already stamped {}",
+            "[i]".cyan(),
+            marker
+        );
+        return Ok(());
+    }
+
+    // With no upstream there is nothing the commit could have been pushed to
+    let pushed = git_output(project_dir, &["rev-parse", "--verify", "--quiet", "@{u}"]).is_ok()
+        && git_output(
+            project_dir,
+            &["merge-base", "--is-ancestor", "HEAD", "@{u}"],
+        )
+        .is_ok();
+    if pushed && !force {
+        anyhow::bail!(
+            "The last commit is already pushed to its upstream; amending it rewrites published \
+             history (use --force to amend anyway)"
+        );
+    }
+
+    let old_hash = git_output(project_dir, &["rev-parse", "--short", "HEAD"])?;
+    let amended = format!("{} {}", marker, message.trim_end());
+    git_output(
+        project_dir,
+        &["commit", "--amend", "--only", "--quiet", "-m", &amended],
+    )?;
+    let new_hash = git_output(project_dir, &["rev-parse", "--short", "HEAD"])?;
+
+    println!(
+        "{} Amended {} -> {}: {}",
+        "[OK]".green(),
+        old_hash.trim(),
+        new_hash.trim(),
+        amended.lines().next().unwrap_or_default()
+    );
+    if pushed {
+        println!(
+            "{} The original was pushed; publishing this needs a force push",
+            "[!]".yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Show history of chat session commits with associated file changes,
+/// optionally only snapshots stamped with `session` (an ID or prefix)
+pub fn git_log(
+    project_path: &str,
+    count: usize,
+    sessions_only: bool,
+    session: Option<&str>,
+) -> Result<()> {
     let project_dir = Path::new(project_path);
 
     println!("{} Chat Session History", "[*]".blue().bold());
@@ -455,6 +713,11 @@ pub fn git_log(project_path: &str, count: usize, sessions_only: bool) -> Result<
         "--date=short".to_string(),
     ];
 
+    if let Some(session) = session {
+        args.push("--fixed-strings".to_string());
+        args.push(format!("--grep={}{}", SESSION_MARKER, session));
+    }
+
     if sessions_only {
         args.push("--".to_string());
         args.push(".vscode/chat-sessions".to_string());
@@ -602,6 +865,270 @@ pub fn git_diff(
     Ok(())
 }
 
+/// Git's well-known empty tree, the base for a diff that starts at a root commit
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+fn git_output(project_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(project_dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Color one line of unified diff output: additions green, deletions red,
+/// hunk headers cyan and file headers bold
+fn colorize_diff_line(line: &str) -> ColoredString {
+    if line.starts_with("+++") || line.starts_with("---") || line.starts_with("diff --git") {
+        line.bold()
+    } else if line.starts_with("@@") {
+        line.cyan()
+    } else if line.starts_with('+') {
+        line.green()
+    } else if line.starts_with('-') {
+        line.red()
+    } else {
+        line.normal()
+    }
+}
+
+/// Files changed and lines added/removed, from `git diff --numstat` output
+/// (binary files count as changed with no lines)
+fn numstat_totals(numstat: &str) -> (usize, usize, usize) {
+    numstat.lines().filter(|l| !l.trim().is_empty()).fold(
+        (0, 0, 0),
+        |(files, added, removed), line| {
+            let mut cols = line.split('\t');
+            let a = cols.next().and_then(|c| c.parse().ok()).unwrap_or(0);
+            let r = cols.next().and_then(|c| c.parse().ok()).unwrap_or(0);
+            (files + 1, added + a, removed + r)
+        },
+    )
+}
+
+/// Repository holding a session's commits: the one `csm link` recorded, if it
+/// still exists, else the one at `project_dir`. A link elsewhere is reported
+/// as "<activity> there".
+fn session_repo(project_dir: &Path, session_id: &str, activity: &str) -> Result<PathBuf> {
+    let index = LinkIndex::load()?;
+    let Some(repo) = index.repo_for(session_id).map(PathBuf::from) else {
+        return Ok(project_dir.to_path_buf());
+    };
+    if !repo.is_dir() {
+        println!(
+            "{} {} is linked to {}, which no longer exists; using {}",
+            "[!]".yellow(),
+            session_id,
+            repo.display(),
+            project_dir.display()
+        );
+        return Ok(project_dir.to_path_buf());
+    }
+
+    let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+    if find_git_root(&canonical(project_dir)).as_deref() != Some(canonical(&repo).as_path()) {
+        println!(
+            "{} {} is linked to {}; {} there",
+            "[i]".cyan(),
+            session_id,
+            repo.display(),
+            activity
+        );
+    }
+    Ok(repo)
+}
+
+/// Combined diff of every commit stamped `[CSM: <session>]`, from the parent
+/// of the earliest to the latest
+pub fn git_diff_session(
+    project_path: &str,
+    session: &str,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let session_id = snapshot_session_id(Path::new(project_path), Some(session), discovery)?
+        .unwrap_or_else(|| session.to_string());
+    let repo = session_repo(Path::new(project_path), &session_id, "diffing")?;
+    let project_dir = repo.as_path();
+
+    let grep = format!("--grep={}{}", SESSION_MARKER, session_id);
+    let log = git_output(
+        project_dir,
+        &["log", "--reverse", "--format=%H", "--fixed-strings", &grep],
+    )?;
+    let commits: Vec<&str> = log.lines().filter(|l| !l.is_empty()).collect();
+    let (Some(first), Some(last)) = (commits.first(), commits.last()) else {
+        anyhow::bail!("No commits found for session {}", session_id);
+    };
+
+    // A session that starts at the root commit has no parent to diff from
+    let parent = format!("{}^", first);
+    let base = if git_output(project_dir, &["rev-parse", "--verify", "--quiet", &parent]).is_ok() {
+        parent
+    } else {
+        EMPTY_TREE.to_string()
+    };
+
+    println!("{} Session Diff: {}", "[*]".blue().bold(), session_id);
+    println!("{}", "=".repeat(60));
+    if commits.len() == 1 {
+        println!("{} 1 commit ({})", "[>]".blue(), &first[..7]);
+    } else {
+        println!(
+            "{} {} commits ({}..{})",
+            "[>]".blue(),
+            commits.len(),
+            &first[..7],
+            &last[..7]
+        );
+    }
+
+    let (files, added, removed) = numstat_totals(&git_output(
+        project_dir,
+        &["diff", "--numstat", &base, last],
+    )?);
+    println!(
+        "{} {} file(s) changed, {} insertion(s), {} deletion(s)",
+        "[=]".blue(),
+        files,
+        format!("+{}", added).green(),
+        format!("-{}", removed).red()
+    );
+
+    let diff = git_output(project_dir, &["diff", &base, last])?;
+    if !diff.is_empty() {
+        println!();
+        for line in diff.lines() {
+            println!("{}", colorize_diff_line(line));
+        }
+    }
+
+    Ok(())
+}
+
+/// How a file differs from HEAD, from `git status --porcelain`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Unknown,
+}
+
+impl FileStatus {
+    /// Marker shown before the file, in the `[+]`/`[-]` style of other listings
+    fn marker(self) -> ColoredString {
+        match self {
+            FileStatus::Added => "[+]".green(),
+            FileStatus::Modified => "[~]".yellow(),
+            FileStatus::Deleted => "[-]".red(),
+            FileStatus::Renamed => "[>]".blue(),
+            FileStatus::Unknown => "[?]".dimmed(),
+        }
+    }
+}
+
+/// Parse one `git status --porcelain` line into its status and path
+///
+/// The staged (index) column wins; an unstaged change is read from the
+/// work-tree column. Untracked files count as added.
+pub fn parse_porcelain_line(line: &str) -> Option<(FileStatus, String)> {
+    let mut codes = line.get(..2)?.chars();
+    let path = line.get(3..)?.trim();
+    if path.is_empty() {
+        return None;
+    }
+    let (index, worktree) = (codes.next()?, codes.next()?);
+    let code = if index == ' ' { worktree } else { index };
+    let status = match code {
+        'A' | '?' => FileStatus::Added,
+        'M' | 'T' => FileStatus::Modified,
+        'D' => FileStatus::Deleted,
+        'R' | 'C' => FileStatus::Renamed,
+        _ => FileStatus::Unknown,
+    };
+    Some((status, path.to_string()))
+}
+
+/// Working-tree changes in the repository a session is linked to, next to
+/// the latest commit stamped `[CSM: <session>]`: what's done and what's pending
+pub fn git_status_session(
+    project_path: &str,
+    session: &str,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let session_id = snapshot_session_id(Path::new(project_path), Some(session), discovery)?
+        .unwrap_or_else(|| session.to_string());
+    let repo = session_repo(Path::new(project_path), &session_id, "reading status")?;
+    let project_dir = repo.as_path();
+    if find_git_root(project_dir).is_none() {
+        anyhow::bail!("Not a git repository: {}", project_dir.display());
+    }
+
+    println!("{} Session Status: {}", "[*]".blue().bold(), session_id);
+    println!("{}", "=".repeat(60));
+    println!("{} Repository: {}", "[>]".blue(), project_dir.display());
+
+    let grep = format!("--grep={}{}", SESSION_MARKER, session_id);
+    // A repository without commits has no log to search
+    let last = git_output(
+        project_dir,
+        &[
+            "log",
+            "-1",
+            "--format=%h%x09%s%x09%cr",
+            "--fixed-strings",
+            &grep,
+        ],
+    )
+    .unwrap_or_default();
+    match last.trim().splitn(3, '\t').collect::<Vec<_>>().as_slice() {
+        [hash, subject, when] => println!(
+            "{} Last session commit: {} {} ({})",
+            "[>]".blue(),
+            hash.yellow(),
+            subject,
+            when
+        ),
+        _ => println!(
+            "{} No commits are stamped with this session yet",
+            "[i]".cyan()
+        ),
+    }
+
+    let status = git_output(project_dir, &["status", "--porcelain"])?;
+    let changes: Vec<(FileStatus, String)> =
+        status.lines().filter_map(parse_porcelain_line).collect();
+    if changes.is_empty() {
+        println!("\n{} Working tree clean; nothing pending", "[OK]".green());
+        return Ok(());
+    }
+
+    let count = |status: FileStatus| changes.iter().filter(|(s, _)| *s == status).count();
+    println!(
+        "\n{} {} pending change(s): {} added, {} modified, {} deleted, {} renamed",
+        "[=]".blue(),
+        changes.len(),
+        count(FileStatus::Added),
+        count(FileStatus::Modified),
+        count(FileStatus::Deleted),
+        count(FileStatus::Renamed)
+    );
+    for (status, path) in &changes {
+        println!("   {} {}", status.marker(), path);
+    }
+
+    Ok(())
+}
+
 /// Restore chat sessions from a specific commit
 pub fn git_restore(project_path: &str, commit: &str, with_files: bool, backup: bool) -> Result<()> {
     let project_dir = Path::new(project_path);
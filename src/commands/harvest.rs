@@ -16,12 +16,16 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Duration;
 
-use crate::browser::{get_installed_browsers, scan_browser_auth, BrowserType};
+use crate::browser::{get_installed_browsers, scan_browser_auth_with_timeout};
+use crate::commands::{export_status, is_stdout_target, load_redactor, OutputSink};
 use crate::database::{ChatDatabase, ShareLinkParser};
+use crate::links::{find_git_root, LinkIndex};
 use crate::models::ChatSession;
 use crate::providers::{ProviderRegistry, ProviderType};
 use crate::storage::parse_session_json;
-use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace};
+use crate::workspace::{
+    discover_workspaces, get_chat_sessions_from_workspace, list_session_files, DiscoveryOptions,
+};
 
 /// Check if a string is an empty code block marker (just ``` with no content)
 fn is_empty_code_block(s: &str) -> bool {
@@ -136,19 +140,27 @@ const WEB_PROVIDERS: &[WebProviderEndpoint] = &[
     },
 ];
 
-/// Scan for reachable web-based LLM providers (parallel)
-fn scan_web_providers(timeout_secs: u64) -> Vec<String> {
+/// One web endpoint probed by `harvest scan --web`
+#[derive(Debug, Clone, Serialize)]
+pub struct WebProbe {
+    pub name: String,
+    pub url: String,
+    pub reachable: bool,
+    /// The endpoint's description when reachable, else why not
+    pub detail: String,
+}
+
+/// Scan for reachable web-based LLM providers (parallel), sorted by name
+fn scan_web_providers(timeout_secs: u64) -> Vec<WebProbe> {
     use std::sync::{Arc, Mutex};
     use std::thread;
 
-    let reachable = Arc::new(Mutex::new(Vec::new()));
     let results = Arc::new(Mutex::new(Vec::new()));
 
     // Create threads for parallel scanning
     let handles: Vec<_> = WEB_PROVIDERS
         .iter()
         .map(|provider| {
-            let reachable = Arc::clone(&reachable);
             let results = Arc::clone(&results);
             let timeout = timeout_secs;
             let name = provider.name;
@@ -166,34 +178,33 @@ fn scan_web_providers(timeout_secs: u64) -> Vec<String> {
                     Err(_) => return,
                 };
 
-                let result: (&str, bool, String, &str) = match client.head(url).send() {
+                let (reachable, detail) = match client.head(url).send() {
                     Ok(response) => {
                         let status = response.status();
                         if status.is_success() || status.is_redirection() {
-                            reachable.lock().unwrap().push(name.to_string());
-                            (name, true, desc.to_string(), url)
+                            (true, desc.to_string())
                         } else {
-                            (name, false, format!("HTTP {}", status.as_u16()), url)
+                            (false, format!("HTTP {}", status.as_u16()))
                         }
                     }
                     Err(e) => {
                         let reason = if e.is_timeout() {
-                            "timeout".to_string()
+                            "timeout"
                         } else if e.is_connect() {
-                            "connection failed".to_string()
+                            "connection failed"
                         } else {
-                            "unreachable".to_string()
+                            "unreachable"
                         };
-                        (name, false, reason, url)
+                        (false, reason.to_string())
                     }
                 };
 
-                results.lock().unwrap().push((
-                    result.0.to_string(),
-                    result.1,
-                    result.2,
-                    result.3.to_string(),
-                ));
+                results.lock().unwrap().push(WebProbe {
+                    name: name.to_string(),
+                    url: url.to_string(),
+                    reachable,
+                    detail,
+                });
             })
         })
         .collect();
@@ -203,81 +214,117 @@ fn scan_web_providers(timeout_secs: u64) -> Vec<String> {
         let _ = handle.join();
     }
 
-    // Print results in order
-    let results = results.lock().unwrap();
-    let mut sorted_results: Vec<_> = results.iter().collect();
-    sorted_results.sort_by_key(|(name, _, _, _)| name.as_str());
+    let mut probes = std::mem::take(&mut *results.lock().unwrap());
+    probes.sort_by(|a, b| a.name.cmp(&b.name));
+    probes
+}
 
-    for (name, success, info, url) in sorted_results {
-        if *success {
+fn print_web_probes(probes: &[WebProbe]) {
+    for probe in probes {
+        if probe.reachable {
             println!(
                 "   {} {}: {} ({})",
                 "[+]".green(),
-                name.bold(),
+                probe.name.bold(),
                 "reachable".green(),
-                info.dimmed()
+                probe.detail.dimmed()
             );
-            println!("      {} {}", "`".dimmed(), url.dimmed());
+            println!("      {} {}", "`".dimmed(), probe.url.dimmed());
         } else {
             println!(
                 "   {} {}: {} ({})",
                 "[-]".dimmed(),
-                name,
+                probe.name,
                 "blocked or unavailable".dimmed(),
-                info.dimmed()
+                probe.detail.dimmed()
             );
         }
     }
+}
 
-    let result = reachable.lock().unwrap().clone();
-    result
+/// A web provider logged into in at least one browser profile
+#[derive(Debug, Clone, Serialize)]
+pub struct WebAuthFinding {
+    pub provider: String,
+    /// Browser and profile of each login, e.g. "Edge (Work)"
+    pub sources: Vec<String>,
+    /// Whether the provider's endpoint answered the probe
+    pub reachable: bool,
 }
 
-/// Scan browser cookies for authenticated web LLM providers
-fn scan_browser_authentication(
-    verbose: bool,
-) -> (std::collections::HashMap<String, Vec<BrowserType>>, usize) {
-    use crate::browser::scan_browser_auth_verbose;
-    use std::collections::HashMap;
+/// What `harvest scan --web` found online and in browser cookies
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WebScan {
+    pub endpoints: Vec<WebProbe>,
+    /// Browsers whose cookies were checked
+    pub browsers: Vec<String>,
+    /// Browsers skipped because they didn't finish within the timeout
+    pub timed_out_browsers: Vec<String>,
+    pub authenticated: Vec<WebAuthFinding>,
+}
+
+/// Scan browser cookies for authenticated web LLM providers, filling in
+/// `browsers`, `timed_out_browsers` and `authenticated` (as not yet reachable)
+fn scan_browser_authentication(verbose: bool, timeout: Duration, json: bool) -> WebScan {
+    use std::collections::BTreeMap;
 
     let installed = get_installed_browsers();
+    let mut web = WebScan {
+        browsers: installed.iter().map(|b| b.name().to_string()).collect(),
+        ..Default::default()
+    };
     if installed.is_empty() {
-        println!("   {} No supported browsers found", "[-]".dimmed());
-        return (HashMap::new(), 0);
+        if !json {
+            println!("   {} No supported browsers found", "[-]".dimmed());
+        }
+        return web;
+    }
+    if !json {
+        println!(
+            "   {} Checking {} browser(s): {}",
+            "[*]".blue(),
+            installed.len(),
+            web.browsers.join(", ")
+        );
     }
 
-    println!(
-        "   {} Checking {} browser(s): {}",
-        "[*]".blue(),
-        installed.len(),
-        installed
-            .iter()
-            .map(|b| b.name())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-
-    let results = if verbose {
-        scan_browser_auth_verbose()
-    } else {
-        scan_browser_auth()
-    };
-
-    // Group results by provider
-    let mut authenticated: HashMap<String, Vec<BrowserType>> = HashMap::new();
+    // Each browser scans on its own thread; stragglers are dropped at the deadline
+    let scan = scan_browser_auth_with_timeout(verbose, timeout);
+    for browser in &scan.timed_out {
+        web.timed_out_browsers.push(browser.name().to_string());
+        if !json {
+            println!(
+                "   {} {} did not finish within {}s (cookie database locked?), skipped",
+                "[!]".yellow(),
+                browser.name(),
+                timeout.as_secs()
+            );
+        }
+    }
 
-    for result in results {
+    // Group results by provider, tagged with browser and profile
+    let mut authenticated: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for result in scan.results {
         if result.authenticated {
             authenticated
                 .entry(result.provider.clone())
                 .or_default()
-                .push(result.browser);
+                .push(result.source());
         }
     }
+    web.authenticated = authenticated
+        .into_iter()
+        .map(|(provider, sources)| WebAuthFinding {
+            provider,
+            sources,
+            reachable: false,
+        })
+        .collect();
 
-    let count = authenticated.len();
-
-    if authenticated.is_empty() {
+    if json {
+        return web;
+    }
+    if web.authenticated.is_empty() {
         println!(
             "   {} No authenticated web LLM providers found",
             "[-]".dimmed()
@@ -287,19 +334,17 @@ fn scan_browser_authentication(
             "`".dimmed()
         );
     } else {
-        for (provider, browsers) in &authenticated {
-            let browser_names: Vec<_> = browsers.iter().map(|b| b.name()).collect();
+        for finding in &web.authenticated {
             println!(
                 "   {} {}: {} in {}",
                 "[+]".green(),
-                provider.bold(),
+                finding.provider.bold(),
                 "authenticated".green(),
-                browser_names.join(", ")
+                finding.sources.join(", ")
             );
         }
     }
-
-    (authenticated, count)
+    web
 }
 
 /// Configuration for the harvest database
@@ -408,22 +453,72 @@ pub fn harvest_init(path: Option<&str>, git_init: bool) -> Result<()> {
     Ok(())
 }
 
-/// Scan for available providers and workspaces
+/// A provider checked by `harvest scan`
+#[derive(Debug, Clone, Serialize)]
+pub struct ScannedProvider {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub provider_type: ProviderType,
+    pub available: bool,
+    pub sessions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Session titles, with `--sessions`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_titles: Option<Vec<String>>,
+}
+
+/// A VS Code workspace holding chat sessions
+#[derive(Debug, Clone, Serialize)]
+pub struct ScannedWorkspace {
+    /// Project path, or the storage hash when it has none
+    pub name: String,
+    pub sessions: usize,
+}
+
+/// VS Code workspaces found by `harvest scan`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScannedWorkspaces {
+    /// Workspaces with at least one session
+    pub count: usize,
+    pub sessions: usize,
+    /// Why workspace discovery failed, if it did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Each of those workspaces, with `--sessions`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list: Option<Vec<ScannedWorkspace>>,
+}
+
+/// Everything `harvest scan` found, as `--json` prints it
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HarvestScan {
+    pub providers: Vec<ScannedProvider>,
+    pub workspaces: ScannedWorkspaces,
+    /// Web endpoints and browser logins, with `--web`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web: Option<WebScan>,
+    pub total_sessions: usize,
+}
+
+/// Scan for available providers and workspaces; with `json`, print the
+/// [`HarvestScan`] instead of the report
 pub fn harvest_scan(
     show_sessions: bool,
     scan_web: bool,
     timeout_secs: u64,
     verbose: bool,
+    json: bool,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
-    println!("\n{} Scanning for Providers", "[H]".magenta().bold());
-    println!("{}", "=".repeat(60));
+    if !json {
+        println!("\n{} Scanning for Providers", "[H]".magenta().bold());
+        println!("{}", "=".repeat(60));
+        println!("\n{} LLM Providers:", "[*]".blue().bold());
+    }
 
     let registry = ProviderRegistry::new();
-    let mut total_sessions = 0;
-    let mut available_providers = Vec::new();
-
-    // Scan LLM providers
-    println!("\n{} LLM Providers:", "[*]".blue().bold());
+    let mut scan = HarvestScan::default();
 
     let provider_types = vec![
         ProviderType::Copilot,
@@ -446,176 +541,252 @@ pub fn harvest_scan(
     ];
 
     for pt in &provider_types {
-        if let Some(provider) = registry.get_provider(*pt) {
-            let available = provider.is_available();
-            let session_count = if available {
-                provider.list_sessions().map(|s| s.len()).unwrap_or(0)
-            } else {
-                0
-            };
-
-            if available {
-                available_providers.push((*pt, session_count));
-                total_sessions += session_count;
-
-                let status = if session_count > 0 {
-                    format!(
-                        "{} {} sessions",
-                        "+".green(),
-                        session_count.to_string().cyan()
-                    )
-                } else {
-                    format!("{} no sessions", "+".green())
-                };
-
-                println!(
-                    "   {} {}: {}",
-                    "[+]".green(),
-                    provider.name().bold(),
-                    status
-                );
-
-                if show_sessions && session_count > 0 {
-                    if let Ok(sessions) = provider.list_sessions() {
-                        for session in sessions.iter().take(3) {
-                            println!("      {} {}", "`".dimmed(), session.title().dimmed());
-                        }
-                        if sessions.len() > 3 {
-                            println!("      {} ... and {} more", "`".dimmed(), sessions.len() - 3);
-                        }
-                    }
-                }
-
-                if let Some(path) = provider.sessions_path() {
-                    println!(
-                        "      {} {}",
-                        "`".dimmed(),
-                        path.display().to_string().dimmed()
-                    );
-                }
-            }
+        let Some(provider) = registry.get_provider(*pt) else {
+            continue;
+        };
+        let available = provider.is_available();
+        let sessions = if available {
+            provider.list_sessions().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let scanned = ScannedProvider {
+            name: provider.name().to_string(),
+            provider_type: *pt,
+            available,
+            sessions: sessions.len(),
+            path: available.then(|| provider.sessions_path()).flatten(),
+            session_titles: show_sessions.then(|| sessions.iter().map(|s| s.title()).collect()),
+        };
+        scan.total_sessions += scanned.sessions;
+        if !json && available {
+            print_scanned_provider(&scanned);
         }
+        scan.providers.push(scanned);
     }
 
     // Scan VS Code workspaces
-    println!("\n{} VS Code Workspaces:", "[*]".blue().bold());
-
-    match discover_workspaces() {
+    if !json {
+        println!("\n{} VS Code Workspaces:", "[*]".blue().bold());
+    }
+    match discover_workspaces(discovery) {
         Ok(workspaces) => {
-            let workspaces_with_sessions: Vec<_> = workspaces
+            let with_sessions: Vec<ScannedWorkspace> = workspaces
                 .iter()
                 .filter(|ws| ws.chat_session_count > 0)
-                .collect();
-
-            let ws_sessions: usize = workspaces_with_sessions
-                .iter()
-                .map(|ws| ws.chat_session_count)
-                .sum();
-
-            println!(
-                "   {} {} workspaces with {} sessions",
-                "[+]".green(),
-                workspaces_with_sessions.len().to_string().cyan(),
-                ws_sessions.to_string().cyan()
-            );
-
-            if show_sessions {
-                for ws in workspaces_with_sessions.iter().take(5) {
-                    let name = ws
+                .map(|ws| ScannedWorkspace {
+                    name: ws
                         .project_path
                         .clone()
-                        .unwrap_or_else(|| ws.hash[..8.min(ws.hash.len())].to_string());
-                    println!(
-                        "      {} {} ({} sessions)",
-                        "`".dimmed(),
-                        name.dimmed(),
-                        ws.chat_session_count
-                    );
-                }
-                if workspaces_with_sessions.len() > 5 {
-                    println!(
-                        "      {} ... and {} more workspaces",
-                        "`".dimmed(),
-                        workspaces_with_sessions.len() - 5
-                    );
-                }
+                        .unwrap_or_else(|| ws.hash[..8.min(ws.hash.len())].to_string()),
+                    sessions: ws.chat_session_count,
+                })
+                .collect();
+            scan.workspaces.count = with_sessions.len();
+            scan.workspaces.sessions = with_sessions.iter().map(|ws| ws.sessions).sum();
+            scan.workspaces.list = show_sessions.then_some(with_sessions);
+            scan.total_sessions += scan.workspaces.sessions;
+            if !json {
+                print_scanned_workspaces(&scan.workspaces);
             }
-
-            total_sessions += ws_sessions;
         }
         Err(e) => {
-            println!("   {} Failed to scan workspaces: {}", "[!]".yellow(), e);
+            if !json {
+                println!("   {} Failed to scan workspaces: {}", "[!]".yellow(), e);
+            }
+            scan.workspaces.error = Some(e.to_string());
         }
     }
 
     // Scan web-based LLM providers if requested
-    let mut web_providers_found = Vec::new();
-    let mut authenticated_count = 0;
     if scan_web {
         // First check browser authentication (no network requests)
-        println!("\n{} Browser Authentication:", "[*]".blue().bold());
-        let (auth_results, auth_count) = scan_browser_authentication(verbose);
-        authenticated_count = auth_count;
+        if !json {
+            println!("\n{} Browser Authentication:", "[*]".blue().bold());
+        }
+        let mut web = scan_browser_authentication(verbose, Duration::from_secs(timeout_secs), json);
 
         // Then probe web endpoints
-        println!("\n{} Web LLM Provider Endpoints:", "[*]".blue().bold());
-        web_providers_found = scan_web_providers(timeout_secs);
-
-        // Show which authenticated providers are reachable
-        if !auth_results.is_empty() {
-            println!("\n{} Authenticated Provider Sessions:", "[*]".blue().bold());
-            for (provider, browsers) in &auth_results {
-                let browser_list: Vec<_> = browsers.iter().map(|b| b.name()).collect();
-                let reachable = web_providers_found.iter().any(|p| p == provider);
-                let status = if reachable {
-                    format!("{} (reachable)", "ready to harvest".green())
-                } else {
-                    format!("{}", "authenticated but endpoint blocked".yellow())
-                };
-                println!(
-                    "   {} {}: {} via {}",
-                    "[+]".green(),
-                    provider.bold(),
-                    status,
-                    browser_list.join(", ").dimmed()
-                );
-            }
+        if !json {
+            println!("\n{} Web LLM Provider Endpoints:", "[*]".blue().bold());
+        }
+        web.endpoints = scan_web_providers(timeout_secs);
+        for finding in &mut web.authenticated {
+            finding.reachable = web
+                .endpoints
+                .iter()
+                .any(|p| p.reachable && p.name == finding.provider);
         }
+        if !json {
+            print_web_probes(&web.endpoints);
+            print_auth_readiness(&web.authenticated);
+        }
+        scan.web = Some(web);
+    }
+
+    if json {
+        println!("{}", crate::commands::to_json(&scan)?);
+        return Ok(());
     }
 
     // Summary
     println!("\n{} Summary:", "[*]".green().bold());
     println!(
         "   {} local providers available",
-        available_providers.len().to_string().cyan()
+        scan.providers
+            .iter()
+            .filter(|p| p.available)
+            .count()
+            .to_string()
+            .cyan()
     );
-    if scan_web {
+    if let Some(web) = &scan.web {
         println!(
             "   {} web providers reachable",
-            web_providers_found.len().to_string().cyan()
+            web.endpoints
+                .iter()
+                .filter(|p| p.reachable)
+                .count()
+                .to_string()
+                .cyan()
         );
         println!(
             "   {} web providers authenticated",
-            authenticated_count.to_string().cyan()
+            web.authenticated.len().to_string().cyan()
         );
     }
     println!(
         "   {} total sessions to harvest",
-        total_sessions.to_string().cyan()
+        scan.total_sessions.to_string().cyan()
     );
 
     Ok(())
 }
 
-/// Run the harvest operation
+fn print_scanned_provider(provider: &ScannedProvider) {
+    let status = if provider.sessions > 0 {
+        format!(
+            "{} {} sessions",
+            "+".green(),
+            provider.sessions.to_string().cyan()
+        )
+    } else {
+        format!("{} no sessions", "+".green())
+    };
+    println!("   {} {}: {}", "[+]".green(), provider.name.bold(), status);
+
+    if let Some(titles) = &provider.session_titles {
+        for title in titles.iter().take(3) {
+            println!("      {} {}", "`".dimmed(), title.dimmed());
+        }
+        if titles.len() > 3 {
+            println!("      {} ... and {} more", "`".dimmed(), titles.len() - 3);
+        }
+    }
+
+    if let Some(path) = &provider.path {
+        println!(
+            "      {} {}",
+            "`".dimmed(),
+            path.display().to_string().dimmed()
+        );
+    }
+}
+
+fn print_scanned_workspaces(workspaces: &ScannedWorkspaces) {
+    println!(
+        "   {} {} workspaces with {} sessions",
+        "[+]".green(),
+        workspaces.count.to_string().cyan(),
+        workspaces.sessions.to_string().cyan()
+    );
+    let Some(list) = &workspaces.list else {
+        return;
+    };
+    for ws in list.iter().take(5) {
+        println!(
+            "      {} {} ({} sessions)",
+            "`".dimmed(),
+            ws.name.dimmed(),
+            ws.sessions
+        );
+    }
+    if list.len() > 5 {
+        println!(
+            "      {} ... and {} more workspaces",
+            "`".dimmed(),
+            list.len() - 5
+        );
+    }
+}
+
+/// Show which authenticated providers are reachable
+fn print_auth_readiness(findings: &[WebAuthFinding]) {
+    if findings.is_empty() {
+        return;
+    }
+    println!("\n{} Authenticated Provider Sessions:", "[*]".blue().bold());
+    for finding in findings {
+        let status = if finding.reachable {
+            format!("{} (reachable)", "ready to harvest".green())
+        } else {
+            format!("{}", "authenticated but endpoint blocked".yellow())
+        };
+        println!(
+            "   {} {}: {} via {}",
+            "[+]".green(),
+            finding.provider.bold(),
+            status,
+            finding.sources.join(", ").dimmed()
+        );
+    }
+}
+
+/// Committer date of `git_ref` in the current directory's repository, in
+/// milliseconds since the epoch (the unit of `last_message_date`)
+fn commit_date_millis(git_ref: &str) -> Result<i64> {
+    let cwd = std::env::current_dir()?;
+    if find_git_root(&cwd).is_none() {
+        anyhow::bail!(
+            "--since-commit: {} is not inside a git repository",
+            cwd.display()
+        );
+    }
+
+    let output = Command::new("git")
+        .args(["show", "-s", "--format=%ct"])
+        .arg(format!("{}^{{commit}}", git_ref))
+        .arg("--")
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!("--since-commit: '{}' does not resolve to a commit", git_ref);
+    }
+
+    let secs: i64 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .with_context(|| format!("--since-commit: no commit date for '{}'", git_ref))?;
+    Ok(secs * 1000)
+}
+
+/// Commit message `harvest run --commit` uses when none is given
+pub const HARVEST_COMMIT_MESSAGE: &str = "Harvest: update chat sessions";
+
+/// Run the harvest operation. With `since_commit`, sessions whose last message
+/// predates that commit are skipped, like unchanged ones in an incremental run.
+/// With `commit_message`, changes to the database are committed afterwards.
 pub fn harvest_run(
     path: Option<&str>,
     providers: Option<&[String]>,
     exclude: Option<&[String]>,
     incremental: bool,
-    auto_commit: bool,
-    message: Option<&str>,
+    since_commit: Option<&str>,
+    commit_message: Option<&str>,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
+    // Resolve the ref before touching the database so a typo fails fast
+    let since_commit_date = since_commit.map(commit_date_millis).transpose()?;
     let db_path = get_db_path(path)?;
 
     println!("\n{} Running Harvest", "[H]".magenta().bold());
@@ -646,6 +817,20 @@ pub fn harvest_run(
             .unwrap_or_default();
         println!("{} Incremental harvest since: {}", "[*]".blue(), dt);
     }
+    if let (Some(git_ref), Some(ts)) = (since_commit, since_commit_date) {
+        let dt = DateTime::from_timestamp_millis(ts)
+            .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        println!(
+            "{} Harvesting sessions active since {} ({})",
+            "[*]".blue(),
+            git_ref,
+            dt
+        );
+    }
+
+    // Sessions last active at or before this are skipped; the later bound wins
+    let cutoff = last_harvest.max(since_commit_date);
 
     let registry = ProviderRegistry::new();
     let include_providers = providers.map(|p| p.to_vec());
@@ -699,8 +884,8 @@ pub fn harvest_run(
                     for session in sessions {
                         stats.sessions_found += 1;
 
-                        // Check if session should be skipped (incremental)
-                        if let Some(last) = last_harvest {
+                        // Check if session should be skipped (incremental/--since-commit)
+                        if let Some(last) = cutoff {
                             if session.last_message_date <= last {
                                 stats.sessions_skipped += 1;
                                 continue;
@@ -756,7 +941,7 @@ pub fn harvest_run(
     {
         println!("\n{} Harvesting from VS Code workspaces...", "[*]".blue());
 
-        if let Ok(workspaces) = discover_workspaces() {
+        if let Ok(workspaces) = discover_workspaces(discovery) {
             for ws in &workspaces {
                 if ws.chat_session_count == 0 {
                     continue;
@@ -768,8 +953,8 @@ pub fn harvest_run(
                     for swp in sessions {
                         stats.sessions_found += 1;
 
-                        // Check if session should be skipped (incremental)
-                        if let Some(last) = last_harvest {
+                        // Check if session should be skipped (incremental/--since-commit)
+                        if let Some(last) = cutoff {
                             if swp.session.last_message_date <= last {
                                 stats.sessions_skipped += 1;
                                 continue;
@@ -855,9 +1040,9 @@ pub fn harvest_run(
     }
 
     // Auto-commit if requested
-    if auto_commit && (stats.sessions_added > 0 || stats.sessions_updated > 0) {
+    let changed = stats.sessions_added > 0 || stats.sessions_updated > 0;
+    if let Some(commit_msg) = commit_message.filter(|_| changed) {
         println!("\n{} Auto-committing changes...", "[*]".blue());
-        let commit_msg = message.unwrap_or("Harvest: update chat sessions");
         if let Err(e) = git_commit_harvest(&db_path, commit_msg) {
             println!("{} Git commit failed: {}", "[!]".yellow(), e);
         } else {
@@ -1122,13 +1307,15 @@ pub fn harvest_list(
 /// Export sessions from the harvest database
 pub fn harvest_export(
     path: Option<&str>,
-    output: &str,
+    output: Option<&str>,
     format: &str,
     provider: Option<&str>,
     session_ids: Option<&[String]>,
+    redact: bool,
 ) -> Result<()> {
     let db_path = get_db_path(path)?;
-    let output_path = PathBuf::from(output);
+    let to_stdout = is_stdout_target(output);
+    let redactor = load_redactor(redact)?;
 
     if !db_path.exists() {
         anyhow::bail!("Database not found: {}", db_path.display());
@@ -1136,8 +1323,11 @@ pub fn harvest_export(
 
     let conn = Connection::open(&db_path)?;
 
-    println!("\n{} Exporting Sessions", "[H]".magenta().bold());
-    println!("{}", "=".repeat(60));
+    export_status(
+        to_stdout,
+        format!("\n{} Exporting Sessions", "[H]".magenta().bold()),
+    );
+    export_status(to_stdout, "=".repeat(60));
 
     // Build query
     let mut query = String::from("SELECT session_json FROM sessions WHERE 1=1");
@@ -1165,36 +1355,36 @@ pub fn harvest_export(
         .collect();
 
     if sessions.is_empty() {
-        println!("{} No sessions to export", "[i]".dimmed());
+        export_status(
+            to_stdout,
+            format!("{} No sessions to export", "[i]".dimmed()),
+        );
         return Ok(());
     }
 
-    // Create output directory if needed
-    if let Some(parent) = output_path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent)?;
+    // Parse each session once, scrubbing string values when redacting
+    let parse_value = |s: &String| {
+        let mut value: serde_json::Value = serde_json::from_str(s).ok()?;
+        if let Some(redactor) = &redactor {
+            redactor.redact_json(&mut value);
         }
-    }
+        Some(value)
+    };
 
-    match format.to_lowercase().as_str() {
+    let document = match format.to_lowercase().as_str() {
         "json" => {
             // Export as JSON array
-            let parsed: Vec<serde_json::Value> = sessions
-                .iter()
-                .filter_map(|s| serde_json::from_str(s).ok())
-                .collect();
-            let json_output = serde_json::to_string_pretty(&parsed)?;
-            fs::write(&output_path, json_output)?;
+            let parsed: Vec<serde_json::Value> = sessions.iter().filter_map(parse_value).collect();
+            serde_json::to_string_pretty(&parsed)?
         }
         "jsonl" => {
             // Export as JSON Lines
-            let content: String = sessions
+            sessions
                 .iter()
-                .filter_map(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                .filter_map(parse_value)
                 .map(|v| serde_json::to_string(&v).unwrap_or_default())
                 .collect::<Vec<_>>()
-                .join("\n");
-            fs::write(&output_path, content)?;
+                .join("\n")
         }
         "md" | "markdown" => {
             // Export as Markdown
@@ -1215,6 +1405,9 @@ pub fn harvest_export(
                                 md_content.push_str(&format!("### User\n\n{}\n\n", text));
                             }
                         }
+                        for call in request.tool_calls() {
+                            md_content.push_str(&format!("### Tool\n\n`{}`\n\n", call.summary()));
+                        }
                         // Extract response text from the JSON value
                         if let Some(response) = &request.response {
                             let response_text = response
@@ -1239,18 +1432,28 @@ pub fn harvest_export(
                     md_content.push_str("---\n\n");
                 }
             }
-            fs::write(&output_path, md_content)?;
+            if let Some(redactor) = &redactor {
+                md_content = redactor.redact(&md_content);
+            }
+            md_content
         }
         _ => {
             anyhow::bail!("Unknown format: {}. Supported: json, jsonl, md", format);
         }
-    }
+    };
 
-    println!(
-        "{} Exported {} sessions to {}",
-        "[+]".green(),
-        sessions.len().to_string().cyan(),
-        output_path.display()
+    let sink = OutputSink::open(output)?;
+    let destination = sink.describe();
+    let to_stdout = sink.is_stdout();
+    sink.write_all_and_finish(&document)?;
+    export_status(
+        to_stdout,
+        format!(
+            "{} Exported {} sessions to {}",
+            "[+]".green(),
+            sessions.len().to_string().cyan(),
+            destination
+        ),
     );
 
     Ok(())
@@ -1387,10 +1590,81 @@ pub fn harvest_git_restore(path: Option<&str>, commit: &str) -> Result<()> {
     Ok(())
 }
 
+/// Link every workspace session to the git repository containing its project
+///
+/// Workspaces without a project folder, or whose folder is not inside a
+/// repository, are skipped silently.
+pub fn harvest_git_auto_link(discovery: &DiscoveryOptions) -> Result<()> {
+    let mut index = LinkIndex::load()?;
+    let mut linked = 0;
+    let mut unchanged = 0;
+
+    for ws in discover_workspaces(discovery)? {
+        if !ws.has_chat_sessions {
+            continue;
+        }
+        let Some(repo) = ws
+            .project_path
+            .as_deref()
+            .and_then(|p| find_git_root(Path::new(p)))
+        else {
+            continue;
+        };
+        let repo = repo.to_string_lossy().to_string();
+
+        for path in list_session_files(&ws.chat_sessions_path) {
+            let Some(session_id) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+            if index.link(&session_id, &repo) {
+                println!("   {} {} -> {}", "[+]".green(), session_id.cyan(), repo);
+                linked += 1;
+            } else {
+                unchanged += 1;
+            }
+        }
+    }
+
+    index.save()?;
+    println!(
+        "\n{} Linked {} session(s) to git repositories ({} already linked)",
+        "[OK]".green(),
+        linked,
+        unchanged
+    );
+
+    Ok(())
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// When the harvest database (`CSM_HARVEST_DB`, else `chat_sessions.db` in
+/// the current directory) last stored a session, in ms. Fails when there is
+/// no database or nothing has been harvested into it yet.
+pub fn last_harvest_time() -> Result<i64> {
+    let db_path = get_db_path(None)?;
+    if !db_path.exists() {
+        anyhow::bail!(
+            "No harvest database at {} (run `csm harvest run` first, or set CSM_HARVEST_DB)",
+            db_path.display()
+        );
+    }
+    let conn = Connection::open(&db_path)?;
+    let last: Option<i64> = conn
+        .query_row("SELECT MAX(harvested_at) FROM sessions", [], |row| {
+            row.get(0)
+        })
+        .with_context(|| format!("{} is not a harvest database", db_path.display()))?;
+    last.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Nothing has been harvested into {} yet (run `csm harvest run` first)",
+            db_path.display()
+        )
+    })
+}
+
 fn get_db_path(path: Option<&str>) -> Result<PathBuf> {
     if let Some(p) = path {
         return Ok(PathBuf::from(p));
@@ -8,18 +8,32 @@ use colored::*;
 use std::path::Path;
 use uuid::Uuid;
 
+use crate::commands::{ensure_dir, print_dry_run, PlannedAction};
 use crate::models::{ChatRequest, ChatSession};
+use crate::status;
 use crate::storage::{
-    add_session_to_index, backup_workspace_sessions, get_workspace_storage_db, is_vscode_running,
-    register_all_sessions_from_directory,
+    add_session_to_index, backup_workspace_sessions, copy_file_atomic, get_workspace_storage_db,
+    is_vscode_running, register_all_sessions_from_directory, workspace_backup_dir,
+    write_session_atomic,
 };
 use crate::workspace::{
     discover_workspaces, find_all_workspaces_for_project, find_workspace_by_path,
-    get_chat_sessions_from_workspace, normalize_path,
+    get_chat_sessions_from_workspace, normalize_path, DiscoveryOptions,
 };
 
+/// How a merge writes the merged session
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MergeOptions {
+    /// Skip the VS Code running check
+    pub force: bool,
+    /// Don't back up the target workspace's sessions first
+    pub no_backup: bool,
+    /// Print what would be written without writing it
+    pub dry_run: bool,
+}
+
 /// Show all chat sessions across workspaces for current project
-pub fn history_show(project_path: Option<&str>) -> Result<()> {
+pub fn history_show(project_path: Option<&str>, discovery: &DiscoveryOptions) -> Result<()> {
     // Resolve the project path, handling "." specially
     let project_path = match project_path {
         Some(".") | None => std::env::current_dir()
@@ -51,7 +65,7 @@ pub fn history_show(project_path: Option<&str>) -> Result<()> {
     println!("{}", "=".repeat(70));
 
     // Find all workspaces for this project
-    let all_workspaces = find_all_workspaces_for_project(&project_name)?;
+    let all_workspaces = find_all_workspaces_for_project(&project_name, discovery)?;
 
     if all_workspaces.is_empty() {
         println!(
@@ -63,7 +77,7 @@ pub fn history_show(project_path: Option<&str>) -> Result<()> {
     }
 
     // Find current workspace
-    let current_ws = find_workspace_by_path(&project_path)?;
+    let current_ws = find_workspace_by_path(&project_path, discovery)?;
     let current_ws_id = current_ws.as_ref().map(|(id, _, _)| id.clone());
 
     let mut total_sessions = 0;
@@ -138,7 +152,13 @@ pub fn history_show(project_path: Option<&str>) -> Result<()> {
 }
 
 /// Fetch chat sessions from other workspaces into current workspace
-pub fn history_fetch(project_path: Option<&str>, force: bool, no_register: bool) -> Result<()> {
+pub fn history_fetch(
+    project_path: Option<&str>,
+    force: bool,
+    no_register: bool,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
     let project_path = match project_path {
         Some(p) => {
             let path = Path::new(p);
@@ -156,43 +176,44 @@ pub fn history_fetch(project_path: Option<&str>, force: bool, no_register: bool)
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| project_path.clone());
 
-    println!(
+    status!(
         "\n{} Fetching Chat History for: {}",
         "[<]".blue(),
         project_name.cyan()
     );
-    println!("{}", "=".repeat(70));
+    status!("{}", "=".repeat(70));
 
     // Find current workspace
-    let current_ws = find_workspace_by_path(&project_path)?
+    let current_ws = find_workspace_by_path(&project_path, discovery)?
         .context("Current workspace not found. Make sure the project is opened in VS Code")?;
     let (current_ws_id, current_ws_dir, _) = current_ws;
 
     // Find all workspaces for this project
-    let all_workspaces = find_all_workspaces_for_project(&project_name)?;
+    let all_workspaces = find_all_workspaces_for_project(&project_name, discovery)?;
     let historical_workspaces: Vec<_> = all_workspaces
         .into_iter()
         .filter(|(id, _, _, _)| *id != current_ws_id)
         .collect();
 
     if historical_workspaces.is_empty() {
-        println!(
+        status!(
             "{} No historical workspaces found for '{}'",
             "[!]".yellow(),
             project_name
         );
-        println!("   Only the current workspace exists.");
+        status!("   Only the current workspace exists.");
         return Ok(());
     }
 
-    println!(
+    status!(
         "Found {} historical workspace(s)\n",
         historical_workspaces.len()
     );
 
     // Create chatSessions directory
     let chat_sessions_dir = current_ws_dir.join("chatSessions");
-    std::fs::create_dir_all(&chat_sessions_dir)?;
+    let mut actions = Vec::new();
+    ensure_dir(&chat_sessions_dir, dry_run, &mut actions)?;
 
     let mut fetched_count = 0;
     let mut skipped_count = 0;
@@ -216,16 +237,22 @@ pub fn history_fetch(project_path: Option<&str>, force: bool, no_register: bool)
             let dest_file = chat_sessions_dir.join(format!("{}.json", session_id));
 
             if dest_file.exists() && !force {
-                println!(
+                status!(
                     "   {} Skipped (exists): {}...",
                     "[>]".yellow(),
                     &session_id[..16.min(session_id.len())]
                 );
                 skipped_count += 1;
+            } else if dry_run {
+                actions.push(PlannedAction::Copy {
+                    from: session_with_path.path.clone(),
+                    to: dest_file,
+                });
+                fetched_count += 1;
             } else {
-                std::fs::copy(&session_with_path.path, &dest_file)?;
+                copy_file_atomic(&session_with_path.path, &dest_file)?;
                 let title = session_with_path.session.title();
-                println!(
+                status!(
                     "   {} Fetched: {} ({}...)",
                     "[OK]".green(),
                     truncate(&title, 40),
@@ -236,29 +263,43 @@ pub fn history_fetch(project_path: Option<&str>, force: bool, no_register: bool)
         }
     }
 
-    println!("\n{}", "=".repeat(70));
-    println!("Fetched: {} sessions", fetched_count);
+    if dry_run {
+        if fetched_count > 0 && !no_register && (force || !is_vscode_running()) {
+            actions.push(PlannedAction::Modify {
+                path: get_workspace_storage_db(&current_ws_id)?,
+                change: "sync the chat session index".to_string(),
+            });
+        }
+        print_dry_run(&actions);
+        if skipped_count > 0 {
+            status!("Skipped: {} (use --force to overwrite)", skipped_count);
+        }
+        return Ok(());
+    }
+
+    status!("\n{}", "=".repeat(70));
+    status!("Fetched: {} sessions", fetched_count);
     if skipped_count > 0 {
-        println!("Skipped: {} (use --force to overwrite)", skipped_count);
+        status!("Skipped: {} (use --force to overwrite)", skipped_count);
     }
 
     // Register sessions in VS Code index
     if fetched_count > 0 && !no_register {
-        println!(
+        status!(
             "\n{} Registering sessions in VS Code index...",
             "[#]".blue()
         );
 
         if is_vscode_running() && !force {
-            println!(
+            status!(
                 "{} VS Code is running. Sessions may not appear until restart.",
                 "[!]".yellow()
             );
-            println!("   Run 'csm history fetch --force' after closing VS Code to register.");
+            status!("   Run 'csm history fetch --force' after closing VS Code to register.");
         } else {
             let registered =
                 register_all_sessions_from_directory(&current_ws_id, &chat_sessions_dir, true)?;
-            println!(
+            status!(
                 "{} Registered {} sessions in index",
                 "[OK]".green(),
                 registered
@@ -266,7 +307,7 @@ pub fn history_fetch(project_path: Option<&str>, force: bool, no_register: bool)
         }
     }
 
-    println!(
+    status!(
         "\n{} Reload VS Code (Ctrl+R) and check Chat history dropdown",
         "[i]".cyan()
     );
@@ -278,9 +319,14 @@ pub fn history_fetch(project_path: Option<&str>, force: bool, no_register: bool)
 pub fn history_merge(
     project_path: Option<&str>,
     title: Option<&str>,
-    force: bool,
-    no_backup: bool,
+    options: MergeOptions,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
+    let MergeOptions {
+        force,
+        no_backup,
+        dry_run,
+    } = options;
     let project_path = match project_path {
         Some(p) => {
             let path = Path::new(p);
@@ -298,23 +344,23 @@ pub fn history_merge(
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| project_path.clone());
 
-    println!(
+    status!(
         "\n{} Merging Chat History for: {}",
         "[M]".blue(),
         project_name.cyan()
     );
-    println!("{}", "=".repeat(70));
+    status!("{}", "=".repeat(70));
 
     // Find current workspace
-    let current_ws = find_workspace_by_path(&project_path)?
+    let current_ws = find_workspace_by_path(&project_path, discovery)?
         .context("Current workspace not found. Make sure the project is opened in VS Code")?;
     let (current_ws_id, current_ws_dir, _) = current_ws;
 
     // Find all workspaces for this project
-    let all_workspaces = find_all_workspaces_for_project(&project_name)?;
+    let all_workspaces = find_all_workspaces_for_project(&project_name, discovery)?;
 
     // Collect ALL sessions from ALL workspaces
-    println!(
+    status!(
         "\n{} Collecting sessions from {} workspace(s)...",
         "[D]".blue(),
         all_workspaces.len()
@@ -324,7 +370,7 @@ pub fn history_merge(
     for (ws_id, ws_dir, _, _) in &all_workspaces {
         let sessions = get_chat_sessions_from_workspace(ws_dir)?;
         if !sessions.is_empty() {
-            println!(
+            status!(
                 "   {} {}... ({} sessions)",
                 "[d]".blue(),
                 &ws_id[..16.min(ws_id.len())],
@@ -335,41 +381,27 @@ pub fn history_merge(
     }
 
     if all_sessions.is_empty() {
-        println!("\n{} No chat sessions found in any workspace", "[X]".red());
+        status!("\n{} No chat sessions found in any workspace", "[X]".red());
         return Ok(());
     }
 
-    println!("\n   Total: {} sessions collected", all_sessions.len());
+    status!("\n   Total: {} sessions collected", all_sessions.len());
 
     // Collect all requests with timestamps
-    println!("\n{} Extracting and sorting messages...", "[*]".blue());
+    status!("\n{} Extracting and sorting messages...", "[*]".blue());
 
-    let mut all_requests: Vec<ChatRequest> = Vec::new();
-    for session_with_path in &all_sessions {
-        let session = &session_with_path.session;
-        let session_title = session.title();
-
-        for req in &session.requests {
-            let mut req = req.clone();
-            // Add source session info
-            req.source_session = Some(session_title.clone());
-            if req.timestamp.is_some() {
-                all_requests.push(req);
-            }
-        }
-    }
+    let timeline = interleave_by_time(all_sessions.iter().map(|s| &s.session));
+    let all_requests = &timeline.requests;
 
     if all_requests.is_empty() {
-        println!("\n{} No messages found in any session", "[X]".red());
+        status!("\n{} No messages found in any session", "[X]".red());
         return Ok(());
     }
-
-    // Sort by timestamp
-    all_requests.sort_by_key(|r| r.timestamp.unwrap_or(0));
+    warn_untimed(&timeline);
 
     // Get timeline info
-    let first_time = all_requests.first().and_then(|r| r.timestamp).unwrap_or(0);
-    let last_time = all_requests.last().and_then(|r| r.timestamp).unwrap_or(0);
+    let first_time = timeline.first_timestamp();
+    let last_time = timeline.last_timestamp();
 
     let first_date = timestamp_to_date(first_time);
     let last_date = timestamp_to_date(last_time);
@@ -379,14 +411,16 @@ pub fn history_merge(
         0
     };
 
-    println!("   Messages: {}", all_requests.len());
-    println!(
+    status!("   Messages: {}", all_requests.len());
+    status!(
         "   Timeline: {} -> {} ({} days)",
-        first_date, last_date, days_span
+        first_date,
+        last_date,
+        days_span
     );
 
     // Create merged session
-    println!("\n{} Creating merged session...", "[+]".blue());
+    status!("\n{} Creating merged session...", "[+]".blue());
 
     let merged_session_id = Uuid::new_v4().to_string();
     let merged_title = title.map(|t| t.to_string()).unwrap_or_else(|| {
@@ -397,27 +431,32 @@ pub fn history_merge(
         )
     });
 
-    let merged_session = ChatSession {
-        version: 3,
-        session_id: Some(merged_session_id.clone()),
-        creation_date: first_time,
-        last_message_date: last_time,
-        is_imported: false,
-        initial_location: "panel".to_string(),
-        custom_title: Some(merged_title.clone()),
-        requester_username: Some("User".to_string()),
-        requester_avatar_icon_uri: None, // Optional - VS Code will use default
-        responder_username: Some("GitHub Copilot".to_string()),
-        responder_avatar_icon_uri: Some(serde_json::json!({"id": "copilot"})),
-        requests: all_requests.clone(),
-    };
+    let merged_session =
+        build_merged_session(&timeline, Some(merged_session_id.clone()), &merged_title);
 
-    // Create backup if requested
     let chat_sessions_dir = current_ws_dir.join("chatSessions");
+    let merged_file = chat_sessions_dir.join(format!("{}.json", merged_session_id));
+
+    if dry_run {
+        let target = MergeTarget::workspace(&current_ws_id, &current_ws_dir);
+        let flags = MergeFlags {
+            force,
+            no_backup,
+            dry_run,
+        };
+        print_dry_run(&plan_merge_writes(
+            &target,
+            &merged_file,
+            &merged_title,
+            &flags,
+        )?);
+        return Ok(());
+    }
 
+    // Create backup if requested
     if !no_backup {
         if let Some(backup_dir) = backup_workspace_sessions(&current_ws_dir)? {
-            println!(
+            status!(
                 "   {} Backup: {}",
                 "[B]".blue(),
                 backup_dir.file_name().unwrap().to_string_lossy()
@@ -427,22 +466,19 @@ pub fn history_merge(
 
     // Write merged session
     std::fs::create_dir_all(&chat_sessions_dir)?;
-    let merged_file = chat_sessions_dir.join(format!("{}.json", merged_session_id));
-
-    let json = serde_json::to_string_pretty(&merged_session)?;
-    std::fs::write(&merged_file, json)?;
+    write_session_atomic(&merged_file, &merged_session)?;
 
-    println!(
+    status!(
         "   {} File: {}",
         "[F]".blue(),
         merged_file.file_name().unwrap().to_string_lossy()
     );
 
     // Register in VS Code index
-    println!("\n{} Registering in VS Code index...", "[#]".blue());
+    status!("\n{} Registering in VS Code index...", "[#]".blue());
 
     if is_vscode_running() && !force {
-        println!(
+        status!(
             "{} VS Code is running. Close it and run again, or use --force",
             "[!]".yellow()
         );
@@ -457,21 +493,21 @@ pub fn history_merge(
             "panel",
             false,
         )?;
-        println!("   {} Registered in index", "[OK]".green());
+        status!("   {} Registered in index", "[OK]".green());
     }
 
-    println!("\n{}", "=".repeat(70));
-    println!("{} MERGE COMPLETE!", "[OK]".green().bold());
-    println!("\n{} Summary:", "[=]".blue());
-    println!("   - Sessions merged: {}", all_sessions.len());
-    println!("   - Total messages: {}", all_requests.len());
-    println!("   - Timeline: {} days", days_span);
-    println!("   - Title: {}", merged_title);
-
-    println!("\n{} Next Steps:", "[i]".cyan());
-    println!("   1. Reload VS Code (Ctrl+R)");
-    println!("   2. Open Chat history dropdown");
-    println!("   3. Select: '{}'", merged_title);
+    status!("\n{}", "=".repeat(70));
+    status!("{} MERGE COMPLETE!", "[OK]".green().bold());
+    status!("\n{} Summary:", "[=]".blue());
+    status!("   - Sessions merged: {}", all_sessions.len());
+    status!("   - Total messages: {}", all_requests.len());
+    status!("   - Timeline: {} days", days_span);
+    status!("   - Title: {}", merged_title);
+
+    status!("\n{} Next Steps:", "[i]".cyan());
+    status!("   1. Reload VS Code (Ctrl+R)");
+    status!("   2. Open Chat history dropdown");
+    status!("   3. Select: '{}'", merged_title);
 
     Ok(())
 }
@@ -481,21 +517,26 @@ pub fn merge_by_workspace_name(
     workspace_name: &str,
     title: Option<&str>,
     target_path: Option<&str>,
-    force: bool,
-    no_backup: bool,
+    options: MergeOptions,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
-    println!(
+    let MergeOptions {
+        force,
+        no_backup,
+        dry_run,
+    } = options;
+    status!(
         "\n{} Merging Sessions by Workspace Name: {}",
         "[M]".blue(),
         workspace_name.cyan()
     );
-    println!("{}", "=".repeat(70));
+    status!("{}", "=".repeat(70));
 
     // Find all workspaces matching the pattern
-    let all_workspaces = find_all_workspaces_for_project(workspace_name)?;
+    let all_workspaces = find_all_workspaces_for_project(workspace_name, discovery)?;
 
     if all_workspaces.is_empty() {
-        println!(
+        status!(
             "\n{} No workspaces found matching '{}'",
             "[X]".red(),
             workspace_name
@@ -503,13 +544,13 @@ pub fn merge_by_workspace_name(
         return Ok(());
     }
 
-    println!(
+    status!(
         "\n{} Found {} workspace(s) matching pattern:",
         "[D]".blue(),
         all_workspaces.len()
     );
     for (ws_id, _, folder_path, _) in &all_workspaces {
-        println!(
+        status!(
             "   {} {}... -> {}",
             "[*]".blue(),
             &ws_id[..16.min(ws_id.len())],
@@ -530,24 +571,24 @@ pub fn merge_by_workspace_name(
             .unwrap_or_else(|_| ".".to_string()),
     };
 
-    let target_ws = find_workspace_by_path(&target_path)?
+    let target_ws = find_workspace_by_path(&target_path, discovery)?
         .context("Target workspace not found. Make sure the project is opened in VS Code")?;
     let (target_ws_id, target_ws_dir, _) = target_ws;
 
-    println!(
+    status!(
         "\n{} Target workspace: {}...",
         "[>]".blue(),
         &target_ws_id[..16.min(target_ws_id.len())]
     );
 
     // Collect sessions from all matching workspaces
-    println!("\n{} Collecting sessions...", "[D]".blue());
+    status!("\n{} Collecting sessions...", "[D]".blue());
 
     let mut all_sessions = Vec::new();
     for (ws_id, ws_dir, _, _) in &all_workspaces {
         let sessions = get_chat_sessions_from_workspace(ws_dir)?;
         if !sessions.is_empty() {
-            println!(
+            status!(
                 "   {} {}... ({} sessions)",
                 "[d]".blue(),
                 &ws_id[..16.min(ws_id.len())],
@@ -558,7 +599,7 @@ pub fn merge_by_workspace_name(
     }
 
     if all_sessions.is_empty() {
-        println!(
+        status!(
             "\n{} No chat sessions found in matching workspaces",
             "[X]".red()
         );
@@ -569,10 +610,12 @@ pub fn merge_by_workspace_name(
     merge_sessions_internal(
         all_sessions,
         title,
-        &target_ws_id,
-        &target_ws_dir,
-        force,
-        no_backup,
+        &MergeTarget::workspace(&target_ws_id, &target_ws_dir),
+        &MergeFlags {
+            force,
+            no_backup,
+            dry_run,
+        },
         &format!("Workspace: {}", workspace_name),
     )
 }
@@ -582,22 +625,428 @@ pub fn merge_sessions_by_list(
     session_ids: &[String],
     title: Option<&str>,
     target_path: Option<&str>,
-    force: bool,
-    no_backup: bool,
+    output: Option<&str>,
+    options: MergeOptions,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
-    println!("\n{} Merging Specific Sessions", "[M]".blue());
-    println!("{}", "=".repeat(70));
+    let MergeOptions {
+        force,
+        no_backup,
+        dry_run,
+    } = options;
+    status!("\n{} Merging Specific Sessions", "[M]".blue());
+    status!("{}", "=".repeat(70));
 
-    println!(
+    status!(
         "\n{} Looking for {} session(s):",
         "[D]".blue(),
         session_ids.len()
     );
     for id in session_ids {
-        println!("   {} {}", "[?]".blue(), id);
+        status!("   {} {}", "[?]".blue(), id);
     }
 
-    // Determine target workspace
+    let (target_ws_id, target_ws_dir) = resolve_merge_workspace(target_path, discovery)?;
+
+    // Find and collect requested sessions from all workspaces
+    status!("\n{} Searching all workspaces...", "[D]".blue());
+
+    let catalog = load_session_catalog(discovery)?;
+    let normalized_ids: Vec<String> = session_ids
+        .iter()
+        .map(|id| normalize_session_ref(id))
+        .collect();
+    let (found, not_found) = resolve_session_refs(&normalized_ids, &catalog);
+    for entry in &found {
+        status!(
+            "   {} Found: {} in workspace {}...",
+            "[OK]".green(),
+            truncate(&entry.session.session.title(), 40),
+            &entry.ws_hash[..16.min(entry.ws_hash.len())]
+        );
+    }
+    let found_sessions: Vec<_> = found.into_iter().map(|e| e.session.clone()).collect();
+
+    if found_sessions.is_empty() {
+        status!("\n{} No matching sessions found", "[X]".red());
+        status!(
+            "\n{} Tip: Use 'csm list sessions' or 'csm find session <pattern>' to find session IDs",
+            "[i]".cyan()
+        );
+        return Ok(());
+    }
+
+    // Report any sessions that weren't found
+    if !not_found.is_empty() {
+        status!("\n{} Sessions not found:", "[!]".yellow());
+        for id in not_found {
+            status!("   {} {}", "[X]".red(), id);
+        }
+    }
+
+    status!("\n   Total: {} sessions found", found_sessions.len());
+
+    let default_title = merged_title_from(found_sessions.iter().map(|s| s.session.title()));
+    // Use the common merge logic
+    merge_sessions_internal(
+        found_sessions,
+        Some(title.unwrap_or(&default_title)),
+        &MergeTarget {
+            output,
+            ..MergeTarget::workspace(&target_ws_id, &target_ws_dir)
+        },
+        &MergeFlags {
+            force,
+            no_backup,
+            dry_run,
+        },
+        &format!("{} selected sessions", session_ids.len()),
+    )
+}
+
+/// How `merge preview` renders the merged session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePreviewFormat {
+    /// Undecorated `key: value` lines, as `show session --plain` prints
+    #[default]
+    Plain,
+    /// The merged turns against the first session, with `+` on the turns
+    /// the other sessions add
+    Diff,
+    Markdown,
+    Text,
+    /// The session file the merge would write
+    Json,
+}
+
+impl MergePreviewFormat {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["plain", "diff", "md", "txt", "json"];
+}
+
+impl std::str::FromStr for MergePreviewFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" => Ok(MergePreviewFormat::Plain),
+            "diff" => Ok(MergePreviewFormat::Diff),
+            "md" | "markdown" => Ok(MergePreviewFormat::Markdown),
+            "txt" | "text" => Ok(MergePreviewFormat::Text),
+            "json" => Ok(MergePreviewFormat::Json),
+            _ => Err(format!(
+                "unknown preview format '{}' (valid formats: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for MergePreviewFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            MergePreviewFormat::Plain => "plain",
+            MergePreviewFormat::Diff => "diff",
+            MergePreviewFormat::Markdown => "md",
+            MergePreviewFormat::Text => "txt",
+            MergePreviewFormat::Json => "json",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Print the session `merge sessions` would write for `session_ids`,
+/// without writing anything
+///
+/// The sessions are merged in memory exactly as `merge sessions` merges
+/// them; the first ID given is the base `--format diff` compares against.
+pub fn merge_preview(
+    session_ids: &[String],
+    title: Option<&str>,
+    format: MergePreviewFormat,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let catalog = load_session_catalog(discovery)?;
+    let normalized_ids: Vec<String> = session_ids
+        .iter()
+        .map(|id| normalize_session_ref(id))
+        .collect();
+    let (mut found, not_found) = resolve_session_refs(&normalized_ids, &catalog);
+    if !not_found.is_empty() {
+        anyhow::bail!("session(s) not found: {}", not_found.join(", "));
+    }
+    if found.len() < 2 {
+        anyhow::bail!("a merge needs at least two distinct sessions");
+    }
+    // Keep the order the IDs were given in, so the first is the base
+    found.sort_by_key(|entry| {
+        normalized_ids
+            .iter()
+            .position(|id| entry.id.starts_with(id.as_str()) || id.starts_with(&entry.id))
+    });
+    let sessions: Vec<&ChatSession> = found.iter().map(|e| &e.session.session).collect();
+
+    let timeline = interleave_by_time(sessions.iter().copied());
+    let title = title
+        .map(str::to_string)
+        .unwrap_or_else(|| merged_title_from(sessions.iter().map(|s| s.title())));
+    let merged = build_merged_session(&timeline, None, &title);
+
+    match format {
+        MergePreviewFormat::Plain => {
+            print!("{}", merge_preview_plain(&merged, &timeline, &sessions))
+        }
+        MergePreviewFormat::Diff => print!("{}", merge_preview_diff(&merged, &timeline, &sessions)),
+        MergePreviewFormat::Markdown => {
+            print!(
+                "{}",
+                crate::providers::session_format::session_to_markdown(&merged)
+            )
+        }
+        MergePreviewFormat::Text => {
+            print!(
+                "{}",
+                crate::providers::session_format::session_to_text(&merged)
+            )
+        }
+        MergePreviewFormat::Json => println!("{}", serde_json::to_string_pretty(&merged)?),
+    }
+    Ok(())
+}
+
+/// A request's prompt on one line, with line breaks escaped
+fn preview_prompt(request: &ChatRequest) -> String {
+    request
+        .message
+        .as_ref()
+        .and_then(|m| m.text.as_deref())
+        .unwrap_or_default()
+        .replace('\r', "")
+        .replace('\n', "\\n")
+}
+
+/// `merge preview --format plain`: the merged session's summary, its
+/// sources, then one `turn:` line per request naming the session it came from
+fn merge_preview_plain(
+    merged: &ChatSession,
+    timeline: &MergedTimeline,
+    sessions: &[&ChatSession],
+) -> String {
+    let mut out = format!("title: {}\n", merged.title());
+    out.push_str(&format!("messages: {}\n", merged.requests.len()));
+    for session in sessions {
+        out.push_str(&format!(
+            "source: {} ({} messages)\n",
+            session.title(),
+            session.requests.len()
+        ));
+    }
+    out.push_str(&format!(
+        "first: {}\nlast: {}\nuntimed: {}\n",
+        timestamp_to_date(merged.creation_date),
+        timestamp_to_date(merged.last_message_date),
+        timeline.untimed
+    ));
+    for (i, (request, &source)) in merged.requests.iter().zip(&timeline.sources).enumerate() {
+        out.push_str(&format!(
+            "turn: {} [{}] {}\n",
+            i + 1,
+            sessions[source].title(),
+            preview_prompt(request)
+        ));
+    }
+    out
+}
+
+/// `merge preview --format diff`: the merged turns as a diff against the
+/// first session. A merge only adds turns, so lines are either context
+/// (` `, from the first session) or additions (`+`, tagged with their source).
+fn merge_preview_diff(
+    merged: &ChatSession,
+    timeline: &MergedTimeline,
+    sessions: &[&ChatSession],
+) -> String {
+    let mut out = format!("--- {}\n+++ {}\n", sessions[0].title(), merged.title());
+    for (request, &source) in merged.requests.iter().zip(&timeline.sources) {
+        let line = if source == 0 {
+            format!("  {}", preview_prompt(request))
+        } else {
+            format!(
+                "+ [{}] {}",
+                sessions[source].title(),
+                preview_prompt(request)
+            )
+            .green()
+            .to_string()
+        };
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// One line of a `merge sessions --from-file` list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeGroup {
+    /// 1-based line number in the list file
+    pub line: usize,
+    pub session_ids: Vec<String>,
+}
+
+/// Parse a merge list: one group of session IDs per line, separated by
+/// whitespace or commas. Blank lines and `#` comments are skipped.
+pub fn parse_merge_groups(content: &str) -> Vec<MergeGroup> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.split('#').next().unwrap_or_default();
+            let session_ids: Vec<String> = line
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect();
+            (!session_ids.is_empty()).then_some(MergeGroup {
+                line: i + 1,
+                session_ids,
+            })
+        })
+        .collect()
+}
+
+/// Merge each group of a `--from-file` list into its own new session,
+/// carrying on past groups that fail. With `atomic`, nothing is merged
+/// unless every ID in the list resolves.
+pub fn merge_session_groups_from_file(
+    list_file: &str,
+    title: Option<&str>,
+    target_path: Option<&str>,
+    options: MergeOptions,
+    atomic: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let MergeOptions {
+        force,
+        no_backup,
+        dry_run,
+    } = options;
+    let content = std::fs::read_to_string(list_file)
+        .with_context(|| format!("Failed to read merge list: {}", list_file))?;
+    let groups = parse_merge_groups(&content);
+    if groups.is_empty() {
+        anyhow::bail!("No session groups in {}", list_file);
+    }
+
+    status!(
+        "\n{} Merging {} Session Group(s) from {}",
+        "[M]".blue(),
+        groups.len(),
+        list_file
+    );
+    status!("{}", "=".repeat(70));
+
+    let (target_ws_id, target_ws_dir) = resolve_merge_workspace(target_path, discovery)?;
+    let catalog = load_session_catalog(discovery)?;
+    let resolved: Vec<_> = groups
+        .iter()
+        .map(|group| {
+            let ids: Vec<String> = group
+                .session_ids
+                .iter()
+                .map(|id| normalize_session_ref(id))
+                .collect();
+            resolve_session_refs(&ids, &catalog)
+        })
+        .collect();
+
+    let unresolved: Vec<(usize, &Vec<String>)> = groups
+        .iter()
+        .zip(&resolved)
+        .filter(|(_, (_, not_found))| !not_found.is_empty())
+        .map(|(group, (_, not_found))| (group.line, not_found))
+        .collect();
+    if atomic && !unresolved.is_empty() {
+        for (line, not_found) in &unresolved {
+            status!(
+                "   {} line {}: not found: {}",
+                "[X]".red(),
+                line,
+                not_found.join(", ")
+            );
+        }
+        anyhow::bail!(
+            "{} group(s) name sessions that don't exist; nothing was merged (--atomic)",
+            unresolved.len()
+        );
+    }
+
+    let mut outcomes: Vec<(usize, Result<usize, String>)> = Vec::new();
+    let mut backed_up = false;
+    for (i, (group, (found, not_found))) in groups.iter().zip(resolved).enumerate() {
+        status!(
+            "\n{} Group {} of {} (line {}): {}",
+            "[>]".blue(),
+            i + 1,
+            groups.len(),
+            group.line,
+            group.session_ids.join(", ")
+        );
+        if !not_found.is_empty() {
+            let reason = format!("not found: {}", not_found.join(", "));
+            status!("   {} {}", "[X]".red(), reason);
+            outcomes.push((group.line, Err(reason)));
+            continue;
+        }
+
+        let sessions: Vec<_> = found.into_iter().map(|e| e.session.clone()).collect();
+        let count = sessions.len();
+        let default_title = merged_title_from(sessions.iter().map(|s| s.session.title()));
+        // One backup of the target workspace covers the whole batch
+        let result = merge_sessions_internal(
+            sessions,
+            Some(title.unwrap_or(&default_title)),
+            &MergeTarget::workspace(&target_ws_id, &target_ws_dir),
+            &MergeFlags {
+                force,
+                no_backup: no_backup || backed_up,
+                dry_run,
+            },
+            &format!("{} selected sessions", count),
+        );
+        backed_up = true;
+        if let Err(e) = &result {
+            status!("   {} {}", "[X]".red(), e);
+        }
+        outcomes.push((group.line, result.map(|_| count).map_err(|e| e.to_string())));
+    }
+
+    let failed = outcomes.iter().filter(|(_, r)| r.is_err()).count();
+    status!(
+        "\n{} {} of {} group(s) merged",
+        "[=]".blue(),
+        outcomes.len() - failed,
+        outcomes.len()
+    );
+    for (line, outcome) in &outcomes {
+        match outcome {
+            Ok(count) => status!("   {} line {}: {} session(s)", "[OK]".green(), line, count),
+            // Failures stay visible under --quiet
+            Err(reason) => println!("   {} line {}: {}", "[X]".red(), line, reason),
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("{} of {} group(s) failed to merge", failed, outcomes.len());
+    }
+    Ok(())
+}
+
+/// Workspace of `target_path` (default: the current directory) that merged
+/// sessions are written to, as its ID and directory
+fn resolve_merge_workspace(
+    target_path: Option<&str>,
+    discovery: &DiscoveryOptions,
+) -> Result<(String, std::path::PathBuf)> {
     let target_path = match target_path {
         Some(p) => {
             let path = Path::new(p);
@@ -610,148 +1059,207 @@ pub fn merge_sessions_by_list(
             .unwrap_or_else(|_| ".".to_string()),
     };
 
-    let target_ws = find_workspace_by_path(&target_path)?
+    let (ws_id, ws_dir, _) = find_workspace_by_path(&target_path, discovery)?
         .context("Target workspace not found. Make sure the project is opened in VS Code")?;
-    let (target_ws_id, target_ws_dir, _) = target_ws;
-
-    // Find and collect requested sessions from all workspaces
-    println!("\n{} Searching all workspaces...", "[D]".blue());
-
-    let all_workspaces = crate::workspace::discover_workspaces()?;
-    let mut found_sessions = Vec::new();
-    let mut found_ids: Vec<String> = Vec::new();
+    Ok((ws_id, ws_dir))
+}
 
-    // Normalize session IDs for comparison (remove .json extension if present)
-    let normalized_ids: Vec<String> = session_ids
-        .iter()
-        .map(|id| {
-            let id = id.trim();
-            if id.to_lowercase().ends_with(".json") {
-                id[..id.len() - 5].to_string()
-            } else {
-                id.to_string()
-            }
-        })
-        .collect();
+/// A session found while resolving requested IDs
+struct CatalogEntry {
+    /// Session ID, or the file stem when the session has none
+    id: String,
+    ws_hash: String,
+    session: crate::models::SessionWithPath,
+}
 
-    for ws in &all_workspaces {
+/// Every session of every workspace
+fn load_session_catalog(discovery: &DiscoveryOptions) -> Result<Vec<CatalogEntry>> {
+    let mut catalog = Vec::new();
+    for ws in discover_workspaces(discovery)? {
         if !ws.has_chat_sessions {
             continue;
         }
-
-        let sessions = get_chat_sessions_from_workspace(&ws.workspace_path)?;
-
-        for session_with_path in sessions {
-            let session_id = session_with_path
-                .session
-                .session_id
-                .clone()
-                .unwrap_or_else(|| {
-                    session_with_path
-                        .path
-                        .file_stem()
-                        .map(|s| s.to_string_lossy().to_string())
-                        .unwrap_or_default()
-                });
-
-            // Check if this session matches any requested ID
-            let matches = normalized_ids.iter().any(|req_id| {
-                session_id.starts_with(req_id)
-                    || req_id.starts_with(&session_id)
-                    || session_id == *req_id
+        for session in get_chat_sessions_from_workspace(&ws.workspace_path)? {
+            catalog.push(CatalogEntry {
+                id: session.get_session_id(),
+                ws_hash: ws.hash.clone(),
+                session,
             });
-
-            if matches && !found_ids.contains(&session_id) {
-                println!(
-                    "   {} Found: {} in workspace {}...",
-                    "[OK]".green(),
-                    truncate(&session_with_path.session.title(), 40),
-                    &ws.hash[..16.min(ws.hash.len())]
-                );
-                found_ids.push(session_id);
-                found_sessions.push(session_with_path);
-            }
         }
     }
+    Ok(catalog)
+}
 
-    if found_sessions.is_empty() {
-        println!("\n{} No matching sessions found", "[X]".red());
-        println!(
-            "\n{} Tip: Use 'csm list sessions' or 'csm find session <pattern>' to find session IDs",
-            "[i]".cyan()
-        );
-        return Ok(());
+/// A requested session ID without any `.json` extension
+fn normalize_session_ref(id: &str) -> String {
+    let id = id.trim();
+    if id.to_lowercase().ends_with(".json") {
+        id[..id.len() - 5].to_string()
+    } else {
+        id.to_string()
     }
+}
 
-    // Report any sessions that weren't found
-    let not_found: Vec<_> = normalized_ids
+/// Sessions matching any of the requested IDs (either may be a prefix of
+/// the other), once each, and the requested IDs nothing matched
+fn resolve_session_refs<'a>(
+    ids: &[String],
+    catalog: &'a [CatalogEntry],
+) -> (Vec<&'a CatalogEntry>, Vec<String>) {
+    let matches =
+        |id: &str, requested: &str| id.starts_with(requested) || requested.starts_with(id);
+    let mut found: Vec<&CatalogEntry> = Vec::new();
+    for entry in catalog {
+        if ids.iter().any(|req| matches(&entry.id, req)) && !found.iter().any(|f| f.id == entry.id)
+        {
+            found.push(entry);
+        }
+    }
+    let not_found = ids
         .iter()
-        .filter(|id| {
-            !found_ids
-                .iter()
-                .any(|found| found.starts_with(*id) || id.starts_with(found))
-        })
+        .filter(|req| !found.iter().any(|f| matches(&f.id, req)))
+        .cloned()
         .collect();
+    (found, not_found)
+}
 
-    if !not_found.is_empty() {
-        println!("\n{} Sessions not found:", "[!]".yellow());
-        for id in not_found {
-            println!("   {} {}", "[X]".red(), id);
+/// Default title of a merge of specific sessions, e.g. "Merged: A + B"
+pub fn merged_title_from(titles: impl IntoIterator<Item = String>) -> String {
+    const SHOWN: usize = 3;
+    let titles: Vec<String> = titles.into_iter().collect();
+    let mut title = format!(
+        "Merged: {}",
+        titles
+            .iter()
+            .take(SHOWN)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" + ")
+    );
+    if titles.len() > SHOWN {
+        title.push_str(&format!(" + {} more", titles.len() - SHOWN));
+    }
+    title
+}
+
+/// Workspace a merge writes its new session into, and optionally the file
+/// or session ID to use for it
+struct MergeTarget<'a> {
+    ws_id: &'a str,
+    ws_dir: &'a Path,
+    /// From `merge sessions --output`: a file path (anything with a path
+    /// separator or a `.json` suffix) or a new session ID; a fresh UUID in
+    /// the workspace when unset
+    output: Option<&'a str>,
+}
+
+impl<'a> MergeTarget<'a> {
+    fn workspace(ws_id: &'a str, ws_dir: &'a Path) -> Self {
+        Self {
+            ws_id,
+            ws_dir,
+            output: None,
         }
     }
 
-    println!("\n   Total: {} sessions found", found_sessions.len());
+    /// Session ID and file of the merged session. Refuses an existing file,
+    /// so a merge never overwrites a session.
+    fn resolve(&self) -> Result<(String, std::path::PathBuf)> {
+        let chat_sessions_dir = self.ws_dir.join("chatSessions");
+        let (session_id, file) = match self.output {
+            Some(output)
+                if output.contains(['/', '\\']) || output.to_lowercase().ends_with(".json") =>
+            {
+                (Uuid::new_v4().to_string(), std::path::PathBuf::from(output))
+            }
+            Some(id) => (
+                id.to_string(),
+                chat_sessions_dir.join(format!("{}.json", id)),
+            ),
+            None => {
+                let id = Uuid::new_v4().to_string();
+                let file = chat_sessions_dir.join(format!("{}.json", id));
+                (id, file)
+            }
+        };
+        if file.exists() {
+            anyhow::bail!(
+                "{} already exists; merges only write new sessions",
+                file.display()
+            );
+        }
+        Ok((session_id, file))
+    }
 
-    // Use the common merge logic
-    merge_sessions_internal(
-        found_sessions,
-        title,
-        &target_ws_id,
-        &target_ws_dir,
-        force,
-        no_backup,
-        &format!("{} selected sessions", session_ids.len()),
-    )
+    /// Whether `file` is a session of the target workspace (and so belongs
+    /// in its backup and VS Code index)
+    fn is_in_workspace(&self, file: &Path) -> bool {
+        file.parent() == Some(self.ws_dir.join("chatSessions").as_path())
+    }
+}
+
+/// How a merge writes its result
+#[derive(Debug, Clone, Copy, Default)]
+struct MergeFlags {
+    /// Register the merged session even while VS Code is running
+    force: bool,
+    /// Don't back up the target workspace's sessions first
+    no_backup: bool,
+    /// Only print what would be written
+    dry_run: bool,
+}
+
+/// Files a merge would write: a backup of the target workspace's sessions,
+/// the merged session, and the workspace's VS Code index
+fn plan_merge_writes(
+    target: &MergeTarget,
+    merged_file: &Path,
+    merged_title: &str,
+    flags: &MergeFlags,
+) -> Result<Vec<PlannedAction>> {
+    let in_workspace = target.is_in_workspace(merged_file);
+    let chat_sessions_dir = target.ws_dir.join("chatSessions");
+    let mut actions = Vec::new();
+
+    if in_workspace && !flags.no_backup && chat_sessions_dir.exists() {
+        actions.push(PlannedAction::Copy {
+            from: chat_sessions_dir,
+            to: workspace_backup_dir(target.ws_dir),
+        });
+    }
+    actions.push(PlannedAction::Create(merged_file.to_path_buf()));
+    if in_workspace && (flags.force || !is_vscode_running()) {
+        actions.push(PlannedAction::Modify {
+            path: get_workspace_storage_db(target.ws_id)?,
+            change: format!("register '{}' in the chat session index", merged_title),
+        });
+    }
+    Ok(actions)
 }
 
 /// Internal function to merge sessions and write to target workspace
 fn merge_sessions_internal(
     sessions: Vec<crate::models::SessionWithPath>,
     title: Option<&str>,
-    target_ws_id: &str,
-    target_ws_dir: &Path,
-    force: bool,
-    no_backup: bool,
+    target: &MergeTarget,
+    flags: &MergeFlags,
     source_description: &str,
 ) -> Result<()> {
     // Collect all requests with timestamps
-    println!("\n{} Extracting and sorting messages...", "[*]".blue());
+    status!("\n{} Extracting and sorting messages...", "[*]".blue());
 
-    let mut all_requests: Vec<ChatRequest> = Vec::new();
-    for session_with_path in &sessions {
-        let session = &session_with_path.session;
-        let session_title = session.title();
-
-        for req in &session.requests {
-            let mut req = req.clone();
-            req.source_session = Some(session_title.clone());
-            if req.timestamp.is_some() {
-                all_requests.push(req);
-            }
-        }
-    }
+    let timeline = interleave_by_time(sessions.iter().map(|s| &s.session));
+    let all_requests = &timeline.requests;
 
     if all_requests.is_empty() {
-        println!("\n{} No messages found in selected sessions", "[X]".red());
+        status!("\n{} No messages found in selected sessions", "[X]".red());
         return Ok(());
     }
-
-    // Sort by timestamp
-    all_requests.sort_by_key(|r| r.timestamp.unwrap_or(0));
+    warn_untimed(&timeline);
 
     // Get timeline info
-    let first_time = all_requests.first().and_then(|r| r.timestamp).unwrap_or(0);
-    let last_time = all_requests.last().and_then(|r| r.timestamp).unwrap_or(0);
+    let first_time = timeline.first_timestamp();
+    let last_time = timeline.last_timestamp();
 
     let first_date = timestamp_to_date(first_time);
     let last_date = timestamp_to_date(last_time);
@@ -761,16 +1269,19 @@ fn merge_sessions_internal(
         0
     };
 
-    println!("   Messages: {}", all_requests.len());
-    println!(
+    status!("   Messages: {}", all_requests.len());
+    status!(
         "   Timeline: {} -> {} ({} days)",
-        first_date, last_date, days_span
+        first_date,
+        last_date,
+        days_span
     );
 
     // Create merged session
-    println!("\n{} Creating merged session...", "[+]".blue());
+    status!("\n{} Creating merged session...", "[+]".blue());
 
-    let merged_session_id = Uuid::new_v4().to_string();
+    let (merged_session_id, merged_file) = target.resolve()?;
+    let in_workspace = target.is_in_workspace(&merged_file);
     let merged_title = title.map(|t| t.to_string()).unwrap_or_else(|| {
         format!(
             "Merged: {} ({} sessions, {} days)",
@@ -780,27 +1291,23 @@ fn merge_sessions_internal(
         )
     });
 
-    let merged_session = ChatSession {
-        version: 3,
-        session_id: Some(merged_session_id.clone()),
-        creation_date: first_time,
-        last_message_date: last_time,
-        is_imported: false,
-        initial_location: "panel".to_string(),
-        custom_title: Some(merged_title.clone()),
-        requester_username: Some("User".to_string()),
-        requester_avatar_icon_uri: None,
-        responder_username: Some("GitHub Copilot".to_string()),
-        responder_avatar_icon_uri: Some(serde_json::json!({"id": "copilot"})),
-        requests: all_requests.clone(),
-    };
+    let merged_session =
+        build_merged_session(&timeline, Some(merged_session_id.clone()), &merged_title);
 
-    // Create backup if requested
-    let chat_sessions_dir = target_ws_dir.join("chatSessions");
+    if flags.dry_run {
+        print_dry_run(&plan_merge_writes(
+            target,
+            &merged_file,
+            &merged_title,
+            flags,
+        )?);
+        return Ok(());
+    }
 
-    if !no_backup {
-        if let Some(backup_dir) = backup_workspace_sessions(target_ws_dir)? {
-            println!(
+    // Create backup if requested
+    if in_workspace && !flags.no_backup {
+        if let Some(backup_dir) = backup_workspace_sessions(target.ws_dir)? {
+            status!(
                 "   {} Backup: {}",
                 "[B]".blue(),
                 backup_dir.file_name().unwrap().to_string_lossy()
@@ -808,29 +1315,37 @@ fn merge_sessions_internal(
         }
     }
 
-    // Write merged session
-    std::fs::create_dir_all(&chat_sessions_dir)?;
-    let merged_file = chat_sessions_dir.join(format!("{}.json", merged_session_id));
-
-    let json = serde_json::to_string_pretty(&merged_session)?;
-    std::fs::write(&merged_file, json)?;
+    // Write merged session; the source sessions are never modified
+    if let Some(parent) = merged_file.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    write_session_atomic(&merged_file, &merged_session)?;
 
-    println!(
-        "   {} File: {}",
-        "[F]".blue(),
-        merged_file.file_name().unwrap().to_string_lossy()
-    );
+    if in_workspace {
+        status!(
+            "   {} File: {}",
+            "[F]".blue(),
+            merged_file.file_name().unwrap().to_string_lossy()
+        );
+    } else {
+        status!("   {} File: {}", "[F]".blue(), merged_file.display());
+    }
 
     // Register in VS Code index
-    println!("\n{} Registering in VS Code index...", "[#]".blue());
+    status!("\n{} Registering in VS Code index...", "[#]".blue());
 
-    if is_vscode_running() && !force {
-        println!(
+    if !in_workspace {
+        status!(
+            "   {} Skipped: the file is outside the workspace's chatSessions",
+            "[i]".cyan()
+        );
+    } else if is_vscode_running() && !flags.force {
+        status!(
             "{} VS Code is running. Close it and run again, or use --force",
             "[!]".yellow()
         );
     } else {
-        let db_path = get_workspace_storage_db(target_ws_id)?;
+        let db_path = get_workspace_storage_db(target.ws_id)?;
         add_session_to_index(
             &db_path,
             &merged_session_id,
@@ -840,25 +1355,145 @@ fn merge_sessions_internal(
             "panel",
             false,
         )?;
-        println!("   {} Registered in index", "[OK]".green());
+        status!("   {} Registered in index", "[OK]".green());
     }
 
-    println!("\n{}", "=".repeat(70));
-    println!("{} MERGE COMPLETE!", "[OK]".green().bold());
-    println!("\n{} Summary:", "[=]".blue());
-    println!("   - Sessions merged: {}", sessions.len());
-    println!("   - Total messages: {}", all_requests.len());
-    println!("   - Timeline: {} days", days_span);
-    println!("   - Title: {}", merged_title);
-
-    println!("\n{} Next Steps:", "[i]".cyan());
-    println!("   1. Reload VS Code (Ctrl+R)");
-    println!("   2. Open Chat history dropdown");
-    println!("   3. Select: '{}'", merged_title);
+    status!("\n{}", "=".repeat(70));
+    status!("{} MERGE COMPLETE!", "[OK]".green().bold());
+    status!("\n{} Summary:", "[=]".blue());
+    status!("   - Sessions merged: {}", sessions.len());
+    status!("   - Total messages: {}", all_requests.len());
+    status!("   - Timeline: {} days", days_span);
+    status!("   - Title: {}", merged_title);
+
+    status!("\n{} Next Steps:", "[i]".cyan());
+    status!("   1. Reload VS Code (Ctrl+R)");
+    status!("   2. Open Chat history dropdown");
+    status!("   3. Select: '{}'", merged_title);
 
     Ok(())
 }
 
+/// Requests from several sessions merged into one timeline
+#[derive(Debug, Clone, Default)]
+pub struct MergedTimeline {
+    /// Timestamped requests in time order, followed by the untimed ones
+    pub requests: Vec<ChatRequest>,
+    /// How many requests at the end have no timestamp
+    pub untimed: usize,
+    /// For each request, the index of the session it came from
+    pub sources: Vec<usize>,
+}
+
+impl MergedTimeline {
+    /// Earliest request timestamp, or 0 if none is timestamped
+    pub fn first_timestamp(&self) -> i64 {
+        self.requests
+            .iter()
+            .filter_map(|r| r.timestamp)
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Latest request timestamp, or 0 if none is timestamped
+    pub fn last_timestamp(&self) -> i64 {
+        self.requests
+            .iter()
+            .filter_map(|r| r.timestamp)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Interleave the requests of several sessions by their timestamps
+///
+/// Each session's own order is kept (a request is never moved ahead of an
+/// earlier one from the same session, so slight clock differences between
+/// sessions can't scramble a conversation); among sessions the earliest
+/// next request goes first, with ties going to the session listed first.
+/// Requests without a timestamp are appended at the end in their original
+/// order. Every request is tagged with its source session's title.
+pub fn interleave_by_time<'a>(
+    sessions: impl IntoIterator<Item = &'a ChatSession>,
+) -> MergedTimeline {
+    let mut timed: Vec<std::collections::VecDeque<ChatRequest>> = Vec::new();
+    let mut untimed = Vec::new();
+
+    for (index, session) in sessions.into_iter().enumerate() {
+        let title = session.title();
+        let mut queue = std::collections::VecDeque::new();
+        for req in &session.requests {
+            let mut req = req.clone();
+            req.source_session = Some(title.clone());
+            if req.timestamp.is_some() {
+                queue.push_back(req);
+            } else {
+                untimed.push((index, req));
+            }
+        }
+        timed.push(queue);
+    }
+
+    let capacity = timed.iter().map(|q| q.len()).sum::<usize>() + untimed.len();
+    let mut requests = Vec::with_capacity(capacity);
+    let mut sources = Vec::with_capacity(capacity);
+    // min_by_key keeps the first of equal keys, so ties go to the earlier session
+    while let Some(next) = timed
+        .iter()
+        .enumerate()
+        .filter_map(|(i, queue)| Some((queue.front()?.timestamp, i)))
+        .min_by_key(|&(timestamp, _)| timestamp)
+        .map(|(_, i)| i)
+    {
+        requests.extend(timed[next].pop_front());
+        sources.push(next);
+    }
+
+    let untimed_count = untimed.len();
+    for (index, req) in untimed {
+        requests.push(req);
+        sources.push(index);
+    }
+    MergedTimeline {
+        requests,
+        untimed: untimed_count,
+        sources,
+    }
+}
+
+/// The session a merge of `timeline` writes, built in memory
+pub fn build_merged_session(
+    timeline: &MergedTimeline,
+    session_id: Option<String>,
+    title: &str,
+) -> ChatSession {
+    ChatSession {
+        version: 3,
+        session_id,
+        creation_date: timeline.first_timestamp(),
+        last_message_date: timeline.last_timestamp(),
+        is_imported: false,
+        initial_location: "panel".to_string(),
+        custom_title: Some(title.to_string()),
+        requester_username: Some("User".to_string()),
+        requester_avatar_icon_uri: None,
+        responder_username: Some("GitHub Copilot".to_string()),
+        responder_avatar_icon_uri: Some(serde_json::json!({"id": "copilot"})),
+        requests: timeline.requests.clone(),
+    }
+}
+
+/// Warn that requests without timestamps went to the end of a merge
+fn warn_untimed(timeline: &MergedTimeline) {
+    if timeline.untimed > 0 {
+        status!(
+            "   {} {} message(s) without timestamps appended at the end",
+            "[!]".yellow(),
+            timeline.untimed
+        );
+    }
+}
+
 /// Convert millisecond timestamp to date string
 fn timestamp_to_date(timestamp: i64) -> String {
     if timestamp == 0 {
@@ -892,13 +1527,14 @@ pub fn fetch_by_workspace(
     target_path: Option<&str>,
     force: bool,
     no_register: bool,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
     use colored::Colorize;
-    use std::fs;
 
-    println!("\n{}", "=".repeat(70));
-    println!("{} FETCH BY WORKSPACE", "[*]".cyan().bold());
-    println!("{}", "=".repeat(70));
+    status!("\n{}", "=".repeat(70));
+    status!("{} FETCH BY WORKSPACE", "[*]".cyan().bold());
+    status!("{}", "=".repeat(70));
 
     // Find target workspace
     let target_dir = match target_path {
@@ -910,11 +1546,11 @@ pub fn fetch_by_workspace(
     };
     let target_normalized = normalize_path(target_dir.to_str().unwrap_or(""));
 
-    println!("\n{} Target: {}", "[>]".blue(), target_normalized);
-    println!("{} Pattern: {}", "[>]".blue(), workspace_name);
+    status!("\n{} Target: {}", "[>]".blue(), target_normalized);
+    status!("{} Pattern: {}", "[>]".blue(), workspace_name);
 
     // Find all workspaces
-    let all_workspaces = discover_workspaces()?;
+    let all_workspaces = discover_workspaces(discovery)?;
     let pattern_lower = workspace_name.to_lowercase();
 
     // Find source workspaces matching pattern
@@ -930,7 +1566,7 @@ pub fn fetch_by_workspace(
         .collect();
 
     if source_workspaces.is_empty() {
-        println!(
+        status!(
             "\n{} No workspaces found matching '{}'",
             "[X]".red(),
             workspace_name
@@ -938,7 +1574,7 @@ pub fn fetch_by_workspace(
         return Ok(());
     }
 
-    println!(
+    status!(
         "\n{} Found {} matching workspace(s)",
         "[OK]".green(),
         source_workspaces.len()
@@ -955,7 +1591,7 @@ pub fn fetch_by_workspace(
     let target_ws_dir = match target_ws {
         Some(ws) => ws.workspace_path.join("workspaceState"),
         None => {
-            println!(
+            status!(
                 "{} Target workspace not found, creating new...",
                 "[!]".yellow()
             );
@@ -966,6 +1602,7 @@ pub fn fetch_by_workspace(
 
     // Collect all sessions from matching workspaces
     let mut fetched_count = 0;
+    let mut actions = Vec::new();
 
     for ws in source_workspaces {
         let sessions = get_chat_sessions_from_workspace(&ws.workspace_path)?;
@@ -980,13 +1617,20 @@ pub fn fetch_by_workspace(
             let dest_file = target_ws_dir.join(&filename);
 
             if dest_file.exists() && !force {
-                println!("   {} Skipping (exists): {}", "[!]".yellow(), filename);
+                status!("   {} Skipping (exists): {}", "[!]".yellow(), filename);
                 continue;
             }
 
-            fs::copy(src_file, &dest_file)?;
+            if dry_run {
+                actions.push(PlannedAction::Copy {
+                    from: src_file.clone(),
+                    to: dest_file,
+                });
+                continue;
+            }
+            copy_file_atomic(src_file, &dest_file)?;
             fetched_count += 1;
-            println!(
+            status!(
                 "   {} Fetched: {}",
                 "[OK]".green(),
                 session_with_path.session.title()
@@ -994,14 +1638,18 @@ pub fn fetch_by_workspace(
         }
     }
 
-    println!(
+    if dry_run {
+        print_dry_run(&actions);
+        return Ok(());
+    }
+    status!(
         "\n{} Fetched {} session(s)",
         "[OK]".green().bold(),
         fetched_count
     );
 
     if !no_register {
-        println!(
+        status!(
             "{} Sessions will appear in VS Code after reload",
             "[i]".cyan()
         );
@@ -1016,16 +1664,17 @@ pub fn fetch_sessions(
     target_path: Option<&str>,
     force: bool,
     no_register: bool,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
     use colored::Colorize;
-    use std::fs;
 
-    println!("\n{}", "=".repeat(70));
-    println!("{} FETCH SESSIONS BY ID", "[*]".cyan().bold());
-    println!("{}", "=".repeat(70));
+    status!("\n{}", "=".repeat(70));
+    status!("{} FETCH SESSIONS BY ID", "[*]".cyan().bold());
+    status!("{}", "=".repeat(70));
 
     if session_ids.is_empty() {
-        println!("{} No session IDs provided", "[X]".red());
+        status!("{} No session IDs provided", "[X]".red());
         return Ok(());
     }
 
@@ -1039,10 +1688,10 @@ pub fn fetch_sessions(
     };
     let target_normalized = normalize_path(target_dir.to_str().unwrap_or(""));
 
-    println!("\n{} Target: {}", "[>]".blue(), target_normalized);
-    println!("{} Sessions: {:?}", "[>]".blue(), session_ids);
+    status!("\n{} Target: {}", "[>]".blue(), target_normalized);
+    status!("{} Sessions: {:?}", "[>]".blue(), session_ids);
 
-    let all_workspaces = discover_workspaces()?;
+    let all_workspaces = discover_workspaces(discovery)?;
 
     // Find target workspace
     let target_ws = all_workspaces.iter().find(|ws| {
@@ -1068,6 +1717,7 @@ pub fn fetch_sessions(
 
     let mut fetched_count = 0;
     let mut found_ids = Vec::new();
+    let mut actions = Vec::new();
 
     for ws in &all_workspaces {
         if !ws.has_chat_sessions {
@@ -1104,15 +1754,22 @@ pub fn fetch_sessions(
                 let dest_file = target_ws_dir.join(&filename);
 
                 if dest_file.exists() && !force {
-                    println!("   {} Skipping (exists): {}", "[!]".yellow(), filename);
+                    status!("   {} Skipping (exists): {}", "[!]".yellow(), filename);
                     found_ids.push(session_id);
                     continue;
                 }
 
-                fs::copy(src_file, &dest_file)?;
-                fetched_count += 1;
                 found_ids.push(session_id);
-                println!(
+                if dry_run {
+                    actions.push(PlannedAction::Copy {
+                        from: src_file.clone(),
+                        to: dest_file,
+                    });
+                    continue;
+                }
+                copy_file_atomic(src_file, &dest_file)?;
+                fetched_count += 1;
+                status!(
                     "   {} Fetched: {}",
                     "[OK]".green(),
                     session_with_path.session.title()
@@ -1132,20 +1789,24 @@ pub fn fetch_sessions(
         .collect();
 
     if !not_found.is_empty() {
-        println!("\n{} Sessions not found:", "[!]".yellow());
+        status!("\n{} Sessions not found:", "[!]".yellow());
         for id in not_found {
-            println!("   {} {}", "[X]".red(), id);
+            status!("   {} {}", "[X]".red(), id);
         }
     }
 
-    println!(
+    if dry_run {
+        print_dry_run(&actions);
+        return Ok(());
+    }
+    status!(
         "\n{} Fetched {} session(s)",
         "[OK]".green().bold(),
         fetched_count
     );
 
     if !no_register {
-        println!(
+        status!(
             "{} Sessions will appear in VS Code after reload",
             "[i]".cyan()
         );
@@ -1159,18 +1820,23 @@ pub fn merge_by_workspace_names(
     workspace_names: &[String],
     title: Option<&str>,
     target_path: Option<&str>,
-    force: bool,
-    no_backup: bool,
+    options: MergeOptions,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
-    println!(
+    let MergeOptions {
+        force,
+        no_backup,
+        dry_run,
+    } = options;
+    status!(
         "\n{} Merging Sessions from Multiple Workspaces",
         "[M]".blue().bold()
     );
-    println!("{}", "=".repeat(70));
+    status!("{}", "=".repeat(70));
 
-    println!("\n{} Workspace patterns:", "[D]".blue());
+    status!("\n{} Workspace patterns:", "[D]".blue());
     for name in workspace_names {
-        println!("   {} {}", "[*]".blue(), name.cyan());
+        status!("   {} {}", "[*]".blue(), name.cyan());
     }
 
     // Collect all matching workspaces
@@ -1178,7 +1844,7 @@ pub fn merge_by_workspace_names(
     let mut seen_ws_ids = std::collections::HashSet::new();
 
     for pattern in workspace_names {
-        let workspaces = find_all_workspaces_for_project(pattern)?;
+        let workspaces = find_all_workspaces_for_project(pattern, discovery)?;
         for ws in workspaces {
             if !seen_ws_ids.contains(&ws.0) {
                 seen_ws_ids.insert(ws.0.clone());
@@ -1188,20 +1854,20 @@ pub fn merge_by_workspace_names(
     }
 
     if all_matching_workspaces.is_empty() {
-        println!(
+        status!(
             "\n{} No workspaces found matching any of the patterns",
             "[X]".red()
         );
         return Ok(());
     }
 
-    println!(
+    status!(
         "\n{} Found {} unique workspace(s):",
         "[D]".blue(),
         all_matching_workspaces.len()
     );
     for (ws_id, _, folder_path, _) in &all_matching_workspaces {
-        println!(
+        status!(
             "   {} {}... -> {}",
             "[*]".blue(),
             &ws_id[..16.min(ws_id.len())],
@@ -1222,36 +1888,37 @@ pub fn merge_by_workspace_names(
             .unwrap_or_else(|_| ".".to_string()),
     };
 
-    let target_ws = find_workspace_by_path(&target_path)?
+    let target_ws = find_workspace_by_path(&target_path, discovery)?
         .context("Target workspace not found. Make sure the project is opened in VS Code")?;
     let (target_ws_id, target_ws_dir, _) = target_ws;
 
-    println!(
+    status!(
         "\n{} Target workspace: {}...",
         "[>]".blue(),
         &target_ws_id[..16.min(target_ws_id.len())]
     );
 
-    // Collect sessions from all matching workspaces
-    println!("\n{} Collecting sessions...", "[D]".blue());
+    // Plan the merge before touching anything on disk
+    status!("\n{} Collecting sessions...", "[D]".blue());
+    let plan = plan_workspace_merge(&all_matching_workspaces)?;
 
-    let mut all_sessions = Vec::new();
-    for (ws_id, ws_dir, _, _) in &all_matching_workspaces {
-        let sessions = get_chat_sessions_from_workspace(ws_dir)?;
-        if !sessions.is_empty() {
-            println!(
-                "   {} {}... ({} sessions)",
-                "[d]".blue(),
-                &ws_id[..16.min(ws_id.len())],
-                sessions.len()
-            );
-            all_sessions.extend(sessions);
-        }
+    if plan.entries.is_empty() {
+        status!(
+            "\n{} No chat sessions found in matching workspaces",
+            "[X]".red()
+        );
+        return Ok(());
     }
 
-    if all_sessions.is_empty() {
-        println!(
-            "\n{} No chat sessions found in matching workspaces",
+    if dry_run {
+        print_merge_plan(&plan, &target_ws_id);
+    } else {
+        print_merge_skips(&plan);
+    }
+
+    if plan.sessions.is_empty() {
+        status!(
+            "\n{} No mergeable sessions found in matching workspaces",
             "[X]".red()
         );
         return Ok(());
@@ -1263,33 +1930,196 @@ pub fn merge_by_workspace_names(
 
     // Use the common merge logic
     merge_sessions_internal(
-        all_sessions,
+        plan.sessions,
         Some(merge_title),
-        &target_ws_id,
-        &target_ws_dir,
-        force,
-        no_backup,
+        &MergeTarget::workspace(&target_ws_id, &target_ws_dir),
+        &MergeFlags {
+            force,
+            no_backup,
+            dry_run,
+        },
         &format!("{} workspaces", workspace_names.len()),
     )
 }
 
+/// What a workspace merge would do with a single source session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeAction {
+    /// The session's messages are copied into the merged session
+    Copy,
+    /// The session is left out, with the reason
+    Skip(String),
+}
+
+impl std::fmt::Display for MergeAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeAction::Copy => write!(f, "copy"),
+            MergeAction::Skip(reason) => write!(f, "skip ({})", reason),
+        }
+    }
+}
+
+/// A single row of a workspace merge plan
+#[derive(Debug, Clone)]
+pub struct MergePlanEntry {
+    pub session_id: String,
+    pub title: String,
+    pub source_workspace: String,
+    pub messages: usize,
+    pub action: MergeAction,
+}
+
+/// The planning phase of a workspace merge, computed without writing anything
+#[derive(Debug)]
+pub struct WorkspaceMergePlan {
+    /// One entry per source session, in discovery order
+    pub entries: Vec<MergePlanEntry>,
+    /// Sessions that will be merged (those planned as [`MergeAction::Copy`])
+    pub sessions: Vec<crate::models::SessionWithPath>,
+}
+
+/// Decide which sessions from the matching workspaces would be merged.
+///
+/// Sessions whose ID was already seen in an earlier workspace are skipped as
+/// collisions (the same session copied into several workspaces would
+/// otherwise appear twice), as are sessions without messages. Messages
+/// without timestamps are still merged, at the end of the timeline.
+pub fn plan_workspace_merge(
+    workspaces: &[crate::workspace::WorkspaceInfo],
+) -> Result<WorkspaceMergePlan> {
+    let mut entries = Vec::new();
+    let mut sessions = Vec::new();
+    let mut seen_ids = std::collections::HashSet::new();
+
+    for (ws_id, ws_dir, _, _) in workspaces {
+        let ws_sessions = get_chat_sessions_from_workspace(ws_dir)?;
+        if ws_sessions.is_empty() {
+            continue;
+        }
+        status!(
+            "   {} {}... ({} sessions)",
+            "[d]".blue(),
+            &ws_id[..16.min(ws_id.len())],
+            ws_sessions.len()
+        );
+
+        for swp in ws_sessions {
+            let session_id = swp.get_session_id();
+            let messages = swp.session.request_count();
+            let action = if !seen_ids.insert(session_id.clone()) {
+                MergeAction::Skip("collision".to_string())
+            } else if swp.session.requests.is_empty() {
+                MergeAction::Skip("no messages".to_string())
+            } else {
+                MergeAction::Copy
+            };
+
+            entries.push(MergePlanEntry {
+                session_id,
+                title: swp.session.title(),
+                source_workspace: ws_id.clone(),
+                messages,
+                action: action.clone(),
+            });
+            if action == MergeAction::Copy {
+                sessions.push(swp);
+            }
+        }
+    }
+
+    Ok(WorkspaceMergePlan { entries, sessions })
+}
+
+/// Print a merge plan as a table
+fn print_merge_plan(plan: &WorkspaceMergePlan, target_ws_id: &str) {
+    use tabled::{settings::Style, Table, Tabled};
+
+    #[derive(Tabled)]
+    struct MergePlanRow {
+        #[tabled(rename = "Session")]
+        session: String,
+        #[tabled(rename = "Title")]
+        title: String,
+        #[tabled(rename = "Source")]
+        source: String,
+        #[tabled(rename = "Messages")]
+        messages: usize,
+        #[tabled(rename = "Action")]
+        action: String,
+        #[tabled(rename = "Target")]
+        target: String,
+    }
+
+    let short = |id: &str| id[..16.min(id.len())].to_string();
+    let rows: Vec<MergePlanRow> = plan
+        .entries
+        .iter()
+        .map(|e| MergePlanRow {
+            session: short(&e.session_id),
+            title: truncate(&e.title, 40),
+            source: short(&e.source_workspace),
+            messages: e.messages,
+            action: e.action.to_string(),
+            target: short(target_ws_id),
+        })
+        .collect();
+
+    status!("\n{} Merge plan:", "[=]".blue());
+    let table = Table::new(&rows).with(Style::ascii_rounded()).to_string();
+    println!("{}", table);
+
+    let copied = plan
+        .entries
+        .iter()
+        .filter(|e| e.action == MergeAction::Copy)
+        .count();
+    status!(
+        "\n{} {} session(s) would be merged, {} skipped",
+        "[i]".cyan(),
+        copied,
+        plan.entries.len() - copied
+    );
+}
+
+/// Name the sessions a merge leaves out, and why; `--dry-run` shows them
+/// in the plan table instead
+fn print_merge_skips(plan: &WorkspaceMergePlan) {
+    for entry in &plan.entries {
+        if let MergeAction::Skip(reason) = &entry.action {
+            status!(
+                "   {} Skipped {} ({}...): {}",
+                "[!]".yellow(),
+                truncate(&entry.title, 40),
+                &entry.session_id[..16.min(entry.session_id.len())],
+                reason
+            );
+        }
+    }
+}
+
 /// Merge chat sessions from an LLM provider
 pub fn merge_from_provider(
     provider_name: &str,
     title: Option<&str>,
     target_path: Option<&str>,
     session_ids: Option<&[String]>,
-    force: bool,
-    no_backup: bool,
+    options: MergeOptions,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
+    let MergeOptions {
+        force,
+        no_backup,
+        dry_run,
+    } = options;
     use crate::providers::{ProviderRegistry, ProviderType};
 
-    println!(
+    status!(
         "\n{} Merging Sessions from Provider: {}",
         "[M]".blue().bold(),
         provider_name.cyan()
     );
-    println!("{}", "=".repeat(70));
+    status!("{}", "=".repeat(70));
 
     // Parse provider name
     let provider_type = match provider_name.to_lowercase().as_str() {
@@ -1305,10 +2135,10 @@ pub fn merge_from_provider(
         "gpt4all" => ProviderType::Gpt4All,
         "llamafile" => ProviderType::Llamafile,
         _ => {
-            println!("{} Unknown provider: {}", "[X]".red(), provider_name);
-            println!("\n{} Available providers:", "[i]".cyan());
-            println!("   copilot, cursor, ollama, vllm, foundry, lm-studio,");
-            println!("   localai, text-gen-webui, jan, gpt4all, llamafile");
+            status!("{} Unknown provider: {}", "[X]".red(), provider_name);
+            status!("\n{} Available providers:", "[i]".cyan());
+            status!("   copilot, cursor, ollama, vllm, foundry, lm-studio,");
+            status!("   localai, text-gen-webui, jan, gpt4all, llamafile");
             return Ok(());
         }
     };
@@ -1320,7 +2150,7 @@ pub fn merge_from_provider(
         .context(format!("Provider '{}' not available", provider_name))?;
 
     if !provider.is_available() {
-        println!(
+        status!(
             "{} Provider '{}' is not available or not configured",
             "[X]".red(),
             provider_name
@@ -1328,7 +2158,7 @@ pub fn merge_from_provider(
         return Ok(());
     }
 
-    println!(
+    status!(
         "{} Provider: {} ({})",
         "[*]".blue(),
         provider.name(),
@@ -1341,11 +2171,11 @@ pub fn merge_from_provider(
         .context("Failed to list sessions from provider")?;
 
     if provider_sessions.is_empty() {
-        println!("{} No sessions found in provider", "[X]".red());
+        status!("{} No sessions found in provider", "[X]".red());
         return Ok(());
     }
 
-    println!(
+    status!(
         "{} Found {} session(s) in provider",
         "[D]".blue(),
         provider_sessions.len()
@@ -1373,17 +2203,17 @@ pub fn merge_from_provider(
     };
 
     if sessions_to_merge.is_empty() {
-        println!("{} No matching sessions found", "[X]".red());
+        status!("{} No matching sessions found", "[X]".red());
         return Ok(());
     }
 
-    println!(
+    status!(
         "{} Merging {} session(s):",
         "[D]".blue(),
         sessions_to_merge.len()
     );
     for s in &sessions_to_merge {
-        println!(
+        status!(
             "   {} {} ({} messages)",
             "[*]".blue(),
             truncate(&s.title(), 50),
@@ -1404,11 +2234,11 @@ pub fn merge_from_provider(
             .unwrap_or_else(|_| ".".to_string()),
     };
 
-    let target_ws = find_workspace_by_path(&target_path)?
+    let target_ws = find_workspace_by_path(&target_path, discovery)?
         .context("Target workspace not found. Make sure the project is opened in VS Code")?;
     let (target_ws_id, target_ws_dir, _) = target_ws;
 
-    println!(
+    status!(
         "\n{} Target workspace: {}...",
         "[>]".blue(),
         &target_ws_id[..16.min(target_ws_id.len())]
@@ -1431,10 +2261,12 @@ pub fn merge_from_provider(
     merge_sessions_internal(
         sessions_with_path,
         Some(merge_title),
-        &target_ws_id,
-        &target_ws_dir,
-        force,
-        no_backup,
+        &MergeTarget::workspace(&target_ws_id, &target_ws_dir),
+        &MergeFlags {
+            force,
+            no_backup,
+            dry_run,
+        },
         &format!("Provider: {}", provider.name()),
     )
 }
@@ -1445,22 +2277,27 @@ pub fn merge_cross_provider(
     title: Option<&str>,
     target_path: Option<&str>,
     workspace_filter: Option<&str>,
-    force: bool,
-    no_backup: bool,
+    options: MergeOptions,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
+    let MergeOptions {
+        force,
+        no_backup,
+        dry_run,
+    } = options;
     use crate::models::ChatSession;
     use crate::providers::{ProviderRegistry, ProviderType};
 
-    println!("\n{} Cross-Provider Merge", "[M]".blue().bold());
-    println!("{}", "=".repeat(70));
-    println!(
+    status!("\n{} Cross-Provider Merge", "[M]".blue().bold());
+    status!("{}", "=".repeat(70));
+    status!(
         "{} Providers: {}",
         "[*]".blue(),
         provider_names.join(", ").cyan()
     );
 
     if let Some(ws) = workspace_filter {
-        println!("{} Workspace filter: {}", "[*]".blue(), ws.cyan());
+        status!("{} Workspace filter: {}", "[*]".blue(), ws.cyan());
     }
 
     let registry = ProviderRegistry::new();
@@ -1481,7 +2318,7 @@ pub fn merge_cross_provider(
             "gpt4all" => Some(ProviderType::Gpt4All),
             "llamafile" => Some(ProviderType::Llamafile),
             _ => {
-                println!(
+                status!(
                     "{} Unknown provider: {} (skipping)",
                     "[!]".yellow(),
                     provider_name
@@ -1505,7 +2342,7 @@ pub fn merge_cross_provider(
                                 sessions
                             };
 
-                            println!(
+                            status!(
                                 "{} {} ({}): {} session(s)",
                                 "[D]".blue(),
                                 provider.name(),
@@ -1521,7 +2358,7 @@ pub fn merge_cross_provider(
                             }
                         }
                         Err(e) => {
-                            println!(
+                            status!(
                                 "{} Failed to get sessions from {}: {}",
                                 "[!]".yellow(),
                                 provider.name(),
@@ -1530,7 +2367,7 @@ pub fn merge_cross_provider(
                         }
                     }
                 } else {
-                    println!(
+                    status!(
                         "{} Provider {} not available",
                         "[!]".yellow(),
                         provider.name()
@@ -1541,11 +2378,11 @@ pub fn merge_cross_provider(
     }
 
     if all_sessions.is_empty() {
-        println!("{} No sessions found across providers", "[X]".red());
+        status!("{} No sessions found across providers", "[X]".red());
         return Ok(());
     }
 
-    println!(
+    status!(
         "\n{} Total: {} sessions from {} provider(s)",
         "[*]".green().bold(),
         all_sessions.len(),
@@ -1568,9 +2405,9 @@ pub fn merge_cross_provider(
     });
 
     // Print sessions being merged
-    println!("\n{} Sessions to merge:", "[D]".blue());
+    status!("\n{} Sessions to merge:", "[D]".blue());
     for (provider_name, session) in &all_sessions {
-        println!(
+        status!(
             "   {} [{}] {} ({} messages)",
             "[*]".blue(),
             provider_name.cyan(),
@@ -1592,11 +2429,11 @@ pub fn merge_cross_provider(
             .unwrap_or_else(|_| ".".to_string()),
     };
 
-    let target_ws = find_workspace_by_path(&target_path)?
+    let target_ws = find_workspace_by_path(&target_path, discovery)?
         .context("Target workspace not found. Make sure the project is opened in VS Code")?;
     let (target_ws_id, target_ws_dir, _) = target_ws;
 
-    println!(
+    status!(
         "\n{} Target workspace: {}...",
         "[>]".blue(),
         &target_ws_id[..16.min(target_ws_id.len())]
@@ -1618,10 +2455,12 @@ pub fn merge_cross_provider(
     merge_sessions_internal(
         sessions_with_path,
         Some(merge_title),
-        &target_ws_id,
-        &target_ws_dir,
-        force,
-        no_backup,
+        &MergeTarget::workspace(&target_ws_id, &target_ws_dir),
+        &MergeFlags {
+            force,
+            no_backup,
+            dry_run,
+        },
         &format!("{} providers", provider_names.len()),
     )
 }
@@ -1631,17 +2470,22 @@ pub fn merge_all_providers(
     title: Option<&str>,
     target_path: Option<&str>,
     workspace_filter: Option<&str>,
-    force: bool,
-    no_backup: bool,
+    options: MergeOptions,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
+    let MergeOptions {
+        force,
+        no_backup,
+        dry_run,
+    } = options;
     use crate::models::ChatSession;
     use crate::providers::{ProviderRegistry, ProviderType};
 
-    println!("\n{} Merge All Providers", "[M]".blue().bold());
-    println!("{}", "=".repeat(70));
+    status!("\n{} Merge All Providers", "[M]".blue().bold());
+    status!("{}", "=".repeat(70));
 
     if let Some(ws) = workspace_filter {
-        println!("{} Workspace filter: {}", "[*]".blue(), ws.cyan());
+        status!("{} Workspace filter: {}", "[*]".blue(), ws.cyan());
     }
 
     let registry = ProviderRegistry::new();
@@ -1663,7 +2507,7 @@ pub fn merge_all_providers(
         ProviderType::Llamafile,
     ];
 
-    println!("{} Scanning providers...", "[*]".blue());
+    status!("{} Scanning providers...", "[*]".blue());
 
     for provider_type in all_provider_types {
         if let Some(provider) = registry.get_provider(provider_type) {
@@ -1681,7 +2525,7 @@ pub fn merge_all_providers(
                         };
 
                         if !filtered.is_empty() {
-                            println!(
+                            status!(
                                 "   {} {}: {} session(s)",
                                 "[+]".green(),
                                 provider.name(),
@@ -1706,11 +2550,11 @@ pub fn merge_all_providers(
     }
 
     if all_sessions.is_empty() {
-        println!("{} No sessions found across any providers", "[X]".red());
+        status!("{} No sessions found across any providers", "[X]".red());
         return Ok(());
     }
 
-    println!(
+    status!(
         "\n{} Found {} sessions across {} provider(s)",
         "[*]".green().bold(),
         all_sessions.len(),
@@ -1733,17 +2577,17 @@ pub fn merge_all_providers(
     });
 
     // Print sessions being merged (limit to first 20)
-    println!("\n{} Sessions to merge:", "[D]".blue());
+    status!("\n{} Sessions to merge:", "[D]".blue());
     for (i, (provider_name, session)) in all_sessions.iter().enumerate() {
         if i >= 20 {
-            println!(
+            status!(
                 "   {} ... and {} more",
                 "[*]".blue(),
                 all_sessions.len() - 20
             );
             break;
         }
-        println!(
+        status!(
             "   {} [{}] {} ({} messages)",
             "[*]".blue(),
             provider_name.cyan(),
@@ -1765,11 +2609,11 @@ pub fn merge_all_providers(
             .unwrap_or_else(|_| ".".to_string()),
     };
 
-    let target_ws = find_workspace_by_path(&target_path)?
+    let target_ws = find_workspace_by_path(&target_path, discovery)?
         .context("Target workspace not found. Make sure the project is opened in VS Code")?;
     let (target_ws_id, target_ws_dir, _) = target_ws;
 
-    println!(
+    status!(
         "\n{} Target workspace: {}...",
         "[>]".blue(),
         &target_ws_id[..16.min(target_ws_id.len())]
@@ -1791,10 +2635,12 @@ pub fn merge_all_providers(
     merge_sessions_internal(
         sessions_with_path,
         Some(merge_title),
-        &target_ws_id,
-        &target_ws_dir,
-        force,
-        no_backup,
+        &MergeTarget::workspace(&target_ws_id, &target_ws_dir),
+        &MergeFlags {
+            force,
+            no_backup,
+            dry_run,
+        },
         &format!("{} providers (all)", providers_found),
     )
 }
@@ -0,0 +1,170 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Session to repository link commands (link, unlink, link --list)
+
+use anyhow::{Context, Result};
+use colored::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tabled::{settings::Style, Table, Tabled};
+
+use crate::commands::{print_dry_run, resolve_session_id, PlannedAction};
+use crate::links::{get_link_index_path, LinkIndex};
+use crate::workspace::DiscoveryOptions;
+
+/// Root of the git work tree containing `path`, as git itself reports it
+fn git_toplevel(path: &Path) -> Result<PathBuf> {
+    if !path.is_dir() {
+        anyhow::bail!("Not a directory: {}", path.display());
+    }
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!("Not a git repository: {}", path.display());
+    }
+    let root = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(root.canonicalize().unwrap_or(root))
+}
+
+/// Dry-run plan for a link index change, e.g. "link ID to REPO"
+fn link_index_plan(change: String) -> Vec<PlannedAction> {
+    vec![PlannedAction::Modify {
+        path: get_link_index_path(),
+        change,
+    }]
+}
+
+/// Link a session (full ID or unique prefix) to the repository containing `repo_path`
+pub fn link_session(
+    session_id: &str,
+    repo_path: &str,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let session_id = resolve_session_id(session_id, discovery)?;
+    let repo = git_toplevel(Path::new(repo_path))?;
+    let repo = repo.to_string_lossy().to_string();
+
+    let mut index = LinkIndex::load()?;
+    let previous = index.repo_for(&session_id).map(str::to_string);
+    if !index.link(&session_id, &repo) {
+        println!(
+            "{} {} is already linked to {}",
+            "[=]".blue(),
+            session_id.cyan(),
+            repo
+        );
+        return Ok(());
+    }
+    if dry_run {
+        let change = match previous {
+            Some(previous) => format!("link {} to {} (was {})", session_id, repo, previous),
+            None => format!("link {} to {}", session_id, repo),
+        };
+        print_dry_run(&link_index_plan(change));
+        return Ok(());
+    }
+    index.save()?;
+
+    match previous {
+        Some(previous) => println!(
+            "{} Linked {} to {} (was {})",
+            "[OK]".green(),
+            session_id.cyan(),
+            repo,
+            previous
+        ),
+        None => println!(
+            "{} Linked {} to {}",
+            "[OK]".green(),
+            session_id.cyan(),
+            repo
+        ),
+    }
+    Ok(())
+}
+
+/// Remove a session's repository link
+pub fn unlink_session(session_id: &str, dry_run: bool, discovery: &DiscoveryOptions) -> Result<()> {
+    let mut index = LinkIndex::load()?;
+
+    // Allow unlinking sessions that no longer exist on disk
+    let session_id = if index.repo_for(session_id).is_some() {
+        session_id.to_string()
+    } else {
+        resolve_session_id(session_id, discovery)?
+    };
+
+    match index.unlink(&session_id) {
+        Some(repo) if dry_run => {
+            print_dry_run(&link_index_plan(format!(
+                "unlink {} from {}",
+                session_id, repo
+            )));
+        }
+        Some(repo) => {
+            index.save()?;
+            println!(
+                "{} Unlinked {} from {}",
+                "[OK]".green(),
+                session_id.cyan(),
+                repo
+            );
+        }
+        None => println!("{} {} is not linked", "[!]".yellow(), session_id.cyan()),
+    }
+    Ok(())
+}
+
+/// Show every session to repository link
+pub fn link_list() -> Result<()> {
+    #[derive(Tabled)]
+    struct LinkRow {
+        #[tabled(rename = "Session")]
+        session: String,
+        #[tabled(rename = "Repository")]
+        repository: String,
+    }
+
+    let index = LinkIndex::load()?;
+    if index.sessions.is_empty() {
+        println!("{} No sessions are linked", "[i]".cyan());
+        return Ok(());
+    }
+
+    let mut missing = 0;
+    let rows: Vec<LinkRow> = index
+        .sessions
+        .iter()
+        .map(|(session, repo)| {
+            let exists = Path::new(repo).is_dir();
+            missing += usize::from(!exists);
+            LinkRow {
+                session: session.clone(),
+                repository: if exists {
+                    repo.clone()
+                } else {
+                    format!("{} (missing)", repo)
+                },
+            }
+        })
+        .collect();
+
+    println!("{}", Table::new(&rows).with(Style::ascii_rounded()));
+    println!(
+        "\n{} {} linked session(s)",
+        "[=]".blue(),
+        rows.len().to_string().yellow()
+    );
+    if missing > 0 {
+        println!(
+            "{} {} link(s) point to a repository that no longer exists",
+            "[!]".yellow(),
+            missing
+        );
+    }
+    Ok(())
+}
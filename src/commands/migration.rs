@@ -7,7 +7,8 @@ use colored::*;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-use crate::workspace::discover_workspaces;
+use crate::storage::copy_file_atomic;
+use crate::workspace::{discover_workspaces, DiscoveryOptions};
 
 /// Migration package manifest
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,11 +27,16 @@ struct MigrationWorkspace {
 }
 
 /// Create a migration package
-pub fn create_migration(output: &str, projects: Option<&str>, include_all: bool) -> Result<()> {
+pub fn create_migration(
+    output: &str,
+    projects: Option<&str>,
+    include_all: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
     let output_path = Path::new(output);
     std::fs::create_dir_all(output_path)?;
 
-    let workspaces = discover_workspaces()?;
+    let workspaces = discover_workspaces(discovery)?;
 
     // Filter workspaces
     let filtered: Vec<_> = if include_all {
@@ -190,7 +196,7 @@ pub fn restore_migration(package: &str, mapping: Option<&str>, dry_run: bool) ->
             // Copy workspace.json
             let ws_json_src = src_dir.join("workspace.json");
             if ws_json_src.exists() {
-                std::fs::copy(&ws_json_src, dst_dir.join("workspace.json"))?;
+                copy_file_atomic(&ws_json_src, &dst_dir.join("workspace.json"))?;
             }
 
             // Copy chat sessions
@@ -201,7 +207,7 @@ pub fn restore_migration(package: &str, mapping: Option<&str>, dry_run: bool) ->
                 std::fs::create_dir_all(&sessions_dst)?;
                 for entry in std::fs::read_dir(&sessions_src)? {
                     let entry = entry?;
-                    std::fs::copy(entry.path(), sessions_dst.join(entry.file_name()))?;
+                    copy_file_atomic(&entry.path(), &sessions_dst.join(entry.file_name()))?;
                 }
             }
         }
@@ -3,23 +3,63 @@
 //! Command implementations
 
 mod agency;
+mod archive;
+mod csv_output;
+mod dedupe;
 mod detect;
+mod doctor;
 mod export_import;
 mod git;
 mod harvest;
 mod history;
+mod links;
 mod migration;
+mod obsidian;
+mod open;
+mod output;
+mod plan;
 mod providers;
+mod purge;
 mod register;
+mod saved_search;
+mod similar;
+mod sink;
+mod snippet;
+mod split;
+mod sqlite_export;
+mod stats;
+mod tags;
+mod text_match;
+mod threads;
 mod workspace_cmds;
 
 pub use agency::*;
+pub use archive::*;
+pub use csv_output::*;
+pub use dedupe::*;
 pub use detect::*;
+pub use doctor::*;
 pub use export_import::*;
 pub use git::*;
 pub use harvest::*;
 pub use history::*;
+pub use links::*;
 pub use migration::*;
+pub use obsidian::*;
+pub use open::*;
+pub use output::*;
+pub use plan::*;
 pub use providers::*;
+pub use purge::*;
 pub use register::*;
+pub use saved_search::*;
+pub use similar::*;
+pub use sink::*;
+pub use snippet::*;
+pub use split::*;
+pub use sqlite_export::*;
+pub use stats::*;
+pub use tags::*;
+pub use text_match::*;
+pub use threads::*;
 pub use workspace_cmds::*;
@@ -0,0 +1,337 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! `export obsidian`: every session as a cross-linked note in an Obsidian vault
+//!
+//! Notes go in one folder per workspace, with YAML front matter tagged `csm`
+//! and the workspace name. Sessions that attached or cited the same files get
+//! `[[wiki-links]]` to each other, and each workspace gets a map-of-content
+//! (MOC) note listing its sessions, which every note links back to.
+
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use colored::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use crate::commands::{export_dir, load_redactor, sanitize_dir_name, scrub, ExportOptions};
+use crate::models::{ChatSession, Workspace};
+use crate::providers::session_format::session_to_markdown;
+use crate::redact::Anonymizer;
+use crate::workspace::{discover_workspaces, load_chat_sessions_from_workspace, DiscoveryOptions};
+
+/// Most shared files named after a related-session link
+const MAX_SHARED_FILES_SHOWN: usize = 3;
+
+/// Counts written by an Obsidian export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ObsidianExportStats {
+    pub notes: usize,
+    pub mocs: usize,
+    /// Related-session links, counted once per pair
+    pub links: usize,
+}
+
+/// A workspace folder in the vault
+struct VaultFolder {
+    /// Folder name, also the MOC's title
+    name: String,
+    /// Front matter tag
+    tag: String,
+    /// Project path, or the hash when the workspace has none
+    label: String,
+    project_path: Option<String>,
+}
+
+/// A session note in the vault
+struct VaultNote {
+    folder: usize,
+    /// Note (and file) name, unique across the vault so wiki-links resolve
+    name: String,
+    session: ChatSession,
+    files: BTreeSet<String>,
+}
+
+/// Characters Obsidian doesn't allow in note names (links break on them)
+fn note_name_safe(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | '#' | '^' | '|' | '\\' | '/' | ':' | '*' | '"' | '<' | '>' | '?' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    cleaned.trim_matches('.').trim().to_string()
+}
+
+/// A workspace name as an Obsidian tag: lowercase letters, digits and dashes,
+/// with at least one non-digit as Obsidian requires
+pub fn obsidian_tag(name: &str) -> String {
+    let mut tag = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() || c == '_' {
+            tag.push(c);
+        } else if !tag.ends_with('-') {
+            tag.push('-');
+        }
+    }
+    let tag = tag.trim_matches('-');
+    if tag.chars().all(|c| c.is_ascii_digit()) {
+        format!("ws-{}", tag)
+    } else {
+        tag.to_string()
+    }
+}
+
+/// `base`, or `base 2`, `base 3`, ... if already taken (case-insensitively,
+/// like the file systems Obsidian runs on)
+fn unique_name(base: &str, taken: &mut HashSet<String>) -> String {
+    let mut name = base.to_string();
+    let mut n = 2;
+    while !taken.insert(name.to_lowercase()) {
+        name = format!("{} {}", base, n);
+        n += 1;
+    }
+    name
+}
+
+/// Quote a string for YAML front matter (a JSON string is a valid YAML scalar)
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn format_date(timestamp: i64) -> Option<String> {
+    (timestamp > 0)
+        .then(|| Utc.timestamp_millis_opt(timestamp).single())
+        .flatten()
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+}
+
+/// When a session was last active: its newest request, else `lastMessageDate`
+fn last_activity(session: &ChatSession) -> i64 {
+    session
+        .timestamp_range()
+        .map(|(_, last)| last)
+        .unwrap_or(session.last_message_date)
+}
+
+/// Other notes citing the same files as each note, most shared files first,
+/// with the files they share
+fn related_notes(notes: &[VaultNote]) -> Vec<Vec<(usize, Vec<&str>)>> {
+    let mut by_file: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, note) in notes.iter().enumerate() {
+        for file in &note.files {
+            by_file.entry(file.as_str()).or_default().push(i);
+        }
+    }
+
+    notes
+        .iter()
+        .enumerate()
+        .map(|(i, note)| {
+            let mut shared: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+            for file in &note.files {
+                for &other in &by_file[file.as_str()] {
+                    if other != i {
+                        shared.entry(other).or_default().push(file.as_str());
+                    }
+                }
+            }
+            let mut related: Vec<(usize, Vec<&str>)> = shared.into_iter().collect();
+            related.sort_by(|(a, a_files), (b, b_files)| {
+                b_files
+                    .len()
+                    .cmp(&a_files.len())
+                    .then_with(|| notes[*a].name.cmp(&notes[*b].name))
+            });
+            related
+        })
+        .collect()
+}
+
+fn render_note(
+    note: &VaultNote,
+    folder: &VaultFolder,
+    related: &[(usize, Vec<&str>)],
+    notes: &[VaultNote],
+) -> String {
+    let session = &note.session;
+    let mut out = String::from("---\n");
+    out.push_str(&format!("title: {}\n", yaml_string(&session.title())));
+    if let Some(id) = &session.session_id {
+        out.push_str(&format!("session_id: {}\n", yaml_string(id)));
+    }
+    out.push_str(&format!("workspace: {}\n", yaml_string(&folder.label)));
+    if let Some(created) = format_date(session.creation_date) {
+        out.push_str(&format!("created: {}\n", created));
+    }
+    out.push_str(&format!("messages: {}\n", session.request_count()));
+    out.push_str(&format!("tags:\n  - csm\n  - {}\n", folder.tag));
+    out.push_str("---\n\n");
+    out.push_str(&format!("Workspace: [[{}]]\n\n", folder.name));
+
+    out.push_str(&session_to_markdown(session));
+
+    if !related.is_empty() {
+        out.push_str("\n## Related sessions\n\n");
+        for (other, files) in related {
+            let mut shown: Vec<String> = files
+                .iter()
+                .take(MAX_SHARED_FILES_SHOWN)
+                .map(|f| format!("`{}`", f))
+                .collect();
+            if files.len() > MAX_SHARED_FILES_SHOWN {
+                shown.push(format!("{} more", files.len() - MAX_SHARED_FILES_SHOWN));
+            }
+            out.push_str(&format!(
+                "- [[{}]] (shares {})\n",
+                notes[*other].name,
+                shown.join(", ")
+            ));
+        }
+    }
+
+    if !note.files.is_empty() {
+        out.push_str("\n## Referenced files\n\n");
+        for file in &note.files {
+            out.push_str(&format!("- `{}`\n", file));
+        }
+    }
+    out
+}
+
+fn render_moc(folder: &VaultFolder, notes: &[&VaultNote]) -> String {
+    let mut out = format!("---\ntags:\n  - csm\n  - {}\n---\n\n", folder.tag);
+    out.push_str(&format!("# {}\n\n", folder.name));
+    if let Some(project) = &folder.project_path {
+        out.push_str(&format!("Project: `{}`\n\n", project));
+    }
+    out.push_str(&format!("{} session(s), newest first:\n\n", notes.len()));
+    for note in notes {
+        match format_date(last_activity(&note.session)) {
+            Some(date) => out.push_str(&format!(
+                "- [[{}]] ({} messages, {})\n",
+                note.name,
+                note.session.request_count(),
+                date
+            )),
+            None => out.push_str(&format!(
+                "- [[{}]] ({} messages)\n",
+                note.name,
+                note.session.request_count()
+            )),
+        }
+    }
+    out
+}
+
+/// Write a vault of session notes and workspace MOCs under `output`
+pub fn export_obsidian_vault(
+    output: &Path,
+    options: &ExportOptions,
+    discovery: &DiscoveryOptions,
+) -> Result<ObsidianExportStats> {
+    let redactor = load_redactor(options.redact)?;
+    let anonymizer = options.anonymize.then(Anonymizer::for_current_user);
+
+    let mut folders: Vec<VaultFolder> = Vec::new();
+    let mut notes: Vec<VaultNote> = Vec::new();
+    let mut folder_names = HashSet::new();
+    let mut note_names = HashSet::new();
+    let workspaces: Vec<Workspace> = discover_workspaces(discovery)?
+        .into_iter()
+        .filter(|ws| ws.has_chat_sessions)
+        .collect();
+
+    for ws in &workspaces {
+        let (sessions, _) = load_chat_sessions_from_workspace(&ws.workspace_path);
+        if sessions.is_empty() {
+            continue;
+        }
+        let project_name = ws
+            .project_path
+            .as_deref()
+            .and_then(|p| p.trim_end_matches(['/', '\\']).rsplit(['/', '\\']).next())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| &ws.hash[..8.min(ws.hash.len())]);
+        let name = unique_name(
+            &note_name_safe(&sanitize_dir_name(project_name)),
+            &mut folder_names,
+        );
+        // Folder and MOC share a name, so keep session notes off it
+        note_names.insert(name.to_lowercase());
+        folders.push(VaultFolder {
+            tag: obsidian_tag(&name),
+            label: ws.project_path.clone().unwrap_or_else(|| ws.hash.clone()),
+            project_path: ws.project_path.clone(),
+            name,
+        });
+
+        for mut s in sessions {
+            options.turns().apply(&mut s.session, &s.path);
+            let id = s.get_session_id();
+            let title = scrub(s.session.title(), redactor.as_ref(), anonymizer.as_ref());
+            let base = note_name_safe(&format!("{} ({})", title, &id[..8.min(id.len())]));
+            notes.push(VaultNote {
+                folder: folders.len() - 1,
+                name: unique_name(&base, &mut note_names),
+                files: s.session.referenced_files(),
+                session: s.session,
+            });
+        }
+    }
+
+    let related = related_notes(&notes);
+    let stats = ObsidianExportStats {
+        notes: notes.len(),
+        mocs: folders.len(),
+        links: related.iter().map(Vec::len).sum::<usize>() / 2,
+    };
+
+    for (i, folder) in folders.iter().enumerate() {
+        let dir = output.join(&folder.name);
+        std::fs::create_dir_all(&dir)?;
+        let anonymizer = anonymizer
+            .as_ref()
+            .map(|a| a.with_project(folder.project_path.as_deref()));
+        let write = |name: &str, content: String| {
+            std::fs::write(
+                dir.join(format!("{}.md", name)),
+                scrub(content, redactor.as_ref(), anonymizer.as_ref()),
+            )
+        };
+
+        let mut members: Vec<&VaultNote> = Vec::new();
+        for (note, related) in notes.iter().zip(&related).filter(|(n, _)| n.folder == i) {
+            write(&note.name, render_note(note, folder, related, &notes))?;
+            members.push(note);
+        }
+        members.sort_by_key(|n| std::cmp::Reverse(last_activity(&n.session)));
+        write(&folder.name, render_moc(folder, &members))?;
+    }
+
+    Ok(stats)
+}
+
+/// `export obsidian --output <vault>`
+pub fn export_obsidian(
+    output: &str,
+    options: &ExportOptions,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let stats = export_obsidian_vault(export_dir(output)?, options, discovery)?;
+    if stats.notes == 0 {
+        println!("No chat sessions to export.");
+        return Ok(());
+    }
+    println!(
+        "{} Wrote {} note(s) and {} workspace MOC(s) to {} ({} related-session link(s))",
+        "[OK]".green(),
+        stats.notes,
+        stats.mocs,
+        output,
+        stats.links
+    );
+    Ok(())
+}
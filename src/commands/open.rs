@@ -0,0 +1,141 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Open commands (reopen a session's workspace in the editor)
+
+use anyhow::Result;
+use colored::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::CsmError;
+use crate::models::Workspace;
+use crate::providers::CsmConfig;
+use crate::workspace::{discover_workspaces, list_session_files, DiscoveryOptions};
+
+/// Editor launched when neither `--editor-cmd` nor the config sets one
+pub const DEFAULT_EDITOR_CMD: &str = "code";
+
+/// Pick the editor command: explicit flag, then config `editor_cmd`, then `code`
+pub fn resolve_editor_cmd(flag: Option<&str>, config: &CsmConfig) -> String {
+    flag.or(config.editor_cmd.as_deref())
+        .map(str::trim)
+        .filter(|cmd| !cmd.is_empty())
+        .unwrap_or(DEFAULT_EDITOR_CMD)
+        .to_string()
+}
+
+/// Find the session file and owning workspace for a full or prefix session ID
+pub(crate) fn find_session_workspace(
+    session_id: &str,
+    discovery: &DiscoveryOptions,
+) -> Result<(String, Workspace, PathBuf)> {
+    let mut matches = Vec::new();
+
+    for ws in discover_workspaces(discovery)? {
+        if !ws.has_chat_sessions {
+            continue;
+        }
+        for path in list_session_files(&ws.chat_sessions_path) {
+            let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+            if stem == session_id {
+                return Ok((stem, ws, path));
+            }
+            if stem.starts_with(session_id) {
+                matches.push((stem, ws.clone(), path));
+            }
+        }
+    }
+
+    match matches.len() {
+        1 => Ok(matches.remove(0)),
+        0 => Err(CsmError::SessionNotFound(session_id.to_string()).into()),
+        n => anyhow::bail!(
+            "Session ID '{}' is ambiguous ({} matches); use a longer prefix",
+            session_id,
+            n
+        ),
+    }
+}
+
+/// Build the process that opens `folder` with `editor_cmd`
+///
+/// The command may carry its own arguments (e.g. `flatpak run com.vscodium.codium`).
+fn editor_command(editor_cmd: &str, folder: &Path) -> Command {
+    let mut parts = editor_cmd.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR_CMD);
+
+    // `code` is a .cmd shim on Windows, which CreateProcess won't resolve
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(program);
+        cmd
+    };
+    #[cfg(not(windows))]
+    let mut cmd = Command::new(program);
+
+    cmd.args(parts).arg(folder);
+    cmd
+}
+
+/// Reopen the workspace that owns a session in VS Code (or a compatible editor)
+pub fn open_session(
+    session_id: &str,
+    editor_cmd: Option<&str>,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let (session_id, ws, _path) = find_session_workspace(session_id, discovery)?;
+
+    let Some(project_path) = ws.project_path.as_deref() else {
+        anyhow::bail!(
+            "Session {} belongs to workspace {} which has no project folder",
+            session_id,
+            ws.hash
+        );
+    };
+    // Only an absolute path names the folder; a relative one would resolve
+    // against the current directory
+    let folder = Path::new(project_path);
+    if !folder.is_absolute() || !folder.exists() {
+        anyhow::bail!(
+            "Project folder for session {} no longer exists: {}",
+            session_id,
+            project_path
+        );
+    }
+
+    let config = CsmConfig::load().unwrap_or_default();
+    let editor = resolve_editor_cmd(editor_cmd, &config);
+
+    println!(
+        "{} Opening {} in {}",
+        "[*]".blue(),
+        project_path.cyan(),
+        editor.bold()
+    );
+
+    let status = match editor_command(&editor, folder).status() {
+        Ok(status) => status,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => anyhow::bail!(
+            "Editor command '{}' was not found on PATH. Install the VS Code shell command \
+             (Command Palette: \"Shell Command: Install 'code' command in PATH\"), or point \
+             --editor-cmd / the `editor_cmd` config key at your editor (e.g. cursor, codium).",
+            editor
+        ),
+        Err(e) => return Err(e.into()),
+    };
+    if !status.success() {
+        anyhow::bail!("'{}' exited with {}", editor, status);
+    }
+
+    // The VS Code CLI has no flag for focusing a specific chat session
+    println!(
+        "{} Session {} is in the Chat view history of that window",
+        "[OK]".green(),
+        session_id.cyan()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! `--quiet`: keep stdout to a command's primary output, and `--pretty`:
+//! how JSON output is laid out
+//!
+//! Summaries, footers, hints and progress lines are printed with
+//! [`status!`](crate::status), which drops them under `--quiet`. Tables,
+//! JSON, counts and anything else a command exists to print use `println!`
+//! as usual, and warnings and errors stay on stderr either way. JSON printed
+//! to stdout goes through [`to_json`], which is compact (one line) unless
+//! `--pretty` asks for indentation.
+
+use serde::Serialize;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether `--quiet` was given (see [`set_quiet`])
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Set whether summary and status lines are suppressed. Like stdout itself
+/// this is process-wide; each call replaces the previous setting.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether summary and status lines are suppressed
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Whether `--pretty` was given (see [`set_pretty_json`])
+static PRETTY_JSON: AtomicBool = AtomicBool::new(false);
+
+/// Set whether JSON output is indented. Process-wide, like [`set_quiet`];
+/// each call replaces the previous setting.
+pub fn set_pretty_json(pretty: bool) {
+    PRETTY_JSON.store(pretty, Ordering::Relaxed);
+}
+
+/// `value` as JSON for stdout: indented under `--pretty`, else on one line
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    if PRETTY_JSON.load(Ordering::Relaxed) {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    }
+}
+
+/// Write `records` to stdout as NDJSON, one compact object per line, each
+/// serialized and written in turn rather than collected first
+pub fn print_ndjson<'a, T: Serialize + 'a>(
+    records: impl IntoIterator<Item = &'a T>,
+) -> anyhow::Result<()> {
+    let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+    for record in records {
+        serde_json::to_writer(&mut out, record)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// `println!` for summary, footer and status lines, skipped under `--quiet`
+#[macro_export]
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !$crate::commands::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
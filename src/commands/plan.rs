@@ -0,0 +1,94 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Planned filesystem changes, printed by `--dry-run`
+//!
+//! Commands that change files (`purge`, `tag`, `link`, `merge`, `import`,
+//! `move`, `fetch`, `register`) describe what they would do as a list of
+//! [`PlannedAction`]s, so every dry run reads the same way.
+
+use anyhow::Result;
+use colored::*;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::status;
+
+/// A single change a command would make on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlannedAction {
+    /// A new file or directory
+    Create(PathBuf),
+    /// `to` written as a copy of `from`, replacing it if it exists
+    Copy { from: PathBuf, to: PathBuf },
+    /// `from` renamed to `to`
+    Move { from: PathBuf, to: PathBuf },
+    /// An existing file changed in place
+    Modify { path: PathBuf, change: String },
+    /// A file deleted
+    Remove(PathBuf),
+}
+
+impl PlannedAction {
+    /// Marker shown before the action, in the `[+]`/`[-]` style of other listings
+    fn marker(&self) -> ColoredString {
+        match self {
+            PlannedAction::Create(_) | PlannedAction::Copy { .. } => "[+]".green(),
+            PlannedAction::Move { .. } => "[>]".blue(),
+            PlannedAction::Modify { .. } => "[~]".yellow(),
+            PlannedAction::Remove(_) => "[-]".red(),
+        }
+    }
+}
+
+impl fmt::Display for PlannedAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlannedAction::Create(path) => write!(f, "create {}", path.display()),
+            PlannedAction::Copy { from, to } => {
+                write!(f, "copy {} -> {}", from.display(), to.display())
+            }
+            PlannedAction::Move { from, to } => {
+                write!(f, "move {} -> {}", from.display(), to.display())
+            }
+            PlannedAction::Modify { path, change } => {
+                write!(f, "modify {} ({})", path.display(), change)
+            }
+            PlannedAction::Remove(path) => write!(f, "remove {}", path.display()),
+        }
+    }
+}
+
+/// List the planned changes
+pub fn print_plan(actions: &[PlannedAction]) {
+    if actions.is_empty() {
+        status!("\n{} Nothing would change", "[i]".cyan());
+        return;
+    }
+    status!("\n{} {} planned change(s):", "[=]".blue(), actions.len());
+    for action in actions {
+        println!("   {} {}", action.marker(), action);
+    }
+}
+
+/// List the planned changes of a `--dry-run` and say that none were made
+pub fn print_dry_run(actions: &[PlannedAction]) {
+    print_plan(actions);
+    status!(
+        "\n{} DRY RUN - No changes were made. Run without --dry-run to apply them.",
+        "[!]".yellow()
+    );
+}
+
+/// Create `dir` if it is missing, or in a dry run, plan its creation
+pub(crate) fn ensure_dir(
+    dir: &Path,
+    dry_run: bool,
+    actions: &mut Vec<PlannedAction>,
+) -> Result<()> {
+    if !dry_run {
+        std::fs::create_dir_all(dir)?;
+    } else if !dir.exists() {
+        actions.push(PlannedAction::Create(dir.to_path_buf()));
+    }
+    Ok(())
+}
@@ -4,94 +4,455 @@
 
 use anyhow::Result;
 use colored::*;
+use std::time::Duration;
+use tabled::{settings::Style, Table, Tabled};
 
+use crate::commands::format_bytes;
 use crate::providers::{
-    config::{CsmConfig, ProviderConfig},
-    discovery::print_provider_summary,
-    ProviderRegistry, ProviderType,
+    config::{validate_base_url, CsmConfig, ProviderConfig},
+    discovery::{print_provider_summary, probe_endpoint_with_retries},
+    openai_compat::{models_url, OpenAICompatProvider},
+    ChatProvider, ProviderRegistry, ProviderType,
 };
+use crate::workspace::DiscoveryOptions;
+
+/// Reachability of one provider, as `provider list --probe` and
+/// `detect providers --json` report it
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProviderProbe {
+    pub name: String,
+    pub kind: ProviderType,
+    /// API endpoint pinged; `None` for providers that only store files
+    pub endpoint: Option<String>,
+    /// Whether the endpoint answered in time, or for file-based providers,
+    /// whether their data was found
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    /// Probes sent before the endpoint answered or retries ran out (0 for
+    /// providers that only store files)
+    pub attempts: u32,
+    /// Why the endpoint couldn't be reached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ProviderProbe {
+    /// Whether `--require <name>` names this provider: its kind (any alias
+    /// `provider info` accepts) or its name, ignoring case
+    pub fn matches(&self, name: &str) -> bool {
+        ProviderType::from_name(name).is_some_and(|kind| kind == self.kind)
+            || self.name.eq_ignore_ascii_case(name)
+    }
+}
+
+/// Ping every discovered provider's endpoint in parallel, one thread each,
+/// retrying each failure up to `retries` times (see
+/// [`probe_endpoint_with_retries`])
+pub fn probe_providers(
+    registry: &ProviderRegistry,
+    timeout: Duration,
+    retries: u32,
+) -> Vec<ProviderProbe> {
+    // Providers aren't Sync, so pull out what the probes need first
+    let targets: Vec<(String, ProviderType, Option<String>, bool)> = registry
+        .providers()
+        .iter()
+        .map(|p| {
+            let endpoint = p
+                .endpoint()
+                .or_else(|| p.provider_type().default_endpoint())
+                .map(String::from);
+            let available = endpoint.is_none() && p.is_available();
+            (p.name().to_string(), p.provider_type(), endpoint, available)
+        })
+        .collect();
+
+    // Probes block on the network and sleep between retries, so each gets a
+    // thread of its own rather than a slot in rayon's CPU-sized pool
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = targets
+            .into_iter()
+            .map(|(name, kind, endpoint, available)| {
+                scope.spawn(move || probe_target(name, kind, endpoint, available, timeout, retries))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("provider probe thread panicked"))
+            .collect()
+    })
+}
+
+/// Ping one provider's endpoint; one without an endpoint counts as reachable
+/// when `available` (its data was found)
+fn probe_target(
+    name: String,
+    kind: ProviderType,
+    endpoint: Option<String>,
+    available: bool,
+    timeout: Duration,
+    retries: u32,
+) -> ProviderProbe {
+    let result = endpoint
+        .as_deref()
+        .map(|url| probe_endpoint_with_retries(url, timeout, retries));
+    let (reachable, latency_ms, error, attempts) = match result {
+        Some((Ok(elapsed), attempts)) => (true, Some(elapsed.as_millis() as u64), None, attempts),
+        Some((Err(reason), attempts)) => (false, None, Some(reason), attempts),
+        None => (available, None, None, 0),
+    };
+    ProviderProbe {
+        name,
+        kind,
+        endpoint,
+        reachable,
+        latency_ms,
+        attempts,
+        error,
+    }
+}
 
 /// List all discovered providers
-pub fn list_providers() -> Result<()> {
+///
+/// With `probe`, every provider endpoint is pinged in parallel (failures are
+/// retried `retries` times) and the results are shown in a single table;
+/// otherwise no network I/O happens, for user-defined providers either.
+pub fn list_providers(probe: bool, timeout_secs: u64, retries: u32) -> Result<()> {
     let registry = ProviderRegistry::new();
-    print_provider_summary(&registry);
+    if !probe {
+        print_provider_summary(&registry);
+        return Ok(());
+    }
+
+    let probes = probe_providers(&registry, Duration::from_secs(timeout_secs), retries);
+
+    #[derive(Tabled)]
+    struct ProbeRow {
+        #[tabled(rename = "Provider")]
+        name: String,
+        #[tabled(rename = "Endpoint")]
+        endpoint: String,
+        #[tabled(rename = "Reachable")]
+        reachable: String,
+        #[tabled(rename = "Latency")]
+        latency: String,
+    }
+
+    let mut reachable = 0;
+    let rows: Vec<ProbeRow> = probes
+        .into_iter()
+        .map(|probe| {
+            let (status, latency) = match (&probe.endpoint, probe.latency_ms, probe.error) {
+                (None, _, _) => (format!("{}", "n/a".dimmed()), "-".to_string()),
+                (Some(_), Some(ms), _) => {
+                    reachable += 1;
+                    (format!("{}", "Yes".green()), format!("{} ms", ms))
+                }
+                (Some(_), None, reason) => (
+                    format!("{} ({})", "No".red(), reason.unwrap_or_default()),
+                    "-".to_string(),
+                ),
+            };
+            ProbeRow {
+                name: probe.name,
+                endpoint: probe
+                    .endpoint
+                    .unwrap_or_else(|| "(local files)".to_string()),
+                reachable: status,
+                latency,
+            }
+        })
+        .collect();
+
+    let total = rows.len();
+    let table = Table::new(rows).with(Style::ascii_rounded()).to_string();
+    println!("{}", table);
+    println!(
+        "\n{} {} of {} provider(s) reachable (timeout {}s)",
+        "[=]".blue(),
+        reachable.to_string().green(),
+        total,
+        timeout_secs
+    );
+
+    Ok(())
+}
+
+/// Everything `provider info` shows about one provider
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProviderInfo {
+    pub name: String,
+    pub kind: ProviderType,
+    pub available: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    pub openai_compatible: bool,
+    pub file_storage: bool,
+    /// Session count; `None` when the provider is unavailable or listing failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sessions: Option<usize>,
+    /// Titles of up to 5 sessions
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recent_sessions: Vec<String>,
+    /// Environment variable a custom provider reads its API key from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_set: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub models: Vec<String>,
+    /// Reachability, with `--probe`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probe: Option<ProviderProbe>,
+    /// Whether listing sessions failed
+    #[serde(skip)]
+    list_failed: bool,
+}
+
+/// Show detailed info about one provider, or with no name, every known
+/// provider (discovered built-ins, then all user-defined ones) in turn
+///
+/// With `probe`, each provider's endpoint is pinged in parallel first; under
+/// `json` one provider prints as an object and all of them as an array.
+pub fn provider_info(
+    provider_name: Option<&str>,
+    probe: bool,
+    timeout_secs: u64,
+    retries: u32,
+    json: bool,
+) -> Result<()> {
+    let timeout = Duration::from_secs(timeout_secs);
+    let mut infos = match provider_name {
+        Some(name) => vec![find_provider_info(name, timeout)?],
+        None => all_provider_infos(timeout)?,
+    };
+
+    if probe {
+        std::thread::scope(|scope| {
+            for info in &mut infos {
+                scope.spawn(move || {
+                    info.probe = Some(probe_target(
+                        info.name.clone(),
+                        info.kind,
+                        info.endpoint.clone(),
+                        info.endpoint.is_none() && info.available,
+                        timeout,
+                        retries,
+                    ));
+                });
+            }
+        });
+    }
+
+    if json {
+        let out = match (provider_name, infos.first()) {
+            (Some(_), Some(info)) => crate::commands::to_json(info)?,
+            _ => crate::commands::to_json(&infos)?,
+        };
+        println!("{}", out);
+        return Ok(());
+    }
+
+    if infos.is_empty() {
+        println!("{} No providers found", "[i]".cyan());
+    }
+    for (i, info) in infos.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_provider_info(info);
+    }
     Ok(())
 }
 
-/// Show detailed info about a specific provider
-pub fn provider_info(provider_name: &str) -> Result<()> {
+/// Info for the provider `provider_name` names: a built-in type or alias, or
+/// failing that, a user-defined provider (whose models are fetched, waiting
+/// up to `timeout`)
+fn find_provider_info(provider_name: &str, timeout: Duration) -> Result<ProviderInfo> {
+    if ProviderType::from_name(provider_name).is_none() {
+        if let Some(config) = CsmConfig::load()?.get_custom_provider(provider_name) {
+            return Ok(custom_provider_info(config, timeout));
+        }
+    }
+
     let provider_type = parse_provider_name(provider_name)?;
     let registry = ProviderRegistry::new();
+    match registry.get_provider(provider_type) {
+        Some(provider) => Ok(builtin_provider_info(provider)),
+        None => {
+            eprintln!("{} Provider not found: {}", "Error:".red(), provider_name);
+            eprintln!();
+            eprintln!("Available providers:");
+            list_provider_types();
+            Err(anyhow::anyhow!("Provider not found"))
+        }
+    }
+}
 
-    if let Some(provider) = registry.get_provider(provider_type) {
-        println!("{}", format!("Provider: {}", provider.name()).bold());
-        println!();
+/// Info for every discovered built-in provider, then every user-defined one
+/// (disabled ones included), whose models are fetched at the same time
+fn all_provider_infos(timeout: Duration) -> Result<Vec<ProviderInfo>> {
+    let registry = ProviderRegistry::new();
+    let mut infos: Vec<ProviderInfo> = registry
+        .providers()
+        .iter()
+        .filter(|p| p.provider_type() != ProviderType::Custom)
+        .map(|p| builtin_provider_info(p.as_ref()))
+        .collect();
+    let config = CsmConfig::load()?;
+    let custom: Vec<&ProviderConfig> = config.custom_providers().collect();
+    infos.extend(std::thread::scope(|scope| {
+        let handles: Vec<_> = custom
+            .into_iter()
+            .map(|config| scope.spawn(move || custom_provider_info(config, timeout)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("provider info thread panicked"))
+            .collect::<Vec<_>>()
+    }));
+    Ok(infos)
+}
 
-        println!("  Type:      {}", provider_type.display_name());
-        println!(
-            "  Available: {}",
-            if provider.is_available() {
-                "Yes".green()
-            } else {
-                "No".red()
-            }
-        );
+/// Gather a built-in provider's details, listing its sessions if available
+fn builtin_provider_info(provider: &dyn ChatProvider) -> ProviderInfo {
+    let kind = provider.provider_type();
+    let available = provider.is_available();
+    let listing = available.then(|| provider.list_sessions());
+    let (sessions, recent_sessions) = match &listing {
+        Some(Ok(sessions)) => (
+            Some(sessions.len()),
+            sessions.iter().take(5).map(|s| s.title()).collect(),
+        ),
+        _ => (None, Vec::new()),
+    };
+
+    ProviderInfo {
+        name: provider.name().to_string(),
+        kind,
+        available,
+        data_path: provider.sessions_path().map(|p| p.display().to_string()),
+        endpoint: provider
+            .endpoint()
+            .or_else(|| kind.default_endpoint())
+            .map(String::from),
+        openai_compatible: kind.is_openai_compatible(),
+        file_storage: kind.uses_file_storage(),
+        sessions,
+        recent_sessions,
+        api_key_env: None,
+        api_key_set: None,
+        models: Vec::new(),
+        probe: None,
+        list_failed: matches!(listing, Some(Err(_))),
+    }
+}
 
-        if let Some(path) = provider.sessions_path() {
-            println!("  Data Path: {}", path.display());
-        }
+/// Gather a user-defined provider's details, asking its endpoint for its
+/// models (it counts as available if that answers within `timeout`)
+fn custom_provider_info(config: &ProviderConfig, timeout: Duration) -> ProviderInfo {
+    let provider = OpenAICompatProvider::custom(config);
+    let models = provider.fetch_models(timeout).and_then(Result::ok);
+    ProviderInfo {
+        name: provider.name().to_string(),
+        kind: ProviderType::Custom,
+        available: models.is_some(),
+        data_path: None,
+        endpoint: config.endpoint.clone(),
+        openai_compatible: true,
+        file_storage: false,
+        sessions: None,
+        recent_sessions: Vec::new(),
+        api_key_env: config.api_key_env.clone(),
+        api_key_set: config
+            .api_key_env
+            .as_ref()
+            .map(|var| std::env::var(var).is_ok()),
+        models: models
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| m.name)
+            .collect(),
+        probe: None,
+        list_failed: false,
+    }
+}
+
+/// Print one provider's info block
+fn print_provider_info(info: &ProviderInfo) {
+    let yes_no = |flag: bool| if flag { "Yes".green() } else { "No".dimmed() };
 
-        if let Some(endpoint) = provider_type.default_endpoint() {
-            println!("  Endpoint:  {}", endpoint);
+    println!("{}", format!("Provider: {}", info.name).bold());
+    println!();
+    println!("  Type:      {}", info.kind.display_name());
+    println!(
+        "  Available: {}",
+        if info.available {
+            "Yes".green()
+        } else {
+            "No".red()
         }
+    );
+    if let Some(path) = &info.data_path {
+        println!("  Data Path: {}", path);
+    }
+    if let Some(endpoint) = &info.endpoint {
+        println!("  Endpoint:  {}", endpoint);
+    }
 
-        println!(
-            "  OpenAI Compatible: {}",
-            if provider_type.is_openai_compatible() {
-                "Yes".green()
-            } else {
-                "No".dimmed()
-            }
-        );
+    if info.kind == ProviderType::Custom {
+        let endpoint = info.endpoint.as_deref().unwrap_or("");
+        println!("  Probe:     {}", models_url(endpoint).dimmed());
+        if let Some(var) = &info.api_key_env {
+            println!(
+                "  API Key:   ${} {}",
+                var,
+                if info.api_key_set == Some(true) {
+                    "(set)".green()
+                } else {
+                    "(not set)".yellow()
+                }
+            );
+        }
+    } else {
+        println!("  OpenAI Compatible: {}", yes_no(info.openai_compatible));
+        println!("  File Storage: {}", yes_no(info.file_storage));
+    }
 
-        println!(
-            "  File Storage: {}",
-            if provider_type.uses_file_storage() {
-                "Yes".green()
-            } else {
-                "No".dimmed()
+    if let Some(probe) = &info.probe {
+        let status = match (&probe.endpoint, probe.latency_ms, &probe.error) {
+            (None, _, _) => format!("{}", "n/a (local files)".dimmed()),
+            (Some(_), Some(ms), _) => format!("{} ({} ms)", "Yes".green(), ms),
+            (Some(_), None, reason) => {
+                format!("{} ({})", "No".red(), reason.as_deref().unwrap_or_default())
             }
-        );
+        };
+        println!("  Reachable: {}", status);
+    }
 
-        // Show sessions if available
-        if provider.is_available() {
-            match provider.list_sessions() {
-                Ok(sessions) => {
-                    println!();
-                    println!("  Sessions:  {}", sessions.len());
-
-                    if !sessions.is_empty() {
-                        println!();
-                        println!("  Recent sessions:");
-                        for session in sessions.iter().take(5) {
-                            println!("    - {}", session.title());
-                        }
-                    }
-                }
-                Err(_) => {
-                    println!("  Sessions:  (unable to list)");
-                }
+    if let Some(count) = info.sessions {
+        println!();
+        println!("  Sessions:  {}", count);
+        if !info.recent_sessions.is_empty() {
+            println!();
+            println!("  Recent sessions:");
+            for title in &info.recent_sessions {
+                println!("    - {}", title);
             }
         }
-    } else {
-        eprintln!("{} Provider not found: {}", "Error:".red(), provider_name);
-        eprintln!();
-        eprintln!("Available providers:");
-        list_provider_types();
-        return Err(anyhow::anyhow!("Provider not found"));
+    } else if info.list_failed {
+        println!("  Sessions:  (unable to list)");
     }
 
-    Ok(())
+    if !info.models.is_empty() {
+        println!();
+        println!("  Models:    {}", info.models.len());
+        for model in info.models.iter().take(10) {
+            println!("    - {}", model);
+        }
+    }
 }
 
 /// Configure a provider
@@ -167,6 +528,7 @@ pub fn import_from_provider(
     from_provider: &str,
     target_path: Option<&str>,
     session_id: Option<&str>,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
     let provider_type = parse_provider_name(from_provider)?;
     let registry = ProviderRegistry::new();
@@ -202,14 +564,13 @@ pub fn import_from_provider(
         let session = provider.import_session(session_id)?;
 
         // Save to target workspace
-        let workspace = crate::workspace::get_workspace_by_path(&project_path)?
+        let workspace = crate::workspace::get_workspace_by_path(&project_path, discovery)?
             .ok_or_else(|| anyhow::anyhow!("Workspace not found for path: {}", project_path))?;
         let sessions_dir = workspace.chat_sessions_path;
         std::fs::create_dir_all(&sessions_dir)?;
 
         let session_file = sessions_dir.join(format!("{}.json", session_id));
-        let content = serde_json::to_string_pretty(&session)?;
-        std::fs::write(&session_file, content)?;
+        crate::storage::write_session_atomic(&session_file, &session)?;
 
         println!("{} Imported session: {}", "+".green(), session.title());
     } else {
@@ -223,7 +584,7 @@ pub fn import_from_provider(
             return Ok(());
         }
 
-        let workspace = crate::workspace::get_workspace_by_path(&project_path)?
+        let workspace = crate::workspace::get_workspace_by_path(&project_path, discovery)?
             .ok_or_else(|| anyhow::anyhow!("Workspace not found for path: {}", project_path))?;
         let sessions_dir = workspace.chat_sessions_path;
         std::fs::create_dir_all(&sessions_dir)?;
@@ -237,8 +598,7 @@ pub fn import_from_provider(
             let session_file = sessions_dir.join(format!("{}.json", id));
 
             if !session_file.exists() {
-                let content = serde_json::to_string_pretty(&session)?;
-                std::fs::write(&session_file, content)?;
+                crate::storage::write_session_atomic(&session_file, session)?;
                 imported += 1;
                 println!("  {} {}", "+".green(), session.title());
             }
@@ -294,29 +654,176 @@ pub fn test_provider(provider_name: &str) -> Result<()> {
     }
 }
 
-/// Parse a provider name string into ProviderType
-fn parse_provider_name(name: &str) -> Result<ProviderType> {
-    match name.to_lowercase().as_str() {
-        "copilot" | "github-copilot" | "vscode" => Ok(ProviderType::Copilot),
-        "cursor" => Ok(ProviderType::Cursor),
-        "ollama" => Ok(ProviderType::Ollama),
-        "vllm" => Ok(ProviderType::Vllm),
-        "foundry" | "azure-foundry" | "foundry-local" | "ai-foundry" => Ok(ProviderType::Foundry),
-        "openai" => Ok(ProviderType::OpenAI),
-        "lm-studio" | "lmstudio" => Ok(ProviderType::LmStudio),
-        "localai" | "local-ai" => Ok(ProviderType::LocalAI),
-        "text-gen-webui" | "textgenwebui" | "oobabooga" => Ok(ProviderType::TextGenWebUI),
-        "jan" | "jan-ai" | "janai" => Ok(ProviderType::Jan),
-        "gpt4all" => Ok(ProviderType::Gpt4All),
-        "llamafile" => Ok(ProviderType::Llamafile),
-        "custom" => Ok(ProviderType::Custom),
-        _ => {
-            eprintln!("{} Unknown provider: {}", "Error:".red(), name);
-            eprintln!();
-            list_provider_types();
-            Err(anyhow::anyhow!("Unknown provider"))
+/// List the models a provider serves, querying its model-listing endpoint
+pub fn list_provider_models(provider_name: &str, timeout_secs: u64) -> Result<()> {
+    let timeout = Duration::from_secs(timeout_secs);
+
+    // Custom providers are built directly, skipping the registry's probes
+    let custom = match ProviderType::from_name(provider_name) {
+        Some(_) => None,
+        None => CsmConfig::load()?
+            .get_custom_provider(provider_name)
+            .map(|config| {
+                let provider = OpenAICompatProvider::new(
+                    ProviderType::Custom,
+                    config.display_name(),
+                    config.endpoint.clone().unwrap_or_default(),
+                );
+                match config.resolve_api_key() {
+                    Some(key) => provider.with_api_key(key),
+                    None => provider,
+                }
+            }),
+    };
+    let registry;
+    let provider: &dyn ChatProvider = match &custom {
+        Some(provider) => provider,
+        None => {
+            let provider_type = parse_provider_name(provider_name)?;
+            if !provider_type.is_openai_compatible() {
+                print_no_model_listing(provider_type.display_name());
+                return Ok(());
+            }
+            registry = ProviderRegistry::new();
+            registry
+                .get_provider(provider_type)
+                .ok_or_else(|| anyhow::anyhow!("Provider not found: {}", provider_name))?
+        }
+    };
+
+    let Some(result) = provider.fetch_models(timeout) else {
+        print_no_model_listing(provider.name());
+        return Ok(());
+    };
+    let endpoint = provider.endpoint().unwrap_or_default();
+    let mut models = result.map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to list {} models from {}: {}",
+            provider.name(),
+            endpoint,
+            e
+        )
+    })?;
+
+    if models.is_empty() {
+        println!(
+            "{} {} reports no models at {}",
+            "[!]".yellow(),
+            provider.name(),
+            endpoint
+        );
+        return Ok(());
+    }
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+
+    #[derive(Tabled)]
+    struct ModelRow {
+        #[tabled(rename = "Model")]
+        name: String,
+        #[tabled(rename = "Size")]
+        size: String,
+        #[tabled(rename = "Quantization")]
+        quantization: String,
+    }
+
+    let total = models.len();
+    let rows: Vec<ModelRow> = models
+        .into_iter()
+        .map(|model| ModelRow {
+            name: model.name,
+            size: model
+                .size
+                .map(format_bytes)
+                .unwrap_or_else(|| "-".to_string()),
+            quantization: model.quantization.unwrap_or_else(|| "-".to_string()),
+        })
+        .collect();
+
+    println!("{}", Table::new(rows).with(Style::ascii_rounded()));
+    println!(
+        "\n{} {} model(s) from {}",
+        "[=]".blue(),
+        total.to_string().yellow(),
+        provider.name()
+    );
+
+    Ok(())
+}
+
+fn print_no_model_listing(provider: &str) {
+    println!(
+        "{} {} does not support listing models",
+        "[!]".yellow(),
+        provider
+    );
+}
+
+/// Add a user-defined OpenAI-compatible provider
+pub fn add_custom_provider(name: &str, base_url: &str, api_key_env: Option<&str>) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        anyhow::bail!(
+            "Invalid provider name '{}': use letters, digits, '-', '_' or '.'",
+            name
+        );
+    }
+    if ProviderType::from_name(name).is_some() {
+        anyhow::bail!(
+            "'{}' is a built-in provider name; choose a different name",
+            name
+        );
+    }
+    let base_url = validate_base_url(base_url)?;
+
+    let mut config = CsmConfig::load()?;
+    let replaced = config.get_custom_provider(name).is_some();
+    config.set_custom_provider(ProviderConfig::custom(name, &base_url, api_key_env));
+    config.save()?;
+
+    println!(
+        "{} {} custom provider: {}",
+        "+".green(),
+        if replaced { "Updated" } else { "Added" },
+        name
+    );
+    println!();
+    println!("  Base URL: {}", base_url);
+    println!("  Models:   {}", models_url(&base_url).dimmed());
+    println!(
+        "  API Key:  {}",
+        match api_key_env {
+            Some(var) if std::env::var(var).is_ok() => format!("${} {}", var, "(set)".green()),
+            Some(var) => format!("${} {}", var, "(not set)".yellow()),
+            None => "(none)".dimmed().to_string(),
         }
+    );
+
+    Ok(())
+}
+
+/// Remove a user-defined provider
+pub fn remove_custom_provider(name: &str) -> Result<()> {
+    let mut config = CsmConfig::load()?;
+    if !config.remove_custom_provider(name) {
+        anyhow::bail!("Custom provider not found: {}", name);
     }
+    config.save()?;
+
+    println!("{} Removed custom provider: {}", "-".green(), name);
+    Ok(())
+}
+
+/// Parse a provider name string into ProviderType
+fn parse_provider_name(name: &str) -> Result<ProviderType> {
+    ProviderType::from_name(name).ok_or_else(|| {
+        eprintln!("{} Unknown provider: {}", "Error:".red(), name);
+        eprintln!();
+        list_provider_types();
+        anyhow::anyhow!("Unknown provider")
+    })
 }
 
 /// Print list of available provider type names
@@ -335,4 +842,6 @@ fn list_provider_types() {
     eprintln!("  gpt4all      - GPT4All");
     eprintln!("  llamafile    - Llamafile");
     eprintln!("  custom       - Custom OpenAI-compatible endpoint");
+    eprintln!();
+    eprintln!("User-defined providers can be added with 'csm provider add'.");
 }
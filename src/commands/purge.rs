@@ -0,0 +1,148 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Purge commands (bulk-remove empty-window sessions)
+
+use anyhow::Result;
+use colored::*;
+use std::io::Write;
+
+use crate::commands::{
+    parse_date_filter, print_dry_run, print_plan, session_activity_date, PlannedAction,
+};
+use crate::status;
+use crate::storage::read_empty_window_sessions_with_paths;
+
+/// Format a byte count for display (e.g. "1.5 MB")
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Ask a yes/no question on stdin, defaulting to no
+pub(crate) fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// List (and with `delete`/`yes`, remove) empty-window sessions
+///
+/// This is a dry run unless `delete` (prompts first) or `yes` (no prompt)
+/// is set, and always with `dry_run`. `older_than` limits the purge to
+/// sessions whose last message is before the given date or age (e.g. `30d`).
+pub fn purge_empty_sessions(
+    older_than: Option<&str>,
+    delete: bool,
+    yes: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let cutoff = older_than
+        .map(|v| parse_date_filter(v).map_err(|e| anyhow::anyhow!("--older-than: {}", e)))
+        .transpose()?;
+
+    let candidates: Vec<_> = read_empty_window_sessions_with_paths()?
+        .into_iter()
+        .filter_map(|s| {
+            let date = session_activity_date(&s.path);
+            if let Some(cutoff) = cutoff {
+                // Sessions with no known date are kept rather than guessed stale
+                if !date.is_some_and(|d| d < cutoff) {
+                    return None;
+                }
+            }
+            let size = s.path.metadata().map(|m| m.len()).unwrap_or(0);
+            Some((s, date, size))
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        status!("{} No empty-window sessions to purge", "[OK]".green());
+        return Ok(());
+    }
+
+    let total_size: u64 = candidates.iter().map(|(_, _, size)| size).sum();
+    status!(
+        "{} Found {} empty-window session(s) ({}):",
+        "[!]".yellow(),
+        candidates.len(),
+        format_bytes(total_size)
+    );
+    for (s, date, size) in &candidates {
+        let id = s
+            .path
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let date = date
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        println!(
+            "   {} {}  {} ({}, {})",
+            "[-]".dimmed(),
+            id,
+            s.session.title(),
+            date,
+            format_bytes(*size)
+        );
+    }
+
+    let actions: Vec<PlannedAction> = candidates
+        .iter()
+        .map(|(s, _, _)| PlannedAction::Remove(s.path.clone()))
+        .collect();
+    if dry_run {
+        print_dry_run(&actions);
+        return Ok(());
+    }
+    if !delete && !yes {
+        print_plan(&actions);
+        status!(
+            "\n{} DRY RUN - No changes were made. Run with --delete (or --yes) to remove them.",
+            "[!]".yellow()
+        );
+        return Ok(());
+    }
+
+    if !yes && !confirm(&format!("\nDelete {} session(s)?", candidates.len()))? {
+        status!("{} Aborted, nothing was deleted", "[i]".cyan());
+        return Ok(());
+    }
+
+    let mut removed = 0;
+    let mut reclaimed = 0;
+    for (s, _, size) in &candidates {
+        match std::fs::remove_file(&s.path) {
+            Ok(()) => {
+                removed += 1;
+                reclaimed += size;
+            }
+            Err(e) => eprintln!(
+                "{} Failed to remove {}: {}",
+                "[X]".red(),
+                s.path.display(),
+                e
+            ),
+        }
+    }
+
+    status!(
+        "\n{} Removed {} session(s), reclaimed {}",
+        "[OK]".green(),
+        removed,
+        format_bytes(reclaimed)
+    );
+
+    Ok(())
+}
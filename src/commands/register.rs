@@ -9,15 +9,16 @@
 use anyhow::Result;
 use colored::*;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::commands::{print_dry_run, PlannedAction};
 use crate::error::CsmError;
 use crate::models::ChatSession;
 use crate::storage::{
     add_session_to_index, get_workspace_storage_db, is_vscode_running, parse_session_json,
     read_chat_session_index, register_all_sessions_from_directory,
 };
-use crate::workspace::find_workspace_by_path;
+use crate::workspace::{find_workspace_by_path, DiscoveryOptions};
 
 /// Resolve a path option to an absolute PathBuf, handling "." and relative paths
 fn resolve_path(path: Option<&str>) -> PathBuf {
@@ -31,7 +32,13 @@ fn resolve_path(path: Option<&str>) -> PathBuf {
 }
 
 /// Register all sessions from a workspace into VS Code's index
-pub fn register_all(project_path: Option<&str>, merge: bool, force: bool) -> Result<()> {
+pub fn register_all(
+    project_path: Option<&str>,
+    merge: bool,
+    force: bool,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
     let path = resolve_path(project_path);
 
     if merge {
@@ -45,9 +52,13 @@ pub fn register_all(project_path: Option<&str>, merge: bool, force: bool) -> Res
         let path_str = path.to_string_lossy().to_string();
         return crate::commands::history_merge(
             Some(&path_str),
-            None,  // title
-            force, // force
-            false, // no_backup
+            None, // title
+            crate::commands::MergeOptions {
+                force,
+                dry_run,
+                ..Default::default()
+            },
+            discovery,
         );
     }
 
@@ -59,7 +70,7 @@ pub fn register_all(project_path: Option<&str>, merge: bool, force: bool) -> Res
 
     // Find the workspace
     let path_str = path.to_string_lossy().to_string();
-    let (ws_id, ws_path, _folder) = find_workspace_by_path(&path_str)?
+    let (ws_id, ws_path, _folder) = find_workspace_by_path(&path_str, discovery)?
         .ok_or_else(|| CsmError::WorkspaceNotFound(path.display().to_string()))?;
 
     let chat_sessions_dir = ws_path.join("chatSessions");
@@ -73,8 +84,8 @@ pub fn register_all(project_path: Option<&str>, merge: bool, force: bool) -> Res
         return Ok(());
     }
 
-    // Check if VS Code is running
-    if !force && is_vscode_running() {
+    // Check if VS Code is running; a dry run writes nothing, so it skips this
+    if !force && !dry_run && is_vscode_running() {
         println!(
             "{} VS Code is running. Use {} to register anyway.",
             "[!]".yellow(),
@@ -91,6 +102,17 @@ pub fn register_all(project_path: Option<&str>, merge: bool, force: bool) -> Res
         sessions_on_disk.to_string().green()
     );
 
+    if dry_run {
+        print_dry_run(&[PlannedAction::Modify {
+            path: get_workspace_storage_db(&ws_id)?,
+            change: format!(
+                "sync the chat session index with {} session file(s)",
+                sessions_on_disk
+            ),
+        }]);
+        return Ok(());
+    }
+
     // Register all sessions
     let registered = register_all_sessions_from_directory(&ws_id, &chat_sessions_dir, force)?;
 
@@ -120,24 +142,34 @@ pub fn register_all(project_path: Option<&str>, merge: bool, force: bool) -> Res
     Ok(())
 }
 
+/// Dry-run plan for adding one session to the index in `db_path`
+fn register_plan(db_path: &Path, session_id: &str, title: &str) -> PlannedAction {
+    PlannedAction::Modify {
+        path: db_path.to_path_buf(),
+        change: format!("register {} (\"{}\")", session_id, title),
+    }
+}
+
 /// Register specific sessions by ID or title
 pub fn register_sessions(
     ids: &[String],
     titles: Option<&[String]>,
     project_path: Option<&str>,
     force: bool,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
 ) -> Result<()> {
     let path = resolve_path(project_path);
 
     // Find the workspace
     let path_str = path.to_string_lossy().to_string();
-    let (ws_id, ws_path, _folder) = find_workspace_by_path(&path_str)?
+    let (ws_id, ws_path, _folder) = find_workspace_by_path(&path_str, discovery)?
         .ok_or_else(|| CsmError::WorkspaceNotFound(path.display().to_string()))?;
 
     let chat_sessions_dir = ws_path.join("chatSessions");
 
     // Check if VS Code is running
-    if !force && is_vscode_running() {
+    if !force && !dry_run && is_vscode_running() {
         println!(
             "{} VS Code is running. Use {} to register anyway.",
             "[!]".yellow(),
@@ -150,6 +182,7 @@ pub fn register_sessions(
     let db_path = get_workspace_storage_db(&ws_id)?;
 
     let mut registered_count = 0;
+    let mut actions = Vec::new();
 
     if let Some(titles) = titles {
         // Register by title
@@ -170,6 +203,10 @@ pub fn register_sessions(
             });
             let title = session.title();
 
+            if dry_run {
+                actions.push(register_plan(&db_path, &session_id, &title));
+                continue;
+            }
             add_session_to_index(
                 &db_path,
                 &session_id,
@@ -213,6 +250,10 @@ pub fn register_sessions(
                         .clone()
                         .unwrap_or_else(|| session_id.to_string());
 
+                    if dry_run {
+                        actions.push(register_plan(&db_path, &actual_session_id, &title));
+                        continue;
+                    }
                     add_session_to_index(
                         &db_path,
                         &actual_session_id,
@@ -248,6 +289,10 @@ pub fn register_sessions(
         }
     }
 
+    if dry_run {
+        print_dry_run(&actions);
+        return Ok(());
+    }
     println!(
         "\n{} Registered {} sessions in VS Code's index",
         "[OK]".green().bold(),
@@ -265,7 +310,7 @@ pub fn register_sessions(
 }
 
 /// List sessions that exist on disk but are not in VS Code's index
-pub fn list_orphaned(project_path: Option<&str>) -> Result<()> {
+pub fn list_orphaned(project_path: Option<&str>, discovery: &DiscoveryOptions) -> Result<()> {
     let path = resolve_path(project_path);
 
     println!(
@@ -276,7 +321,7 @@ pub fn list_orphaned(project_path: Option<&str>) -> Result<()> {
 
     // Find the workspace
     let path_str = path.to_string_lossy().to_string();
-    let (ws_id, ws_path, _folder) = find_workspace_by_path(&path_str)?
+    let (ws_id, ws_path, _folder) = find_workspace_by_path(&path_str, discovery)?
         .ok_or_else(|| CsmError::WorkspaceNotFound(path.display().to_string()))?;
 
     let chat_sessions_dir = ws_path.join("chatSessions");
@@ -0,0 +1,144 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Saved `find session` queries (save, run, saved)
+//!
+//! Searches are stored by name under `saved_searches` in the config file.
+//! `find run` replays one through [`find_sessions_filtered`], with any flags
+//! given on its command line taking precedence over the saved ones.
+
+use anyhow::Result;
+use colored::*;
+use tabled::{settings::Style, Table, Tabled};
+
+use crate::commands::{find_sessions_filtered, suggest_closest, FindSessionOptions};
+use crate::providers::{CsmConfig, SavedSearch};
+use crate::status;
+use crate::workspace::DiscoveryOptions;
+
+/// `find session` options for a saved search
+pub fn saved_search_options(search: &SavedSearch) -> FindSessionOptions {
+    let defaults = FindSessionOptions::default();
+    FindSessionOptions {
+        workspace: search.workspace.clone(),
+        title_only: search.title_only,
+        search_content: search.content,
+        after: search.after.clone(),
+        before: search.before.clone(),
+        limit: search.limit.unwrap_or(defaults.limit),
+        tag: search.tag.clone(),
+        match_all: search.all,
+        case_sensitive: search.case_sensitive,
+        whole_word: search.word,
+        ..defaults
+    }
+}
+
+/// `find save <name>`: store a query, replacing any of the same name
+pub fn find_save(name: &str, search: SavedSearch) -> Result<()> {
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        anyhow::bail!("Invalid search name '{}': use a single word", name);
+    }
+    let mut config = CsmConfig::load()?;
+    let replaced = config
+        .saved_searches
+        .insert(name.to_string(), search.clone())
+        .is_some();
+    config.save()?;
+
+    status!(
+        "{} {} saved search '{}': {}",
+        "[OK]".green(),
+        if replaced { "Updated" } else { "Created" },
+        name.cyan(),
+        describe(&search)
+    );
+    status!("   Run it with: csm find run {}", name);
+    Ok(())
+}
+
+/// `find run <name>`: replay a saved query. Returns the number of matches,
+/// like [`find_sessions_filtered`].
+pub fn find_run(
+    name: &str,
+    overrides: &SavedSearch,
+    json: bool,
+    count: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<usize> {
+    let config = CsmConfig::load()?;
+    let Some(saved) = config.saved_searches.get(name) else {
+        let names: Vec<&str> = config.saved_searches.keys().map(String::as_str).collect();
+        match suggest_closest(name, names.iter().copied()) {
+            Some(closest) => anyhow::bail!(
+                "No saved search named '{}'; did you mean {}?",
+                name,
+                closest
+            ),
+            None => anyhow::bail!("No saved search named '{}' (see `csm find saved`)", name),
+        }
+    };
+
+    let search = saved.with_overrides(overrides);
+    if !json && !count {
+        status!("{} {}: {}", "[=]".blue(), name.bold(), describe(&search));
+    }
+    find_sessions_filtered(
+        &search.patterns,
+        &FindSessionOptions {
+            json,
+            count,
+            ..saved_search_options(&search)
+        },
+        discovery,
+    )
+}
+
+/// `find saved`: list the saved queries
+pub fn find_saved_list() -> Result<()> {
+    let config = CsmConfig::load()?;
+    if config.saved_searches.is_empty() {
+        println!(
+            "{} No saved searches. Create one with: csm find save <name> <patterns> [flags]",
+            "[i]".cyan()
+        );
+        return Ok(());
+    }
+
+    #[derive(Tabled)]
+    struct SavedRow {
+        #[tabled(rename = "Name")]
+        name: String,
+        #[tabled(rename = "Patterns")]
+        patterns: String,
+        #[tabled(rename = "Options")]
+        options: String,
+    }
+
+    let rows: Vec<SavedRow> = config
+        .saved_searches
+        .iter()
+        .map(|(name, search)| SavedRow {
+            name: name.clone(),
+            patterns: search.patterns.join(" "),
+            options: search.flags().join(" "),
+        })
+        .collect();
+    println!("{}", Table::new(&rows).with(Style::ascii_rounded()));
+    status!(
+        "\n{} {} saved search(es)",
+        "[=]".blue(),
+        config.saved_searches.len()
+    );
+    Ok(())
+}
+
+/// A query on one line: its patterns (or "all sessions") and flags
+fn describe(search: &SavedSearch) -> String {
+    let mut parts = if search.patterns.is_empty() {
+        vec!["(all sessions)".to_string()]
+    } else {
+        search.patterns.clone()
+    };
+    parts.extend(search.flags());
+    parts.join(" ")
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! `find session --similar`: rank sessions by how much they talk about the
+//! same things as a reference session
+//!
+//! Each session is reduced to the words of its title and messages, lowercased
+//! and minus short words and common English filler. Words are weighted TF-IDF
+//! style over the sessions scanned, so one that every session uses counts for
+//! little, and two sessions score the cosine of their weight vectors.
+
+use std::collections::HashMap;
+
+use crate::models::ChatSession;
+use crate::providers::session_format::extract_response_text;
+
+/// How often each word occurs in a session
+pub type TermCounts = HashMap<String, usize>;
+
+/// Shorter words are too generic to say what a session is about
+const MIN_TERM_CHARS: usize = 3;
+
+/// Longer "words" are hashes, base64 and minified code
+const MAX_TERM_CHARS: usize = 40;
+
+/// Words too common in any conversation to tell sessions apart (sorted,
+/// for binary search)
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "again", "all", "also", "and", "any", "are", "because", "been", "before",
+    "being", "but", "can", "could", "did", "does", "doing", "don", "each", "for", "from", "get",
+    "got", "had", "has", "have", "here", "how", "into", "its", "just", "let", "like", "make",
+    "may", "might", "more", "most", "much", "must", "need", "not", "now", "off", "one", "only",
+    "other", "our", "out", "over", "please", "same", "should", "some", "such", "sure", "than",
+    "thank", "thanks", "that", "the", "their", "them", "then", "there", "these", "they", "this",
+    "those", "through", "too", "use", "using", "very", "want", "was", "way", "were", "what",
+    "when", "where", "which", "while", "who", "why", "will", "with", "would", "yes", "you", "your",
+];
+
+/// Add the words of `text` to `counts`
+pub fn count_terms(text: &str, counts: &mut TermCounts) {
+    for word in text.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        let len = word.chars().count();
+        if !(MIN_TERM_CHARS..=MAX_TERM_CHARS).contains(&len)
+            || word.chars().all(|c| c.is_ascii_digit())
+        {
+            continue;
+        }
+        let word = word.to_lowercase();
+        if STOP_WORDS.binary_search(&word.as_str()).is_err() {
+            *counts.entry(word).or_insert(0) += 1;
+        }
+    }
+}
+
+/// The words of a session's title, prompts and responses
+pub fn session_terms(session: &ChatSession) -> TermCounts {
+    let mut counts = TermCounts::new();
+    count_terms(&session.title(), &mut counts);
+    for request in &session.requests {
+        if let Some(text) = request.message.as_ref().and_then(|m| m.text.as_deref()) {
+            count_terms(text, &mut counts);
+        }
+        if let Some(text) = request.response.as_ref().and_then(extract_response_text) {
+            count_terms(&text, &mut counts);
+        }
+    }
+    counts
+}
+
+/// A reference session's word weights, for scoring others against it
+pub struct Similarity {
+    /// Inverse document frequency of every word seen
+    idf: HashMap<String, f64>,
+    /// The reference's weight per word
+    reference: HashMap<String, f64>,
+    reference_norm: f64,
+}
+
+impl Similarity {
+    /// Weigh `reference` against the sessions it will be compared with
+    ///
+    /// A word's weight is `1 + ln(count)` times `ln((N + 1) / (df + 1)) + 1`,
+    /// where N counts the reference and `corpus`, and df how many of them use
+    /// the word.
+    pub fn new(reference: &TermCounts, corpus: &[&TermCounts]) -> Self {
+        let mut df: HashMap<&str, usize> = HashMap::new();
+        for counts in std::iter::once(reference).chain(corpus.iter().copied()) {
+            for term in counts.keys() {
+                *df.entry(term.as_str()).or_insert(0) += 1;
+            }
+        }
+        let docs = (corpus.len() + 1) as f64;
+        let idf: HashMap<String, f64> = df
+            .into_iter()
+            .map(|(term, df)| {
+                let idf = ((docs + 1.0) / (df as f64 + 1.0)).ln() + 1.0;
+                (term.to_string(), idf)
+            })
+            .collect();
+
+        let mut similarity = Self {
+            idf,
+            reference: HashMap::new(),
+            reference_norm: 0.0,
+        };
+        similarity.reference = similarity.weights(reference);
+        similarity.reference_norm = norm(&similarity.reference);
+        similarity
+    }
+
+    fn weights(&self, counts: &TermCounts) -> HashMap<String, f64> {
+        counts
+            .iter()
+            .map(|(term, &count)| {
+                let idf = self.idf.get(term).copied().unwrap_or(1.0);
+                (term.clone(), (1.0 + (count as f64).ln()) * idf)
+            })
+            .collect()
+    }
+
+    /// Cosine similarity of `counts` to the reference, from 0 (no word in
+    /// common) to 1
+    pub fn score(&self, counts: &TermCounts) -> f64 {
+        let weights = self.weights(counts);
+        let dot: f64 = weights
+            .iter()
+            .filter_map(|(term, w)| self.reference.get(term).map(|r| r * w))
+            .sum();
+        let denominator = self.reference_norm * norm(&weights);
+        if denominator == 0.0 {
+            0.0
+        } else {
+            dot / denominator
+        }
+    }
+
+    /// [`score`](Self::score) as a whole percentage
+    pub fn percent(&self, counts: &TermCounts) -> usize {
+        (self.score(counts) * 100.0).round() as usize
+    }
+
+    /// The reference's `n` heaviest words, heaviest first
+    pub fn keywords(&self, n: usize) -> Vec<&str> {
+        let mut terms: Vec<(&str, f64)> = self
+            .reference
+            .iter()
+            .map(|(term, &w)| (term.as_str(), w))
+            .collect();
+        terms.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        terms.into_iter().take(n).map(|(term, _)| term).collect()
+    }
+}
+
+fn norm(weights: &HashMap<String, f64>) -> f64 {
+    weights.values().map(|w| w * w).sum::<f64>().sqrt()
+}
@@ -0,0 +1,156 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Where an export's document goes: a file, or stdout for `-` or no path
+//!
+//! Exporters write through [`OutputSink`] instead of choosing between
+//! `println!` and `fs::write` themselves. Output is UTF-8 without a byte
+//! order mark (a leading one, e.g. from a template or pasted text, is
+//! dropped) and is flushed by [`OutputSink::finish`]; a file whose name ends
+//! in `.gz` is gzip-compressed. While the document goes to stdout,
+//! [`export_status`] sends progress and summary lines to stderr so they
+//! don't end up in the pipe.
+
+use anyhow::{Context, Result};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::status;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Whether an `--output`/destination value means stdout
+pub fn is_stdout_target(target: Option<&str>) -> bool {
+    matches!(target, None | Some("-"))
+}
+
+/// Print an exporter's progress or summary line: to stderr while its
+/// document goes to stdout, else as a [`status!`] line
+pub fn export_status(to_stdout: bool, line: impl Display) {
+    if !to_stdout {
+        status!("{}", line);
+    } else if !crate::commands::is_quiet() {
+        eprintln!("{}", line);
+    }
+}
+
+/// `destination` as a directory to export into; `-` is refused, since one
+/// file per session can't go to stdout
+pub fn export_dir(destination: &str) -> Result<&Path> {
+    if destination == "-" {
+        anyhow::bail!(
+            "this export writes a directory of files, so it can't go to `-` (stdout); \
+             give a directory, or use `export workspace|path --combine` for one document"
+        );
+    }
+    Ok(Path::new(destination))
+}
+
+/// A buffered writer to stdout or a file (see the [module docs](self))
+pub struct OutputSink {
+    /// `None` for stdout
+    path: Option<PathBuf>,
+    writer: Box<dyn Write>,
+    /// Whether anything has been written, i.e. whether a BOM would lead
+    started: bool,
+    /// Everything written so far, when it's gzipped by [`OutputSink::finish`]
+    pending: Option<Vec<u8>>,
+}
+
+impl OutputSink {
+    /// Open `target`: stdout for `-` or `None`, else the file, replacing it
+    /// and creating its parent directories, gzipped if its name ends in `.gz`
+    pub fn open(target: Option<&str>) -> Result<Self> {
+        let path = match target {
+            Some(path) if !is_stdout_target(target) => PathBuf::from(path),
+            _ => return Ok(Self::stdout()),
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file =
+            File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let gzip = path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("gz"));
+        let sink = Self {
+            path: Some(path),
+            writer: Box::new(BufWriter::new(file)),
+            started: false,
+            pending: None,
+        };
+        Ok(if gzip { sink.gzip() } else { sink })
+    }
+
+    /// A sink for the locked stdout handle
+    pub fn stdout() -> Self {
+        Self {
+            path: None,
+            writer: Box::new(BufWriter::new(std::io::stdout().lock())),
+            started: false,
+            pending: None,
+        }
+    }
+
+    /// Gzip-compress what's written, in one member written by
+    /// [`OutputSink::finish`]
+    pub fn gzip(mut self) -> Self {
+        self.pending.get_or_insert_with(Vec::new);
+        self
+    }
+
+    pub fn is_stdout(&self) -> bool {
+        self.path.is_none()
+    }
+
+    /// Where the output goes, for messages: the path, or "stdout"
+    pub fn describe(&self) -> String {
+        match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "stdout".to_string(),
+        }
+    }
+
+    /// Write `text` and flush
+    pub fn write_all_and_finish(mut self, text: &str) -> Result<()> {
+        self.write_all(text.as_bytes())?;
+        self.finish()
+    }
+
+    /// Flush everything written, compressing it first for [`OutputSink::gzip`]
+    pub fn finish(mut self) -> Result<()> {
+        let result = match self.pending.take() {
+            Some(data) => self.writer.write_all(&crate::commands::gzip(&data)),
+            None => Ok(()),
+        };
+        result
+            .and_then(|_| self.flush())
+            .with_context(|| format!("Failed to write {}", self.describe()))
+    }
+}
+
+impl Write for OutputSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut skipped = 0;
+        let mut buf = buf;
+        if !self.started && !buf.is_empty() {
+            self.started = true;
+            if let Some(rest) = buf.strip_prefix(UTF8_BOM) {
+                (buf, skipped) = (rest, UTF8_BOM.len());
+            }
+        }
+        let written = match &mut self.pending {
+            Some(pending) => {
+                pending.extend_from_slice(buf);
+                buf.len()
+            }
+            None => self.writer.write(buf)?,
+        };
+        Ok(written + skipped)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Transcript lines around a content match, for `find session --context N`
+//!
+//! Each message that contains a search term contributes the line it matched
+//! on plus up to N lines either side, grep style. Snippets are capped in
+//! count and width so a match inside a pasted log stays readable.
+
+use colored::*;
+
+use crate::commands::TextMatcher;
+use crate::models::ChatSession;
+use crate::providers::session_format::extract_response_text;
+
+/// Most snippets shown for one session
+pub const MAX_SNIPPETS_PER_SESSION: usize = 3;
+
+/// Most characters shown of one transcript line
+pub const MAX_SNIPPET_LINE_CHARS: usize = 160;
+
+/// Lines around one match in one message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentSnippet {
+    /// Zero-based request index within the session
+    pub turn: usize,
+    /// `user` or `assistant`
+    pub role: &'static str,
+    /// Window lines with whether each is the matching one
+    pub lines: Vec<(bool, String)>,
+}
+
+/// Snippets for the first match of any needle (already normalized by
+/// `matcher`) in each message, in transcript order
+pub fn content_snippets(
+    session: &ChatSession,
+    needles: &[String],
+    matcher: TextMatcher,
+    context: usize,
+) -> Vec<ContentSnippet> {
+    let mut snippets = Vec::new();
+    for (turn, request) in session.requests.iter().enumerate() {
+        let user = request.message.as_ref().map(|m| m.get_text());
+        let assistant = request.response.as_ref().and_then(extract_response_text);
+        for (role, text) in [("user", user), ("assistant", assistant)] {
+            let Some(text) = text else { continue };
+            if let Some(lines) = window_around_match(&text, needles, matcher, context) {
+                snippets.push(ContentSnippet { turn, role, lines });
+                if snippets.len() == MAX_SNIPPETS_PER_SESSION {
+                    return snippets;
+                }
+            }
+        }
+    }
+    snippets
+}
+
+/// The first line containing a needle plus `context` lines either side, each
+/// clipped to [`MAX_SNIPPET_LINE_CHARS`]
+fn window_around_match(
+    text: &str,
+    needles: &[String],
+    matcher: TextMatcher,
+    context: usize,
+) -> Option<Vec<(bool, String)>> {
+    let lines: Vec<&str> = text.lines().collect();
+    let (hit, at) = lines.iter().enumerate().find_map(|(i, line)| {
+        let normalized = matcher.normalize(line);
+        needles
+            .iter()
+            .filter_map(|n| matcher.find(&normalized, n))
+            .min()
+            // Offset in chars, since lowercasing can change byte lengths
+            .map(|pos| (i, normalized[..pos].chars().count()))
+    })?;
+
+    let start = hit.saturating_sub(context);
+    let end = (hit + context + 1).min(lines.len());
+    Some(
+        (start..end)
+            .map(|i| {
+                let focus = if i == hit { at } else { 0 };
+                (i == hit, clip_line(lines[i], focus, MAX_SNIPPET_LINE_CHARS))
+            })
+            .collect(),
+    )
+}
+
+/// At most `width` chars of `line`, shifted so that the char at `focus` is
+/// kept; cut ends are marked with `...`
+pub fn clip_line(line: &str, focus: usize, width: usize) -> String {
+    let line = line.trim_end();
+    let total = line.chars().count();
+    if total <= width {
+        return line.to_string();
+    }
+    // Start a third of the way before the focus, never running past the end
+    let start = focus.saturating_sub(width / 3).min(total - width);
+    let clipped: String = line.chars().skip(start).take(width).collect();
+    format!(
+        "{}{}{}",
+        if start > 0 { "..." } else { "" },
+        clipped,
+        if start + width < total { "..." } else { "" }
+    )
+}
+
+/// Print the snippets of one session beneath the results table
+pub fn print_content_snippets(title: &str, snippets: &[ContentSnippet]) {
+    println!("\n{} {}", "[i]".cyan(), title.bold());
+    for (i, snippet) in snippets.iter().enumerate() {
+        if i > 0 {
+            println!("   {}", "--".dimmed());
+        }
+        println!(
+            "   {}",
+            format!("turn {} ({})", snippet.turn + 1, snippet.role).dimmed()
+        );
+        for (is_match, line) in &snippet.lines {
+            if *is_match {
+                println!("   {} {}", ">".yellow().bold(), line);
+            } else {
+                println!("   {} {}", "|".dimmed(), line);
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! `split`: break a long session into consecutive parts
+//!
+//! `--at 10,25` ends the first part after turn 10 and the second after turn
+//! 25, so three parts are written. Every part lands in the session's own
+//! workspace under a fresh ID, titled "<original> (part N)", and the original
+//! is left untouched unless `--in-place` has it keep only the first part.
+
+use anyhow::{Context, Result};
+use colored::*;
+use uuid::Uuid;
+
+use crate::commands::{find_session_workspace, print_dry_run, PlannedAction};
+use crate::models::ChatSession;
+use crate::status;
+use crate::storage::{
+    add_session_to_index, backup_workspace_sessions, is_vscode_running, parse_session_json,
+    workspace_backup_dir, write_session_atomic,
+};
+use crate::workspace::DiscoveryOptions;
+
+/// Cut `session` after each turn in `at` (1-based, in any order)
+///
+/// Every part is a copy of the session with its share of the requests and
+/// dates narrowed to them; IDs and titles are left for the caller. Fails
+/// unless every split point leaves both sides at least one turn, and none
+/// repeats.
+pub fn split_session(session: &ChatSession, at: &[usize]) -> Result<Vec<ChatSession>> {
+    let total = session.requests.len();
+    if total < 2 {
+        anyhow::bail!(
+            "a session needs at least 2 turns to split (it has {})",
+            total
+        );
+    }
+    let mut points = at.to_vec();
+    points.sort_unstable();
+    for (i, &point) in points.iter().enumerate() {
+        if point == 0 || point >= total {
+            anyhow::bail!(
+                "split point {} is out of range (valid: 1-{} for {} turns)",
+                point,
+                total - 1,
+                total
+            );
+        }
+        if i > 0 && points[i - 1] == point {
+            anyhow::bail!("split point {} is given twice", point);
+        }
+    }
+
+    let mut bounds = vec![0];
+    bounds.extend(&points);
+    bounds.push(total);
+    Ok(bounds
+        .windows(2)
+        .map(|range| {
+            let mut part = session.clone();
+            part.requests = session.requests[range[0]..range[1]].to_vec();
+            if let Some((first, last)) = part.timestamp_range() {
+                part.creation_date = first;
+                part.last_message_date = last;
+            }
+            part
+        })
+        .collect())
+}
+
+/// Split a workspace session after the given turns, writing each part as a
+/// new session next to it (or, with `in_place`, keeping the first part in
+/// the original file)
+pub fn split_session_command(
+    session_id: &str,
+    at: &[usize],
+    in_place: bool,
+    force: bool,
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let (_, workspace, path) = find_session_workspace(session_id, discovery)?;
+    let content = std::fs::read_to_string(&path)?;
+    let session = parse_session_json(&content)
+        .with_context(|| format!("Failed to parse session: {}", path.display()))?;
+    let title = session.title();
+
+    let mut parts = split_session(&session, at)?;
+    let part_count = parts.len();
+    for (i, part) in parts.iter_mut().enumerate() {
+        if i == 0 && in_place {
+            continue;
+        }
+        part.session_id = Some(Uuid::new_v4().to_string());
+        part.custom_title = Some(format!("{} (part {})", title, i + 1));
+    }
+
+    let chat_sessions_dir = workspace.workspace_path.join("chatSessions");
+    let files: Vec<_> = parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| match part.session_id.as_deref() {
+            Some(id) if !(i == 0 && in_place) => chat_sessions_dir.join(format!("{}.json", id)),
+            _ => path.clone(),
+        })
+        .collect();
+    let db_path = workspace.workspace_path.join("state.vscdb");
+    let register = force || !is_vscode_running();
+
+    if dry_run {
+        let mut actions = Vec::new();
+        if in_place {
+            actions.push(PlannedAction::Copy {
+                from: chat_sessions_dir.clone(),
+                to: workspace_backup_dir(&workspace.workspace_path),
+            });
+        }
+        for (i, (part, file)) in parts.iter().zip(&files).enumerate() {
+            if i == 0 && in_place {
+                actions.push(PlannedAction::Modify {
+                    path: file.clone(),
+                    change: format!("keep turns 1-{}", part.requests.len()),
+                });
+            } else {
+                actions.push(PlannedAction::Create(file.clone()));
+            }
+        }
+        if register {
+            actions.push(PlannedAction::Modify {
+                path: db_path,
+                change: format!(
+                    "register {} session(s) in the chat session index",
+                    part_count
+                ),
+            });
+        }
+        print_dry_run(&actions);
+        return Ok(());
+    }
+
+    if in_place {
+        if let Some(backup_dir) = backup_workspace_sessions(&workspace.workspace_path)? {
+            status!(
+                "{} Backup: {}",
+                "[B]".blue(),
+                backup_dir.file_name().unwrap().to_string_lossy()
+            );
+        }
+    }
+
+    let mut first_turn = 1;
+    for (part, file) in parts.iter().zip(&files) {
+        write_session_atomic(file, part)?;
+        let last_turn = first_turn + part.requests.len() - 1;
+        println!(
+            "{} Turns {}-{}: \"{}\" -> {}",
+            "[+]".green(),
+            first_turn,
+            last_turn,
+            part.title(),
+            file.display()
+        );
+        first_turn = last_turn + 1;
+    }
+
+    if !db_path.exists() {
+        status!(
+            "{} {} has no state.vscdb; run `csm register` once VS Code has opened it",
+            "[i]".cyan(),
+            workspace.project_path.as_deref().unwrap_or(&workspace.hash)
+        );
+    } else if !register {
+        status!(
+            "{} VS Code is running. Close it and run `csm register` to index the new parts, or use --force",
+            "[!]".yellow()
+        );
+    } else {
+        for (part, file) in parts.iter().zip(&files) {
+            // Named like the file, which is how VS Code finds it
+            let id = file.file_stem().unwrap_or_default().to_string_lossy();
+            add_session_to_index(
+                &db_path,
+                &id,
+                &part.title(),
+                part.last_message_date,
+                part.is_imported,
+                &part.initial_location,
+                false,
+            )?;
+        }
+    }
+
+    status!(
+        "{} Split \"{}\" into {} sessions{}",
+        "[OK]".green(),
+        title,
+        part_count,
+        if in_place {
+            ""
+        } else {
+            " (the original is unchanged)"
+        }
+    );
+    Ok(())
+}
@@ -0,0 +1,217 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! `harvest export --to <db>`: every discovered session in one SQLite file
+//!
+//! Unlike the harvest database, which stores raw session JSON per provider,
+//! this writes a plain relational layout (workspaces, sessions, messages)
+//! meant for ad-hoc SQL. Re-exporting upserts by session ID, so the file can
+//! be refreshed in place.
+
+use anyhow::{Context, Result};
+use colored::*;
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+use crate::commands::load_redactor;
+use crate::models::{DominantModel, Workspace};
+use crate::providers::session_format::extract_response_text;
+use crate::redact::Redactor;
+use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace, DiscoveryOptions};
+
+/// Tables of the exported database. Messages are keyed by (session, turn),
+/// which also serves lookups by session ID.
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS workspaces (
+    hash TEXT PRIMARY KEY,
+    project_path TEXT,
+    storage_root TEXT,
+    last_modified TEXT
+);
+
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    workspace_hash TEXT NOT NULL REFERENCES workspaces(hash) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    file TEXT NOT NULL,
+    created_at INTEGER,
+    last_message_at INTEGER,
+    message_count INTEGER NOT NULL,
+    model TEXT
+);
+
+CREATE TABLE IF NOT EXISTS messages (
+    session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    turn INTEGER NOT NULL,
+    request_id TEXT,
+    timestamp INTEGER,
+    model TEXT,
+    agent TEXT,
+    user_text TEXT,
+    response_text TEXT,
+    PRIMARY KEY (session_id, turn)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_workspace ON sessions(workspace_hash);
+"#;
+
+/// Row counts written by an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SqliteExportStats {
+    pub workspaces: usize,
+    pub sessions: usize,
+    pub messages: usize,
+}
+
+/// Open (creating if needed) an export database with its schema in place
+pub fn open_export_db(path: &Path) -> Result<Connection> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let conn = Connection::open(path)
+        .with_context(|| format!("Failed to open database: {}", path.display()))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+/// Upsert one workspace and all of its sessions
+fn export_workspace(
+    conn: &Connection,
+    ws: &Workspace,
+    redactor: Option<&Redactor>,
+    stats: &mut SqliteExportStats,
+) -> Result<()> {
+    let scrub = |text: String| match redactor {
+        Some(r) => r.redact(&text),
+        None => text,
+    };
+
+    conn.execute(
+        "INSERT INTO workspaces (hash, project_path, storage_root, last_modified)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(hash) DO UPDATE SET
+             project_path = excluded.project_path,
+             storage_root = excluded.storage_root,
+             last_modified = excluded.last_modified",
+        params![
+            ws.hash,
+            ws.project_path,
+            ws.storage_root,
+            ws.last_modified.map(|t| t.to_rfc3339()),
+        ],
+    )?;
+    stats.workspaces += 1;
+
+    for s in get_chat_sessions_from_workspace(&ws.workspace_path)? {
+        let id = s.get_session_id();
+        let file = s
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let model = match s.session.dominant_model() {
+            DominantModel::Unknown => None,
+            model => Some(model.to_string()),
+        };
+        let (created, last) = s.session.timestamp_range().unzip();
+
+        conn.execute(
+            "INSERT INTO sessions (id, workspace_hash, title, file, created_at,
+                                   last_message_at, message_count, model)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(id) DO UPDATE SET
+                 workspace_hash = excluded.workspace_hash,
+                 title = excluded.title,
+                 file = excluded.file,
+                 created_at = excluded.created_at,
+                 last_message_at = excluded.last_message_at,
+                 message_count = excluded.message_count,
+                 model = excluded.model",
+            params![
+                id,
+                ws.hash,
+                scrub(s.session.title()),
+                file,
+                created,
+                last,
+                s.session.request_count() as i64,
+                model,
+            ],
+        )?;
+
+        // Turns can be edited or removed, so replace them wholesale
+        conn.execute("DELETE FROM messages WHERE session_id = ?1", [&id])?;
+        for (turn, request) in s.session.requests.iter().enumerate() {
+            let user_text = request
+                .message
+                .as_ref()
+                .map(|m| m.get_text())
+                .filter(|t| !t.is_empty());
+            let response_text = request.response.as_ref().and_then(extract_response_text);
+            conn.execute(
+                "INSERT INTO messages (session_id, turn, request_id, timestamp, model,
+                                       agent, user_text, response_text)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    id,
+                    turn as i64,
+                    request.request_id,
+                    request.timestamp,
+                    request.model(),
+                    request.agent_id(),
+                    user_text.map(scrub),
+                    response_text.map(scrub),
+                ],
+            )?;
+            stats.messages += 1;
+        }
+        stats.sessions += 1;
+    }
+
+    Ok(())
+}
+
+/// Write every discovered workspace and session into the database at `path`
+pub fn export_sessions_to_sqlite(
+    path: &Path,
+    redact: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<SqliteExportStats> {
+    let redactor = load_redactor(redact)?;
+    let mut conn = open_export_db(path)?;
+    let mut stats = SqliteExportStats::default();
+
+    let tx = conn.transaction()?;
+    for ws in discover_workspaces(discovery)? {
+        if !ws.has_chat_sessions {
+            continue;
+        }
+        export_workspace(&tx, &ws, redactor.as_ref(), &mut stats)?;
+    }
+    tx.commit()?;
+
+    Ok(stats)
+}
+
+/// `harvest export --to <db>`
+pub fn harvest_export_sqlite(
+    output: &str,
+    redact: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    println!("\n{} Exporting Sessions to SQLite", "[H]".magenta().bold());
+    println!("{}", "=".repeat(60));
+
+    let path = Path::new(output);
+    let stats = export_sessions_to_sqlite(path, redact, discovery)?;
+
+    println!(
+        "{} Wrote {} workspace(s), {} session(s), {} message(s) to {}",
+        "[OK]".green(),
+        stats.workspaces,
+        stats.sessions,
+        stats.messages,
+        path.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,400 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Per-session activity statistics, and the `timeline` calendar across all
+//! sessions
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use colored::*;
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use crate::commands::suggest_closest;
+use crate::models::ChatSession;
+use crate::workspace::{
+    discover_workspaces, get_chat_sessions_from_workspace, load_chat_sessions_from_workspace,
+    DiscoveryOptions,
+};
+
+/// Widest bar of the `--by-day` chart, in terminal cells
+const BAR_WIDTH: usize = 40;
+
+/// Partial blocks for the fractional end of a bar, in eighths of a cell
+const PARTIAL_BLOCKS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// Cells of the `timeline` calendar, from no messages up to the busiest day
+pub const CALENDAR_SHADES: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+/// Width of the weekday labels left of the calendar
+const WEEKDAY_LABEL_WIDTH: usize = 4;
+
+/// When a session's messages were sent, from their per-request timestamps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionActivity {
+    pub first: DateTime<Utc>,
+    pub last: DateTime<Utc>,
+    /// Longest stretch between two consecutive messages, if there are two
+    pub longest_gap: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// Messages per UTC calendar day, for days with any
+    pub by_day: BTreeMap<NaiveDate, usize>,
+    /// Messages without a (valid) timestamp, left out of the above
+    pub untimed: usize,
+}
+
+/// Bucket a session's messages by day. `None` when no message has a timestamp.
+pub fn session_activity(session: &ChatSession) -> Option<SessionActivity> {
+    let mut times: Vec<DateTime<Utc>> = session
+        .requests
+        .iter()
+        .filter_map(|r| r.timestamp)
+        .filter_map(DateTime::from_timestamp_millis)
+        .collect();
+    times.sort();
+
+    let (&first, &last) = (times.first()?, times.last()?);
+    let longest_gap = times
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .max_by_key(|(from, to)| *to - *from);
+
+    let mut by_day = BTreeMap::new();
+    for time in &times {
+        *by_day.entry(time.date_naive()).or_insert(0) += 1;
+    }
+
+    Some(SessionActivity {
+        first,
+        last,
+        longest_gap,
+        by_day,
+        untimed: session.requests.len() - times.len(),
+    })
+}
+
+/// Bar of `count` out of `max`, in block characters up to [`BAR_WIDTH`] cells
+pub fn activity_bar(count: usize, max: usize) -> String {
+    if count == 0 || max == 0 {
+        return String::new();
+    }
+    // Any activity gets at least a sliver
+    let eighths = (count * BAR_WIDTH * 8 / max).max(1);
+    format!("{}{}", "█".repeat(eighths / 8), PARTIAL_BLOCKS[eighths % 8])
+}
+
+/// Short human duration, e.g. "2d 3h", "4h 10m", "25m" or "40s"
+fn format_gap(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds();
+    let (days, hours, mins) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else if mins > 0 {
+        format!("{}m", mins)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M").to_string()
+}
+
+/// Find the first session whose ID or file name contains `session_id`
+fn find_session(session_id: &str, discovery: &DiscoveryOptions) -> Result<ChatSession> {
+    let needle = session_id.to_lowercase();
+    let mut candidates = Vec::new();
+
+    for ws in discover_workspaces(discovery)? {
+        if !ws.has_chat_sessions {
+            continue;
+        }
+        for s in get_chat_sessions_from_workspace(&ws.workspace_path)? {
+            let filename = s
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let matches = s
+                .session
+                .session_id
+                .as_ref()
+                .is_some_and(|id| id.to_lowercase().contains(&needle))
+                || filename.to_lowercase().contains(&needle);
+            if matches {
+                return Ok(s.session);
+            }
+            candidates.extend(s.session.session_id.clone());
+            candidates.push(filename);
+        }
+    }
+
+    match suggest_closest(session_id, candidates.iter().map(String::as_str)) {
+        Some(id) => anyhow::bail!("No exact match; did you mean {}?", id),
+        None => anyhow::bail!("No session found matching '{}'", session_id),
+    }
+}
+
+/// Print when a session was active, and with `by_day` a messages/day chart
+pub fn session_stats(session_id: &str, by_day: bool, discovery: &DiscoveryOptions) -> Result<()> {
+    let session = find_session(session_id, discovery)?;
+    println!(
+        "{} {} ({} messages)",
+        "[=]".blue(),
+        session.title().bold(),
+        session.request_count()
+    );
+
+    let Some(activity) = session_activity(&session) else {
+        println!("{} No timestamps available", "[i]".cyan());
+        return Ok(());
+    };
+
+    println!("First activity: {} UTC", format_time(activity.first));
+    println!("Last activity:  {} UTC", format_time(activity.last));
+    match activity.longest_gap {
+        Some((from, to)) => println!(
+            "Longest gap:    {} ({} -> {})",
+            format_gap(to - from),
+            format_time(from),
+            format_time(to)
+        ),
+        None => println!("Longest gap:    -"),
+    }
+    if activity.untimed > 0 {
+        println!(
+            "{} {} message(s) without a timestamp not counted",
+            "[i]".cyan(),
+            activity.untimed
+        );
+    }
+
+    if by_day {
+        let max = activity.by_day.values().copied().max().unwrap_or(0);
+        println!();
+        for (day, count) in &activity.by_day {
+            println!(
+                "{} {:>4} {}",
+                day.format("%Y-%m-%d"),
+                count,
+                activity_bar(*count, max).green()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Messages per day of one year, across sessions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YearActivity {
+    pub year: i32,
+    /// Messages per UTC calendar day, for days with any
+    pub by_day: BTreeMap<NaiveDate, usize>,
+    /// Messages without a timestamp, counted on their file's modification day
+    pub approximate_messages: usize,
+    /// Sessions that had any such messages
+    pub approximate_sessions: usize,
+}
+
+impl YearActivity {
+    pub fn new(year: i32) -> Self {
+        Self {
+            year,
+            ..Default::default()
+        }
+    }
+
+    /// Count a session's messages that fall in the year. Messages without a
+    /// timestamp are put on `modified`, the day the session file last changed.
+    pub fn add_session(&mut self, session: &ChatSession, modified: Option<NaiveDate>) {
+        let mut untimed = 0;
+        for request in &session.requests {
+            match request.timestamp.and_then(DateTime::from_timestamp_millis) {
+                Some(time) => self.add(time.date_naive(), 1),
+                None => untimed += 1,
+            }
+        }
+        if let Some(day) = modified.filter(|d| untimed > 0 && d.year() == self.year) {
+            self.add(day, untimed);
+            self.approximate_messages += untimed;
+            self.approximate_sessions += 1;
+        }
+    }
+
+    fn add(&mut self, day: NaiveDate, count: usize) {
+        if day.year() == self.year {
+            *self.by_day.entry(day).or_insert(0) += count;
+        }
+    }
+
+    /// Fold in the counts of another tally of the same year
+    pub fn merge(mut self, other: YearActivity) -> Self {
+        for (day, count) in other.by_day {
+            self.add(day, count);
+        }
+        self.approximate_messages += other.approximate_messages;
+        self.approximate_sessions += other.approximate_sessions;
+        self
+    }
+
+    pub fn total(&self) -> usize {
+        self.by_day.values().sum()
+    }
+
+    /// The day with the most messages (the earliest, on a tie)
+    pub fn busiest_day(&self) -> Option<(NaiveDate, usize)> {
+        self.by_day
+            .iter()
+            .map(|(day, count)| (*day, *count))
+            .max_by(|(a_day, a), (b_day, b)| a.cmp(b).then(b_day.cmp(a_day)))
+    }
+}
+
+/// Index into [`CALENDAR_SHADES`] for `count` messages when the busiest day
+/// had `max`: 0 for none, otherwise 1 to 4 by quarter of `max`
+pub fn calendar_shade(count: usize, max: usize) -> usize {
+    if count == 0 || max == 0 {
+        return 0;
+    }
+    (count * 4).div_ceil(max).clamp(1, 4)
+}
+
+/// The year as a grid of week columns (Sunday first) by weekday rows, under
+/// a row of month labels. Days outside the year are left blank.
+pub fn render_calendar(activity: &YearActivity) -> Vec<String> {
+    let year = activity.year;
+    let (Some(jan1), Some(dec31)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Vec::new();
+    };
+    let start = jan1 - chrono::Duration::days(jan1.weekday().num_days_from_sunday() as i64);
+    let weeks = ((dec31 - start).num_days() / 7 + 1) as usize;
+    let max = activity.by_day.values().copied().max().unwrap_or(0);
+
+    let mut months = vec![' '; WEEKDAY_LABEL_WIDTH + weeks];
+    let mut free_from = 0;
+    for month in 1..=12 {
+        let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            continue;
+        };
+        let at = WEEKDAY_LABEL_WIDTH + ((first - start).num_days() / 7) as usize;
+        // Skip a label that would run into the previous one
+        if at < free_from {
+            continue;
+        }
+        for (i, c) in first.format("%b").to_string().chars().enumerate() {
+            if let Some(cell) = months.get_mut(at + i) {
+                *cell = c;
+            }
+        }
+        free_from = at + 4;
+    }
+
+    let mut lines = vec![months
+        .into_iter()
+        .collect::<String>()
+        .trim_end()
+        .to_string()];
+    for weekday in 0..7 {
+        let label = match weekday {
+            1 => "Mon",
+            3 => "Wed",
+            5 => "Fri",
+            _ => "",
+        };
+        let mut line = format!("{:<width$}", label, width = WEEKDAY_LABEL_WIDTH);
+        for week in 0..weeks {
+            let day = start + chrono::Duration::days((week * 7 + weekday) as i64);
+            if day.year() != year {
+                line.push(' ');
+                continue;
+            }
+            let count = activity.by_day.get(&day).copied().unwrap_or(0);
+            let shade = calendar_shade(count, max);
+            let cell = CALENDAR_SHADES[shade];
+            if shade == 0 {
+                line.push_str(&cell.dimmed().to_string());
+            } else {
+                line.push_str(&cell.green().to_string());
+            }
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+/// Tally every discovered session's messages in `year`, one workspace per
+/// thread
+pub fn year_activity(year: i32, discovery: &DiscoveryOptions) -> Result<YearActivity> {
+    use rayon::prelude::*;
+
+    let workspaces: Vec<_> = discover_workspaces(discovery)?
+        .into_iter()
+        .filter(|ws| ws.has_chat_sessions)
+        .collect();
+    Ok(workspaces
+        .par_iter()
+        .map(|ws| {
+            let mut activity = YearActivity::new(year);
+            let (sessions, _) = load_chat_sessions_from_workspace(&ws.workspace_path);
+            for s in sessions {
+                let modified = s
+                    .path
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .map(|t: SystemTime| DateTime::<Utc>::from(t).date_naive());
+                activity.add_session(&s.session, modified);
+            }
+            activity
+        })
+        .reduce(|| YearActivity::new(year), YearActivity::merge))
+}
+
+/// Print a GitHub-style calendar of messages per day in `year` (default:
+/// the current year)
+pub fn activity_timeline(year: Option<i32>, discovery: &DiscoveryOptions) -> Result<()> {
+    let year = year.unwrap_or_else(|| Utc::now().year());
+    if NaiveDate::from_ymd_opt(year, 1, 1).is_none() {
+        anyhow::bail!("Year out of range: {}", year);
+    }
+    let activity = year_activity(year, discovery)?;
+
+    println!(
+        "{} {} message(s) on {} day(s) in {}",
+        "[=]".blue(),
+        activity.total(),
+        activity.by_day.len(),
+        year
+    );
+    println!();
+    for line in render_calendar(&activity) {
+        println!("{}", line);
+    }
+    println!(
+        "\n{:width$}Less {} More",
+        "",
+        CALENDAR_SHADES.join(" "),
+        width = WEEKDAY_LABEL_WIDTH
+    );
+
+    if let Some((day, count)) = activity.busiest_day() {
+        println!(
+            "\nBusiest day: {} ({} messages)",
+            day.format("%Y-%m-%d"),
+            count
+        );
+    }
+    if activity.approximate_messages > 0 {
+        println!(
+            "{} {} message(s) from {} session(s) had no timestamp and were counted \
+             on the day their session file was last modified (approximate)",
+            "[i]".cyan(),
+            activity.approximate_messages,
+            activity.approximate_sessions
+        );
+    }
+    Ok(())
+}
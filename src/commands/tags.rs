@@ -0,0 +1,242 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Session tag commands (add, rm, list, gc)
+
+use anyhow::Result;
+use colored::*;
+use std::collections::BTreeSet;
+
+use crate::commands::{print_dry_run, PlannedAction};
+use crate::storage::read_empty_window_sessions;
+use crate::tags::{get_tag_index_path, normalize_tag, TagIndex};
+use crate::workspace::{discover_workspaces, list_session_files, DiscoveryOptions};
+
+/// Collect the IDs of every session currently on disk
+pub(crate) fn known_session_ids(discovery: &DiscoveryOptions) -> Result<BTreeSet<String>> {
+    let mut ids = BTreeSet::new();
+
+    for ws in discover_workspaces(discovery)? {
+        if !ws.has_chat_sessions {
+            continue;
+        }
+        for path in list_session_files(&ws.chat_sessions_path) {
+            if let Some(stem) = path.file_stem() {
+                ids.insert(stem.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    if let Ok(empty_sessions) = read_empty_window_sessions() {
+        ids.extend(empty_sessions.into_iter().filter_map(|s| s.session_id));
+    }
+
+    Ok(ids)
+}
+
+/// Resolve a full or prefix session ID against the sessions on disk
+pub(crate) fn resolve_session_id(session_id: &str, discovery: &DiscoveryOptions) -> Result<String> {
+    let known = known_session_ids(discovery)?;
+    if known.contains(session_id) {
+        return Ok(session_id.to_string());
+    }
+
+    let matches: Vec<&String> = known
+        .iter()
+        .filter(|id| id.starts_with(session_id))
+        .collect();
+    match matches.as_slice() {
+        [only] => Ok((*only).clone()),
+        [] => anyhow::bail!("Session not found: {}", session_id),
+        _ => anyhow::bail!(
+            "Session ID '{}' is ambiguous ({} matches); use a longer prefix",
+            session_id,
+            matches.len()
+        ),
+    }
+}
+
+/// Dry-run plan for a tag index change, e.g. "add 'x' to ID"; nothing when
+/// no tag changed
+fn tag_index_plan(verb: &str, tags: &[String], preposition: &str, id: &str) -> Vec<PlannedAction> {
+    if tags.is_empty() {
+        return Vec::new();
+    }
+    let quoted: Vec<String> = tags.iter().map(|t| format!("'{}'", t)).collect();
+    vec![PlannedAction::Modify {
+        path: get_tag_index_path(),
+        change: format!("{} {} {} {}", verb, quoted.join(", "), preposition, id),
+    }]
+}
+
+/// Add tags to a session
+pub fn tag_add(
+    session_id: &str,
+    tags: &[String],
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let session_id = resolve_session_id(session_id, discovery)?;
+    let mut index = TagIndex::load()?;
+    let mut added = Vec::new();
+
+    for tag in tags {
+        if normalize_tag(tag).is_empty() {
+            println!("{} Ignoring empty tag", "[!]".yellow());
+        } else if index.add(&session_id, tag) {
+            added.push(normalize_tag(tag));
+            if !dry_run {
+                println!(
+                    "{} Tagged {} with '{}'",
+                    "[OK]".green(),
+                    session_id.cyan(),
+                    normalize_tag(tag)
+                );
+            }
+        } else {
+            println!(
+                "{} {} already has tag '{}'",
+                "[=]".blue(),
+                session_id.cyan(),
+                normalize_tag(tag)
+            );
+        }
+    }
+
+    if dry_run {
+        print_dry_run(&tag_index_plan("add", &added, "to", &session_id));
+        return Ok(());
+    }
+    index.save()?;
+    Ok(())
+}
+
+/// Remove tags from a session
+pub fn tag_remove(
+    session_id: &str,
+    tags: &[String],
+    dry_run: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let mut index = TagIndex::load()?;
+
+    // Allow removing tags from sessions that no longer exist on disk
+    let session_id = if index.sessions.contains_key(session_id) {
+        session_id.to_string()
+    } else {
+        resolve_session_id(session_id, discovery)?
+    };
+
+    let mut removed = Vec::new();
+    for tag in tags {
+        if index.remove(&session_id, tag) {
+            removed.push(normalize_tag(tag));
+            if !dry_run {
+                println!(
+                    "{} Removed '{}' from {}",
+                    "[OK]".green(),
+                    normalize_tag(tag),
+                    session_id.cyan()
+                );
+            }
+        } else {
+            println!(
+                "{} {} does not have tag '{}'",
+                "[!]".yellow(),
+                session_id.cyan(),
+                normalize_tag(tag)
+            );
+        }
+    }
+
+    if dry_run {
+        print_dry_run(&tag_index_plan("remove", &removed, "from", &session_id));
+        return Ok(());
+    }
+    index.save()?;
+    Ok(())
+}
+
+/// List tags for one session, or all tags with their session counts
+pub fn tag_list(session_id: Option<&str>, discovery: &DiscoveryOptions) -> Result<()> {
+    let index = TagIndex::load()?;
+
+    if let Some(session_id) = session_id {
+        let session_id = if index.sessions.contains_key(session_id) {
+            session_id.to_string()
+        } else {
+            resolve_session_id(session_id, discovery)?
+        };
+        let tags = index.tags_for(&session_id);
+        if tags.is_empty() {
+            println!("{} {} has no tags", "[i]".cyan(), session_id.cyan());
+        } else {
+            println!("{}: {}", session_id.cyan(), tags.join(", "));
+        }
+        return Ok(());
+    }
+
+    let counts = index.tag_counts();
+    if counts.is_empty() {
+        println!("{} No tags defined", "[i]".cyan());
+        return Ok(());
+    }
+
+    for (tag, count) in &counts {
+        println!("   {} {} ({} session(s))", "[*]".blue(), tag.bold(), count);
+    }
+    println!(
+        "\n{} {} tag(s) across {} session(s)",
+        "[=]".blue(),
+        counts.len().to_string().yellow(),
+        index.sessions.len().to_string().yellow()
+    );
+
+    Ok(())
+}
+
+/// List (and unless `dry_run`, remove) tags whose sessions no longer exist
+///
+/// Sessions hidden by `--channel`, `--exclude` or `.csmignore` still exist,
+/// so the known set comes from an unfiltered scan.
+pub fn tag_gc(dry_run: bool, discovery: &DiscoveryOptions) -> Result<()> {
+    let mut index = TagIndex::load()?;
+    let known = known_session_ids(&discovery.unfiltered())?;
+    let orphaned = index.orphaned(&known);
+
+    if orphaned.is_empty() {
+        println!("{} No orphaned tags found", "[OK]".green());
+        return Ok(());
+    }
+
+    println!(
+        "{} Found {} session(s) with orphaned tags:",
+        "[!]".yellow(),
+        orphaned.len()
+    );
+    for id in &orphaned {
+        println!(
+            "   {} {}: {}",
+            "[-]".dimmed(),
+            id,
+            index.tags_for(id).join(", ")
+        );
+    }
+
+    if dry_run {
+        print_dry_run(&[PlannedAction::Modify {
+            path: get_tag_index_path(),
+            change: format!("remove tags of {} orphaned session(s)", orphaned.len()),
+        }]);
+        return Ok(());
+    }
+
+    let removed = index.remove_sessions(&orphaned);
+    index.save()?;
+    println!(
+        "\n{} Removed tags for {} orphaned session(s)",
+        "[OK]".green(),
+        removed
+    );
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! How `find session` compares search terms with IDs, titles and content
+//!
+//! By default a term matches anywhere, ignoring case. `--case-sensitive`
+//! compares text as written, and `--word` only accepts matches that don't
+//! sit inside a longer word, so `IO` no longer finds "audio".
+
+use std::borrow::Cow;
+
+/// Case and word-boundary rules for matching search terms
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextMatcher {
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+}
+
+impl TextMatcher {
+    /// `text` as it is compared: lowercased unless case-sensitive. Apply to
+    /// both the terms and the text searched.
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.case_sensitive {
+            Cow::Borrowed(text)
+        } else {
+            Cow::Owned(text.to_lowercase())
+        }
+    }
+
+    /// Byte offset of the first match of `needle` in `haystack`, both
+    /// already [normalized](Self::normalize)
+    pub fn find(&self, haystack: &str, needle: &str) -> Option<usize> {
+        if !self.whole_word {
+            return haystack.find(needle);
+        }
+        haystack
+            .match_indices(needle)
+            .map(|(at, _)| at)
+            .find(|&at| is_whole_word(haystack, at, needle.len()))
+    }
+
+    /// Matches of `needle` in `haystack` (both normalized), and how many of
+    /// them are whole words. Matches don't overlap.
+    pub fn count(&self, haystack: &str, needle: &str) -> (usize, usize) {
+        let mut total = 0;
+        let mut words = 0;
+        for (at, _) in haystack.match_indices(needle) {
+            let whole = is_whole_word(haystack, at, needle.len());
+            if whole || !self.whole_word {
+                total += 1;
+                words += usize::from(whole);
+            }
+        }
+        (total, words)
+    }
+
+    /// Whether `needle` matches in `haystack`, both already normalized
+    pub fn matches(&self, haystack: &str, needle: &str) -> bool {
+        self.find(haystack, needle).is_some()
+    }
+}
+
+/// Whether the `len` bytes at `at` aren't part of a longer word
+fn is_whole_word(haystack: &str, at: usize, len: usize) -> bool {
+    let before = haystack[..at].chars().next_back();
+    let after = haystack[at + len..].chars().next();
+    !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+}
+
+/// Characters that continue a word, as in regex `\w`
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
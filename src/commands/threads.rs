@@ -0,0 +1,138 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! `--flatten-threads`: how turns that were edited or regenerated render
+//!
+//! A turn with [`ChatRequest::branches`] has several versions: the earlier
+//! ones in `branches`, oldest first, and the request's own message and
+//! response as the newest. `latest` renders only the version the session
+//! has selected (the newest unless `selectedBranch` says otherwise); `all`
+//! renders every version as a turn of its own, its prompt marked
+//! `[branch i/n]`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::models::{ChatMessage, ChatRequest, ChatSession};
+
+/// Which versions of a branched turn to render
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlattenThreads {
+    /// Only the selected version
+    #[default]
+    Latest,
+    /// Every version, with markers
+    All,
+}
+
+impl FlattenThreads {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["latest", "all"];
+}
+
+impl FromStr for FlattenThreads {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "latest" | "selected" => Ok(FlattenThreads::Latest),
+            "all" => Ok(FlattenThreads::All),
+            _ => Err(format!(
+                "unknown thread mode '{}' (valid modes: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for FlattenThreads {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FlattenThreads::Latest => "latest",
+            FlattenThreads::All => "all",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The versions of `request` to render under `mode`, each without branches;
+/// a turn that was never branched is returned as it is
+pub fn turn_versions(request: &ChatRequest, mode: FlattenThreads) -> Vec<ChatRequest> {
+    let newest = request.branches.len();
+    if newest == 0 {
+        return vec![request.clone()];
+    }
+    let selected = request.selected_branch.unwrap_or(newest).min(newest);
+    let total = newest + 1;
+
+    // A branch without a prompt was a regeneration of the version before it
+    let mut prompt = request.message.clone();
+    let mut versions = Vec::with_capacity(total);
+    for (i, branch) in request.branches.iter().enumerate() {
+        let mut version = ChatRequest {
+            branches: Vec::new(),
+            selected_branch: None,
+            ..request.clone()
+        };
+        if branch.message.is_some() {
+            prompt = branch.message.clone();
+        }
+        version.message = prompt.clone();
+        version.response = branch.response.clone();
+        version.timestamp = branch.timestamp.or(request.timestamp);
+        version.model_id = branch.model_id.clone().or(version.model_id);
+        versions.push((i, version));
+    }
+    versions.push((
+        newest,
+        ChatRequest {
+            branches: Vec::new(),
+            selected_branch: None,
+            ..request.clone()
+        },
+    ));
+
+    match mode {
+        FlattenThreads::Latest => versions
+            .into_iter()
+            .filter(|(i, _)| *i == selected)
+            .map(|(_, version)| version)
+            .collect(),
+        FlattenThreads::All => versions
+            .into_iter()
+            .map(|(i, mut version)| {
+                let marker = if i == selected {
+                    format!("[branch {}/{}, selected]", i + 1, total)
+                } else {
+                    format!("[branch {}/{}]", i + 1, total)
+                };
+                mark_prompt(&mut version, &marker);
+                version
+            })
+            .collect(),
+    }
+}
+
+/// Replace every branched turn of `session` with its [`turn_versions`]
+pub fn flatten_threads(session: &mut ChatSession, mode: FlattenThreads) {
+    if session.requests.iter().all(|r| r.branches.is_empty()) {
+        return;
+    }
+    session.requests = session
+        .requests
+        .iter()
+        .flat_map(|request| turn_versions(request, mode))
+        .collect();
+}
+
+/// Put `marker` in front of a request's prompt text
+fn mark_prompt(request: &mut ChatRequest, marker: &str) {
+    let message = request.message.get_or_insert(ChatMessage {
+        text: None,
+        parts: None,
+    });
+    message.text = Some(match message.text.as_deref() {
+        Some(text) if !text.is_empty() => format!("{} {}", marker, text),
+        _ => marker.to_string(),
+    });
+}
@@ -2,24 +2,48 @@
 // SPDX-License-Identifier: Apache-2.0
 //! Workspace listing commands
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
-use tabled::{settings::Style, Table, Tabled};
-
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tabled::{
+    settings::{object::Columns, Remove, Style},
+    Table, Tabled,
+};
+
+use crate::commands::{
+    check_columns, content_snippets, format_bytes, print_content_snippets, print_ndjson,
+    select_columns, session_last_activity, session_terms, to_csv, to_json, turn_versions,
+    ContentSnippet, CsvColumn, FlattenThreads, OutputFormat, Similarity, TermCounts, TextMatcher,
+};
 use crate::models::Workspace;
+use crate::status;
 use crate::storage::read_empty_window_sessions;
-use crate::workspace::discover_workspaces;
+use crate::tags::TagIndex;
+use crate::workspace::{
+    discover_workspaces, scan_workspaces, DiscoveryError, DiscoveryOptions, SkippedSessionFile,
+    VsCodeChannel,
+};
 
 #[derive(Tabled)]
 struct WorkspaceRow {
+    #[tabled(rename = "Root")]
+    root: String,
     #[tabled(rename = "Hash")]
     hash: String,
+    #[tabled(rename = "Channel")]
+    channel: String,
+    #[tabled(rename = "Host")]
+    host: String,
     #[tabled(rename = "Project Path")]
     project_path: String,
     #[tabled(rename = "Sessions")]
     sessions: String,
     #[tabled(rename = "Has Chats")]
     has_chats: String,
+    #[tabled(rename = "Size")]
+    size: String,
 }
 
 #[derive(Tabled)]
@@ -32,24 +56,322 @@ struct SessionRow {
     last_modified: String,
     #[tabled(rename = "Messages")]
     messages: usize,
+    #[tabled(rename = "Model")]
+    model: String,
+    #[tabled(rename = "Tags")]
+    tags: String,
+    /// Start of the first prompt, only with `--with-preview` (or the csv
+    /// `preview` column)
+    #[tabled(rename = "Preview")]
+    preview: String,
+    #[tabled(skip)]
+    session_id: String,
+    #[tabled(skip)]
+    title: String,
 }
 
-/// List all VS Code workspaces
-pub fn list_workspaces() -> Result<()> {
-    let workspaces = discover_workspaces()?;
+/// Position of the Preview column in the `list sessions` table
+const SESSION_PREVIEW_COLUMN: usize = 6;
 
-    if workspaces.is_empty() {
-        println!("{} No workspaces found.", "[!]".yellow());
-        return Ok(());
+/// Characters of the first prompt `list sessions --with-preview` shows
+const PREVIEW_CHARS: usize = 60;
+
+/// The first prompt of `session` on one line, cut to [`PREVIEW_CHARS`]
+fn first_prompt_preview(session: &crate::models::ChatSession) -> String {
+    let text = session
+        .requests
+        .iter()
+        .filter_map(|r| r.message.as_ref()?.text.as_deref())
+        .find(|text| !text.trim().is_empty())
+        .unwrap_or_default();
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate_string(&line, PREVIEW_CHARS)
+}
+
+/// `list sessions --format csv` fields; `session_id` and `title` aren't in
+/// the table, and `preview` only with `--with-preview`
+const SESSION_CSV_COLUMNS: &[CsvColumn<SessionRow>] = &[
+    CsvColumn {
+        key: "workspace",
+        header: "Project Path",
+        value: |r| r.project_path.clone(),
+    },
+    CsvColumn {
+        key: "file",
+        header: "Session File",
+        value: |r| r.session_file.clone(),
+    },
+    CsvColumn {
+        key: "session_id",
+        header: "Session ID",
+        value: |r| r.session_id.clone(),
+    },
+    CsvColumn {
+        key: "title",
+        header: "Title",
+        value: |r| r.title.clone(),
+    },
+    CsvColumn {
+        key: "modified",
+        header: "Last Modified",
+        value: |r| r.last_modified.clone(),
+    },
+    CsvColumn {
+        key: "messages",
+        header: "Messages",
+        value: |r| r.messages.to_string(),
+    },
+    CsvColumn {
+        key: "model",
+        header: "Model",
+        value: |r| r.model.clone(),
+    },
+    CsvColumn {
+        key: "tags",
+        header: "Tags",
+        value: |r| r.tags.clone(),
+    },
+    CsvColumn {
+        key: "preview",
+        header: "Preview",
+        value: |r| r.preview.clone(),
+    },
+];
+
+/// Columns `list sessions --format csv` writes without `--columns`: the table's
+const SESSION_CSV_DEFAULTS: &[&str] =
+    &["workspace", "file", "modified", "messages", "model", "tags"];
+
+/// A workspace and its chat sessions' disk usage, for `--format csv`
+struct WorkspaceCsvRow<'a> {
+    workspace: &'a Workspace,
+    size: Option<u64>,
+}
+
+/// `list workspaces --format csv` fields; hashes are written in full
+fn workspace_csv_columns<'a>() -> [CsvColumn<WorkspaceCsvRow<'a>>; 8] {
+    [
+        CsvColumn {
+            key: "root",
+            header: "Root",
+            value: |r| {
+                r.workspace
+                    .storage_root
+                    .clone()
+                    .unwrap_or_else(|| ".".to_string())
+            },
+        },
+        CsvColumn {
+            key: "hash",
+            header: "Hash",
+            value: |r| r.workspace.hash.clone(),
+        },
+        CsvColumn {
+            key: "channel",
+            header: "Channel",
+            value: |r| r.workspace.channel.to_string(),
+        },
+        CsvColumn {
+            key: "host",
+            header: "Host",
+            value: |r| workspace_host(r.workspace).to_string(),
+        },
+        CsvColumn {
+            key: "path",
+            header: "Project Path",
+            value: |r| r.workspace.project_path.clone().unwrap_or_default(),
+        },
+        CsvColumn {
+            key: "sessions",
+            header: "Sessions",
+            value: |r| r.workspace.chat_session_count.to_string(),
+        },
+        CsvColumn {
+            key: "has_chats",
+            header: "Has Chats",
+            value: |r| {
+                if r.workspace.has_chat_sessions {
+                    "Yes"
+                } else {
+                    "No"
+                }
+                .to_string()
+            },
+        },
+        // Bytes rather than "1.5 MB", so spreadsheets can sum them
+        CsvColumn {
+            key: "size",
+            header: "Size (bytes)",
+            value: |r| r.size.map(|b| b.to_string()).unwrap_or_default(),
+        },
+    ]
+}
+
+/// `find session --format csv` fields; titles are written in full
+const FIND_CSV_COLUMNS: &[CsvColumn<SessionSearchResult>] = &[
+    CsvColumn {
+        key: "session_id",
+        header: "Session ID",
+        value: |r| r.session_id.clone(),
+    },
+    CsvColumn {
+        key: "title",
+        header: "Title",
+        value: |r| r.title.clone(),
+    },
+    CsvColumn {
+        key: "workspace",
+        header: "Workspace",
+        value: |r| r.workspace.clone(),
+    },
+    CsvColumn {
+        key: "modified",
+        header: "Modified",
+        value: |r| r.modified.clone(),
+    },
+    CsvColumn {
+        key: "messages",
+        header: "Msgs",
+        value: |r| r.messages.to_string(),
+    },
+    CsvColumn {
+        key: "match",
+        header: "Match",
+        value: |r| r.match_type.clone(),
+    },
+    CsvColumn {
+        key: "score",
+        header: "Score",
+        value: |r| r.score.map(|s| s.to_string()).unwrap_or_default(),
+    },
+    CsvColumn {
+        key: "path",
+        header: "Path",
+        value: |r| r.path.display().to_string(),
+    },
+];
+
+/// The `find session --format csv` columns for `options`: `--columns`, else
+/// those of the table
+fn find_csv_columns(
+    options: &FindSessionOptions,
+) -> Result<Vec<&'static CsvColumn<SessionSearchResult>>> {
+    let defaults: &[&str] = if options.sort == FindSort::Relevance {
+        &[
+            "title",
+            "workspace",
+            "modified",
+            "messages",
+            "match",
+            "score",
+        ]
+    } else {
+        &["title", "workspace", "modified", "messages", "match"]
+    };
+    select_columns(FIND_CSV_COLUMNS, defaults, &options.columns)
+}
+
+/// Format a session's tags for a table cell
+fn format_tags(index: &TagIndex, session_id: &str) -> String {
+    index.tags_for(session_id).join(", ")
+}
+
+/// Session ID of a workspace session file (its file stem)
+fn session_file_id(path: &std::path::Path) -> String {
+    path.file_stem()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Sort order for `list workspaces`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorkspaceSort {
+    /// Project path, A-Z
+    #[default]
+    Path,
+    /// Session count, most first
+    Sessions,
+    /// Last session modification, newest first
+    Modified,
+}
+
+impl WorkspaceSort {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["path", "sessions", "modified"];
+
+    /// Sort workspaces in this order (reversed if `reverse` is set)
+    pub fn sort(&self, workspaces: &mut [Workspace], reverse: bool) {
+        match self {
+            WorkspaceSort::Path => workspaces.sort_by(|a, b| {
+                let key = |w: &Workspace| w.project_path.clone().unwrap_or_default().to_lowercase();
+                key(a).cmp(&key(b))
+            }),
+            WorkspaceSort::Sessions => workspaces.sort_by(|a, b| {
+                b.chat_session_count
+                    .cmp(&a.chat_session_count)
+                    .then_with(|| a.project_path.cmp(&b.project_path))
+            }),
+            WorkspaceSort::Modified => {
+                workspaces.sort_by_key(|w| std::cmp::Reverse(w.last_modified))
+            }
+        }
+        if reverse {
+            workspaces.reverse();
+        }
+    }
+}
+
+impl std::str::FromStr for WorkspaceSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "path" => Ok(WorkspaceSort::Path),
+            "sessions" => Ok(WorkspaceSort::Sessions),
+            "modified" => Ok(WorkspaceSort::Modified),
+            _ => Err(format!(
+                "unknown sort key '{}' (valid keys: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
     }
+}
+
+impl std::fmt::Display for WorkspaceSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            WorkspaceSort::Path => "path",
+            WorkspaceSort::Sessions => "sessions",
+            WorkspaceSort::Modified => "modified",
+        };
+        write!(f, "{}", name)
+    }
+}
 
+/// Render workspaces as a table
+///
+/// The root column is only shown when a nested `--depth` scan found workspaces
+/// outside the top-level storage directory, the channel column when any
+/// workspace is from VS Code Insiders, the host column when any is from a
+/// remote server, and the size column when `sizes` (one per workspace, see
+/// [`workspace_sizes`]) is given.
+fn workspace_table<'a>(
+    workspaces: impl IntoIterator<Item = &'a Workspace>,
+    sizes: Option<&[u64]>,
+) -> String {
+    let workspaces: Vec<&Workspace> = workspaces.into_iter().collect();
     let rows: Vec<WorkspaceRow> = workspaces
         .iter()
-        .map(|ws| WorkspaceRow {
+        .enumerate()
+        .map(|(i, ws)| WorkspaceRow {
+            root: ws.storage_root.clone().unwrap_or_else(|| ".".to_string()),
             hash: format!(
                 "{}",
                 format!("{}...", &ws.hash[..12.min(ws.hash.len())]).cyan()
             ),
+            channel: ws.channel.to_string(),
+            host: workspace_host(ws).to_string(),
             project_path: ws
                 .project_path
                 .clone()
@@ -60,21 +382,209 @@ pub fn list_workspaces() -> Result<()> {
             } else {
                 format!("{}", "No".red())
             },
+            size: sizes
+                .and_then(|sizes| sizes.get(i))
+                .map(|&bytes| format_bytes(bytes))
+                .unwrap_or_default(),
         })
         .collect();
 
-    let table = Table::new(rows).with(Style::ascii_rounded()).to_string();
-    println!("{}", table);
-    println!(
-        "\n{} Total workspaces: {}",
+    let mut table = Table::new(rows);
+    table.with(Style::ascii_rounded());
+    if workspaces.iter().all(|ws| !ws.remote) {
+        table.with(Remove::column(Columns::one(3)));
+    }
+    if workspaces
+        .iter()
+        .all(|ws| ws.channel == VsCodeChannel::Stable)
+    {
+        table.with(Remove::column(Columns::one(2)));
+    }
+    if sizes.is_none() {
+        table.with(Remove::column(Columns::last()));
+    }
+    if workspaces.iter().all(|ws| ws.storage_root.is_none()) {
+        table.with(Remove::column(Columns::first()));
+    }
+    table.to_string()
+}
+
+/// "remote" for workspaces of a VS Code Server layout, else "local"
+fn workspace_host(ws: &Workspace) -> &'static str {
+    if ws.remote {
+        "remote"
+    } else {
+        "local"
+    }
+}
+
+/// Footer suffix for workspaces dropped by `--exclude` / `exclude_globs`
+fn excluded_suffix(excluded: usize) -> String {
+    if excluded > 0 {
+        format!(" ({} excluded)", excluded)
+    } else {
+        String::new()
+    }
+}
+
+/// Report workspace directories discovery couldn't fully read: each one and
+/// why under `verbose`, otherwise just how many
+fn report_discovery_errors(errors: &[DiscoveryError], verbose: bool) {
+    if errors.is_empty() {
+        return;
+    }
+    if verbose {
+        for error in errors {
+            eprintln!("{} {}", "[!]".yellow(), error);
+        }
+    } else {
+        eprintln!(
+            "{} {} workspace dir(s) could not be fully read (run with --verbose for details)",
+            "[!]".yellow(),
+            errors.len()
+        );
+    }
+}
+
+/// Report session files that were skipped as unreadable: each one and why
+/// under `verbose`, otherwise just how many
+fn report_skipped_sessions(skipped: &[SkippedSessionFile], verbose: bool) {
+    if skipped.is_empty() {
+        return;
+    }
+    if verbose {
+        for file in skipped {
+            eprintln!("{} Skipped {}", "[!]".yellow(), file);
+        }
+    } else {
+        eprintln!(
+            "{} {} session file(s) could not be read (run with --verbose for details)",
+            "[!]".yellow(),
+            skipped.len()
+        );
+    }
+}
+
+/// Under `verbose`, say how many workspaces and sessions `.csmignore` hid
+fn report_ignored(workspaces: usize, sessions: usize, verbose: bool) {
+    if verbose && workspaces + sessions > 0 {
+        eprintln!(
+            "{} {} hid {} workspace(s) and {} session(s)",
+            "[i]".cyan(),
+            crate::workspace::IGNORE_FILE_NAME,
+            workspaces,
+            sessions
+        );
+    }
+}
+
+/// Disk usage of each workspace's chat sessions, measured in parallel
+fn workspace_sizes(workspaces: &[Workspace]) -> Vec<u64> {
+    use rayon::prelude::*;
+
+    workspaces
+        .par_iter()
+        .map(|ws| crate::workspace::chat_sessions_size(&ws.chat_sessions_path))
+        .collect()
+}
+
+/// `list workspaces --format csv`: by default the columns the table would
+/// show, with the size in bytes when `size` is set
+fn print_workspaces_csv(workspaces: &[Workspace], size: bool, columns: &[String]) -> Result<()> {
+    let mut defaults = vec![
+        "root",
+        "hash",
+        "channel",
+        "host",
+        "path",
+        "sessions",
+        "has_chats",
+        "size",
+    ];
+    if !size {
+        defaults.retain(|&c| c != "size");
+    }
+    if workspaces.iter().all(|ws| ws.storage_root.is_none()) {
+        defaults.retain(|&c| c != "root");
+    }
+    if workspaces
+        .iter()
+        .all(|ws| ws.channel == VsCodeChannel::Stable)
+    {
+        defaults.retain(|&c| c != "channel");
+    }
+    if workspaces.iter().all(|ws| !ws.remote) {
+        defaults.retain(|&c| c != "host");
+    }
+    let available = workspace_csv_columns();
+    let columns = select_columns(&available, &defaults, columns)?;
+
+    let sizes =
+        (size || columns.iter().any(|c| c.key == "size")).then(|| workspace_sizes(workspaces));
+    let rows: Vec<WorkspaceCsvRow> = workspaces
+        .iter()
+        .enumerate()
+        .map(|(i, workspace)| WorkspaceCsvRow {
+            workspace,
+            size: sizes.as_ref().and_then(|s| s.get(i).copied()),
+        })
+        .collect();
+    print!("{}", to_csv(&rows, &columns)?);
+    Ok(())
+}
+
+/// List all VS Code workspaces, with a disk usage column if `size` is set
+/// and each unreadable workspace directory reported if `verbose` is set.
+/// `columns` picks the fields of `--format csv`.
+pub fn list_workspaces(
+    sort: WorkspaceSort,
+    reverse: bool,
+    size: bool,
+    verbose: bool,
+    format: OutputFormat,
+    columns: &[String],
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    check_columns(format, columns)?;
+    let scan = scan_workspaces(discovery)?;
+    report_discovery_errors(&scan.errors, verbose);
+    report_ignored(scan.ignored, 0, verbose);
+    let (mut workspaces, excluded) = (scan.workspaces, scan.excluded);
+
+    sort.sort(&mut workspaces, reverse);
+    if format == OutputFormat::Csv {
+        return print_workspaces_csv(&workspaces, size, columns);
+    }
+
+    if workspaces.is_empty() {
+        status!(
+            "{} No workspaces found.{}",
+            "[!]".yellow(),
+            excluded_suffix(excluded)
+        );
+        return Ok(());
+    }
+
+    let sizes = size.then(|| workspace_sizes(&workspaces));
+    println!("{}", workspace_table(&workspaces, sizes.as_deref()));
+    status!(
+        "\n{} Total workspaces: {}{}",
         "[=]".blue(),
-        workspaces.len().to_string().yellow()
+        workspaces.len().to_string().yellow(),
+        excluded_suffix(excluded)
     );
+    if let Some(sizes) = &sizes {
+        status!(
+            "{} Total size: {}",
+            "[=]".blue(),
+            format_bytes(sizes.iter().sum()).yellow()
+        );
+    }
 
     // Show empty window sessions count (ALL SESSIONS)
     if let Ok(empty_count) = crate::storage::count_empty_window_sessions() {
         if empty_count > 0 {
-            println!(
+            status!(
                 "{} Empty window sessions (ALL SESSIONS): {}",
                 "[i]".cyan(),
                 empty_count.to_string().yellow()
@@ -85,9 +595,39 @@ pub fn list_workspaces() -> Result<()> {
     Ok(())
 }
 
-/// List all chat sessions
-pub fn list_sessions(project_path: Option<&str>) -> Result<()> {
-    let workspaces = discover_workspaces()?;
+/// Label of empty-window sessions (VS Code's "ALL SESSIONS"), which belong
+/// to no workspace
+const EMPTY_WINDOW_LABEL: &str = "(ALL SESSIONS)";
+
+/// List all chat sessions. Empty-window sessions are only listed with
+/// `include_empty` and no project filter, as in `find session`. Files that
+/// can't be parsed are skipped and reported, each one under `verbose`.
+/// `columns` picks the fields of `--format csv`; `with_preview` adds the
+/// start of each session's first prompt to the table (and the csv defaults).
+pub fn list_sessions(
+    project_path: Option<&str>,
+    include_empty: bool,
+    verbose: bool,
+    format: OutputFormat,
+    columns: &[String],
+    with_preview: bool,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    check_columns(format, columns)?;
+    let mut csv_defaults = SESSION_CSV_DEFAULTS.to_vec();
+    if with_preview {
+        csv_defaults.push("preview");
+    }
+    let csv_columns = select_columns(SESSION_CSV_COLUMNS, &csv_defaults, columns)?;
+    // Sessions are parsed for the other columns anyway, so this is cheap
+    let preview = |session: &crate::models::ChatSession| {
+        if with_preview || csv_columns.iter().any(|c| c.key == "preview") {
+            first_prompt_preview(session)
+        } else {
+            String::new()
+        }
+    };
+    let workspaces = discover_workspaces(discovery)?;
 
     let filtered_workspaces: Vec<&Workspace> = if let Some(path) = project_path {
         let normalized = crate::workspace::normalize_path(path);
@@ -105,9 +645,15 @@ pub fn list_sessions(project_path: Option<&str>) -> Result<()> {
     };
 
     let mut rows: Vec<SessionRow> = Vec::new();
-
-    // Add empty window sessions (ALL SESSIONS) if no specific project filter
-    if project_path.is_none() {
+    let mut skipped = Vec::new();
+    // A broken tag index shouldn't prevent listing sessions
+    let tag_index = TagIndex::load().unwrap_or_default();
+
+    // Add empty window sessions (ALL SESSIONS) if asked and no specific project filter
+    let mut empty_hidden = 0;
+    if project_path.is_none() && !include_empty {
+        empty_hidden = crate::storage::count_empty_window_sessions().unwrap_or(0);
+    } else if project_path.is_none() {
         if let Ok(empty_sessions) = read_empty_window_sessions() {
             for session in empty_sessions {
                 let modified = chrono::DateTime::from_timestamp_millis(session.last_message_date)
@@ -115,11 +661,19 @@ pub fn list_sessions(project_path: Option<&str>) -> Result<()> {
                     .unwrap_or_else(|| "unknown".to_string());
 
                 let session_id = session.session_id.as_deref().unwrap_or("unknown");
+                if discovery.is_session_ignored(session_id) {
+                    continue;
+                }
                 rows.push(SessionRow {
-                    project_path: "(ALL SESSIONS)".to_string(),
+                    project_path: EMPTY_WINDOW_LABEL.to_string(),
                     session_file: format!("{}.json", session_id),
                     last_modified: modified,
                     messages: session.request_count(),
+                    model: session.dominant_model().to_string(),
+                    tags: format_tags(&tag_index, session_id),
+                    preview: preview(&session),
+                    session_id: session_id.to_string(),
+                    title: session.title(),
                 });
             }
         }
@@ -130,9 +684,14 @@ pub fn list_sessions(project_path: Option<&str>) -> Result<()> {
             continue;
         }
 
-        let sessions = crate::workspace::get_chat_sessions_from_workspace(&ws.workspace_path)?;
+        let (sessions, unreadable) =
+            crate::workspace::load_chat_sessions_from_workspace(&ws.workspace_path);
+        skipped.extend(unreadable);
 
         for session_with_path in sessions {
+            if discovery.is_session_ignored(&session_file_id(&session_with_path.path)) {
+                continue;
+            }
             let modified = session_with_path
                 .path
                 .metadata()
@@ -156,89 +715,132 @@ pub fn list_sessions(project_path: Option<&str>) -> Result<()> {
                     .unwrap_or_else(|| "unknown".to_string()),
                 last_modified: modified,
                 messages: session_with_path.session.request_count(),
+                model: session_with_path.session.dominant_model().to_string(),
+                tags: format_tags(&tag_index, &session_file_id(&session_with_path.path)),
+                preview: preview(&session_with_path.session),
+                session_id: session_file_id(&session_with_path.path),
+                title: session_with_path.session.title(),
             });
         }
     }
+    report_skipped_sessions(&skipped, verbose);
+
+    let empty_hint = || {
+        if empty_hidden > 0 {
+            status!(
+                "{} {} empty-window session(s) not shown (use --include-empty)",
+                "[i]".cyan(),
+                empty_hidden
+            );
+        }
+    };
 
+    if format == OutputFormat::Csv {
+        print!("{}", to_csv(&rows, &csv_columns)?);
+        return Ok(());
+    }
     if rows.is_empty() {
-        println!("{} No chat sessions found.", "[!]".yellow());
+        status!("{} No chat sessions found.", "[!]".yellow());
+        empty_hint();
         return Ok(());
     }
 
-    let table = Table::new(&rows).with(Style::ascii_rounded()).to_string();
+    let mut table = Table::new(&rows);
+    if !with_preview {
+        table.with(Remove::column(Columns::one(SESSION_PREVIEW_COLUMN)));
+    }
+    let table = table.with(Style::ascii_rounded()).to_string();
 
     println!("{}", table.dimmed());
-    println!(
+    status!(
         "\n{} Total sessions: {}",
         "[=]".blue(),
         rows.len().to_string().yellow()
     );
+    empty_hint();
 
     Ok(())
 }
 
-/// Find workspaces by search pattern
-pub fn find_workspaces(pattern: &str) -> Result<()> {
-    let workspaces = discover_workspaces()?;
+/// Workspace pattern for `find workspace` and `find session --workspace`
+///
+/// Matches a case-insensitive substring of the project path or hash. `.`
+/// stands for the current directory: it matches that directory's name, or
+/// its full path.
+struct WorkspacePattern {
+    /// Pattern with `.` resolved, as shown in messages
+    display: String,
+    needle: String,
+    /// Normalized current directory, when the pattern was `.`
+    cwd: Option<String>,
+}
 
-    // Resolve "." to current directory name
-    let pattern = if pattern == "." {
-        std::env::current_dir()
-            .ok()
+impl WorkspacePattern {
+    fn new(pattern: &str) -> Self {
+        let cwd = (pattern == ".")
+            .then(std::env::current_dir)
+            .and_then(|cwd| cwd.ok());
+        let display = cwd
+            .as_ref()
             .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
-            .unwrap_or_else(|| pattern.to_string())
-    } else {
-        pattern.to_string()
-    };
-    let pattern_lower = pattern.to_lowercase();
+            .unwrap_or_else(|| pattern.to_string());
+        Self {
+            needle: display.to_lowercase(),
+            display,
+            cwd: cwd.map(|p| normalize_for_match(&p.to_string_lossy())),
+        }
+    }
+
+    fn matches(&self, hash: &str, project_path: Option<&str>) -> bool {
+        if let (Some(cwd), Some(path)) = (&self.cwd, project_path) {
+            if normalize_for_match(path) == *cwd {
+                return true;
+            }
+        }
+        project_path.is_some_and(|p| p.to_lowercase().contains(&self.needle))
+            || hash.to_lowercase().contains(&self.needle)
+    }
+}
+
+/// `normalize_path` without the leading separator, which decoded workspace
+/// folders lack on Unix
+fn normalize_for_match(path: &str) -> String {
+    crate::workspace::normalize_path(path)
+        .trim_start_matches(['/', '\\'])
+        .to_string()
+}
+
+/// Find workspaces by search pattern
+pub fn find_workspaces(pattern: &str, verbose: bool, discovery: &DiscoveryOptions) -> Result<()> {
+    let scan = scan_workspaces(discovery)?;
+    report_discovery_errors(&scan.errors, verbose);
+    report_ignored(scan.ignored, 0, verbose);
+    let (workspaces, excluded) = (scan.workspaces, scan.excluded);
+
+    let filter = WorkspacePattern::new(pattern);
+    let pattern = &filter.display;
 
     let matching: Vec<&Workspace> = workspaces
         .iter()
-        .filter(|ws| {
-            ws.project_path
-                .as_ref()
-                .map(|p| p.to_lowercase().contains(&pattern_lower))
-                .unwrap_or(false)
-                || ws.hash.to_lowercase().contains(&pattern_lower)
-        })
+        .filter(|ws| filter.matches(&ws.hash, ws.project_path.as_deref()))
         .collect();
 
     if matching.is_empty() {
-        println!(
-            "{} No workspaces found matching '{}'",
+        status!(
+            "{} No workspaces found matching '{}'{}",
             "[!]".yellow(),
-            pattern.cyan()
+            pattern.cyan(),
+            excluded_suffix(excluded)
         );
         return Ok(());
     }
 
-    let rows: Vec<WorkspaceRow> = matching
-        .iter()
-        .map(|ws| WorkspaceRow {
-            hash: format!(
-                "{}",
-                format!("{}...", &ws.hash[..12.min(ws.hash.len())]).cyan()
-            ),
-            project_path: ws
-                .project_path
-                .clone()
-                .unwrap_or_else(|| "(none)".to_string()),
-            sessions: format!("{}", ws.chat_session_count.to_string().green()),
-            has_chats: if ws.has_chat_sessions {
-                format!("{}", "Yes".green())
-            } else {
-                format!("{}", "No".red())
-            },
-        })
-        .collect();
-
-    let table = Table::new(rows).with(Style::ascii_rounded()).to_string();
-
-    println!("{}", table);
-    println!(
-        "\n{} Found {} matching workspace(s)",
+    println!("{}", workspace_table(matching.iter().copied(), None));
+    status!(
+        "\n{} Found {} matching workspace(s){}",
         "[=]".blue(),
-        matching.len().to_string().yellow()
+        matching.len().to_string().yellow(),
+        excluded_suffix(excluded)
     );
 
     // Show session paths for each matching workspace
@@ -262,8 +864,12 @@ pub fn find_workspaces(pattern: &str) -> Result<()> {
 
 /// Find sessions by search pattern
 #[allow(dead_code)]
-pub fn find_sessions(pattern: &str, project_path: Option<&str>) -> Result<()> {
-    let workspaces = discover_workspaces()?;
+pub fn find_sessions(
+    pattern: &str,
+    project_path: Option<&str>,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
+    let workspaces = discover_workspaces(discovery)?;
     let pattern_lower = pattern.to_lowercase();
 
     let filtered_workspaces: Vec<&Workspace> = if let Some(path) = project_path {
@@ -282,6 +888,7 @@ pub fn find_sessions(pattern: &str, project_path: Option<&str>) -> Result<()> {
     };
 
     let mut rows: Vec<SessionRow> = Vec::new();
+    let tag_index = TagIndex::load().unwrap_or_default();
 
     for ws in filtered_workspaces {
         if !ws.has_chat_sessions {
@@ -342,19 +949,27 @@ pub fn find_sessions(pattern: &str, project_path: Option<&str>) -> Result<()> {
                     .unwrap_or_else(|| "unknown".to_string()),
                 last_modified: modified,
                 messages: session_with_path.session.request_count(),
+                model: session_with_path.session.dominant_model().to_string(),
+                tags: format_tags(&tag_index, &session_file_id(&session_with_path.path)),
+                preview: String::new(),
+                session_id: session_file_id(&session_with_path.path),
+                title: session_with_path.session.title(),
             });
         }
     }
 
     if rows.is_empty() {
-        println!("No sessions found matching '{}'", pattern);
+        status!("No sessions found matching '{}'", pattern);
         return Ok(());
     }
 
-    let table = Table::new(&rows).with(Style::ascii_rounded()).to_string();
+    let table = Table::new(&rows)
+        .with(Remove::column(Columns::one(SESSION_PREVIEW_COLUMN)))
+        .with(Style::ascii_rounded())
+        .to_string();
 
     println!("{}", table);
-    println!(
+    status!(
         "\n{} Found {} matching session(s)",
         "[=]".blue(),
         rows.len().to_string().yellow()
@@ -363,214 +978,773 @@ pub fn find_sessions(pattern: &str, project_path: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-/// Optimized session search with filtering
-///
-/// This function is optimized for speed by:
-/// 1. Filtering workspaces first (by name/path)
-/// 2. Filtering by file modification date before reading content
-/// 3. Only parsing JSON when needed
-/// 4. Content search is opt-in (expensive)
-/// 5. Parallel file scanning with rayon
-pub fn find_sessions_filtered(
-    pattern: &str,
-    workspace_filter: Option<&str>,
-    title_only: bool,
-    search_content: bool,
-    after: Option<&str>,
-    before: Option<&str>,
-    limit: usize,
-) -> Result<()> {
-    use chrono::{NaiveDate, Utc};
-    use rayon::prelude::*;
-    use std::sync::atomic::{AtomicUsize, Ordering};
+/// How `find session --group-by` splits the results table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindGroupBy {
+    /// One table per workspace
+    Workspace,
+}
 
-    let pattern_lower = pattern.to_lowercase();
+impl FindGroupBy {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["workspace"];
+}
+
+impl std::str::FromStr for FindGroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "workspace" | "ws" => Ok(FindGroupBy::Workspace),
+            _ => Err(format!(
+                "unknown grouping '{}' (valid groupings: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
+    }
+}
 
-    // Parse date filters upfront
-    let after_date = after.and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
-    let before_date = before.and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
+/// Order of `find session` results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FindSort {
+    /// Last modification, newest first
+    #[default]
+    Modified,
+    /// Best match first (see [`relevance_score`])
+    Relevance,
+}
 
-    // Get workspace storage path directly - avoid full discovery if filtering
-    let storage_path = crate::workspace::get_workspace_storage_path()?;
-    if !storage_path.exists() {
-        println!("No workspaces found");
-        return Ok(());
+impl FindSort {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["modified", "relevance"];
+}
+
+impl std::str::FromStr for FindSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "modified" => Ok(FindSort::Modified),
+            "relevance" => Ok(FindSort::Relevance),
+            _ => Err(format!(
+                "unknown sort key '{}' (valid keys: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
     }
+}
 
-    // Collect workspace directories with minimal I/O
-    let ws_filter_lower = workspace_filter.map(|s| s.to_lowercase());
-
-    let workspace_dirs: Vec<_> = std::fs::read_dir(&storage_path)?
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_dir())
-        .filter_map(|entry| {
-            let workspace_dir = entry.path();
-            let workspace_json_path = workspace_dir.join("workspace.json");
-
-            // Quick check: does chatSessions exist?
-            let chat_sessions_dir = workspace_dir.join("chatSessions");
-            if !chat_sessions_dir.exists() {
-                return None;
-            }
+impl std::fmt::Display for FindSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FindSort::Modified => "modified",
+            FindSort::Relevance => "relevance",
+        };
+        write!(f, "{}", name)
+    }
+}
 
-            // Parse workspace.json for project path (needed for filtering)
-            let project_path =
-                std::fs::read_to_string(&workspace_json_path)
-                    .ok()
-                    .and_then(|content| {
-                        serde_json::from_str::<crate::models::WorkspaceJson>(&content)
-                            .ok()
-                            .and_then(|ws| {
-                                ws.folder
-                                    .map(|f| crate::workspace::decode_workspace_folder(&f))
-                            })
-                    });
-
-            // Apply workspace filter early
-            if let Some(ref filter) = ws_filter_lower {
-                let hash = entry.file_name().to_string_lossy().to_lowercase();
-                let path_matches = project_path
-                    .as_ref()
-                    .map(|p| p.to_lowercase().contains(filter))
-                    .unwrap_or(false);
-                if !hash.contains(filter) && !path_matches {
-                    return None;
-                }
-            }
+/// Keywords of the reference session `find session --similar` prints
+const SIMILAR_KEYWORDS: usize = 8;
 
-            let ws_name = project_path
-                .as_ref()
-                .and_then(|p| std::path::Path::new(p).file_name())
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| {
-                    entry.file_name().to_string_lossy()[..8.min(entry.file_name().len())]
-                        .to_string()
-                });
+/// How much more an occurrence in a session's ID or title counts towards
+/// its relevance than one in its content
+pub const TITLE_MATCH_WEIGHT: usize = 10;
 
-            Some((chat_sessions_dir, ws_name))
+/// Relevance of a session to the search terms, for `--sort relevance`
+///
+/// Every occurrence of a term scores a point, and another if it is a whole
+/// word; occurrences in the ID or title count [`TITLE_MATCH_WEIGHT`] times.
+/// All texts are already normalized by `matcher`; `content` is `None` when
+/// it wasn't searched.
+pub fn relevance_score(
+    matcher: TextMatcher,
+    needles: &[String],
+    id: &str,
+    title: &str,
+    content: Option<&str>,
+) -> usize {
+    let points = |haystack: &str, needle: &str| {
+        let (total, words) = matcher.count(haystack, needle);
+        total + words
+    };
+    needles
+        .iter()
+        .map(|needle| {
+            (points(id, needle) + points(title, needle)) * TITLE_MATCH_WEIGHT
+                + content.map_or(0, |c| points(c, needle))
         })
-        .collect();
+        .sum()
+}
 
-    if workspace_dirs.is_empty() {
-        if let Some(ws) = workspace_filter {
-            println!("No workspaces found matching '{}'", ws);
-        } else {
-            println!("No workspaces with chat sessions found");
+/// Options for `find session` filtering and output
+#[derive(Debug, Clone)]
+pub struct FindSessionOptions {
+    /// Filter by project path or workspace name
+    pub workspace: Option<String>,
+    /// Only search in session titles
+    pub title_only: bool,
+    /// Include message content in the search
+    pub search_content: bool,
+    /// Only include sessions modified after this date (YYYY-MM-DD)
+    pub after: Option<String>,
+    /// Only include sessions modified before this date (YYYY-MM-DD)
+    pub before: Option<String>,
+    /// Maximum number of results to display
+    pub limit: usize,
+    /// Print only the number of matches
+    pub count: bool,
+    /// Emit results as JSON
+    pub json: bool,
+    /// Emit results as NDJSON, one object per line
+    pub ndjson: bool,
+    /// Only include sessions carrying this tag
+    pub tag: Option<String>,
+    /// Only include sessions with at least this many messages (inclusive)
+    pub min_messages: Option<usize>,
+    /// Only include sessions with at most this many messages (inclusive)
+    pub max_messages: Option<usize>,
+    /// Log each unreadable session file and workspace directory to stderr as
+    /// it is skipped
+    pub verbose: bool,
+    /// Require every search term to match, rather than any of them
+    pub match_all: bool,
+    /// Also search empty-window sessions (only without a workspace filter)
+    pub include_empty: bool,
+    /// Split the results table (table output only)
+    pub group_by: Option<FindGroupBy>,
+    /// Print this many transcript lines around each content match beneath
+    /// the table (table output only)
+    pub context: Option<usize>,
+    /// Skip the content search of session files larger than this (0 for no
+    /// limit); their ID and title are still matched
+    pub max_bytes: u64,
+    /// Match search terms with their case as given
+    pub case_sensitive: bool,
+    /// Only match search terms as whole words
+    pub whole_word: bool,
+    /// Result order
+    pub sort: FindSort,
+    /// Table or CSV
+    pub format: OutputFormat,
+    /// Fields of `--format csv`, by key (empty for the table's)
+    pub columns: Vec<String>,
+    /// Drop matches whose content also contains any of these terms
+    /// (content searches only)
+    pub exclude_content: Vec<String>,
+    /// Only include sessions modified since the last harvest run
+    pub updated_since_harvest: bool,
+    /// Rank sessions by similarity to this one (an ID or prefix) instead of
+    /// matching search terms
+    pub similar: Option<String>,
+}
+
+/// Default `find session --max-bytes`: larger files are usually pasted logs
+/// or dumps, and reading them dominates a content search
+pub const DEFAULT_MAX_SESSION_BYTES: u64 = 10 * 1024 * 1024;
+
+impl Default for FindSessionOptions {
+    fn default() -> Self {
+        Self {
+            workspace: None,
+            title_only: false,
+            search_content: false,
+            after: None,
+            before: None,
+            limit: 50,
+            count: false,
+            json: false,
+            ndjson: false,
+            tag: None,
+            min_messages: None,
+            max_messages: None,
+            verbose: false,
+            match_all: false,
+            include_empty: false,
+            group_by: None,
+            context: None,
+            max_bytes: DEFAULT_MAX_SESSION_BYTES,
+            case_sensitive: false,
+            whole_word: false,
+            sort: FindSort::default(),
+            format: OutputFormat::default(),
+            columns: Vec::new(),
+            exclude_content: Vec::new(),
+            updated_since_harvest: false,
+            similar: None,
         }
-        return Ok(());
     }
+}
 
-    // Collect all session file paths
-    let session_files: Vec<_> = workspace_dirs
-        .iter()
-        .flat_map(|(chat_dir, ws_name)| {
-            std::fs::read_dir(chat_dir)
-                .into_iter()
-                .flatten()
-                .filter_map(|e| e.ok())
-                .filter(|e| {
-                    e.path()
-                        .extension()
-                        .map(|ext| ext == "json")
-                        .unwrap_or(false)
-                })
-                .map(|e| (e.path(), ws_name.clone()))
-                .collect::<Vec<_>>()
-        })
-        .collect();
+/// Session files `find session` had to skip because they couldn't be read or parsed
+struct UnreadableFiles {
+    count: AtomicUsize,
+    samples: Mutex<Vec<PathBuf>>,
+    verbose: bool,
+}
 
-    let total_files = session_files.len();
-    let scanned = AtomicUsize::new(0);
-    let skipped_by_date = AtomicUsize::new(0);
+impl UnreadableFiles {
+    /// Paths kept for the summary when not logging every file
+    const MAX_SAMPLES: usize = 3;
 
-    // Process files in parallel
-    let mut results: Vec<_> = session_files
-        .par_iter()
-        .filter_map(|(path, ws_name)| {
-            // Date filter using file metadata (very fast)
-            if after_date.is_some() || before_date.is_some() {
-                if let Ok(metadata) = path.metadata() {
-                    if let Ok(modified) = metadata.modified() {
-                        let file_date: chrono::DateTime<Utc> = modified.into();
-                        let file_naive = file_date.date_naive();
+    fn new(verbose: bool) -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            samples: Mutex::new(Vec::new()),
+            verbose,
+        }
+    }
 
-                        if let Some(after) = after_date {
-                            if file_naive < after {
-                                skipped_by_date.fetch_add(1, Ordering::Relaxed);
-                                return None;
-                            }
-                        }
-                        if let Some(before) = before_date {
-                            if file_naive > before {
-                                skipped_by_date.fetch_add(1, Ordering::Relaxed);
-                                return None;
-                            }
-                        }
-                    }
-                }
+    fn record(&self, path: &Path, reason: impl std::fmt::Display) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        if self.verbose {
+            eprintln!("{} Skipped {}: {}", "[!]".yellow(), path.display(), reason);
+        } else if let Ok(mut samples) = self.samples.lock() {
+            if samples.len() < Self::MAX_SAMPLES {
+                samples.push(path.to_path_buf());
+            }
+        }
+    }
+
+    /// Print the "N files unreadable" tail summary to stderr, if any were skipped
+    fn report(&self) {
+        let count = self.count.load(Ordering::Relaxed);
+        if count == 0 {
+            return;
+        }
+        eprintln!("{} {} file(s) unreadable", "[!]".yellow(), count);
+        if self.verbose {
+            return;
+        }
+        if let Ok(samples) = self.samples.lock() {
+            for path in samples.iter() {
+                eprintln!("   {} {}", "[-]".dimmed(), path.display());
+            }
+        }
+        eprintln!("   Run with --verbose to see every file and the reason");
+    }
+}
+
+/// A single `find session` match
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SessionSearchResult {
+    pub session_id: String,
+    pub title: String,
+    pub workspace: String,
+    pub modified: String,
+    /// Exact modification time behind `modified`, for sorting
+    #[serde(skip)]
+    pub modified_at: Option<std::time::SystemTime>,
+    pub messages: usize,
+    pub match_type: String,
+    /// Relevance to the search terms with `--sort relevance`, or with
+    /// `--similar`, similarity in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<usize>,
+    pub path: std::path::PathBuf,
+}
+
+/// Short display name for a project path (`myproj.code-workspace` -> `myproj`)
+fn workspace_display_name(project_path: &str) -> Option<String> {
+    let path = std::path::Path::new(project_path);
+    let is_multi_root = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("code-workspace"));
+    let name = if is_multi_root {
+        path.file_stem()
+    } else {
+        path.file_name()
+    }?;
+    Some(name.to_string_lossy().to_string())
+}
+
+/// Parse a `--after`/`--before` value relative to today (UTC)
+///
+/// Accepts an absolute `YYYY-MM-DD` date or a relative age such as
+/// `7d`, `2w`, `3mo` or `1y`.
+pub fn parse_date_filter(value: &str) -> Result<chrono::NaiveDate> {
+    parse_date_filter_from(value, chrono::Utc::now().date_naive())
+}
+
+/// Parse a `--after`/`--before` value, resolving relative ages against `today`
+pub fn parse_date_filter_from(value: &str, today: chrono::NaiveDate) -> Result<chrono::NaiveDate> {
+    use chrono::{Days, Months, NaiveDate};
+
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid date '{}': expected YYYY-MM-DD or a relative age like 7d, 2w, 3mo, 1y",
+            value
+        )
+    };
+
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(split);
+    let amount: u32 = amount.parse().map_err(|_| invalid())?;
+
+    let date = match unit.to_lowercase().as_str() {
+        "d" => today.checked_sub_days(Days::new(amount as u64)),
+        "w" => today.checked_sub_days(Days::new(amount as u64 * 7)),
+        "mo" => today.checked_sub_months(Months::new(amount)),
+        "y" => amount
+            .checked_mul(12)
+            .and_then(|months| today.checked_sub_months(Months::new(months))),
+        _ => return Err(invalid()),
+    };
+
+    date.ok_or_else(|| anyhow::anyhow!("Date '{}' is out of range", value))
+}
+
+/// Parse an inclusive `(flag, value)` start/end pair of date filters
+///
+/// Errors name the offending flag, and a start later than the end is
+/// rejected since no session could match.
+pub fn parse_date_range(
+    start: (&str, Option<&str>),
+    end: (&str, Option<&str>),
+) -> Result<(Option<chrono::NaiveDate>, Option<chrono::NaiveDate>)> {
+    let parse = |(flag, value): (&str, Option<&str>)| {
+        value
+            .map(|v| parse_date_filter(v).map_err(|e| anyhow::anyhow!("{}: {}", flag, e)))
+            .transpose()
+    };
+    let start_date = parse(start)?;
+    let end_date = parse(end)?;
+
+    if let (Some(s), Some(e)) = (start_date, end_date) {
+        if s > e {
+            anyhow::bail!(
+                "{} ({}) is later than {} ({}); no session can match",
+                start.0,
+                s,
+                end.0,
+                e
+            );
+        }
+    }
+
+    Ok((start_date, end_date))
+}
+
+/// Optimized session search with filtering
+///
+/// This function is optimized for speed by:
+/// 1. Filtering workspaces first (by name/path)
+/// 2. Filtering by file modification date before reading content
+/// 3. Only parsing JSON when needed
+/// 4. Content search is opt-in (expensive)
+/// 5. Parallel file scanning with rayon
+///
+/// Returns the number of matching sessions so the caller can set the exit
+/// status. "Nothing found" messages go to stderr to keep stdout pipeable.
+pub fn find_sessions_filtered(
+    patterns: &[String],
+    options: &FindSessionOptions,
+    discovery: &DiscoveryOptions,
+) -> Result<usize> {
+    use chrono::Utc;
+    use rayon::prelude::*;
+
+    // Normalized search terms; none means every session matches (for listing)
+    let matcher = TextMatcher {
+        case_sensitive: options.case_sensitive,
+        whole_word: options.whole_word,
+    };
+    check_columns(options.format, &options.columns)?;
+    let csv_columns = find_csv_columns(options)?;
+    let mut needles: Vec<String> = patterns
+        .iter()
+        .map(|p| matcher.normalize(p).into_owned())
+        .filter(|p| !p.is_empty())
+        .collect();
+    needles.dedup();
+    let excludes: Vec<String> = options
+        .exclude_content
+        .iter()
+        .map(|p| matcher.normalize(p).into_owned())
+        .filter(|p| !p.is_empty())
+        .collect();
+    let pattern = match &options.similar {
+        Some(id) => format!("similar:{}", id),
+        None => patterns.join(if options.match_all { " AND " } else { " OR " }),
+    };
+    let workspace_filter = options.workspace.as_deref();
+    let title_only = options.title_only;
+    let search_content = options.search_content;
+    let limit = options.limit;
+
+    // Parse date filters upfront; a bad value aborts before any scanning
+    let (after_date, before_date) = parse_date_range(
+        ("--after", options.after.as_deref()),
+        ("--before", options.before.as_deref()),
+    )?;
+    // An exact time, unlike the whole days of --after
+    let harvested_at = if options.updated_since_harvest {
+        Some(crate::commands::last_harvest_time()?)
+    } else {
+        None
+    };
+    if let (Some(min), Some(max)) = (options.min_messages, options.max_messages) {
+        if min > max {
+            anyhow::bail!(
+                "--min-messages ({}) is greater than --max-messages ({}); no session can match",
+                min,
+                max
+            );
+        }
+    }
+
+    // The session --similar ranks against: its file ID, title and words
+    let reference = match &options.similar {
+        Some(id) => {
+            let (session_id, _, path) = crate::commands::find_session_workspace(id, discovery)?;
+            let content = std::fs::read_to_string(&path)?;
+            let session = crate::storage::parse_session_json(&content)
+                .with_context(|| format!("Failed to parse session: {}", path.display()))?;
+            Some((session_id, session.title(), session_terms(&session)))
+        }
+        None => None,
+    };
+
+    // Get workspace storage path directly - avoid full discovery if filtering
+    let storage_paths = crate::workspace::get_workspace_storage_paths(discovery.channel)?;
+    if !storage_paths.iter().any(|p| p.exists()) {
+        return print_find_no_results(options, || eprintln!("No workspaces found"));
+    }
+
+    // Collect workspace directories with minimal I/O
+    let ws_filter = workspace_filter.map(WorkspacePattern::new);
+    let mut excluded_count = 0;
+    let mut ignored_workspaces = 0;
+    let mut discovery_errors = Vec::new();
+
+    let mut workspace_dirs: Vec<_> =
+        crate::workspace::storage_workspace_dirs(discovery, &mut discovery_errors)?
+            .into_iter()
+            .filter_map(|(_, workspace_dir)| {
+                let dir_name = workspace_dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let workspace_json_path = workspace_dir.join("workspace.json");
+
+                // Quick check: does chatSessions exist?
+                let chat_sessions_dir = workspace_dir.join("chatSessions");
+                if !chat_sessions_dir.exists() {
+                    return None;
+                }
+
+                // Parse workspace.json for project path (needed for filtering)
+                let project_path = std::fs::read_to_string(&workspace_json_path)
+                    .ok()
+                    .and_then(|content| {
+                        serde_json::from_str::<crate::models::WorkspaceJson>(&content).ok()
+                    })
+                    .and_then(|ws| ws.project_path());
+                if discovery.is_excluded(project_path.as_deref()) {
+                    excluded_count += 1;
+                    return None;
+                }
+                if discovery.is_ignored(project_path.as_deref()) {
+                    ignored_workspaces += 1;
+                    return None;
+                }
+
+                // Apply workspace filter early
+                if let Some(ref filter) = ws_filter {
+                    if !filter.matches(&dir_name, project_path.as_deref()) {
+                        return None;
+                    }
+                }
+
+                let ws_name = project_path
+                    .as_deref()
+                    .and_then(workspace_display_name)
+                    .unwrap_or_else(|| dir_name.chars().take(8).collect());
+
+                Some((chat_sessions_dir, ws_name))
+            })
+            .collect();
+    report_discovery_errors(&discovery_errors, options.verbose);
+
+    // Empty-window sessions belong to no workspace, so a workspace filter skips them
+    if options.include_empty && ws_filter.is_none() {
+        if let Ok(empty_dir) = crate::workspace::get_empty_window_sessions_path() {
+            if empty_dir.is_dir() {
+                workspace_dirs.push((empty_dir, EMPTY_WINDOW_LABEL.to_string()));
+            }
+        }
+    }
+
+    if workspace_dirs.is_empty() {
+        return print_find_no_results(options, || {
+            if let Some(ws) = &ws_filter {
+                eprintln!("No workspaces found matching '{}'", ws.display);
+            } else {
+                eprintln!("No workspaces with chat sessions found");
+            }
+        });
+    }
+
+    // Collect all session file paths, minus those `.csmignore` hides
+    let mut ignored_sessions = 0;
+    let session_files: Vec<_> = workspace_dirs
+        .iter()
+        .flat_map(|(chat_dir, ws_name)| {
+            crate::workspace::list_session_files(chat_dir)
+                .into_iter()
+                .map(|path| (path, ws_name.clone()))
+                .collect::<Vec<_>>()
+        })
+        .filter(|(path, _)| {
+            let ignored = discovery.is_session_ignored(&session_file_id(path));
+            ignored_sessions += usize::from(ignored);
+            !ignored
+        })
+        .collect();
+    report_ignored(ignored_workspaces, ignored_sessions, options.verbose);
+
+    // Tag filter works on session IDs, so it can run before any file is read
+    let tag_index = match options.tag {
+        Some(_) => Some(crate::tags::TagIndex::load()?),
+        None => None,
+    };
+    let session_files: Vec<_> = match (&tag_index, options.tag.as_deref()) {
+        (Some(index), Some(tag)) => session_files
+            .into_iter()
+            .filter(|(path, _)| {
+                path.file_stem()
+                    .is_some_and(|id| index.has_tag(&id.to_string_lossy(), tag))
+            })
+            .collect(),
+        _ => session_files,
+    };
+
+    let needs_full_content = reference.is_some()
+        || search_content && !title_only && (!needles.is_empty() || !excludes.is_empty());
+    // Message range filters need the parsed request count, not the key estimate
+    let needs_exact_count = options.min_messages.is_some() || options.max_messages.is_some();
+    // Otherwise counting streams the whole file, so only do it for the rows
+    // shown after --limit (and not at all for --count)
+    let deferred_count = !needs_full_content && !needs_exact_count;
+
+    let total_files = session_files.len();
+    let scanned = AtomicUsize::new(0);
+    let skipped_by_date = AtomicUsize::new(0);
+    let too_large = AtomicUsize::new(0);
+    let excluded_by_content = AtomicUsize::new(0);
+    let unreadable = UnreadableFiles::new(options.verbose);
+
+    // Process files in parallel, keeping each session's words for --similar
+    let scored: Vec<(SessionSearchResult, Option<TermCounts>)> = session_files
+        .par_iter()
+        .filter_map(|(path, ws_name)| {
+            if let Some((reference_id, _, _)) = &reference {
+                if session_file_id(path) == *reference_id {
+                    return None;
+                }
+            }
+
+            // Date filter using file metadata (very fast)
+            if after_date.is_some() || before_date.is_some() || harvested_at.is_some() {
+                if let Ok(metadata) = path.metadata() {
+                    if let Ok(modified) = metadata.modified() {
+                        let file_date: chrono::DateTime<Utc> = modified.into();
+                        let file_naive = file_date.date_naive();
+
+                        if let Some(harvested_at) = harvested_at {
+                            if file_date.timestamp_millis() <= harvested_at {
+                                skipped_by_date.fetch_add(1, Ordering::Relaxed);
+                                return None;
+                            }
+                        }
+
+                        if let Some(after) = after_date {
+                            if file_naive < after {
+                                skipped_by_date.fetch_add(1, Ordering::Relaxed);
+                                return None;
+                            }
+                        }
+                        if let Some(before) = before_date {
+                            if file_naive > before {
+                                skipped_by_date.fetch_add(1, Ordering::Relaxed);
+                                return None;
+                            }
+                        }
+                    }
+                }
             }
 
             scanned.fetch_add(1, Ordering::Relaxed);
 
-            // Read file content once
-            let content = match std::fs::read_to_string(path) {
-                Ok(c) => c,
-                Err(_) => return None,
+            // Files over --max-bytes only get the bounded title read below
+            let oversized = needs_full_content
+                && options.max_bytes > 0
+                && path.metadata().is_ok_and(|m| m.len() > options.max_bytes);
+            if oversized {
+                too_large.fetch_add(1, Ordering::Relaxed);
+            }
+
+            // Only a content search needs the whole file; otherwise read the
+            // title from the ends of the file with bounded memory
+            let content = if needs_full_content && !oversized {
+                match std::fs::read_to_string(path) {
+                    Ok(c) => Some(c),
+                    Err(e) => {
+                        unreadable.record(path, e);
+                        return None;
+                    }
+                }
+            } else {
+                None
             };
 
-            // Extract title from content
-            let title =
-                extract_title_from_content(&content).unwrap_or_else(|| "Untitled".to_string());
-            let title_lower = title.to_lowercase();
+            let title = match &content {
+                Some(content) => extract_title_from_content(content),
+                None => match read_session_title(path) {
+                    Ok(title) => title,
+                    Err(e) => {
+                        unreadable.record(path, e);
+                        return None;
+                    }
+                },
+            }
+            .unwrap_or_else(|| "Untitled".to_string());
+            let title_normalized = matcher.normalize(&title);
 
             // Check session ID from filename
             let session_id = path
                 .file_stem()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default();
-            let id_matches =
-                !pattern_lower.is_empty() && session_id.to_lowercase().contains(&pattern_lower);
-
-            // Check title match
-            let title_matches = !pattern_lower.is_empty() && title_lower.contains(&pattern_lower);
-
-            // Content search if requested
-            let content_matches = if search_content
-                && !title_only
-                && !id_matches
-                && !title_matches
-                && !pattern_lower.is_empty()
-            {
-                content.to_lowercase().contains(&pattern_lower)
-            } else {
-                false
+            let session_id_normalized = matcher.normalize(&session_id);
+
+            // Where each term matched: ID, then title, then (if requested) content,
+            // which is only normalized once a term misses the ID and title
+            let content_normalized = std::cell::OnceCell::new();
+            let hits: Vec<(&str, &str)> = needles
+                .iter()
+                .filter_map(|needle| {
+                    let kind = if matcher.matches(&session_id_normalized, needle) {
+                        "ID"
+                    } else if matcher.matches(&title_normalized, needle) {
+                        "title"
+                    } else if search_content
+                        && !title_only
+                        && content.as_ref().is_some_and(|c| {
+                            matcher.matches(
+                                content_normalized.get_or_init(|| matcher.normalize(c)),
+                                needle,
+                            )
+                        })
+                    {
+                        "content"
+                    } else {
+                        return None;
+                    };
+                    Some((needle.as_str(), kind))
+                })
+                .collect();
+
+            // --similar scores every session it can read in full
+            let terms = match (&reference, &content) {
+                (Some(_), Some(content)) => match crate::storage::parse_session_json(content) {
+                    Ok(session) => Some(session_terms(&session)),
+                    Err(e) => {
+                        unreadable.record(path, e);
+                        return None;
+                    }
+                },
+                (Some(_), None) => return None,
+                (None, _) => None,
             };
 
-            // Empty pattern matches everything (for listing)
-            let matches =
-                pattern_lower.is_empty() || id_matches || title_matches || content_matches;
+            let matches = if options.match_all {
+                hits.len() == needles.len()
+            } else {
+                needles.is_empty() || !hits.is_empty()
+            };
             if !matches {
                 return None;
             }
+            // A match that also mentions an excluded term is noise; files too
+            // large to read keep their ID or title match
+            if let (false, Some(c)) = (excludes.is_empty(), &content) {
+                let normalized = content_normalized.get_or_init(|| matcher.normalize(c));
+                if excludes.iter().any(|e| matcher.matches(normalized, e)) {
+                    excluded_by_content.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+            }
+            let score = (options.sort == FindSort::Relevance).then(|| {
+                let content = content.as_ref().map(|c| {
+                    content_normalized
+                        .get_or_init(|| matcher.normalize(c))
+                        .as_ref()
+                });
+                relevance_score(
+                    matcher,
+                    &needles,
+                    &session_id_normalized,
+                    &title_normalized,
+                    content,
+                )
+            });
 
-            let match_type = if pattern_lower.is_empty() {
-                ""
-            } else if id_matches {
-                "ID"
-            } else if title_matches {
-                "title"
-            } else {
-                "content"
+            // A single term keeps the plain kind; several list which terms hit where
+            let match_type = match hits.as_slice() {
+                [] if terms.is_some() => "similar".to_string(),
+                [] => String::new(),
+                [(_, kind)] if needles.len() == 1 => kind.to_string(),
+                hits => hits
+                    .iter()
+                    .map(|(needle, kind)| format!("{} ({})", needle, kind))
+                    .collect::<Vec<_>>()
+                    .join(", "),
             };
 
-            // Count messages from content (already loaded)
-            let message_count = content.matches("\"message\":").count();
+            // Count messages only for matches, streaming the file if it wasn't loaded
+            let message_count = if needs_exact_count {
+                let count = match &content {
+                    Some(content) => crate::storage::parse_session_json(content)
+                        .map(|s| s.request_count())
+                        .map_err(|e| e.to_string()),
+                    None => count_session_requests(path).map_err(|e| e.to_string()),
+                };
+                // Unparseable files can't satisfy a message range
+                let count = match count {
+                    Ok(count) => count,
+                    Err(e) => {
+                        unreadable.record(path, e);
+                        return None;
+                    }
+                };
+                if options.min_messages.is_some_and(|min| count < min)
+                    || options.max_messages.is_some_and(|max| count > max)
+                {
+                    return None;
+                }
+                count
+            } else {
+                // Filled in after --limit when deferred
+                match &content {
+                    Some(content) => content.matches(MESSAGE_KEY).count(),
+                    None if oversized => count_messages_streaming(path).unwrap_or(0),
+                    None => 0,
+                }
+            };
 
             // Get modification time
-            let modified = path
-                .metadata()
-                .ok()
-                .and_then(|m| m.modified().ok())
+            let modified_at = path.metadata().ok().and_then(|m| m.modified().ok());
+            let modified = modified_at
                 .map(|t| {
                     let datetime: chrono::DateTime<chrono::Utc> = t.into();
                     datetime.format("%Y-%m-%d %H:%M").to_string()
@@ -578,31 +1752,120 @@ pub fn find_sessions_filtered(
                 .unwrap_or_else(|| "unknown".to_string());
 
             Some((
-                title,
-                ws_name.clone(),
-                modified,
-                message_count,
-                match_type.to_string(),
+                SessionSearchResult {
+                    session_id,
+                    title,
+                    workspace: ws_name.clone(),
+                    modified,
+                    modified_at,
+                    messages: message_count,
+                    match_type,
+                    score,
+                    path: path.clone(),
+                },
+                terms,
             ))
         })
         .collect();
 
+    // Score against the reference once every session's words are known, and
+    // drop those with no word in common
+    let (mut results, terms): (Vec<_>, Vec<_>) = scored.into_iter().unzip();
+    let mut keywords = Vec::new();
+    if let Some((_, _, reference_terms)) = &reference {
+        let corpus: Vec<&TermCounts> = terms.iter().flatten().collect();
+        let similarity = Similarity::new(reference_terms, &corpus);
+        for (result, terms) in results.iter_mut().zip(&terms) {
+            result.score = terms.as_ref().map(|t| similarity.percent(t));
+        }
+        results.retain(|r| r.score.is_some_and(|s| s > 0));
+        keywords = similarity
+            .keywords(SIMILAR_KEYWORDS)
+            .into_iter()
+            .map(String::from)
+            .collect();
+    }
+
     let scanned_count = scanned.load(Ordering::Relaxed);
     let skipped_count = skipped_by_date.load(Ordering::Relaxed);
+    let excluded_sessions = excluded_by_content.load(Ordering::Relaxed);
+    report_too_large(too_large.load(Ordering::Relaxed), options.max_bytes);
+    let total_matches = results.len();
 
-    if results.is_empty() {
-        println!("No sessions found matching '{}'", pattern);
-        if skipped_count > 0 {
-            println!("  ({} sessions skipped due to date filter)", skipped_count);
+    // --count wins over every other output mode
+    if options.count {
+        if options.json {
+            println!(
+                "{}",
+                to_json(&serde_json::json!({ "count": total_matches }))?
+            );
+        } else {
+            println!("{}", total_matches);
         }
-        return Ok(());
+        unreadable.report();
+        return Ok(total_matches);
+    }
+
+    if results.is_empty() {
+        unreadable.report();
+        return print_find_no_results(options, || {
+            eprintln!("No sessions found matching '{}'", pattern);
+            if skipped_count > 0 {
+                eprintln!("  ({} sessions skipped due to date filter)", skipped_count);
+            }
+            if excluded_sessions > 0 {
+                eprintln!(
+                    "  ({} sessions excluded by --exclude-content)",
+                    excluded_sessions
+                );
+            }
+        });
     }
 
-    // Sort by modification date (newest first)
-    results.sort_by(|a, b| b.2.cmp(&a.2));
+    // Best or newest first, then by title and ID so ties don't follow rayon's order
+    results.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| b.modified_at.cmp(&a.modified_at))
+            .then_with(|| a.title.cmp(&b.title))
+            .then_with(|| a.session_id.cmp(&b.session_id))
+    });
 
     // Apply limit
     results.truncate(limit);
+    if deferred_count {
+        results.par_iter_mut().for_each(|r| {
+            r.messages = count_messages_streaming(&r.path).unwrap_or(0);
+        });
+    }
+    if options.verbose {
+        report_date_disagreements(&results);
+    }
+
+    if options.json {
+        println!("{}", to_json(&results)?);
+        unreadable.report();
+        return Ok(results.len());
+    }
+    if options.ndjson {
+        print_ndjson(&results)?;
+        unreadable.report();
+        return Ok(results.len());
+    }
+    if options.format == OutputFormat::Csv {
+        print!("{}", to_csv(&results, &csv_columns)?);
+        unreadable.report();
+        return Ok(results.len());
+    }
+
+    if let Some((_, title, _)) = &reference {
+        status!(
+            "{} Similar to \"{}\" (keywords: {})",
+            "[i]".cyan(),
+            title,
+            keywords.join(", ")
+        );
+    }
 
     #[derive(Tabled)]
     struct SearchResultRow {
@@ -616,99 +1879,353 @@ pub fn find_sessions_filtered(
         messages: usize,
         #[tabled(rename = "Match")]
         match_type: String,
+        #[tabled(rename = "Score")]
+        score: String,
     }
 
-    let rows: Vec<SearchResultRow> = results
-        .into_iter()
-        .map(
-            |(title, workspace, modified, messages, match_type)| SearchResultRow {
-                title: truncate_string(&title, 40),
-                workspace: truncate_string(&workspace, 20),
-                modified,
-                messages,
-                match_type,
-            },
-        )
-        .collect();
+    let to_row = |r: SessionSearchResult| SearchResultRow {
+        title: truncate_string(&r.title, 40),
+        workspace: truncate_string(&r.workspace, 20),
+        modified: r.modified,
+        messages: r.messages,
+        match_type: r.match_type,
+        score: r.score.map(|s| s.to_string()).unwrap_or_default(),
+    };
+    // The score column only means something when results are ranked by it
+    let show_score = options.sort == FindSort::Relevance || reference.is_some();
+    let score_column = |table: &mut Table| {
+        if !show_score {
+            table.with(Remove::column(Columns::one(5)));
+        }
+    };
 
-    let table = Table::new(&rows).with(Style::ascii_rounded()).to_string();
+    // Snippets are gathered before the rows consume the results
+    let snippets: Vec<(String, Vec<ContentSnippet>)> = match options.context {
+        Some(context) => results
+            .par_iter()
+            .filter(|r| r.match_type.contains("content"))
+            .filter_map(|r| {
+                let content = std::fs::read_to_string(&r.path).ok()?;
+                let session = crate::storage::parse_session_json(&content).ok()?;
+                let snippets = content_snippets(&session, &needles, matcher, context);
+                (!snippets.is_empty()).then(|| (r.title.clone(), snippets))
+            })
+            .collect(),
+        None => Vec::new(),
+    };
 
-    println!("{}", table);
-    println!(
-        "\nFound {} session(s) (scanned {} of {} files{})",
-        rows.len(),
+    let shown = results.len();
+    match options.group_by {
+        // One table per workspace, in order of each workspace's newest match
+        Some(FindGroupBy::Workspace) => {
+            let mut groups: Vec<(String, Vec<SearchResultRow>)> = Vec::new();
+            for r in results {
+                let workspace = r.workspace.clone();
+                match groups.iter_mut().find(|(name, _)| *name == workspace) {
+                    Some((_, rows)) => rows.push(to_row(r)),
+                    None => groups.push((workspace, vec![to_row(r)])),
+                }
+            }
+            for (i, (workspace, rows)) in groups.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                println!("{} {} ({})", "[=]".blue(), workspace.bold(), rows.len());
+                let mut table = Table::new(rows);
+                score_column(&mut table);
+                table
+                    .with(Style::ascii_rounded())
+                    .with(Remove::column(Columns::one(1)));
+                println!("{}", table);
+            }
+        }
+        None => {
+            let rows: Vec<SearchResultRow> = results.into_iter().map(to_row).collect();
+            let mut table = Table::new(&rows);
+            score_column(&mut table);
+            println!("{}", table.with(Style::ascii_rounded()));
+        }
+    }
+    for (title, snippets) in &snippets {
+        print_content_snippets(title, snippets);
+    }
+
+    status!(
+        "\nFound {} session(s) (scanned {} of {} files{}{}{})",
+        shown,
         scanned_count,
         total_files,
         if skipped_count > 0 {
             format!(", {} skipped by date", skipped_count)
         } else {
             String::new()
+        },
+        if excluded_sessions > 0 {
+            format!(", {} excluded by --exclude-content", excluded_sessions)
+        } else {
+            String::new()
+        },
+        if excluded_count > 0 {
+            format!(", {} workspace(s) excluded", excluded_count)
+        } else {
+            String::new()
         }
     );
-    if rows.len() >= limit {
-        println!("  (results limited to {}; use --limit to show more)", limit);
+    if shown >= limit {
+        status!("  (results limited to {}; use --limit to show more)", limit);
     }
+    unreadable.report();
 
-    Ok(())
+    Ok(shown)
+}
+
+/// How far a session's last message may be from its file's modification
+/// time before `--verbose` points it out
+const DATE_DISAGREEMENT_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// `--verbose`: note each result whose last message and file modification
+/// time are over a day apart. Date filters and the Modified column use the
+/// file time, which VS Code bumps whenever it rewrites a session.
+fn report_date_disagreements(results: &[SessionSearchResult]) {
+    use chrono::{DateTime, Utc};
+    use rayon::prelude::*;
+
+    let notes: Vec<String> = results
+        .par_iter()
+        .filter_map(|r| {
+            let modified: DateTime<Utc> = r.modified_at?.into();
+            let content = std::fs::read_to_string(&r.path).ok()?;
+            let session = crate::storage::parse_session_json(&content).ok()?;
+            let last = session_last_activity(&session)?;
+            if (modified.timestamp_millis() - last).abs() <= DATE_DISAGREEMENT_MS {
+                return None;
+            }
+            let last = DateTime::<Utc>::from_timestamp_millis(last)?;
+            Some(format!(
+                "{} {}: last message {} but file modified {} (rewritten since?); \
+                 date filters use the file time",
+                "[i]".cyan(),
+                r.session_id,
+                last.format("%Y-%m-%d"),
+                modified.format("%Y-%m-%d")
+            ))
+        })
+        .collect();
+    for note in notes {
+        eprintln!("{}", note);
+    }
+}
+
+/// Tell stderr how many files were too large for the content search
+fn report_too_large(count: usize, max_bytes: u64) {
+    if count > 0 {
+        eprintln!(
+            "{} {} file(s) skipped as too large for content search (over {}; ID and title \
+             still matched, raise --max-bytes or pass 0 to search them)",
+            "[!]".yellow(),
+            count,
+            format_bytes(max_bytes)
+        );
+    }
+}
+
+/// Report an empty `find session` result in the requested output mode
+fn print_find_no_results(options: &FindSessionOptions, human: impl FnOnce()) -> Result<usize> {
+    if options.count {
+        if options.json {
+            println!("{}", to_json(&serde_json::json!({ "count": 0 }))?);
+        } else {
+            println!("0");
+        }
+    } else if options.json {
+        println!("[]");
+    } else if options.ndjson {
+        // No records, so no lines
+    } else if options.format == OutputFormat::Csv {
+        // Just the header, so the output is still a (empty) table
+        print!("{}", to_csv(&[], &find_csv_columns(options)?)?);
+        human();
+    } else {
+        human();
+    }
+    Ok(0)
 }
 
 /// Extract title from full JSON content (more reliable than header-only)
 fn extract_title_from_content(content: &str) -> Option<String> {
-    // Look for "customTitle" first (user-set title)
-    if let Some(start) = content.find("\"customTitle\"") {
-        if let Some(colon) = content[start..].find(':') {
-            let after_colon = &content[start + colon + 1..];
-            let trimmed = after_colon.trim_start();
-            if let Some(stripped) = trimmed.strip_prefix('"') {
-                if let Some(end) = stripped.find('"') {
-                    let title = &stripped[..end];
-                    if !title.is_empty() && title != "null" {
-                        return Some(title.to_string());
-                    }
-                }
-            }
+    extract_custom_title(content).or_else(|| extract_first_message_text(content))
+}
+
+/// Extract a user-set "customTitle" value
+fn extract_custom_title(content: &str) -> Option<String> {
+    let start = content.find("\"customTitle\"")?;
+    let colon = content[start..].find(':')?;
+    let after_colon = &content[start + colon + 1..];
+    let stripped = after_colon.trim_start().strip_prefix('"')?;
+    let end = stripped.find('"')?;
+    let title = &stripped[..end];
+    if !title.is_empty() && title != "null" {
+        Some(title.to_string())
+    } else {
+        None
+    }
+}
+
+/// Extract the first request's message text, if short enough to be a title
+fn extract_first_message_text(content: &str) -> Option<String> {
+    let start = content.find("\"text\"")?;
+    let colon = content[start..].find(':')?;
+    let after_colon = &content[start + colon + 1..];
+    let stripped = after_colon.trim_start().strip_prefix('"')?;
+    let end = stripped.find('"')?;
+    let title = &stripped[..end];
+    if !title.is_empty() && title.len() < 100 {
+        Some(title.to_string())
+    } else {
+        None
+    }
+}
+
+/// Bytes read from each end of a session file when only the title is needed
+const TITLE_SCAN_BYTES: u64 = 64 * 1024;
+
+/// Key counted to estimate the number of messages in a session file
+const MESSAGE_KEY: &str = "\"message\":";
+
+/// Read a session title without loading the whole file.
+///
+/// Sessions written by chasm keep `customTitle` near the start, while VS Code
+/// writes it after `requests`, so only the first and last [`TITLE_SCAN_BYTES`]
+/// are read, and memory use doesn't grow with the file.
+///
+/// The whole file is only read when neither end has a `customTitle` and the
+/// head holds no message `text` to fall back on, e.g. a session whose first
+/// request starts past a large preamble.
+fn read_session_title(path: &std::path::Path) -> std::io::Result<Option<String>> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+
+    let mut head = Vec::new();
+    (&mut file).take(TITLE_SCAN_BYTES).read_to_end(&mut head)?;
+    let head = String::from_utf8_lossy(&head);
+    if let Some(title) = extract_custom_title(&head) {
+        return Ok(Some(title));
+    }
+
+    if len > TITLE_SCAN_BYTES {
+        // The tail overlaps the head in files under two windows, so a title
+        // straddling the end of the head is still read whole
+        let tail_start = len - TITLE_SCAN_BYTES;
+        file.seek(SeekFrom::Start(tail_start))?;
+        let mut tail = Vec::new();
+        file.read_to_end(&mut tail)?;
+        if let Some(title) = extract_custom_title(&String::from_utf8_lossy(&tail)) {
+            return Ok(Some(title));
         }
     }
 
-    // Fall back to first request's message text
-    if let Some(start) = content.find("\"text\"") {
-        if let Some(colon) = content[start..].find(':') {
-            let after_colon = &content[start + colon + 1..];
-            let trimmed = after_colon.trim_start();
-            if let Some(stripped) = trimmed.strip_prefix('"') {
-                if let Some(end) = stripped.find('"') {
-                    let title = &stripped[..end];
-                    if !title.is_empty() && title.len() < 100 {
-                        return Some(title.to_string());
-                    }
-                }
-            }
+    if len > 2 * TITLE_SCAN_BYTES && !head.contains("\"text\"") {
+        let content = String::from_utf8_lossy(&std::fs::read(path)?).into_owned();
+        return Ok(extract_title_from_content(&content));
+    }
+
+    Ok(extract_first_message_text(&head))
+}
+
+/// Count messages in a session file using a fixed-size buffer
+fn count_messages_streaming(path: &std::path::Path) -> std::io::Result<usize> {
+    use std::io::Read;
+
+    let needle = MESSAGE_KEY.as_bytes();
+    let mut reader = std::io::BufReader::new(std::fs::File::open(path)?);
+    let mut buf = vec![0u8; TITLE_SCAN_BYTES as usize];
+    // Carry the end of the previous chunk so keys split across reads are counted
+    let mut window: Vec<u8> = Vec::with_capacity(buf.len() + needle.len());
+    let mut count = 0;
+
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
         }
+        window.extend_from_slice(&buf[..n]);
+        // Split UTF-8 sequences become U+FFFD, which can't affect an ASCII key
+        count += String::from_utf8_lossy(&window)
+            .matches(MESSAGE_KEY)
+            .count();
+        let keep = window.len().min(needle.len() - 1);
+        window.drain(..window.len() - keep);
     }
 
-    None
+    Ok(count)
 }
 
-/// Fast title extraction from JSON header
-#[allow(dead_code)]
-fn extract_title_fast(header: &str) -> Option<String> {
-    extract_title_from_content(header)
+/// Count the requests in a session file by parsing it
+///
+/// Unlike [`count_messages_streaming`] this is not fooled by `"message":`
+/// appearing inside message text. Request bodies are skipped rather than
+/// materialized, so memory stays bounded.
+pub fn count_session_requests(path: &std::path::Path) -> Result<usize> {
+    #[derive(serde::Deserialize)]
+    struct RequestsOnly {
+        #[serde(default)]
+        requests: Vec<serde::de::IgnoredAny>,
+    }
+
+    let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+    match serde_json::from_reader::<_, RequestsOnly>(reader) {
+        Ok(session) => Ok(session.requests.len()),
+        // Fall back to the sanitizing parser for files with broken Unicode escapes
+        Err(_) => {
+            let content = std::fs::read_to_string(path)?;
+            Ok(crate::storage::parse_session_json(&content)?.request_count())
+        }
+    }
 }
 
 /// Truncate string to max length with ellipsis
 fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        let kept: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
     }
 }
 
-/// Show workspace details
-pub fn show_workspace(workspace: &str) -> Result<()> {
+/// Closest candidate to a name that matched nothing, for "did you mean"
+/// hints. Candidates are compared whole and by their prefix of the same
+/// length (so partial IDs work), allowing about one typo (or swapped pair)
+/// per three characters.
+pub fn suggest_closest<'a>(
+    needle: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let needle = needle.to_lowercase();
+    let len = needle.chars().count();
+    let max_distance = (len / 3).max(1);
+
+    candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let lower = candidate.to_lowercase();
+            let prefix: String = lower.chars().take(len).collect();
+            let distance =
+                strsim::osa_distance(&needle, &lower).min(strsim::osa_distance(&needle, &prefix));
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Show workspace details. Session files that can't be parsed are left out
+/// of the listing and reported, each one under `verbose`.
+pub fn show_workspace(workspace: &str, verbose: bool, discovery: &DiscoveryOptions) -> Result<()> {
     use colored::Colorize;
 
-    let workspaces = discover_workspaces()?;
+    let workspaces = discover_workspaces(discovery)?;
     let workspace_lower = workspace.to_lowercase();
 
     // Find workspace by name or hash
@@ -725,11 +2242,32 @@ pub fn show_workspace(workspace: &str) -> Result<()> {
         .collect();
 
     if matching.is_empty() {
-        println!(
-            "{} No workspace found matching '{}'",
-            "!".yellow(),
-            workspace
-        );
+        // Typos: compare against hashes and project folder names, suggest
+        // the hash or full project path (which the substring match accepts)
+        let names: Vec<(String, &str)> = workspaces
+            .iter()
+            .flat_map(|ws| {
+                let folder = ws.project_path.as_deref().and_then(workspace_display_name);
+                let shown = ws.project_path.as_deref().unwrap_or(&ws.hash);
+                std::iter::once((ws.hash.clone(), ws.hash.as_str()))
+                    .chain(folder.map(|folder| (folder, shown)))
+            })
+            .collect();
+        let suggestion = suggest_closest(workspace, names.iter().map(|(name, _)| name.as_str()))
+            .and_then(|name| names.iter().find(|(n, _)| n == name))
+            .map(|(_, shown)| *shown);
+        match suggestion {
+            Some(shown) => println!(
+                "{} No exact match; did you mean {}?",
+                "!".yellow(),
+                shown.cyan()
+            ),
+            None => println!(
+                "{} No workspace found matching '{}'",
+                "!".yellow(),
+                workspace
+            ),
+        }
         return Ok(());
     }
 
@@ -758,9 +2296,16 @@ pub fn show_workspace(workspace: &str) -> Result<()> {
             "Workspace Path".bright_white().bold(),
             ws.workspace_path.display()
         );
+        println!(
+            "{}: {}",
+            "Disk Usage".bright_white().bold(),
+            format_bytes(crate::workspace::chat_sessions_size(&ws.chat_sessions_path))
+        );
 
         if ws.has_chat_sessions {
-            let sessions = crate::workspace::get_chat_sessions_from_workspace(&ws.workspace_path)?;
+            let (sessions, skipped) =
+                crate::workspace::load_chat_sessions_from_workspace(&ws.workspace_path);
+            report_skipped_sessions(&skipped, verbose);
             println!(
                 "{}: {}",
                 "Session Count".bright_white().bold(),
@@ -786,11 +2331,109 @@ pub fn show_workspace(workspace: &str) -> Result<()> {
     Ok(())
 }
 
+/// Which turns `show session` previews
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewTurns {
+    /// The first N turns
+    First(usize),
+    /// The last N turns
+    Last(usize),
+    /// A range of turns, as given to `--messages`
+    Range(crate::commands::MessageRange),
+}
+
+impl PreviewTurns {
+    /// Indices of the previewed turns in a session of `len` turns
+    pub fn range(self, len: usize) -> std::ops::Range<usize> {
+        match self {
+            PreviewTurns::First(n) => 0..n.min(len),
+            PreviewTurns::Last(n) => len.saturating_sub(n)..len,
+            PreviewTurns::Range(range) => {
+                let end = range.end.unwrap_or(len).min(len);
+                (range.start - 1).min(end)..end
+            }
+        }
+    }
+}
+
+/// Output options for `show session`
+#[derive(Debug, Clone)]
+pub struct ShowSessionOptions {
+    /// Print undecorated `key: value` lines
+    pub plain: bool,
+    /// Print a JSON object
+    pub json: bool,
+    /// Turns to include in the preview
+    pub preview: PreviewTurns,
+    /// Print the complete text of the previewed turns instead of the details
+    pub full: bool,
+    /// Which versions of edited or regenerated turns to preview
+    pub threads: FlattenThreads,
+}
+
+impl Default for ShowSessionOptions {
+    fn default() -> Self {
+        Self {
+            plain: false,
+            json: false,
+            preview: PreviewTurns::First(3),
+            full: false,
+            threads: FlattenThreads::Latest,
+        }
+    }
+}
+
+/// Scriptable `show session` output
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SessionDetails {
+    pub title: String,
+    pub session_id: Option<String>,
+    pub file: String,
+    pub messages: usize,
+    /// Dominant model, "mixed", or `None` when no request records one
+    pub model: Option<String>,
+    /// Chat participants that answered, in order of first use
+    pub agents: Vec<String>,
+    pub workspace: Option<String>,
+    /// Full text of the first messages
+    pub preview: Vec<String>,
+}
+
+/// Distinct agent IDs of a session's requests, in order of first use
+fn session_agents(session: &crate::models::ChatSession) -> Vec<String> {
+    let mut agents: Vec<String> = Vec::new();
+    for id in session.requests.iter().filter_map(|r| r.agent_id()) {
+        if !agents.iter().any(|a| a == id) {
+            agents.push(id.to_string());
+        }
+    }
+    agents
+}
+
+/// Dominant model, with a per-model breakdown when there is more than one
+fn format_session_models(session: &crate::models::ChatSession) -> String {
+    let counts = session.model_counts();
+    let dominant = session.dominant_model().to_string();
+    if counts.len() < 2 {
+        return dominant;
+    }
+    let breakdown: Vec<String> = counts
+        .iter()
+        .map(|(name, count)| format!("{}: {}", name, count))
+        .collect();
+    format!("{} ({})", dominant, breakdown.join(", "))
+}
+
 /// Show session details
-pub fn show_session(session_id: &str, project_path: Option<&str>) -> Result<()> {
+pub fn show_session(
+    session_id: &str,
+    project_path: Option<&str>,
+    options: &ShowSessionOptions,
+    discovery: &DiscoveryOptions,
+) -> Result<()> {
     use colored::Colorize;
 
-    let workspaces = discover_workspaces()?;
+    let workspaces = discover_workspaces(discovery)?;
     let session_id_lower = session_id.to_lowercase();
 
     let filtered_workspaces: Vec<&Workspace> = if let Some(path) = project_path {
@@ -808,6 +2451,9 @@ pub fn show_session(session_id: &str, project_path: Option<&str>) -> Result<()>
         workspaces.iter().collect()
     };
 
+    // Session IDs and file names seen, for a "did you mean" hint
+    let mut candidates = Vec::new();
+
     for ws in filtered_workspaces {
         if !ws.has_chat_sessions {
             continue;
@@ -821,6 +2467,8 @@ pub fn show_session(session_id: &str, project_path: Option<&str>) -> Result<()>
                 .file_name()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default();
+            candidates.extend(s.session.session_id.clone());
+            candidates.push(filename.clone());
 
             let matches = s
                 .session
@@ -831,6 +2479,34 @@ pub fn show_session(session_id: &str, project_path: Option<&str>) -> Result<()>
                 || filename.to_lowercase().contains(&session_id_lower);
 
             if matches {
+                if options.full {
+                    let turns = options.preview.range(s.session.requests.len());
+                    print!("{}", full_turns_text(&s.session, turns, options.threads));
+                    return Ok(());
+                }
+                if options.plain || options.json {
+                    let details = SessionDetails {
+                        title: s.session.title(),
+                        session_id: s.session.session_id.clone(),
+                        file: filename,
+                        messages: s.session.request_count(),
+                        model: match s.session.dominant_model() {
+                            crate::models::DominantModel::Unknown => None,
+                            model => Some(model.to_string()),
+                        },
+                        agents: session_agents(&s.session),
+                        workspace: ws.project_path.clone(),
+                        preview: s.session.requests
+                            [options.preview.range(s.session.requests.len())]
+                        .iter()
+                        .flat_map(|req| turn_versions(req, options.threads))
+                        .filter_map(|req| request_preview(&req))
+                        .collect(),
+                    };
+                    print_session_details(&details, options.json)?;
+                    return Ok(());
+                }
+
                 println!("\n{}", "=".repeat(60).bright_blue());
                 println!("{}", "Session Details".bright_blue().bold());
                 println!("{}", "=".repeat(60).bright_blue());
@@ -854,21 +2530,49 @@ pub fn show_session(session_id: &str, project_path: Option<&str>) -> Result<()>
                     "Messages".bright_white().bold(),
                     s.session.request_count()
                 );
+                println!(
+                    "{}: {}",
+                    "Model".bright_white().bold(),
+                    format_session_models(&s.session)
+                );
+                let agents = session_agents(&s.session);
+                if !agents.is_empty() {
+                    println!("{}: {}", "Agent".bright_white().bold(), agents.join(", "));
+                }
                 println!(
                     "{}: {}",
                     "Workspace".bright_white().bold(),
                     ws.project_path.as_ref().unwrap_or(&"(none)".to_string())
                 );
 
-                // Show first few messages as preview
+                // Preview the requested turns, numbered by their place in the session
                 println!("\n{}", "Preview:".bright_yellow());
-                for (i, req) in s.session.requests.iter().take(3).enumerate() {
-                    if let Some(msg) = &req.message {
-                        if let Some(text) = &msg.text {
-                            let preview: String = text.chars().take(100).collect();
-                            let truncated = if text.len() > 100 { "..." } else { "" };
-                            println!("  {}. {}{}", i + 1, preview.dimmed(), truncated);
-                        }
+                let turns = options.preview.range(s.session.requests.len());
+                for (i, req) in s.session.requests[turns.clone()]
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, req)| {
+                        turn_versions(req, options.threads)
+                            .into_iter()
+                            .map(move |req| (turns.start + i, req))
+                    })
+                {
+                    if let Some(text) = req.message.as_ref().and_then(|m| m.text.as_ref()) {
+                        let preview: String = text.chars().take(100).collect();
+                        let truncated = if text.len() > 100 { "..." } else { "" };
+                        println!("  {}. {}{}", i + 1, preview.dimmed(), truncated);
+                    }
+                    for call in req.tool_calls() {
+                        let summary = call.summary();
+                        let preview: String = summary.chars().take(100).collect();
+                        let truncated = if summary.len() > 100 { "..." } else { "" };
+                        println!(
+                            "  {}. {} {}{}",
+                            i + 1,
+                            "[tool]".yellow(),
+                            preview.dimmed(),
+                            truncated
+                        );
                     }
                 }
 
@@ -877,10 +2581,117 @@ pub fn show_session(session_id: &str, project_path: Option<&str>) -> Result<()>
         }
     }
 
+    let suggestion = suggest_closest(session_id, candidates.iter().map(String::as_str));
+    if options.plain || options.json || options.full {
+        match suggestion {
+            Some(id) => anyhow::bail!("No exact match; did you mean {}?", id),
+            None => anyhow::bail!("No session found matching '{}'", session_id),
+        }
+    }
+
+    match suggestion {
+        Some(id) => println!(
+            "{} No exact match; did you mean {}?",
+            "!".yellow(),
+            id.cyan()
+        ),
+        None => println!(
+            "{} No session found matching '{}'",
+            "!".yellow(),
+            session_id
+        ),
+    }
+    Ok(())
+}
+
+/// Preview text for a request: the message text followed by one
+/// `tool: ...` line per tool call. `None` when the turn has neither.
+fn request_preview(request: &crate::models::ChatRequest) -> Option<String> {
+    let lines: Vec<String> = request
+        .message
+        .as_ref()
+        .and_then(|m| m.text.clone())
+        .into_iter()
+        .chain(
+            request
+                .tool_calls()
+                .iter()
+                .map(|call| format!("tool: {}", call.summary())),
+        )
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// The complete text of `turns`, for `show session --full`, with the
+/// versions of branched turns `threads` selects
+///
+/// Each message gets a `--- turn N (role) ---` line followed by its text as
+/// stored, so code fences and indentation survive a copy-paste. Tool calls
+/// are listed one per line under a `tool` heading.
+pub fn full_turns_text(
+    session: &crate::models::ChatSession,
+    turns: std::ops::Range<usize>,
+    threads: FlattenThreads,
+) -> String {
+    let mut out = String::new();
+    let start = turns.start;
+    let versions = session.requests[turns]
+        .iter()
+        .enumerate()
+        .flat_map(|(i, request)| {
+            turn_versions(request, threads)
+                .into_iter()
+                .map(move |r| (i, r))
+        });
+    for (i, request) in versions {
+        let user = request.message.as_ref().and_then(|m| m.text.clone());
+        let tools: Vec<String> = request.tool_calls().iter().map(|c| c.summary()).collect();
+        let tools = (!tools.is_empty()).then(|| tools.join("\n"));
+        let assistant = request
+            .response
+            .as_ref()
+            .and_then(crate::providers::session_format::extract_response_text);
+        for (role, text) in [("user", user), ("tool", tools), ("assistant", assistant)] {
+            let Some(text) = text else { continue };
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("--- turn {} ({}) ---\n", start + i + 1, role));
+            out.push_str(text.trim_end_matches(['\r', '\n']));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Print `show session` details as JSON or as `key: value` lines
+///
+/// In plain mode newlines inside preview texts are escaped as `\n` so each
+/// entry stays on one line.
+fn print_session_details(details: &SessionDetails, json: bool) -> Result<()> {
+    if json {
+        println!("{}", to_json(details)?);
+        return Ok(());
+    }
+
+    println!("title: {}", details.title);
     println!(
-        "{} No session found matching '{}'",
-        "!".yellow(),
-        session_id
+        "session_id: {}",
+        details.session_id.as_deref().unwrap_or_default()
     );
+    println!("file: {}", details.file);
+    println!("messages: {}", details.messages);
+    println!("model: {}", details.model.as_deref().unwrap_or_default());
+    for agent in &details.agents {
+        println!("agent: {}", agent);
+    }
+    println!(
+        "workspace: {}",
+        details.workspace.as_deref().unwrap_or_default()
+    );
+    for text in &details.preview {
+        println!("preview: {}", text.replace('\r', "").replace('\n', "\\n"));
+    }
+
     Ok(())
 }
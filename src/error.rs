@@ -42,6 +42,18 @@ pub enum CsmError {
 
     #[error("Must specify either --hash or --path")]
     MissingTargetSpecifier,
+
+    #[error("Invalid redaction pattern: {0}")]
+    InvalidRedactPattern(String),
+
+    #[error("Invalid exclude glob {0}")]
+    InvalidExcludeGlob(String),
+
+    #[error("Invalid pattern in {0}")]
+    InvalidIgnorePattern(String),
+
+    #[error("Template error: {0}")]
+    Template(String),
 }
 
 pub type Result<T> = std::result::Result<T, CsmError>;
@@ -50,10 +50,15 @@ pub mod commands;
 pub mod database;
 pub mod error;
 pub mod integrations;
+pub mod links;
 pub mod mcp;
 pub mod models;
 pub mod providers;
+pub mod redact;
+pub mod schema;
 pub mod storage;
+pub mod tags;
+pub mod template;
 pub mod tui;
 pub mod workspace;
 
@@ -0,0 +1,111 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Session to git repository links
+//!
+//! Links are stored in a sidecar JSON index under the csm data directory,
+//! keyed by session ID, alongside the tag index.
+
+use crate::error::Result;
+use crate::storage::write_json_atomic;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Current on-disk format version of the link index
+const LINK_INDEX_VERSION: u32 = 1;
+
+/// Get the default link index path
+pub fn get_link_index_path() -> PathBuf {
+    dirs::data_local_dir()
+        .map(|p| p.join("csm").join("links.json"))
+        .unwrap_or_else(|| PathBuf::from("links.json"))
+}
+
+/// Find the git repository containing `path` by walking up to a `.git` entry
+///
+/// `.git` may be a file for worktrees and submodules, so any entry counts.
+/// Relative and empty paths find nothing, rather than resolving against the
+/// current directory.
+pub fn find_git_root(path: &Path) -> Option<PathBuf> {
+    if !path.is_absolute() {
+        return None;
+    }
+    path.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Sidecar index mapping session IDs to the repository they belong to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LinkIndex {
+    pub version: u32,
+    /// Repository root per session ID
+    pub sessions: BTreeMap<String, String>,
+}
+
+impl Default for LinkIndex {
+    fn default() -> Self {
+        Self {
+            version: LINK_INDEX_VERSION,
+            sessions: BTreeMap::new(),
+        }
+    }
+}
+
+impl LinkIndex {
+    /// Load the link index from the default location
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_link_index_path())
+    }
+
+    /// Load a link index from a file, returning an empty index if it doesn't exist
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the link index to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_link_index_path())
+    }
+
+    /// Save the link index to a file
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_json_atomic(path, self)
+    }
+
+    /// Link a session to a repository. Returns false if it was already linked there.
+    pub fn link(&mut self, session_id: &str, repo_path: &str) -> bool {
+        self.sessions
+            .insert(session_id.to_string(), repo_path.to_string())
+            .as_deref()
+            != Some(repo_path)
+    }
+
+    /// Remove a session's link, returning the repository it was linked to
+    pub fn unlink(&mut self, session_id: &str) -> Option<String> {
+        self.sessions.remove(session_id)
+    }
+
+    /// Repository root a session is linked to
+    pub fn repo_for(&self, session_id: &str) -> Option<&str> {
+        self.sessions.get(session_id).map(String::as_str)
+    }
+
+    /// Session IDs linked to the repository at `repo_root`
+    pub fn sessions_for_repo(&self, repo_root: &Path) -> Vec<&str> {
+        let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+        let repo_root = canonical(repo_root);
+        self.sessions
+            .iter()
+            .filter(|(_, repo)| canonical(Path::new(repo)) == repo_root)
+            .map(|(id, _)| id.as_str())
+            .collect()
+    }
+}
@@ -14,10 +14,15 @@ mod cli;
 mod commands;
 mod database;
 mod error;
+mod links;
 mod mcp;
 mod models;
 mod providers;
+mod redact;
+mod schema;
 mod storage;
+mod tags;
+mod template;
 mod tui;
 mod workspace;
 
@@ -26,7 +31,8 @@ use clap::Parser;
 use cli::{
     AgencyCommands, ApiCommands, Cli, Commands, DetectCommands, ExportCommands, FetchCommands,
     FindCommands, GitCommands, HarvestCommands, HarvestGitCommands, ImportCommands, ListCommands,
-    MergeCommands, MigrationCommands, MoveCommands, ProviderCommands, RunCommands, ShowCommands,
+    MergeCommands, MigrationCommands, MoveCommands, OpenCommands, ProviderCommands, RunCommands,
+    ShowCommands, TagCommands,
 };
 
 /// Get the current directory name as a default pattern
@@ -37,56 +43,170 @@ fn get_current_dir_name() -> String {
         .unwrap_or_else(|| ".".to_string())
 }
 
+/// Exit like grep for `find session`/`find path`: 0 on matches, 1 on none, 2 on error
+fn exit_with_find_status(result: Result<usize>) -> Result<()> {
+    match result {
+        Ok(0) => std::process::exit(1),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            std::process::exit(2)
+        }
+    }
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    cli.color.apply();
+    commands::set_quiet(cli.quiet);
+    commands::set_pretty_json(cli.pretty);
+    let mut exclude_globs = cli.exclude.clone();
+    exclude_globs
+        .extend(providers::CsmConfig::load().map_or_else(|_| Vec::new(), |c| c.exclude_globs));
+    let discovery = workspace::DiscoveryOptions {
+        depth: cli.depth,
+        follow_symlinks: cli.follow_symlinks,
+        channel: cli.channel,
+        ..Default::default()
+    }
+    .with_exclude_globs(&exclude_globs)?
+    .with_ignore_files()?;
 
     match cli.command {
         // ====================================================================
         // List Commands
         // ====================================================================
         Commands::List { command } => match command {
-            Some(ListCommands::Workspaces) => commands::list_workspaces(),
-            Some(ListCommands::Sessions { project_path }) => {
-                commands::list_sessions(project_path.as_deref())
-            }
-            Some(ListCommands::Path { project_path }) => {
-                commands::list_sessions(project_path.as_deref())
+            Some(ListCommands::Workspaces {
+                sort,
+                reverse,
+                size,
+                verbose,
+                format,
+                columns,
+            }) => commands::list_workspaces(
+                sort, reverse, size, verbose, format, &columns, &discovery,
+            ),
+            Some(ListCommands::Sessions {
+                project_path,
+                include_empty,
+                verbose,
+                format,
+                columns,
+                with_preview,
+            }) => commands::list_sessions(
+                project_path.as_deref(),
+                include_empty,
+                verbose,
+                format,
+                &columns,
+                with_preview,
+                &discovery,
+            ),
+            Some(ListCommands::Path { project_path }) => commands::list_sessions(
+                project_path.as_deref(),
+                false,
+                false,
+                Default::default(),
+                &[],
+                false,
+                &discovery,
+            ),
+            Some(ListCommands::Orphaned { path }) => {
+                commands::list_orphaned(path.as_deref(), &discovery)
             }
-            Some(ListCommands::Orphaned { path }) => commands::list_orphaned(path.as_deref()),
             Some(ListCommands::Providers { with_sessions }) => {
                 commands::detect_providers(with_sessions)
             }
             Some(ListCommands::Models { provider }) => commands::list_models(provider.as_deref()),
-            None => commands::list_workspaces(), // Default to workspaces
+            // Default to workspaces
+            None => commands::list_workspaces(
+                Default::default(),
+                false,
+                false,
+                false,
+                Default::default(),
+                &[],
+                &discovery,
+            ),
         },
 
         // ====================================================================
         // Find Commands
         // ====================================================================
         Commands::Find { command } => match command {
-            Some(FindCommands::Workspace { pattern }) => {
+            Some(FindCommands::Workspace { pattern, verbose }) => {
                 let pattern = pattern.unwrap_or_else(get_current_dir_name);
-                commands::find_workspaces(&pattern)
+                commands::find_workspaces(&pattern, verbose, &discovery)
             }
             Some(FindCommands::Session {
-                pattern,
+                patterns,
+                all,
+                any: _,
                 workspace,
                 title_only,
                 content,
                 after,
                 before,
                 limit,
+                count,
+                json,
+                ndjson,
+                tag,
+                min_messages,
+                max_messages,
+                verbose,
+                include_empty,
+                group_by,
+                context,
+                max_bytes,
+                case_sensitive,
+                word,
+                sort,
+                format,
+                columns,
+                exclude_content,
+                updated_since_harvest,
+                similar,
             }) => {
-                let pattern = pattern.unwrap_or_else(get_current_dir_name);
-                commands::find_sessions_filtered(
-                    &pattern,
-                    workspace.as_deref(),
-                    title_only,
-                    content,
-                    after.as_deref(),
-                    before.as_deref(),
-                    limit,
-                )
+                // --similar ranks by content, so no directory-name default
+                let patterns = if patterns.is_empty() && similar.is_none() {
+                    vec![get_current_dir_name()]
+                } else {
+                    patterns
+                };
+                exit_with_find_status(commands::find_sessions_filtered(
+                    &patterns,
+                    &commands::FindSessionOptions {
+                        workspace,
+                        title_only,
+                        search_content: content,
+                        after,
+                        before,
+                        limit,
+                        count,
+                        json,
+                        ndjson,
+                        tag,
+                        min_messages,
+                        max_messages,
+                        verbose,
+                        match_all: all,
+                        include_empty,
+                        group_by,
+                        context,
+                        max_bytes,
+                        case_sensitive,
+                        whole_word: word,
+                        sort,
+                        format,
+                        columns,
+                        exclude_content,
+                        updated_since_harvest,
+                        similar,
+                    },
+                    &discovery,
+                ))
             }
             Some(FindCommands::Path {
                 pattern,
@@ -94,42 +214,159 @@ fn main() -> Result<()> {
             }) => {
                 let pattern = pattern.unwrap_or_else(get_current_dir_name);
                 // Use title-only search by default for path-based search (faster)
-                commands::find_sessions_filtered(
-                    &pattern,
-                    project_path.as_deref(),
-                    false,
-                    false,
-                    None,
-                    None,
-                    50,
-                )
+                exit_with_find_status(commands::find_sessions_filtered(
+                    &[pattern],
+                    &commands::FindSessionOptions {
+                        workspace: project_path,
+                        ..Default::default()
+                    },
+                    &discovery,
+                ))
             }
+            Some(FindCommands::Save { name, query }) => {
+                commands::find_save(&name, query.into_saved_search())
+            }
+            Some(FindCommands::Run {
+                name,
+                overrides,
+                count,
+                json,
+            }) => exit_with_find_status(commands::find_run(
+                &name,
+                &overrides.into_saved_search(),
+                json,
+                count,
+                &discovery,
+            )),
+            Some(FindCommands::Saved) => commands::find_saved_list(),
             None => {
                 // Default to finding workspaces matching current directory
                 let pattern = get_current_dir_name();
-                commands::find_workspaces(&pattern)
+                commands::find_workspaces(&pattern, false, &discovery)
+            }
+        },
+
+        // ====================================================================
+        // Tag Commands
+        // ====================================================================
+        Commands::Tag { command } => match command {
+            TagCommands::Add {
+                session_id,
+                tags,
+                dry_run,
+            } => commands::tag_add(&session_id, &tags, dry_run, &discovery),
+            TagCommands::Remove {
+                session_id,
+                tags,
+                dry_run,
+            } => commands::tag_remove(&session_id, &tags, dry_run, &discovery),
+            TagCommands::List { session_id } => {
+                commands::tag_list(session_id.as_deref(), &discovery)
+            }
+            TagCommands::Gc { dry_run } => commands::tag_gc(dry_run, &discovery),
+        },
+
+        // ====================================================================
+        // Link Commands
+        // ====================================================================
+        Commands::Link {
+            session_id,
+            repo_path,
+            list,
+            dry_run,
+        } => match (session_id, repo_path) {
+            (Some(session_id), Some(repo_path)) if !list => {
+                commands::link_session(&session_id, &repo_path, dry_run, &discovery)
             }
+            _ => commands::link_list(),
         },
+        Commands::Unlink {
+            session_id,
+            dry_run,
+        } => commands::unlink_session(&session_id, dry_run, &discovery),
+
+        // ====================================================================
+        // Stats Command
+        // ====================================================================
+        Commands::Stats { session_id, by_day } => {
+            commands::session_stats(&session_id, by_day, &discovery)
+        }
+        Commands::Timeline { year } => commands::activity_timeline(year, &discovery),
+
+        // ====================================================================
+        // Open Commands
+        // ====================================================================
+        Commands::Open { command } => match command {
+            OpenCommands::Session {
+                session_id,
+                editor_cmd,
+            } => commands::open_session(&session_id, editor_cmd.as_deref(), &discovery),
+        },
+
+        // ====================================================================
+        // Purge Commands
+        // ====================================================================
+        Commands::Purge {
+            empty: _,
+            older_than,
+            delete,
+            yes,
+            dry_run,
+        } => commands::purge_empty_sessions(older_than.as_deref(), delete, yes, dry_run),
+        Commands::Dedupe { remove, yes } => commands::dedupe_sessions(remove, yes, &discovery),
+        Commands::Split {
+            session_id,
+            at,
+            in_place,
+            force,
+            dry_run,
+        } => {
+            commands::split_session_command(&session_id, &at, in_place, force, dry_run, &discovery)
+        }
 
         // ====================================================================
         // Show Commands
         // ====================================================================
         Commands::Show { command } => match command {
-            Some(ShowCommands::Workspace { workspace }) => commands::show_workspace(&workspace),
+            Some(ShowCommands::Workspace { workspace, verbose }) => {
+                commands::show_workspace(&workspace, verbose, &discovery)
+            }
             Some(ShowCommands::Session {
                 session_id,
                 project_path,
-            }) => commands::show_session(&session_id, project_path.as_deref()),
+                plain,
+                json,
+                preview_count,
+                last,
+                messages,
+                full,
+                flatten_threads,
+            }) => commands::show_session(
+                &session_id,
+                project_path.as_deref(),
+                &commands::ShowSessionOptions {
+                    plain,
+                    json,
+                    preview: match (messages, last) {
+                        (Some(range), _) => commands::PreviewTurns::Range(range),
+                        (None, Some(n)) => commands::PreviewTurns::Last(n),
+                        (None, None) => commands::PreviewTurns::First(preview_count),
+                    },
+                    full,
+                    threads: flatten_threads,
+                },
+                &discovery,
+            ),
             Some(ShowCommands::Path { project_path }) => {
-                commands::history_show(project_path.as_deref())
+                commands::history_show(project_path.as_deref(), &discovery)
             }
-            None => commands::history_show(None), // Default to current directory
+            None => commands::history_show(None, &discovery), // Default to current directory
         },
 
         // ====================================================================
         // Fetch Commands
         // ====================================================================
-        Commands::Fetch { command } => match command {
+        Commands::Fetch { dry_run, command } => match command {
             Some(FetchCommands::Workspace {
                 workspace_name,
                 target_path,
@@ -140,20 +377,33 @@ fn main() -> Result<()> {
                 target_path.as_deref(),
                 force,
                 no_register,
+                dry_run,
+                &discovery,
             ),
             Some(FetchCommands::Session {
                 session_ids,
                 target_path,
                 force,
                 no_register,
-            }) => {
-                commands::fetch_sessions(&session_ids, target_path.as_deref(), force, no_register)
-            }
+            }) => commands::fetch_sessions(
+                &session_ids,
+                target_path.as_deref(),
+                force,
+                no_register,
+                dry_run,
+                &discovery,
+            ),
             Some(FetchCommands::Path {
                 project_path,
                 force,
                 no_register,
-            }) => commands::history_fetch(project_path.as_deref(), force, no_register),
+            }) => commands::history_fetch(
+                project_path.as_deref(),
+                force,
+                no_register,
+                dry_run,
+                &discovery,
+            ),
             None => {
                 eprintln!("Usage: csm fetch <workspace|session|path> ...");
                 eprintln!("Run 'csm fetch --help' for more information.");
@@ -164,7 +414,7 @@ fn main() -> Result<()> {
         // ====================================================================
         // Merge Commands
         // ====================================================================
-        Commands::Merge { command } => match command {
+        Commands::Merge { dry_run, command } => match command {
             Some(MergeCommands::Workspace {
                 workspace_name,
                 title,
@@ -175,8 +425,12 @@ fn main() -> Result<()> {
                 &workspace_name,
                 title.as_deref(),
                 target_path.as_deref(),
-                force,
-                no_backup,
+                commands::MergeOptions {
+                    force,
+                    no_backup,
+                    dry_run,
+                },
+                &discovery,
             ),
             Some(MergeCommands::Workspaces {
                 workspace_names,
@@ -188,30 +442,68 @@ fn main() -> Result<()> {
                 &workspace_names,
                 title.as_deref(),
                 target_path.as_deref(),
-                force,
-                no_backup,
+                commands::MergeOptions {
+                    force,
+                    no_backup,
+                    dry_run,
+                },
+                &discovery,
             ),
             Some(MergeCommands::Sessions {
                 sessions,
+                from_file,
+                atomic,
                 title,
                 target_path,
+                output,
                 force,
                 no_backup,
-            }) => commands::merge_sessions_by_list(
-                &sessions,
-                title.as_deref(),
-                target_path.as_deref(),
-                force,
-                no_backup,
-            ),
+            }) => match from_file {
+                Some(list_file) => commands::merge_session_groups_from_file(
+                    &list_file,
+                    title.as_deref(),
+                    target_path.as_deref(),
+                    commands::MergeOptions {
+                        force,
+                        no_backup,
+                        dry_run,
+                    },
+                    atomic,
+                    &discovery,
+                ),
+                None => commands::merge_sessions_by_list(
+                    &sessions,
+                    title.as_deref(),
+                    target_path.as_deref(),
+                    output.as_deref(),
+                    commands::MergeOptions {
+                        force,
+                        no_backup,
+                        dry_run,
+                    },
+                    &discovery,
+                ),
+            },
+            Some(MergeCommands::Preview {
+                sessions,
+                title,
+                format,
+            }) => commands::merge_preview(&sessions, title.as_deref(), format, &discovery),
             Some(MergeCommands::Path {
                 project_path,
                 title,
                 force,
                 no_backup,
-            }) => {
-                commands::history_merge(project_path.as_deref(), title.as_deref(), force, no_backup)
-            }
+            }) => commands::history_merge(
+                project_path.as_deref(),
+                title.as_deref(),
+                commands::MergeOptions {
+                    force,
+                    no_backup,
+                    dry_run,
+                },
+                &discovery,
+            ),
             Some(MergeCommands::Provider {
                 provider_name,
                 title,
@@ -224,8 +516,12 @@ fn main() -> Result<()> {
                 title.as_deref(),
                 target_path.as_deref(),
                 sessions.as_deref(),
-                force,
-                no_backup,
+                commands::MergeOptions {
+                    force,
+                    no_backup,
+                    dry_run,
+                },
+                &discovery,
             ),
             Some(MergeCommands::Providers {
                 providers,
@@ -239,8 +535,12 @@ fn main() -> Result<()> {
                 title.as_deref(),
                 target_path.as_deref(),
                 workspace.as_deref(),
-                force,
-                no_backup,
+                commands::MergeOptions {
+                    force,
+                    no_backup,
+                    dry_run,
+                },
+                &discovery,
             ),
             Some(MergeCommands::All {
                 title,
@@ -252,8 +552,12 @@ fn main() -> Result<()> {
                 title.as_deref(),
                 target_path.as_deref(),
                 workspace.as_deref(),
-                force,
-                no_backup,
+                commands::MergeOptions {
+                    force,
+                    no_backup,
+                    dry_run,
+                },
+                &discovery,
             ),
             None => {
                 eprintln!("Usage: csm merge <workspace|workspaces|sessions|path|provider|providers|all> ...");
@@ -265,49 +569,155 @@ fn main() -> Result<()> {
         // ====================================================================
         // Export Commands
         // ====================================================================
-        Commands::Export { command } => match command {
-            Some(ExportCommands::Workspace { destination, hash }) => {
-                commands::export_sessions(&destination, Some(&hash), None)
-            }
-            Some(ExportCommands::Sessions {
-                destination,
-                session_ids,
-                project_path,
-            }) => commands::export_specific_sessions(
-                &destination,
-                &session_ids,
-                project_path.as_deref(),
-            ),
-            Some(ExportCommands::Path {
-                destination,
-                project_path,
-            }) => commands::export_sessions(&destination, None, project_path.as_deref()),
-            None => {
-                eprintln!("Usage: csm export <workspace|sessions|path> ...");
-                eprintln!("Run 'csm export --help' for more information.");
-                Ok(())
+        Commands::Export {
+            format,
+            redact,
+            anonymize,
+            template,
+            dump_context,
+            stdin_ids,
+            output,
+            messages,
+            flatten_threads,
+            layout,
+            split_by_code,
+            gzip,
+            archive,
+            command,
+        } => {
+            let options = commands::ExportOptions {
+                format,
+                redact,
+                anonymize,
+                template,
+                messages,
+                threads: flatten_threads,
+                layout,
+                split_by_code,
+                gzip,
+                archive,
+            };
+            match command {
+                Some(_) if stdin_ids => {
+                    anyhow::bail!(
+                        "--stdin-ids takes no subcommand: csm export --stdin-ids --output <dir>"
+                    )
+                }
+                None if stdin_ids => {
+                    commands::export_stdin_ids(&output.unwrap_or_default(), &options, &discovery)
+                }
+                Some(ExportCommands::Workspace {
+                    destination,
+                    hash,
+                    since,
+                    until,
+                    combine,
+                }) => {
+                    let export = if combine {
+                        commands::export_combined
+                    } else {
+                        commands::export_sessions
+                    };
+                    export(
+                        &destination,
+                        Some(&hash),
+                        None,
+                        since.as_deref(),
+                        until.as_deref(),
+                        &options,
+                        &discovery,
+                    )
+                }
+                Some(ExportCommands::All {
+                    destination,
+                    since,
+                    until,
+                }) => commands::export_all_sessions(
+                    &destination,
+                    since.as_deref(),
+                    until.as_deref(),
+                    &options,
+                    &discovery,
+                ),
+                Some(ExportCommands::Sessions {
+                    destination,
+                    session_ids,
+                    project_path,
+                }) => commands::export_specific_sessions(
+                    &destination,
+                    &session_ids,
+                    project_path.as_deref(),
+                    &options,
+                    &discovery,
+                ),
+                Some(ExportCommands::Path {
+                    destination,
+                    project_path,
+                    since,
+                    until,
+                    combine,
+                }) => {
+                    let export = if combine {
+                        commands::export_combined
+                    } else {
+                        commands::export_sessions
+                    };
+                    export(
+                        &destination,
+                        None,
+                        project_path.as_deref(),
+                        since.as_deref(),
+                        until.as_deref(),
+                        &options,
+                        &discovery,
+                    )
+                }
+                Some(ExportCommands::Obsidian { output }) => {
+                    commands::export_obsidian(&output, &options, &discovery)
+                }
+                None => match dump_context {
+                    Some(session_id) => commands::export_dump_context(&session_id, &discovery),
+                    None => {
+                        eprintln!("Usage: csm export <workspace|all|sessions|path> ...");
+                        eprintln!("Run 'csm export --help' for more information.");
+                        Ok(())
+                    }
+                },
             }
-        },
+        }
 
         // ====================================================================
         // Import Commands
         // ====================================================================
-        Commands::Import { command } => match command {
+        Commands::Import { dry_run, command } => match command {
             Some(ImportCommands::Workspace {
                 source,
                 hash,
                 force,
-            }) => commands::import_sessions(&source, Some(&hash), None, force),
+            }) => commands::import_sessions(&source, Some(&hash), None, force, dry_run, &discovery),
             Some(ImportCommands::Sessions {
                 session_files,
                 target_path,
                 force,
-            }) => commands::import_specific_sessions(&session_files, target_path.as_deref(), force),
+            }) => commands::import_specific_sessions(
+                &session_files,
+                target_path.as_deref(),
+                force,
+                dry_run,
+                &discovery,
+            ),
             Some(ImportCommands::Path {
                 source,
                 target_path,
                 force,
-            }) => commands::import_sessions(&source, None, target_path.as_deref(), force),
+            }) => commands::import_sessions(
+                &source,
+                None,
+                target_path.as_deref(),
+                force,
+                dry_run,
+                &discovery,
+            ),
             None => {
                 eprintln!("Usage: csm import <workspace|sessions|path> ...");
                 eprintln!("Run 'csm import --help' for more information.");
@@ -318,19 +728,19 @@ fn main() -> Result<()> {
         // ====================================================================
         // Move Commands
         // ====================================================================
-        Commands::Move { command } => match command {
+        Commands::Move { dry_run, command } => match command {
             Some(MoveCommands::Workspace {
                 source_hash,
                 target,
-            }) => commands::move_workspace(&source_hash, &target),
+            }) => commands::move_workspace(&source_hash, &target, dry_run, &discovery),
             Some(MoveCommands::Sessions {
                 session_ids,
                 target_path,
-            }) => commands::move_specific_sessions(&session_ids, &target_path),
+            }) => commands::move_specific_sessions(&session_ids, &target_path, dry_run, &discovery),
             Some(MoveCommands::Path {
                 source_path,
                 target_path,
-            }) => commands::move_by_path(&source_path, &target_path),
+            }) => commands::move_by_path(&source_path, &target_path, dry_run, &discovery),
             None => {
                 eprintln!("Usage: csm move <workspace|sessions|path> ...");
                 eprintln!("Run 'csm move --help' for more information.");
@@ -345,40 +755,81 @@ fn main() -> Result<()> {
             GitCommands::Config { name, email, path } => {
                 commands::git_config(name.as_deref(), email.as_deref(), path.as_deref())
             }
-            GitCommands::Init { path } => commands::git_init(&path),
+            GitCommands::Init {
+                path,
+                link_session,
+                commit,
+            } => match (link_session, path) {
+                (Some(session), path) => {
+                    commands::git_init_linked(path.as_deref(), &session, commit, &discovery)
+                }
+                (None, Some(path)) => commands::git_init(&path, &discovery),
+                (None, None) => unreachable!("clap requires a path without --link-session"),
+            },
             GitCommands::Add {
                 path,
                 commit,
                 message,
             } => commands::git_add(&path, commit, message.as_deref()),
-            GitCommands::Status { path } => commands::git_status(&path),
-            GitCommands::Snapshot { path, tag, message } => {
-                commands::git_snapshot(&path, tag.as_deref(), message.as_deref())
-            }
+            GitCommands::Status { path, session } => match session {
+                Some(session) => commands::git_status_session(&path, &session, &discovery),
+                None => commands::git_status(&path, &discovery),
+            },
+            GitCommands::Snapshot {
+                path,
+                tag,
+                message,
+                session,
+                note,
+            } => commands::git_snapshot(
+                &path,
+                tag.as_deref(),
+                message.as_deref(),
+                session.as_deref(),
+                note,
+                &discovery,
+            ),
+            GitCommands::Track {
+                path,
+                session,
+                amend: true,
+                force,
+                ..
+            } => commands::git_track_amend(&path, session.as_deref(), force, &discovery),
             GitCommands::Track {
                 path,
                 message,
                 all,
                 files,
                 tag,
+                session,
+                amend: false,
+                force: _,
             } => commands::git_track(
                 &path,
                 message.as_deref(),
                 all,
                 files.as_deref(),
                 tag.as_deref(),
+                session.as_deref(),
+                &discovery,
             ),
             GitCommands::Log {
                 path,
                 count,
                 sessions_only,
-            } => commands::git_log(&path, count, sessions_only),
+                session,
+            } => commands::git_log(&path, count, sessions_only, session.as_deref()),
             GitCommands::Diff {
                 path,
                 from,
                 to,
                 with_files,
-            } => commands::git_diff(&path, from.as_deref(), to.as_deref(), with_files),
+                session,
+            } => match session {
+                Some(session) => commands::git_diff_session(&path, &session, &discovery),
+                None => commands::git_diff(&path, from.as_deref(), to.as_deref(), with_files),
+            },
             GitCommands::Restore {
                 path,
                 commit,
@@ -395,7 +846,7 @@ fn main() -> Result<()> {
                 output,
                 projects,
                 all,
-            } => commands::create_migration(&output, projects.as_deref(), all),
+            } => commands::create_migration(&output, projects.as_deref(), all, &discovery),
             MigrationCommands::Restore {
                 package,
                 mapping,
@@ -407,15 +858,32 @@ fn main() -> Result<()> {
         // Run Commands (TUI)
         // ====================================================================
         Commands::Run { command } => match command {
-            RunCommands::Tui => tui::run_tui(),
+            RunCommands::Tui { watch } => tui::run_tui(watch, &discovery),
         },
 
         // ====================================================================
         // Provider Commands
         // ====================================================================
         Commands::Provider { command } => match command {
-            ProviderCommands::List => commands::list_providers(),
-            ProviderCommands::Info { provider } => commands::provider_info(&provider),
+            ProviderCommands::List {
+                probe,
+                timeout,
+                retries,
+            } => commands::list_providers(probe, timeout, retries),
+            ProviderCommands::Add {
+                name,
+                base_url,
+                api_key_env,
+            } => commands::add_custom_provider(&name, &base_url, api_key_env.as_deref()),
+            ProviderCommands::Remove { name } => commands::remove_custom_provider(&name),
+            ProviderCommands::Info {
+                provider,
+                all: _,
+                probe,
+                timeout,
+                retries,
+                json,
+            } => commands::provider_info(provider.as_deref(), probe, timeout, retries, json),
             ProviderCommands::Config {
                 provider,
                 endpoint,
@@ -433,27 +901,50 @@ fn main() -> Result<()> {
                 from,
                 path,
                 session,
-            } => commands::import_from_provider(&from, path.as_deref(), session.as_deref()),
+            } => commands::import_from_provider(
+                &from,
+                path.as_deref(),
+                session.as_deref(),
+                &discovery,
+            ),
             ProviderCommands::Test { provider } => commands::test_provider(&provider),
+            ProviderCommands::Models { provider, timeout } => {
+                commands::list_provider_models(&provider, timeout)
+            }
         },
 
         // ====================================================================
         // Detect Commands
         // ====================================================================
         Commands::Detect { command } => match command {
-            Some(DetectCommands::Workspace { path }) => commands::detect_workspace(path.as_deref()),
-            Some(DetectCommands::Providers { with_sessions }) => {
-                commands::detect_providers(with_sessions)
+            Some(DetectCommands::Workspace { path }) => {
+                commands::detect_workspace(path.as_deref(), &discovery)
+            }
+            Some(DetectCommands::Providers {
+                with_sessions,
+                json,
+                require,
+                timeout,
+                retries,
+                verbose,
+            }) => {
+                if !json {
+                    commands::detect_providers(with_sessions)?;
+                }
+                if json || !require.is_empty() {
+                    commands::check_providers(json, &require, timeout, retries, verbose)?;
+                }
+                Ok(())
             }
             Some(DetectCommands::Session { session_id, path }) => {
-                commands::detect_session(&session_id, path.as_deref())
+                commands::detect_session(&session_id, path.as_deref(), &discovery)
             }
             Some(DetectCommands::All { path, verbose }) => {
-                commands::detect_all(path.as_deref(), verbose)
+                commands::detect_all(path.as_deref(), verbose, &discovery)
             }
             None => {
                 // Default to detect all for current directory
-                commands::detect_all(None, false)
+                commands::detect_all(None, false, &discovery)
             }
         },
 
@@ -467,12 +958,14 @@ fn main() -> Result<()> {
                 web,
                 timeout,
                 verbose,
-            } => commands::harvest_scan(sessions, web, timeout, verbose),
+                json,
+            } => commands::harvest_scan(sessions, web, timeout, verbose, json, &discovery),
             HarvestCommands::Run {
                 path,
                 providers,
                 exclude,
                 incremental,
+                since_commit,
                 commit,
                 message,
             } => commands::harvest_run(
@@ -480,8 +973,13 @@ fn main() -> Result<()> {
                 providers.as_deref(),
                 exclude.as_deref(),
                 incremental,
-                commit,
-                message.as_deref(),
+                since_commit.as_deref(),
+                commit.then(|| {
+                    message
+                        .as_deref()
+                        .unwrap_or(commands::HARVEST_COMMIT_MESSAGE)
+                }),
+                &discovery,
             ),
             HarvestCommands::Status { path } => commands::harvest_status(path.as_deref()),
             HarvestCommands::List {
@@ -497,17 +995,23 @@ fn main() -> Result<()> {
             ),
             HarvestCommands::Export {
                 output,
+                to,
                 path,
                 format,
                 provider,
                 sessions,
-            } => commands::harvest_export(
-                path.as_deref(),
-                &output,
-                &format,
-                provider.as_deref(),
-                sessions.as_deref(),
-            ),
+                redact,
+            } => match to {
+                Some(db) => commands::harvest_export_sqlite(&db, redact, &discovery),
+                None => commands::harvest_export(
+                    path.as_deref(),
+                    output.as_deref(),
+                    &format,
+                    provider.as_deref(),
+                    sessions.as_deref(),
+                    redact,
+                ),
+            },
             HarvestCommands::Share {
                 url,
                 path,
@@ -544,18 +1048,26 @@ fn main() -> Result<()> {
                 provider,
                 limit,
             } => commands::harvest_search(path.as_deref(), &query, provider.as_deref(), limit),
-            HarvestCommands::Git { command: git_cmd } => match git_cmd {
-                HarvestGitCommands::Init { path } => commands::harvest_git_init(path.as_deref()),
-                HarvestGitCommands::Commit { path, message } => {
+            HarvestCommands::Git {
+                auto_link: true, ..
+            } => commands::harvest_git_auto_link(&discovery),
+            HarvestCommands::Git {
+                command: git_cmd, ..
+            } => match git_cmd {
+                None => unreachable!("clap requires a subcommand or --auto-link"),
+                Some(HarvestGitCommands::Init { path }) => {
+                    commands::harvest_git_init(path.as_deref())
+                }
+                Some(HarvestGitCommands::Commit { path, message }) => {
                     commands::harvest_git_commit(path.as_deref(), message.as_deref())
                 }
-                HarvestGitCommands::Log { path, count } => {
+                Some(HarvestGitCommands::Log { path, count }) => {
                     commands::harvest_git_log(path.as_deref(), count)
                 }
-                HarvestGitCommands::Diff { path, commit } => {
+                Some(HarvestGitCommands::Diff { path, commit }) => {
                     commands::harvest_git_diff(path.as_deref(), commit.as_deref())
                 }
-                HarvestGitCommands::Restore { commit, path } => {
+                Some(HarvestGitCommands::Restore { commit, path }) => {
                     commands::harvest_git_restore(path.as_deref(), &commit)
                 }
             },
@@ -564,16 +1076,23 @@ fn main() -> Result<()> {
         // ====================================================================
         // Register Commands
         // ====================================================================
-        Commands::Register { command } => match command {
+        Commands::Register { dry_run, command } => match command {
             cli::RegisterCommands::All { path, merge, force } => {
-                commands::register_all(path.as_deref(), merge, force)
+                commands::register_all(path.as_deref(), merge, force, dry_run, &discovery)
             }
             cli::RegisterCommands::Session {
                 ids,
                 title,
                 path,
                 force,
-            } => commands::register_sessions(&ids, title.as_deref(), path.as_deref(), force),
+            } => commands::register_sessions(
+                &ids,
+                title.as_deref(),
+                path.as_deref(),
+                force,
+                dry_run,
+                &discovery,
+            ),
         },
 
         // ====================================================================
@@ -604,6 +1123,17 @@ fn main() -> Result<()> {
             }
         },
 
+        Commands::Serve { host, port } => {
+            let rt = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()?;
+            rt.block_on(api::start_local_server(api::LocalServerConfig {
+                host,
+                port,
+                discovery,
+            }))
+        }
+
         // ====================================================================
         // Agency (Agent Development Kit)
         // ====================================================================
@@ -628,6 +1158,27 @@ fn main() -> Result<()> {
             AgencyCommands::Templates => commands::list_templates(),
         },
 
+        // ====================================================================
+        // Schema Command
+        // ====================================================================
+        Commands::Schema => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&schema::session_schema())?
+            );
+            Ok(())
+        }
+
+        // ====================================================================
+        // Doctor Command
+        // ====================================================================
+        Commands::Doctor => {
+            if !commands::run_doctor(&discovery)? {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+
         // ====================================================================
         // Easter Egg
         // ====================================================================
@@ -5,6 +5,7 @@
 #![allow(dead_code, unused_imports)]
 
 use super::types::*;
+use crate::workspace::DiscoveryOptions;
 use serde_json::json;
 
 /// Get the list of available resources
@@ -94,7 +95,7 @@ pub fn read_resource(uri: &str) -> ReadResourceResult {
 fn read_workspaces_resource() -> ReadResourceResult {
     use crate::workspace::discover_workspaces;
 
-    match discover_workspaces() {
+    match discover_workspaces(&DiscoveryOptions::default()) {
         Ok(workspaces) => {
             let infos: Vec<serde_json::Value> = workspaces
                 .iter()
@@ -138,7 +139,7 @@ fn read_workspaces_resource() -> ReadResourceResult {
 fn read_sessions_resource() -> ReadResourceResult {
     use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace};
 
-    match discover_workspaces() {
+    match discover_workspaces(&DiscoveryOptions::default()) {
         Ok(workspaces) => {
             let mut all_sessions = Vec::new();
 
@@ -233,7 +234,7 @@ fn read_providers_resource() -> ReadResourceResult {
 fn read_workspace_resource(hash: &str) -> ReadResourceResult {
     use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace};
 
-    match discover_workspaces() {
+    match discover_workspaces(&DiscoveryOptions::default()) {
         Ok(workspaces) => {
             for ws in &workspaces {
                 if ws.hash.starts_with(hash) || ws.hash == hash {
@@ -295,7 +296,7 @@ fn read_workspace_resource(hash: &str) -> ReadResourceResult {
 fn read_session_resource(session_id: &str) -> ReadResourceResult {
     use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace};
 
-    match discover_workspaces() {
+    match discover_workspaces(&DiscoveryOptions::default()) {
         Ok(workspaces) => {
             for ws in &workspaces {
                 if let Ok(sessions) = get_chat_sessions_from_workspace(&ws.workspace_path) {
@@ -5,6 +5,7 @@
 #![allow(dead_code, unused_imports)]
 
 use super::types::*;
+use crate::workspace::DiscoveryOptions;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
@@ -436,7 +437,7 @@ pub fn call_tool(name: &str, arguments: &HashMap<String, serde_json::Value>) ->
 fn execute_list_workspaces() -> CallToolResult {
     use crate::workspace::discover_workspaces;
 
-    match discover_workspaces() {
+    match discover_workspaces(&DiscoveryOptions::default()) {
         Ok(workspaces) => {
             let infos: Vec<McpWorkspaceInfo> = workspaces
                 .iter()
@@ -471,7 +472,7 @@ fn execute_list_workspaces() -> CallToolResult {
 fn execute_find_workspace(pattern: &str) -> CallToolResult {
     use crate::workspace::{find_all_workspaces_for_project, get_chat_sessions_from_workspace};
 
-    match find_all_workspaces_for_project(pattern) {
+    match find_all_workspaces_for_project(pattern, &DiscoveryOptions::default()) {
         Ok(workspaces) => {
             let infos: Vec<serde_json::Value> = workspaces
                 .iter()
@@ -521,7 +522,7 @@ fn execute_find_workspace(pattern: &str) -> CallToolResult {
 fn execute_list_sessions(project_path: Option<&str>) -> CallToolResult {
     use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace};
 
-    match discover_workspaces() {
+    match discover_workspaces(&DiscoveryOptions::default()) {
         Ok(workspaces) => {
             let mut all_sessions = Vec::new();
 
@@ -576,7 +577,7 @@ fn execute_list_orphaned(path: Option<&str>) -> CallToolResult {
 
     // Capture stdout (list_orphaned prints to stdout)
     // For now, we'll call it and return a simplified response
-    match list_orphaned(path) {
+    match list_orphaned(path, &DiscoveryOptions::default()) {
         Ok(_) => CallToolResult {
             content: vec![ToolContent::Text {
                 text: json!({
@@ -599,7 +600,7 @@ fn execute_list_orphaned(path: Option<&str>) -> CallToolResult {
 fn execute_register_all(path: Option<&str>, merge: bool, force: bool) -> CallToolResult {
     use crate::commands::register_all;
 
-    match register_all(path, merge, force) {
+    match register_all(path, merge, force, false, &DiscoveryOptions::default()) {
         Ok(_) => CallToolResult {
             content: vec![ToolContent::Text {
                 text: json!({
@@ -629,7 +630,14 @@ fn execute_register_sessions(
 ) -> CallToolResult {
     use crate::commands::register_sessions;
 
-    match register_sessions(ids, titles, path, force) {
+    match register_sessions(
+        ids,
+        titles,
+        path,
+        force,
+        false,
+        &DiscoveryOptions::default(),
+    ) {
         Ok(_) => CallToolResult {
             content: vec![ToolContent::Text {
                 text: json!({
@@ -655,7 +663,7 @@ fn execute_register_sessions(
 fn execute_show_session(session_id: &str) -> CallToolResult {
     use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace};
 
-    match discover_workspaces() {
+    match discover_workspaces(&DiscoveryOptions::default()) {
         Ok(workspaces) => {
             for ws in &workspaces {
                 if let Ok(sessions) = get_chat_sessions_from_workspace(&ws.workspace_path) {
@@ -715,7 +723,7 @@ fn execute_show_session(session_id: &str) -> CallToolResult {
 fn execute_show_history(path: Option<&str>) -> CallToolResult {
     use crate::commands::history_show;
 
-    match history_show(path) {
+    match history_show(path, &DiscoveryOptions::default()) {
         Ok(_) => CallToolResult {
             content: vec![ToolContent::Text {
                 text: json!({
@@ -736,9 +744,13 @@ fn execute_show_history(path: Option<&str>) -> CallToolResult {
 }
 
 fn execute_merge_sessions(path: Option<&str>, title: Option<&str>, force: bool) -> CallToolResult {
-    use crate::commands::history_merge;
+    use crate::commands::{history_merge, MergeOptions};
 
-    match history_merge(path, title, force, false) {
+    let options = MergeOptions {
+        force,
+        ..Default::default()
+    };
+    match history_merge(path, title, options, &DiscoveryOptions::default()) {
         Ok(_) => CallToolResult {
             content: vec![ToolContent::Text {
                 text: json!({
@@ -790,7 +802,7 @@ fn execute_search(query: &str, limit: Option<usize>) -> CallToolResult {
 fn execute_detect(path: Option<&str>) -> CallToolResult {
     use crate::commands::detect_all;
 
-    match detect_all(path, false) {
+    match detect_all(path, false, &DiscoveryOptions::default()) {
         Ok(_) => CallToolResult {
             content: vec![ToolContent::Text {
                 text: json!({
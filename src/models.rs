@@ -11,6 +11,14 @@ use std::collections::HashMap;
 pub struct Workspace {
     /// Workspace hash (folder name in workspaceStorage)
     pub hash: String,
+    /// Directory the workspace was found in, relative to the storage root
+    /// (only set for nested scans, where hashes may repeat across roots)
+    pub storage_root: Option<String>,
+    /// VS Code build (Stable or Insiders) whose storage holds the workspace
+    pub channel: crate::workspace::VsCodeChannel,
+    /// Found in a VS Code Server (or Cursor server) layout, as on the remote
+    /// host of a Remote - SSH session
+    pub remote: bool,
     /// Associated project path
     pub project_path: Option<String>,
     /// Full path to workspace directory
@@ -19,7 +27,7 @@ pub struct Workspace {
     pub chat_sessions_path: std::path::PathBuf,
     /// Number of chat session files
     pub chat_session_count: usize,
-    /// Whether chatSessions directory exists
+    /// Whether the chatSessions directory contains any session files
     pub has_chat_sessions: bool,
     /// Last modified timestamp
     #[allow(dead_code)]
@@ -30,6 +38,20 @@ pub struct Workspace {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceJson {
     pub folder: Option<String>,
+    /// `.code-workspace` file URI, set instead of `folder` for multi-root workspaces
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+impl WorkspaceJson {
+    /// Decoded project path: the folder, or the `.code-workspace` file for
+    /// multi-root workspaces
+    pub fn project_path(&self) -> Option<String> {
+        self.folder
+            .as_deref()
+            .or(self.workspace.as_deref())
+            .map(crate::workspace::decode_workspace_folder)
+    }
 }
 
 /// VS Code Chat Session (version 3 format)
@@ -105,8 +127,10 @@ pub struct ChatRequest {
     #[serde(default)]
     pub message: Option<ChatMessage>,
 
-    /// The AI's response (complex structure - use Value for flexibility)
-    #[serde(default)]
+    /// The AI's response (complex structure - use Value for flexibility).
+    /// Either an array of parts, a `{ "value": [...] }` object, or, in newer
+    /// sessions, a single `responseMessage` object.
+    #[serde(default, alias = "responseMessage")]
     pub response: Option<serde_json::Value>,
 
     /// Variable data (context, files, etc.)
@@ -156,14 +180,196 @@ pub struct ChatRequest {
     /// Source session for merged requests
     #[serde(rename = "_sourceSession", skip_serializing_if = "Option::is_none")]
     pub source_session: Option<String>,
+
+    /// Earlier versions of this turn, oldest first, when it was edited or
+    /// regenerated; the request's own message and response are the newest
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<ChatBranch>,
+
+    /// Which version of the turn is shown: an index into `branches`, or
+    /// `branches.len()` for the request itself. `None` means the newest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_branch: Option<usize>,
+}
+
+/// An earlier version of a [`ChatRequest`] turn
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatBranch {
+    /// Branch timestamp (milliseconds)
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+
+    /// The prompt of this version; `None` when the response was regenerated,
+    /// reusing the prompt of the version before it
+    #[serde(default)]
+    pub message: Option<ChatMessage>,
+
+    /// The response of this version, in any shape [`ChatRequest::response`]
+    /// takes
+    #[serde(default, alias = "responseMessage")]
+    pub response: Option<serde_json::Value>,
+
+    /// Model ID
+    #[serde(default)]
+    pub model_id: Option<String>,
+}
+
+impl ChatRequest {
+    /// Model this request ran on, without the vendor prefix VS Code stores
+    /// (`copilot/gpt-4o` -> `gpt-4o`)
+    pub fn model(&self) -> Option<&str> {
+        let id = self.model_id.as_deref()?.trim();
+        let name = id.rsplit('/').next().unwrap_or(id);
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// ID of the chat participant that answered (e.g. `github.copilot.editsAgent`)
+    pub fn agent_id(&self) -> Option<&str> {
+        self.agent
+            .as_ref()?
+            .get("id")
+            .and_then(|id| id.as_str())
+            .filter(|id| !id.is_empty())
+    }
+
+    /// Tool/function invocations recorded in the message or response parts
+    pub fn tool_calls(&self) -> Vec<ToolCall> {
+        let message_parts = self
+            .message
+            .as_ref()
+            .and_then(|m| m.parts.as_deref())
+            .unwrap_or_default();
+        let response_parts = self
+            .response
+            .as_ref()
+            .and_then(|r| {
+                r.as_array()
+                    .or_else(|| r.get("value").and_then(|v| v.as_array()))
+                    .or_else(|| r.get("parts").and_then(|v| v.as_array()))
+            })
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        message_parts
+            .iter()
+            .chain(response_parts)
+            .filter_map(ToolCall::from_part)
+            .collect()
+    }
+
+    /// Local files attached to or cited by this request: every `fsPath` in
+    /// its variables and content references
+    pub fn referenced_files(&self) -> Vec<String> {
+        fn collect(value: &serde_json::Value, files: &mut Vec<String>) {
+            match value {
+                serde_json::Value::Object(map) => {
+                    for (key, value) in map {
+                        match value.as_str() {
+                            Some(path) if key == "fsPath" && !path.is_empty() => {
+                                files.push(path.to_string())
+                            }
+                            _ => collect(value, files),
+                        }
+                    }
+                }
+                serde_json::Value::Array(items) => {
+                    items.iter().for_each(|item| collect(item, files))
+                }
+                _ => {}
+            }
+        }
+
+        let mut files = Vec::new();
+        if let Some(variables) = &self.variable_data {
+            collect(variables, &mut files);
+        }
+        for reference in self.content_references.iter().flatten() {
+            collect(reference, &mut files);
+        }
+        files
+    }
+}
+
+/// A tool or function call made during a request (agent-style sessions)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolCall {
+    /// Tool name (e.g. `read_file`)
+    pub name: String,
+    /// Arguments, as text or compact JSON
+    pub arguments: Option<String>,
+    /// Result, as text or compact JSON
+    pub result: Option<String>,
+}
+
+impl ToolCall {
+    /// Part `kind` values that carry a tool invocation
+    const KINDS: &'static [&'static str] = &[
+        "toolInvocation",
+        "toolInvocationSerialized",
+        "toolCall",
+        "functionCall",
+        "tool_use",
+    ];
+
+    /// Parse a message/response part, if it is a tool call
+    ///
+    /// Understands VS Code `toolInvocationSerialized` parts as well as generic
+    /// `{ name, arguments, result }` and OpenAI `{ function: { name, arguments } }`
+    /// shapes.
+    pub fn from_part(part: &serde_json::Value) -> Option<Self> {
+        let kind = part
+            .get("kind")
+            .or_else(|| part.get("type"))
+            .and_then(|k| k.as_str());
+        let function = part.get("function").filter(|f| f.is_object());
+        if !kind.is_some_and(|k| Self::KINDS.contains(&k)) && function.is_none() {
+            return None;
+        }
+
+        let field = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|n| part.get(*n).or_else(|| function?.get(*n)))
+                .and_then(value_text)
+        };
+
+        Some(Self {
+            name: field(&["toolName", "toolId", "name"])?,
+            arguments: field(&["arguments", "input", "parameters", "invocationMessage"]),
+            result: field(&["result", "output", "resultDetails", "pastTenseMessage"]),
+        })
+    }
+
+    /// One-line summary, e.g. `read_file({"path":"a.rs"}) -> fn main() {}`
+    pub fn summary(&self) -> String {
+        let mut line = format!("{}({})", self.name, self.arguments.as_deref().unwrap_or(""));
+        if let Some(result) = &self.result {
+            line.push_str(" -> ");
+            line.push_str(result);
+        }
+        line
+    }
+}
+
+/// Text of a JSON value: strings as-is, `{ "value": "..." }` unwrapped,
+/// anything else as compact JSON
+fn value_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        _ => match value.get("value").and_then(|v| v.as_str()) {
+            Some(s) => Some(s.to_string()),
+            None => Some(value.to_string()),
+        },
+    }
 }
 
 /// User message in a chat request
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", from = "ChatMessageRepr")]
 pub struct ChatMessage {
     /// Message text
-    #[serde(alias = "content")]
     pub text: Option<String>,
 
     /// Message parts (for complex messages)
@@ -171,6 +377,45 @@ pub struct ChatMessage {
     pub parts: Option<Vec<serde_json::Value>>,
 }
 
+/// Accepted on-disk layouts of a request message
+///
+/// Older sessions store `{ "text", "parts" }`; newer ones may store a bare
+/// string, or only `parts` with no top-level `text`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChatMessageRepr {
+    Text(String),
+    Object {
+        #[serde(default, alias = "content")]
+        text: Option<String>,
+        #[serde(default)]
+        parts: Option<Vec<serde_json::Value>>,
+    },
+}
+
+impl From<ChatMessageRepr> for ChatMessage {
+    fn from(repr: ChatMessageRepr) -> Self {
+        match repr {
+            ChatMessageRepr::Text(text) => Self {
+                text: Some(text),
+                parts: None,
+            },
+            ChatMessageRepr::Object { text, parts } => {
+                // Rebuild the text from its parts when only parts were stored
+                let text = text.or_else(|| {
+                    let joined: String = parts
+                        .iter()
+                        .flatten()
+                        .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+                        .collect();
+                    (!joined.is_empty()).then_some(joined)
+                });
+                Self { text, parts }
+            }
+        }
+    }
+}
+
 impl ChatMessage {
     /// Get the text content of this message
     pub fn get_text(&self) -> String {
@@ -314,6 +559,40 @@ impl ChatSession {
         self.requests.len()
     }
 
+    /// Models used by this session's requests, most used first
+    pub fn model_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for model in self.requests.iter().filter_map(ChatRequest::model) {
+            match counts.iter_mut().find(|(name, _)| name == model) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((model.to_string(), 1)),
+            }
+        }
+        // Stable, so ties keep first-use order
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        counts
+    }
+
+    /// The model used for most of this session's requests
+    pub fn dominant_model(&self) -> DominantModel {
+        let counts = self.model_counts();
+        let total: usize = counts.iter().map(|(_, count)| count).sum();
+        match counts.into_iter().next() {
+            None => DominantModel::Unknown,
+            Some((name, count)) if count * 2 > total => DominantModel::Model(name),
+            Some(_) => DominantModel::Mixed,
+        }
+    }
+
+    /// Local files the session's requests attached or cited, deduplicated
+    /// and sorted
+    pub fn referenced_files(&self) -> std::collections::BTreeSet<String> {
+        self.requests
+            .iter()
+            .flat_map(ChatRequest::referenced_files)
+            .collect()
+    }
+
     /// Get the timestamp range of requests
     pub fn timestamp_range(&self) -> Option<(i64, i64)> {
         if self.requests.is_empty() {
@@ -331,3 +610,24 @@ impl ChatSession {
         Some((min, max))
     }
 }
+
+/// Summary of which model a session ran on, for one-column displays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DominantModel {
+    /// Used for more than half of the requests that record a model
+    Model(String),
+    /// Several models, none of them a majority
+    Mixed,
+    /// No request records a model
+    Unknown,
+}
+
+impl std::fmt::Display for DominantModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DominantModel::Model(name) => f.write_str(name),
+            DominantModel::Mixed => f.write_str("mixed"),
+            DominantModel::Unknown => f.write_str("—"),
+        }
+    }
+}
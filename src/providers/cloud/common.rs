@@ -88,6 +88,8 @@ impl CloudConversation {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: Some(format!("{}:{}", provider_name, self.id)),
+                    branches: Vec::new(),
+                    selected_branch: None,
                 };
 
                 // Check if next message is assistant response
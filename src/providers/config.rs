@@ -5,6 +5,7 @@
 #![allow(dead_code)]
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Supported LLM provider types
@@ -229,6 +230,26 @@ impl ProviderType {
     pub fn requires_api_key(&self) -> bool {
         self.is_cloud_provider()
     }
+
+    /// Parse a built-in provider name or alias (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "copilot" | "github-copilot" | "vscode" => Some(Self::Copilot),
+            "cursor" => Some(Self::Cursor),
+            "ollama" => Some(Self::Ollama),
+            "vllm" => Some(Self::Vllm),
+            "foundry" | "azure-foundry" | "foundry-local" | "ai-foundry" => Some(Self::Foundry),
+            "openai" => Some(Self::OpenAI),
+            "lm-studio" | "lmstudio" => Some(Self::LmStudio),
+            "localai" | "local-ai" => Some(Self::LocalAI),
+            "text-gen-webui" | "textgenwebui" | "oobabooga" => Some(Self::TextGenWebUI),
+            "jan" | "jan-ai" | "janai" => Some(Self::Jan),
+            "gpt4all" => Some(Self::Gpt4All),
+            "llamafile" => Some(Self::Llamafile),
+            "custom" => Some(Self::Custom),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for ProviderType {
@@ -259,6 +280,10 @@ pub struct ProviderConfig {
     /// Custom name for this provider instance
     pub name: Option<String>,
 
+    /// Environment variable holding the API key (custom providers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+
     /// Path to session storage (for file-based providers)
     pub storage_path: Option<PathBuf>,
 
@@ -281,17 +306,52 @@ impl ProviderConfig {
             api_key: None,
             model: None,
             name: None,
+            api_key_env: None,
             storage_path: None,
             extra: std::collections::HashMap::new(),
         }
     }
 
+    /// Create a user-defined OpenAI-compatible provider
+    pub fn custom(name: &str, base_url: &str, api_key_env: Option<&str>) -> Self {
+        Self {
+            endpoint: Some(base_url.to_string()),
+            name: Some(name.to_string()),
+            api_key_env: api_key_env.map(String::from),
+            ..Self::new(ProviderType::Custom)
+        }
+    }
+
     /// Get the display name for this provider
     pub fn display_name(&self) -> String {
         self.name
             .clone()
             .unwrap_or_else(|| self.provider_type.display_name().to_string())
     }
+
+    /// Resolve the API key, reading `api_key_env` if no key is stored
+    pub fn resolve_api_key(&self) -> Option<String> {
+        self.api_key.clone().or_else(|| {
+            self.api_key_env
+                .as_deref()
+                .and_then(|var| std::env::var(var).ok())
+        })
+    }
+}
+
+/// Validate a custom provider base URL, returning it without a trailing slash
+pub fn validate_base_url(url: &str) -> anyhow::Result<String> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| anyhow::anyhow!("Invalid base URL '{}': {}", url, e))?;
+
+    if !matches!(parsed.scheme(), "http" | "https") {
+        anyhow::bail!("Invalid base URL '{}': scheme must be http or https", url);
+    }
+    if !parsed.host_str().is_some_and(|h| !h.is_empty()) {
+        anyhow::bail!("Invalid base URL '{}': missing host", url);
+    }
+
+    Ok(url.trim_end_matches('/').to_string())
 }
 
 /// Global CSM configuration including all providers
@@ -307,6 +367,106 @@ pub struct CsmConfig {
     /// Whether to auto-discover providers
     #[serde(default = "default_true")]
     pub auto_discover: bool,
+
+    /// Editor command used by `open session` (defaults to `code`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor_cmd: Option<String>,
+
+    /// Extra regexes applied by `export --redact`, on top of the built-ins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redact_patterns: Vec<String>,
+
+    /// Project path globs always excluded from workspace discovery
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_globs: Vec<String>,
+
+    /// `find session` queries stored by `find save`, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub saved_searches: BTreeMap<String, SavedSearch>,
+}
+
+/// A stored `find session` query. Dates keep the form they were given in,
+/// so a relative `7d` is resolved each time the search runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedSearch {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub title_only: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub content: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Require every pattern to match
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub all: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub case_sensitive: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub word: bool,
+}
+
+impl SavedSearch {
+    /// This search with `overrides` applied: given patterns replace the
+    /// saved ones, given values replace saved values, and flags add to them
+    pub fn with_overrides(&self, overrides: &SavedSearch) -> SavedSearch {
+        SavedSearch {
+            patterns: if overrides.patterns.is_empty() {
+                self.patterns.clone()
+            } else {
+                overrides.patterns.clone()
+            },
+            workspace: overrides
+                .workspace
+                .clone()
+                .or_else(|| self.workspace.clone()),
+            title_only: self.title_only || overrides.title_only,
+            content: self.content || overrides.content,
+            after: overrides.after.clone().or_else(|| self.after.clone()),
+            before: overrides.before.clone().or_else(|| self.before.clone()),
+            limit: overrides.limit.or(self.limit),
+            tag: overrides.tag.clone().or_else(|| self.tag.clone()),
+            all: self.all || overrides.all,
+            case_sensitive: self.case_sensitive || overrides.case_sensitive,
+            word: self.word || overrides.word,
+        }
+    }
+
+    /// The search's options as `find session` flags, e.g. `--after 7d --content`
+    pub fn flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+        let mut value = |name: &str, value: &Option<String>| {
+            if let Some(value) = value {
+                flags.push(format!("--{} {}", name, value));
+            }
+        };
+        value("workspace", &self.workspace);
+        value("after", &self.after);
+        value("before", &self.before);
+        value("limit", &self.limit.map(|n| n.to_string()));
+        value("tag", &self.tag);
+        for (set, name) in [
+            (self.title_only, "--title-only"),
+            (self.content, "--content"),
+            (self.all, "--all"),
+            (self.case_sensitive, "--case-sensitive"),
+            (self.word, "--word"),
+        ] {
+            if set {
+                flags.push(name.to_string());
+            }
+        }
+        flags
+    }
 }
 
 impl Default for CsmConfig {
@@ -315,6 +475,10 @@ impl Default for CsmConfig {
             providers: Vec::new(),
             default_provider: None,
             auto_discover: true, // Important: enable auto-discovery by default
+            editor_cmd: None,
+            redact_patterns: Vec::new(),
+            exclude_globs: Vec::new(),
+            saved_searches: BTreeMap::new(),
         }
     }
 }
@@ -360,6 +524,41 @@ impl CsmConfig {
             .find(|p| p.provider_type == provider_type)
     }
 
+    /// Iterate over user-defined (custom) providers
+    pub fn custom_providers(&self) -> impl Iterator<Item = &ProviderConfig> {
+        self.providers
+            .iter()
+            .filter(|p| p.provider_type == ProviderType::Custom && p.name.is_some())
+    }
+
+    /// Get a custom provider by name (case-insensitive)
+    pub fn get_custom_provider(&self, name: &str) -> Option<&ProviderConfig> {
+        self.custom_providers().find(|p| {
+            p.name
+                .as_deref()
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Add or replace a custom provider, keyed by name
+    pub fn set_custom_provider(&mut self, config: ProviderConfig) {
+        let name = config.name.clone().unwrap_or_default();
+        self.remove_custom_provider(&name);
+        self.providers.push(config);
+    }
+
+    /// Remove a custom provider by name. Returns false if it didn't exist.
+    pub fn remove_custom_provider(&mut self, name: &str) -> bool {
+        let before = self.providers.len();
+        self.providers.retain(|p| {
+            !(p.provider_type == ProviderType::Custom
+                && p.name
+                    .as_deref()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name)))
+        });
+        self.providers.len() != before
+    }
+
     /// Add or update a provider config
     pub fn set_provider(&mut self, config: ProviderConfig) {
         if let Some(existing) = self
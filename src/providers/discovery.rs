@@ -4,6 +4,7 @@
 
 use super::{ProviderRegistry, ProviderType};
 use colored::*;
+use std::time::{Duration, Instant};
 
 /// Discover all available LLM providers and return a summary
 pub fn discover_all_providers() -> ProviderRegistry {
@@ -44,7 +45,11 @@ pub fn print_provider_summary(registry: &ProviderRegistry) {
         }
 
         // Show default endpoint for server-based providers
-        if provider_type.is_openai_compatible() {
+        if provider_type == ProviderType::Custom {
+            if let Some(endpoint) = provider.endpoint() {
+                print!(" [{}] {}", endpoint.dimmed(), "(custom)".dimmed());
+            }
+        } else if provider_type.is_openai_compatible() {
             if let Some(endpoint) = provider_type.default_endpoint() {
                 print!(" [{}]", endpoint.dimmed());
             }
@@ -111,3 +116,77 @@ pub fn get_provider_endpoints() -> Vec<(ProviderType, Option<&'static str>)> {
         ),
     ]
 }
+
+/// GET `url` and parse its JSON body, giving up after `timeout`
+pub fn fetch_json<T: serde::de::DeserializeOwned>(
+    url: &str,
+    api_key: Option<&str>,
+    timeout: Duration,
+) -> anyhow::Result<T> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .connect_timeout(timeout)
+        .build()?;
+
+    let mut request = client.get(url);
+    if let Some(key) = api_key {
+        request = request.bearer_auth(key);
+    }
+
+    Ok(request.send()?.error_for_status()?.json()?)
+}
+
+/// First wait between probe attempts; it doubles after every failure
+pub const PROBE_BACKOFF: Duration = Duration::from_millis(250);
+
+/// [`probe_endpoint`], retried up to `retries` times after a failure with
+/// exponential backoff (250 ms, 500 ms, 1 s, ...), for servers that are
+/// briefly unready while loading a model
+///
+/// Attempts and waits together stay within `(retries + 1) * timeout`: a
+/// retry only starts if the wait before it fits in what is left, and gets
+/// at most the remainder as its timeout. Returns the last attempt's result
+/// and how many attempts were made.
+pub fn probe_endpoint_with_retries(
+    url: &str,
+    timeout: Duration,
+    retries: u32,
+) -> (Result<Duration, String>, u32) {
+    let budget = timeout * (retries + 1);
+    let start = Instant::now();
+    let mut backoff = PROBE_BACKOFF;
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let remaining = budget.saturating_sub(start.elapsed());
+        let result = probe_endpoint(url, timeout.min(remaining));
+        if result.is_ok() || attempts > retries {
+            return (result, attempts);
+        }
+        if budget.saturating_sub(start.elapsed()) <= backoff {
+            return (result, attempts);
+        }
+        std::thread::sleep(backoff);
+        backoff *= 2;
+    }
+}
+
+/// Check whether an endpoint answers HTTP within `timeout`
+///
+/// Any HTTP response counts as reachable (a 404 still means a server is
+/// listening). Returns the round-trip latency, or a short failure reason.
+pub fn probe_endpoint(url: &str, timeout: Duration) -> Result<Duration, String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .connect_timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let start = Instant::now();
+    match client.get(url).send() {
+        Ok(_) => Ok(start.elapsed()),
+        Err(e) if e.is_timeout() => Err("timeout".to_string()),
+        Err(e) if e.is_connect() => Err("connection failed".to_string()),
+        Err(_) => Err("unreachable".to_string()),
+    }
+}
@@ -41,7 +41,7 @@ pub mod session_format;
 pub use cloud::{CloudConversation, CloudMessage, CloudProvider, FetchOptions};
 pub use config::ProviderType;
 #[allow(unused_imports)]
-pub use config::{CsmConfig, ProviderConfig};
+pub use config::{CsmConfig, ProviderConfig, SavedSearch};
 #[allow(unused_imports)]
 pub use discovery::discover_all_providers;
 #[allow(unused_imports)]
@@ -50,6 +50,18 @@ pub use session_format::{GenericMessage, GenericSession};
 use crate::models::ChatSession;
 use anyhow::Result;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// A model offered by a provider's model-listing endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelInfo {
+    /// Model name, as passed to the provider's chat API
+    pub name: String,
+    /// Size on disk in bytes, if reported
+    pub size: Option<u64>,
+    /// Quantization level (e.g. `Q4_K_M`), if reported
+    pub quantization: Option<String>,
+}
 
 /// Trait for LLM chat providers
 pub trait ChatProvider: Send + Sync {
@@ -65,6 +77,11 @@ pub trait ChatProvider: Send + Sync {
     /// Get the base path where sessions are stored
     fn sessions_path(&self) -> Option<PathBuf>;
 
+    /// Get the API endpoint this provider talks to, if any
+    fn endpoint(&self) -> Option<&str> {
+        None
+    }
+
     /// List all chat sessions from this provider
     fn list_sessions(&self) -> Result<Vec<ChatSession>>;
 
@@ -80,6 +97,13 @@ pub trait ChatProvider: Send + Sync {
         // Default implementation returns empty list
         Ok(Vec::new())
     }
+
+    /// Query the provider's model-listing endpoint, giving up after `timeout`
+    ///
+    /// Returns `None` for providers that have no such endpoint.
+    fn fetch_models(&self, _timeout: Duration) -> Option<Result<Vec<ModelInfo>>> {
+        None
+    }
 }
 
 /// Registry of available providers
@@ -113,6 +137,11 @@ impl ProviderRegistry {
         for provider in openai_compat::discover_openai_compatible_providers() {
             self.providers.push(Box::new(provider));
         }
+
+        // Add user-defined providers from config (`provider add`)
+        for provider in openai_compat::discover_custom_providers() {
+            self.providers.push(Box::new(provider));
+        }
     }
 
     /// Get all registered providers
@@ -137,6 +166,15 @@ impl ProviderRegistry {
             .map(|p| p.as_ref())
     }
 
+    /// Get all user-defined providers
+    pub fn custom_providers(&self) -> Vec<&dyn ChatProvider> {
+        self.providers
+            .iter()
+            .filter(|p| p.provider_type() == ProviderType::Custom)
+            .map(|p| p.as_ref())
+            .collect()
+    }
+
     /// List all sessions from all providers
     #[allow(dead_code)]
     pub fn list_all_sessions(&self) -> Result<Vec<(ProviderType, ChatSession)>> {
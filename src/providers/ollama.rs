@@ -4,11 +4,13 @@
 
 #![allow(dead_code)]
 
-use super::{ChatProvider, ProviderType};
+use super::discovery::fetch_json;
+use super::{ChatProvider, ModelInfo, ProviderType};
 use crate::models::{ChatMessage, ChatRequest, ChatSession};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Ollama API provider
 ///
@@ -35,6 +37,14 @@ struct OllamaModel {
     name: String,
     modified_at: Option<String>,
     size: Option<u64>,
+    #[serde(default)]
+    details: Option<OllamaModelDetails>,
+}
+
+/// Ollama model details (format, family, quantization)
+#[derive(Debug, Deserialize)]
+struct OllamaModelDetails {
+    quantization_level: Option<String>,
 }
 
 /// Ollama chat message format
@@ -181,6 +191,8 @@ impl OllamaProvider {
                             code_citations: None,
                             response_markdown_info: None,
                             source_session: None,
+                            branches: Vec::new(),
+                            selected_branch: None,
                         });
                     }
                 }
@@ -222,6 +234,27 @@ impl ChatProvider for OllamaProvider {
         self.data_path.clone()
     }
 
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.endpoint)
+    }
+
+    fn fetch_models(&self, timeout: Duration) -> Option<Result<Vec<ModelInfo>>> {
+        let url = format!("{}/api/tags", self.endpoint.trim_end_matches('/'));
+        Some(
+            fetch_json::<OllamaModelsResponse>(&url, None, timeout).map(|response| {
+                response
+                    .models
+                    .into_iter()
+                    .map(|model| ModelInfo {
+                        name: model.name,
+                        size: model.size,
+                        quantization: model.details.and_then(|d| d.quantization_level),
+                    })
+                    .collect()
+            }),
+        )
+    }
+
     fn list_sessions(&self) -> Result<Vec<ChatSession>> {
         // Ollama doesn't persist chat history by default
         // This would need integration with Ollama's history feature
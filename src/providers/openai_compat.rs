@@ -15,11 +15,13 @@
 
 #![allow(dead_code)]
 
-use super::{ChatProvider, ProviderType};
+use super::discovery::fetch_json;
+use super::{ChatProvider, ModelInfo, ProviderConfig, ProviderType};
 use crate::models::{ChatMessage, ChatRequest, ChatSession};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// OpenAI-compatible API provider
 pub struct OpenAICompatProvider {
@@ -37,6 +39,8 @@ pub struct OpenAICompatProvider {
     available: bool,
     /// Local data path (if any)
     data_path: Option<PathBuf>,
+    /// Models reported by the endpoint (custom providers are probed)
+    models: Vec<String>,
 }
 
 /// OpenAI chat message format
@@ -92,6 +96,26 @@ impl OpenAICompatProvider {
             model: None,
             available: Self::check_availability(&endpoint),
             data_path: None,
+            models: Vec::new(),
+        }
+    }
+
+    /// Create a user-defined provider from config
+    ///
+    /// Nothing is sent to the endpoint: like the built-in servers it counts
+    /// as available once configured, and its models are only known after
+    /// [`fetch_models`](ChatProvider::fetch_models) asks for them.
+    pub fn custom(config: &ProviderConfig) -> Self {
+        let endpoint = config.endpoint.clone().unwrap_or_default();
+        Self {
+            provider_type: ProviderType::Custom,
+            name: config.display_name(),
+            available: Self::check_availability(&endpoint),
+            endpoint,
+            api_key: config.resolve_api_key(),
+            model: config.model.clone(),
+            data_path: None,
+            models: Vec::new(),
         }
     }
 
@@ -188,6 +212,8 @@ impl OpenAICompatProvider {
                             code_citations: None,
                             response_markdown_info: None,
                             source_session: None,
+                            branches: Vec::new(),
+                            selected_branch: None,
                         });
                     }
                 }
@@ -232,6 +258,10 @@ impl ChatProvider for OpenAICompatProvider {
         self.data_path.clone()
     }
 
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.endpoint)
+    }
+
     fn list_sessions(&self) -> Result<Vec<ChatSession>> {
         // OpenAI-compatible APIs don't persist sessions
         // This would need a local history storage layer
@@ -246,6 +276,77 @@ impl ChatProvider for OpenAICompatProvider {
         // Could implement by sending messages to recreate context
         anyhow::bail!("Export to {} not yet implemented", self.name)
     }
+
+    fn list_models(&self) -> Result<Vec<String>> {
+        Ok(self.models.clone())
+    }
+
+    fn fetch_models(&self, timeout: Duration) -> Option<Result<Vec<ModelInfo>>> {
+        Some(
+            fetch_model_ids(&self.endpoint, self.api_key.as_deref(), timeout).map(|ids| {
+                ids.into_iter()
+                    .map(|name| ModelInfo {
+                        name,
+                        size: None,
+                        quantization: None,
+                    })
+                    .collect()
+            }),
+        )
+    }
+}
+
+/// Build the models URL for a base URL, with or without a trailing `/v1`
+pub fn models_url(base_url: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    if base.ends_with("/v1") {
+        format!("{}/models", base)
+    } else {
+        format!("{}/v1/models", base)
+    }
+}
+
+/// How long `detect providers` waits for a user-defined provider's models
+pub const CUSTOM_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Query an OpenAI-compatible endpoint for its model IDs, giving up after
+/// [`CUSTOM_PROBE_TIMEOUT`]
+pub fn probe_models(base_url: &str, api_key: Option<&str>) -> Result<Vec<String>> {
+    fetch_model_ids(base_url, api_key, CUSTOM_PROBE_TIMEOUT)
+}
+
+/// Query an OpenAI-compatible endpoint for its model IDs, giving up after `timeout`
+pub fn fetch_model_ids(
+    base_url: &str,
+    api_key: Option<&str>,
+    timeout: Duration,
+) -> Result<Vec<String>> {
+    let body: serde_json::Value = fetch_json(&models_url(base_url), api_key, timeout)?;
+
+    Ok(body
+        .get("data")
+        .and_then(|d| d.as_array())
+        .map(|models| {
+            models
+                .iter()
+                .filter_map(|m| m.get("id").and_then(|id| id.as_str()))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Load user-defined providers from config
+pub fn discover_custom_providers() -> Vec<OpenAICompatProvider> {
+    let Ok(config) = super::CsmConfig::load() else {
+        return Vec::new();
+    };
+
+    config
+        .custom_providers()
+        .filter(|p| p.enabled)
+        .map(OpenAICompatProvider::custom)
+        .collect()
 }
 
 /// Discover available OpenAI-compatible providers
@@ -487,6 +588,16 @@ fn find_gpt4all_data() -> Option<PathBuf> {
 
 /// Extract text from various response formats
 fn extract_response_text(response: &serde_json::Value) -> Option<String> {
+    // Response stored directly as an array of parts
+    if let Some(parts) = response.as_array() {
+        let parts: Vec<String> = parts
+            .iter()
+            .filter_map(|v| v.get("value").and_then(|v| v.as_str()))
+            .map(String::from)
+            .collect();
+        return (!parts.is_empty()).then(|| parts.join("\n"));
+    }
+
     // Try direct text field
     if let Some(text) = response.get("text").and_then(|v| v.as_str()) {
         return Some(text.to_string());
@@ -117,6 +117,8 @@ impl From<GenericSession> for ChatSession {
                             code_citations: None,
                             response_markdown_info: None,
                             source_session: None,
+                            branches: Vec::new(),
+                            selected_branch: None,
                         });
                     }
                 }
@@ -174,6 +176,17 @@ pub fn session_to_markdown(session: &ChatSession) -> String {
             }
         }
 
+        // Tool calls
+        for call in request.tool_calls() {
+            md.push_str(&format!("#### Tool: `{}`\n\n", call.name));
+            if let Some(args) = &call.arguments {
+                md.push_str(&format!("```\n{}\n```\n\n", args));
+            }
+            if let Some(result) = &call.result {
+                md.push_str(&format!("Result:\n\n```\n{}\n```\n\n", result));
+            }
+        }
+
         // Assistant response
         if let Some(response) = &request.response {
             if let Some(text) = extract_response_text(response) {
@@ -190,6 +203,346 @@ pub fn session_to_markdown(session: &ChatSession) -> String {
     md
 }
 
+/// Stylesheet of exported HTML documents
+const HTML_STYLE: &str =
+    "<style>\nbody { font-family: sans-serif; max-width: 900px; margin: 2em auto; }\n\
+     .user { background: #eef4ff; }\n.assistant { background: #f6f6f6; }\n\
+     .tool { background: #fff8e6; }\n\
+     .message { padding: 0.5em 1em; margin: 1em 0; border-radius: 6px; }\n\
+     pre { white-space: pre-wrap; }\n</style>\n";
+
+/// Everything of an HTML export up to and including `<body>`; `title` is
+/// already escaped
+fn html_head(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n{}</head>\n<body>\n",
+        title, HTML_STYLE
+    )
+}
+
+/// Convert a session to a standalone HTML document
+pub fn session_to_html(session: &ChatSession) -> String {
+    let title = escape_html(&session.title());
+    let mut html = String::new();
+
+    html.push_str(&html_head(&title));
+    html.push_str(&format!("<h1>{}</h1>\n", title));
+
+    if let Some(id) = &session.session_id {
+        html.push_str(&format!(
+            "<p>Session ID: <code>{}</code></p>\n",
+            escape_html(id)
+        ));
+    }
+    html.push_str(&format!(
+        "<p>Created: {}<br>Last Updated: {}</p>\n<hr>\n",
+        format_timestamp(session.creation_date),
+        format_timestamp(session.last_message_date)
+    ));
+
+    for (i, request) in session.requests.iter().enumerate() {
+        if let Some(text) = request.message.as_ref().and_then(|m| m.text.as_ref()) {
+            html.push_str(&format!(
+                "<div class=\"message user\">\n<h3>User ({})</h3>\n<pre>{}</pre>\n</div>\n",
+                i + 1,
+                escape_html(text)
+            ));
+        }
+
+        for call in request.tool_calls() {
+            html.push_str(&format!(
+                "<div class=\"message tool\">\n<h4>Tool: <code>{}</code></h4>\n",
+                escape_html(&call.name)
+            ));
+            for text in [&call.arguments, &call.result].into_iter().flatten() {
+                html.push_str(&format!("<pre>{}</pre>\n", escape_html(text)));
+            }
+            html.push_str("</div>\n");
+        }
+
+        if let Some(text) = request.response.as_ref().and_then(extract_response_text) {
+            let model = request.model_id.as_deref().unwrap_or("Assistant");
+            html.push_str(&format!(
+                "<div class=\"message assistant\">\n<h3>{} ({})</h3>\n<pre>{}</pre>\n</div>\n",
+                escape_html(model),
+                i + 1,
+                escape_html(&text)
+            ));
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Convert a session to a plain, un-styled transcript (suitable for grep)
+pub fn session_to_text(session: &ChatSession) -> String {
+    let mut txt = String::new();
+
+    txt.push_str(&format!("Title: {}\n", session.title()));
+    if let Some(id) = &session.session_id {
+        txt.push_str(&format!("Session ID: {}\n", id));
+    }
+    txt.push_str(&format!(
+        "Created: {}\n",
+        format_timestamp(session.creation_date)
+    ));
+    txt.push_str(&format!(
+        "Last Updated: {}\n\n",
+        format_timestamp(session.last_message_date)
+    ));
+
+    for request in &session.requests {
+        if let Some(text) = request.message.as_ref().and_then(|m| m.text.as_ref()) {
+            txt.push_str(&format!("User: {}\n\n", text));
+        }
+
+        for call in request.tool_calls() {
+            txt.push_str(&format!("Tool: {}\n\n", call.summary()));
+        }
+
+        if let Some(text) = request.response.as_ref().and_then(extract_response_text) {
+            let model = request.model_id.as_deref().unwrap_or("Assistant");
+            txt.push_str(&format!("{}: {}\n\n", model, text));
+        }
+    }
+
+    txt
+}
+
+/// Anchor IDs for the sections of a combined export: each title as a
+/// lowercase slug, numbered when repeated
+fn section_anchors(sessions: &[ChatSession]) -> Vec<String> {
+    let mut taken = std::collections::HashSet::new();
+    sessions
+        .iter()
+        .map(|session| {
+            let mut slug = String::new();
+            for c in session.title().chars().flat_map(char::to_lowercase) {
+                if c.is_alphanumeric() {
+                    slug.push(c);
+                } else if !slug.is_empty() && !slug.ends_with('-') {
+                    slug.push('-');
+                }
+            }
+            let base = match slug.trim_end_matches('-') {
+                "" => "session".to_string(),
+                slug => slug.to_string(),
+            };
+            let mut anchor = base.clone();
+            let mut n = 2;
+            while !taken.insert(anchor.clone()) {
+                anchor = format!("{}-{}", base, n);
+                n += 1;
+            }
+            anchor
+        })
+        .collect()
+}
+
+/// Table of contents line for a session: date of its first message and size
+fn toc_details(session: &ChatSession) -> String {
+    let first = session
+        .timestamp_range()
+        .map(|(first, _)| first)
+        .unwrap_or(session.creation_date);
+    let date = format_timestamp(first);
+    format!(
+        "{}, {} messages",
+        date.split(' ').next().unwrap_or(&date),
+        session.request_count()
+    )
+}
+
+/// Push every markdown heading down one level, leaving code blocks alone
+fn demote_markdown_headings(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len() + 64);
+    let mut in_code = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        } else if !in_code && line.starts_with('#') {
+            out.push('#');
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Several sessions as one markdown document: an H1 `title`, an optional
+/// `intro` paragraph, a linked table of contents, then each session's own
+/// markdown one heading level down
+pub fn sessions_to_combined_markdown(
+    title: &str,
+    intro: Option<&str>,
+    sessions: &[ChatSession],
+) -> String {
+    let anchors = section_anchors(sessions);
+    let mut md = format!("# {}\n\n", title);
+    if let Some(intro) = intro {
+        md.push_str(&format!("{}\n\n", intro));
+    }
+    md.push_str("## Contents\n\n");
+    for (i, (session, anchor)) in sessions.iter().zip(&anchors).enumerate() {
+        md.push_str(&format!(
+            "{}. [{}](#{}) ({})\n",
+            i + 1,
+            session.title(),
+            anchor,
+            toc_details(session)
+        ));
+    }
+    md.push('\n');
+    for (session, anchor) in sessions.iter().zip(&anchors) {
+        md.push_str(&format!("<a id=\"{}\"></a>\n\n", anchor));
+        md.push_str(&demote_markdown_headings(&session_to_markdown(session)));
+    }
+    md
+}
+
+/// Several sessions as one HTML document, laid out like
+/// [`sessions_to_combined_markdown`]
+pub fn sessions_to_combined_html(
+    title: &str,
+    intro: Option<&str>,
+    sessions: &[ChatSession],
+) -> String {
+    let anchors = section_anchors(sessions);
+    let title = escape_html(title);
+    let mut html = html_head(&title);
+    html.push_str(&format!("<h1>{}</h1>\n", title));
+    if let Some(intro) = intro {
+        html.push_str(&format!("<p>{}</p>\n", escape_html(intro)));
+    }
+    html.push_str("<h2>Contents</h2>\n<ol>\n");
+    for (session, anchor) in sessions.iter().zip(&anchors) {
+        html.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a> ({})</li>\n",
+            anchor,
+            escape_html(&session.title()),
+            toc_details(session)
+        ));
+    }
+    html.push_str("</ol>\n<hr>\n");
+
+    for (session, anchor) in sessions.iter().zip(&anchors) {
+        let document = session_to_html(session);
+        // Message text is escaped, so these tags are only ever markup
+        let body = document
+            .split_once("<body>\n")
+            .and_then(|(_, rest)| rest.rsplit_once("</body>"))
+            .map_or("", |(body, _)| body)
+            .replace("<h4>", "<h5>")
+            .replace("</h4>", "</h5>")
+            .replace("<h3>", "<h4>")
+            .replace("</h3>", "</h4>")
+            .replacen("<h1>", &format!("<h2 id=\"{}\">", anchor), 1)
+            .replacen("</h1>", "</h2>", 1);
+        html.push_str(&format!("<section>\n{}</section>\n", body));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Several sessions as one plain text document, with a numbered contents
+/// list in place of links
+pub fn sessions_to_combined_text(
+    title: &str,
+    intro: Option<&str>,
+    sessions: &[ChatSession],
+) -> String {
+    let rule = "=".repeat(70);
+    let mut txt = format!("{}\n{}\n\n", title, rule);
+    if let Some(intro) = intro {
+        txt.push_str(&format!("{}\n\n", intro));
+    }
+    txt.push_str("Contents:\n");
+    for (i, session) in sessions.iter().enumerate() {
+        txt.push_str(&format!(
+            "  {}. {} ({})\n",
+            i + 1,
+            session.title(),
+            toc_details(session)
+        ));
+    }
+    for (i, session) in sessions.iter().enumerate() {
+        txt.push_str(&format!("\n{}\n{}. ", rule, i + 1));
+        txt.push_str(&session_to_text(session));
+    }
+    txt
+}
+
+/// One message in an export template context
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateMessage {
+    /// `user`, `assistant` or `tool`
+    pub role: String,
+    pub text: String,
+    /// Request time (`YYYY-MM-DD HH:MM:SS`, UTC), if recorded
+    pub timestamp: Option<String>,
+}
+
+/// Context passed to `export --template` templates (see `export --dump-context`)
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateContext {
+    pub title: String,
+    pub session_id: Option<String>,
+    pub workspace: String,
+    pub messages: Vec<TemplateMessage>,
+}
+
+impl TemplateContext {
+    /// Flatten a session into user, tool and assistant messages
+    pub fn new(session: &ChatSession, workspace: &str) -> Self {
+        let mut messages = Vec::new();
+
+        for request in &session.requests {
+            let timestamp = request.timestamp.map(format_timestamp);
+            let mut push = |role: &str, text: String| {
+                messages.push(TemplateMessage {
+                    role: role.to_string(),
+                    text,
+                    timestamp: timestamp.clone(),
+                })
+            };
+
+            if let Some(text) = request.message.as_ref().and_then(|m| m.text.as_ref()) {
+                push("user", text.clone());
+            }
+            for call in request.tool_calls() {
+                push("tool", call.summary());
+            }
+            if let Some(text) = request.response.as_ref().and_then(extract_response_text) {
+                push("assistant", text);
+            }
+        }
+
+        Self {
+            title: session.title(),
+            session_id: session.session_id.clone(),
+            workspace: workspace.to_string(),
+            messages,
+        }
+    }
+}
+
+/// Escape text for safe inclusion in HTML
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 /// Parse a markdown file into a session
 pub fn markdown_to_session(markdown: &str, title: Option<String>) -> ChatSession {
     let now = chrono::Utc::now().timestamp_millis();
@@ -306,11 +659,23 @@ fn create_request(
         code_citations: None,
         response_markdown_info: None,
         source_session: None,
+        branches: Vec::new(),
+        selected_branch: None,
     }
 }
 
 /// Extract text from various response formats
-fn extract_response_text(response: &serde_json::Value) -> Option<String> {
+pub fn extract_response_text(response: &serde_json::Value) -> Option<String> {
+    // Response stored directly as an array of parts
+    if let Some(parts) = response.as_array() {
+        let parts: Vec<String> = parts
+            .iter()
+            .filter_map(|v| v.get("value").and_then(|v| v.as_str()))
+            .map(String::from)
+            .collect();
+        return (!parts.is_empty()).then(|| parts.join("\n"));
+    }
+
     // Try direct text field
     if let Some(text) = response.get("text").and_then(|v| v.as_str()) {
         return Some(text.to_string());
@@ -336,6 +701,139 @@ fn extract_response_text(response: &serde_json::Value) -> Option<String> {
     None
 }
 
+/// Replace a response's text, as [`extract_response_text`] reads it, with
+/// `text`; other parts such as tool calls are kept
+pub fn set_response_text(response: &mut serde_json::Value, text: String) {
+    fn replace_text_parts(parts: &mut Vec<serde_json::Value>, text: String) -> bool {
+        let is_text = |p: &serde_json::Value| p.get("value").is_some_and(|v| v.is_string());
+        let Some(at) = parts.iter().position(is_text) else {
+            return false;
+        };
+        parts.retain(|p| !is_text(p));
+        parts.insert(at, serde_json::json!({ "value": text }));
+        true
+    }
+
+    if let Some(parts) = response.as_array_mut() {
+        replace_text_parts(parts, text);
+        return;
+    }
+    if let Some(field) = response.get_mut("text").filter(|v| v.is_string()) {
+        *field = serde_json::Value::String(text);
+        return;
+    }
+    if let Some(parts) = response.get_mut("value").and_then(|v| v.as_array_mut()) {
+        if replace_text_parts(parts, text.clone()) {
+            return;
+        }
+    }
+    if let Some(field) = response.get_mut("content").filter(|v| v.is_string()) {
+        *field = serde_json::Value::String(text);
+    }
+}
+
+/// File extension for code fenced with the `language` hint: common names
+/// and aliases map to their usual extension, other short alphanumeric hints
+/// are used as-is, and anything else (or no hint) is `txt`
+pub fn code_extension(language: &str) -> String {
+    let language = language.to_lowercase();
+    let extension = match language.as_str() {
+        "rust" | "rs" => "rs",
+        "python" | "py" | "python3" => "py",
+        "javascript" | "js" | "node" => "js",
+        "typescript" | "ts" => "ts",
+        "tsx" => "tsx",
+        "jsx" => "jsx",
+        "bash" | "sh" | "shell" | "zsh" | "console" => "sh",
+        "powershell" | "pwsh" | "ps1" => "ps1",
+        "batch" | "bat" | "cmd" => "bat",
+        "c" | "h" => "c",
+        "cpp" | "c++" | "cxx" | "hpp" => "cpp",
+        "csharp" | "cs" | "c#" => "cs",
+        "fsharp" | "fs" | "f#" => "fs",
+        "go" | "golang" => "go",
+        "java" => "java",
+        "kotlin" | "kt" => "kt",
+        "swift" => "swift",
+        "ruby" | "rb" => "rb",
+        "php" => "php",
+        "perl" | "pl" => "pl",
+        "lua" => "lua",
+        "r" => "r",
+        "scala" => "scala",
+        "haskell" | "hs" => "hs",
+        "elixir" | "ex" => "ex",
+        "dart" => "dart",
+        "zig" => "zig",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "scss" => "scss",
+        "json" | "jsonc" => "json",
+        "yaml" | "yml" => "yaml",
+        "toml" => "toml",
+        "xml" => "xml",
+        "sql" => "sql",
+        "markdown" | "md" => "md",
+        "dockerfile" | "docker" => "dockerfile",
+        "makefile" | "make" => "mk",
+        "diff" | "patch" => "diff",
+        "" | "text" | "plaintext" | "txt" => "txt",
+        other if other.len() <= 10 && other.chars().all(|c| c.is_ascii_alphanumeric()) => {
+            return other.to_string()
+        }
+        _ => "txt",
+    };
+    extension.to_string()
+}
+
+/// The fence (run of three or more backticks or tildes) opening a code
+/// block on `line`, and its info string
+fn opening_fence(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let run = trimmed.len() - trimmed.trim_start_matches(marker).len();
+    let info = trimmed[run..].trim();
+    // A backtick in the info string makes it inline code, not a fence
+    (run >= 3 && !(marker == '`' && info.contains('`'))).then(|| (&trimmed[..run], info))
+}
+
+/// Whether `line` closes a code block opened by `fence`
+fn closes_fence(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let marker = fence.as_bytes()[0] as char;
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| c == marker)
+}
+
+/// `text` with each fenced code block replaced by what `reference` returns
+/// for it, given the block's language hint (first word of the info string,
+/// empty when unlabeled) and its body. Unclosed fences are left as written.
+pub fn split_code_blocks(text: &str, mut reference: impl FnMut(&str, &str) -> String) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut out: Vec<String> = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        let close = opening_fence(lines[i]).and_then(|(fence, info)| {
+            let end = lines[i + 1..].iter().position(|l| closes_fence(l, fence))?;
+            Some((info, i + 1 + end))
+        });
+        match close {
+            Some((info, end)) => {
+                let language = info
+                    .split(|c: char| c.is_whitespace() || matches!(c, ',' | ':' | '{'))
+                    .next()
+                    .unwrap_or_default();
+                out.push(reference(language, &lines[i + 1..end].join("\n")));
+                i = end + 1;
+            }
+            None => {
+                out.push(lines[i].to_string());
+                i += 1;
+            }
+        }
+    }
+    out.join("\n")
+}
+
 /// Format a timestamp for display
 fn format_timestamp(timestamp: i64) -> String {
     use chrono::{TimeZone, Utc};
@@ -390,6 +888,8 @@ mod tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -399,6 +899,39 @@ mod tests {
         assert!(md.contains("Hi there!"));
     }
 
+    #[test]
+    fn test_session_to_html_escapes_content() {
+        let mut session = ChatSession {
+            version: 3,
+            session_id: Some("test-123".to_string()),
+            creation_date: 1700000000000,
+            last_message_date: 1700000000000,
+            is_imported: false,
+            initial_location: "panel".to_string(),
+            custom_title: Some("<Test>".to_string()),
+            requester_username: None,
+            requester_avatar_icon_uri: None,
+            responder_username: None,
+            responder_avatar_icon_uri: None,
+            requests: vec![],
+        };
+        session.requests.push(create_request(
+            "Is a < b?".to_string(),
+            "Yes & no".to_string(),
+            1700000000000,
+            None,
+        ));
+
+        let html = session_to_html(&session);
+        assert!(html.contains("<title>&lt;Test&gt;</title>"));
+        assert!(html.contains("Is a &lt; b?"));
+        assert!(html.contains("Yes &amp; no"));
+
+        let txt = session_to_text(&session);
+        assert!(txt.contains("User: Is a < b?"));
+        assert!(txt.contains("Assistant: Yes & no"));
+    }
+
     #[test]
     fn test_generic_session_conversion() {
         let session = ChatSession {
@@ -0,0 +1,167 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Secret redaction for exported transcripts
+//!
+//! Used by `export --redact` and `harvest export --redact` to scrub API keys,
+//! tokens and email addresses before a transcript is shared, and by
+//! `export --anonymize` to replace local paths and the username.
+
+use crate::error::{CsmError, Result};
+use regex::{Captures, Regex};
+use serde_json::Value;
+
+/// Replacement text for redacted matches
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Built-in patterns, always applied before any configured `redact_patterns`
+pub const DEFAULT_PATTERNS: &[&str] = &[
+    // AWS access key IDs
+    r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b",
+    // Bearer tokens in Authorization headers
+    r"(?i)\bbearer\s+[A-Za-z0-9\-._~+/]+=*",
+    // OpenAI-style secret keys (sk-..., sk-proj-...)
+    r"\bsk-[A-Za-z0-9_\-]{6,}",
+    // Email addresses
+    r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}",
+];
+
+/// A compiled set of redaction patterns
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+}
+
+impl Redactor {
+    /// Build a redactor from the built-in patterns plus `extra` user patterns
+    pub fn new(extra: &[String]) -> Result<Self> {
+        let patterns = DEFAULT_PATTERNS
+            .iter()
+            .copied()
+            .chain(extra.iter().map(String::as_str))
+            .map(|p| Regex::new(p).map_err(|e| CsmError::InvalidRedactPattern(e.to_string())))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Replace every match in `text` with `[REDACTED]`
+    pub fn redact(&self, text: &str) -> String {
+        self.patterns.iter().fold(text.to_string(), |acc, re| {
+            re.replace_all(&acc, REDACTED).into_owned()
+        })
+    }
+
+    /// Redact every string value in a JSON document, leaving keys intact
+    pub fn redact_json(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.redact(s),
+            Value::Array(items) => items.iter_mut().for_each(|v| self.redact_json(v)),
+            Value::Object(map) => map.values_mut().for_each(|v| self.redact_json(v)),
+            _ => {}
+        }
+    }
+}
+
+/// Placeholder for the home directory in anonymized exports
+pub const HOME_PLACEHOLDER: &str = "$HOME";
+
+/// Placeholder for the local username in anonymized exports
+pub const USER_PLACEHOLDER: &str = "$USER";
+
+/// Placeholder for the session's own project path in anonymized exports
+pub const PROJECT_PLACEHOLDER: &str = "$PROJECT";
+
+/// Match `path` written with either separator, up to the end of a path
+/// component so `/home/al` doesn't match inside `/home/alice` or
+/// `/home/al-old`. The character after the path is captured as `end`. `None`
+/// for the root or an empty path, which would match everywhere.
+fn path_regex(path: &str) -> Option<Regex> {
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    if trimmed.trim_start_matches(['/', '\\']).is_empty() {
+        return None;
+    }
+    let pattern = trimmed
+        .split(['/', '\\'])
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(r"[/\\]");
+    let case = if cfg!(windows) { "(?i)" } else { "" };
+    // A trailing `.` ends a sentence, but `.bak` continues a file name
+    let end = r"(?P<end>$|[^\w.\-]|\.$|\.[^\w.\-])";
+    Regex::new(&format!("{}{}{}", case, pattern, end)).ok()
+}
+
+/// Replaces the home directory, the username and a workspace's project path
+/// with [`HOME_PLACEHOLDER`], [`USER_PLACEHOLDER`] and [`PROJECT_PLACEHOLDER`]
+#[derive(Debug, Clone, Default)]
+pub struct Anonymizer {
+    home: Option<Regex>,
+    user: Option<Regex>,
+    project: Option<Regex>,
+}
+
+impl Anonymizer {
+    /// Anonymize `home` and `user`; the username only matches as a whole word
+    pub fn new(home: Option<&str>, user: Option<&str>) -> Self {
+        Self {
+            home: home.and_then(path_regex),
+            user: user
+                .filter(|u| !u.is_empty())
+                .and_then(|u| Regex::new(&format!(r"\b{}(?P<end>\b)", regex::escape(u))).ok()),
+            project: None,
+        }
+    }
+
+    /// Anonymizer for whoever runs the export: their home directory, and
+    /// `$USER`/`$USERNAME` (or else the home directory's name)
+    pub fn for_current_user() -> Self {
+        let home = dirs::home_dir();
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok()
+            .or_else(|| {
+                home.as_ref()?
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+            });
+        Self::new(
+            home.as_ref().map(|h| h.to_string_lossy()).as_deref(),
+            user.as_deref(),
+        )
+    }
+
+    /// This anonymizer, also replacing a workspace's `project_path`
+    pub fn with_project(&self, project_path: Option<&str>) -> Self {
+        Self {
+            project: project_path.and_then(path_regex),
+            ..self.clone()
+        }
+    }
+
+    /// Replace the project path, then the home directory, then the username,
+    /// so a project under the home directory becomes `$PROJECT`, not `$HOME/...`
+    pub fn anonymize(&self, text: &str) -> String {
+        [
+            (&self.project, PROJECT_PLACEHOLDER),
+            (&self.home, HOME_PLACEHOLDER),
+            (&self.user, USER_PLACEHOLDER),
+        ]
+        .into_iter()
+        .filter_map(|(re, placeholder)| Some((re.as_ref()?, placeholder)))
+        .fold(text.to_string(), |acc, (re, placeholder)| {
+            re.replace_all(&acc, |caps: &Captures| {
+                format!("{}{}", placeholder, &caps["end"])
+            })
+            .into_owned()
+        })
+    }
+
+    /// Anonymize every string value in a JSON document, leaving keys intact
+    pub fn anonymize_json(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.anonymize(s),
+            Value::Array(items) => items.iter_mut().for_each(|v| self.anonymize_json(v)),
+            Value::Object(map) => map.values_mut().for_each(|v| self.anonymize_json(v)),
+            _ => {}
+        }
+    }
+}
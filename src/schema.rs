@@ -0,0 +1,368 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! JSON Schema for the session model
+//!
+//! Used by `schema` to describe both the VS Code-native session files we parse
+//! and the normalized session shape `export --dump-context` and templates see.
+//! The schemas are written by hand next to the model types (the `schemars`
+//! crate is not in the dependency set); `tests/schema_tests.rs` checks they
+//! stay in step with what serde actually reads and writes.
+
+use crate::models::{ChatBranch, ChatMessage, ChatRequest, ChatSession};
+use crate::providers::session_format::{TemplateContext, TemplateMessage};
+use serde_json::{json, Map, Value};
+
+/// JSON Schema dialect of the generated document
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A type with a JSON Schema, referenced from other schemas by name
+pub trait JsonSchema {
+    /// Name under `$defs`
+    fn schema_name() -> &'static str;
+
+    /// Schema for a single value of this type
+    fn json_schema() -> Value;
+
+    /// `$ref` to this type's definition
+    fn schema_ref() -> Value {
+        json!({ "$ref": format!("#/$defs/{}", Self::schema_name()) })
+    }
+}
+
+/// A described property of `type` (a JSON Schema type name or list of them)
+fn property(ty: Value, description: &str) -> Value {
+    json!({ "type": ty, "description": description })
+}
+
+/// A described property that may hold any JSON value
+fn any_property(description: &str) -> Value {
+    json!({ "description": description })
+}
+
+/// An object schema with the given properties
+fn object(description: &str, properties: Vec<(&str, Value)>) -> Value {
+    let properties: Map<String, Value> = properties
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect();
+    json!({
+        "type": "object",
+        "description": description,
+        "properties": properties,
+    })
+}
+
+impl JsonSchema for ChatSession {
+    fn schema_name() -> &'static str {
+        "ChatSession"
+    }
+
+    fn json_schema() -> Value {
+        object(
+            "VS Code chat session file (chatSessions/<id>.json, version 3 format)",
+            vec![
+                (
+                    "version",
+                    json!({"type": "integer", "description": "Session format version", "default": 3}),
+                ),
+                (
+                    "sessionId",
+                    property(
+                        json!(["string", "null"]),
+                        "Unique session identifier; the file name is used when absent",
+                    ),
+                ),
+                (
+                    "creationDate",
+                    property(json!("integer"), "Creation timestamp (milliseconds)"),
+                ),
+                (
+                    "lastMessageDate",
+                    property(json!("integer"), "Last message timestamp (milliseconds)"),
+                ),
+                (
+                    "isImported",
+                    property(json!("boolean"), "Whether this session was imported"),
+                ),
+                (
+                    "initialLocation",
+                    json!({
+                        "type": "string",
+                        "description": "Initial location (panel, terminal, notebook, editor)",
+                        "default": "panel",
+                    }),
+                ),
+                (
+                    "customTitle",
+                    property(json!(["string", "null"]), "Custom title set by user"),
+                ),
+                (
+                    "requesterUsername",
+                    property(json!(["string", "null"]), "Requester username"),
+                ),
+                (
+                    "requesterAvatarIconUri",
+                    any_property("Requester avatar URI"),
+                ),
+                (
+                    "responderUsername",
+                    property(json!(["string", "null"]), "Responder username"),
+                ),
+                (
+                    "responderAvatarIconUri",
+                    any_property("Responder avatar URI"),
+                ),
+                (
+                    "requests",
+                    json!({
+                        "type": "array",
+                        "description": "Chat requests/messages",
+                        "items": ChatRequest::schema_ref(),
+                    }),
+                ),
+            ],
+        )
+    }
+}
+
+impl JsonSchema for ChatRequest {
+    fn schema_name() -> &'static str {
+        "ChatRequest"
+    }
+
+    fn json_schema() -> Value {
+        let values = |description: &str| json!({"type": ["array", "null"], "description": description, "items": {}});
+        object(
+            "A single chat request (message + response)",
+            vec![
+                (
+                    "timestamp",
+                    property(
+                        json!(["integer", "null"]),
+                        "Request timestamp (milliseconds)",
+                    ),
+                ),
+                (
+                    "message",
+                    json!({
+                        "description": "The user's message",
+                        "anyOf": [ChatMessage::schema_ref(), {"type": "null"}],
+                    }),
+                ),
+                (
+                    "response",
+                    any_property(
+                        "The response: an array of parts, a { \"value\": [...] } object, \
+                         or a single responseMessage object (also read from `responseMessage`)",
+                    ),
+                ),
+                (
+                    "variableData",
+                    any_property("Variable data (context, files, etc.)"),
+                ),
+                (
+                    "requestId",
+                    property(json!(["string", "null"]), "Request ID"),
+                ),
+                (
+                    "responseId",
+                    property(json!(["string", "null"]), "Response ID"),
+                ),
+                ("modelId", property(json!(["string", "null"]), "Model ID")),
+                ("agent", any_property("Agent information")),
+                ("result", any_property("Result metadata")),
+                ("followups", values("Follow-up suggestions")),
+                (
+                    "isCanceled",
+                    property(json!(["boolean", "null"]), "Whether canceled"),
+                ),
+                ("contentReferences", values("Content references")),
+                ("codeCitations", values("Code citations")),
+                ("responseMarkdownInfo", values("Response markdown info")),
+                (
+                    "_sourceSession",
+                    property(json!("string"), "Source session for merged requests"),
+                ),
+                (
+                    "branches",
+                    json!({
+                        "type": "array",
+                        "description": "Earlier versions of this turn, oldest first, when it \
+                                        was edited or regenerated",
+                        "items": ChatBranch::schema_ref(),
+                    }),
+                ),
+                (
+                    "selectedBranch",
+                    property(
+                        json!(["integer", "null"]),
+                        "Version shown: an index into `branches`, or its length for the \
+                         request itself; the newest when absent",
+                    ),
+                ),
+            ],
+        )
+    }
+}
+
+impl JsonSchema for ChatBranch {
+    fn schema_name() -> &'static str {
+        "ChatBranch"
+    }
+
+    fn json_schema() -> Value {
+        object(
+            "An earlier version of a chat request turn",
+            vec![
+                (
+                    "timestamp",
+                    property(
+                        json!(["integer", "null"]),
+                        "Branch timestamp (milliseconds)",
+                    ),
+                ),
+                (
+                    "message",
+                    json!({
+                        "description": "The prompt of this version; null when the response \
+                                        was regenerated from the prompt before it",
+                        "anyOf": [ChatMessage::schema_ref(), {"type": "null"}],
+                    }),
+                ),
+                (
+                    "response",
+                    any_property(
+                        "The response of this version, in any shape a request's `response` \
+                         takes (also read from `responseMessage`)",
+                    ),
+                ),
+                ("modelId", property(json!(["string", "null"]), "Model ID")),
+            ],
+        )
+    }
+}
+
+impl JsonSchema for ChatMessage {
+    fn schema_name() -> &'static str {
+        "ChatMessage"
+    }
+
+    fn json_schema() -> Value {
+        let mut message = object(
+            "Message object; `content` is accepted for `text`, and the text is \
+             rebuilt from `parts` when only parts are stored",
+            vec![
+                ("text", property(json!(["string", "null"]), "Message text")),
+                (
+                    "parts",
+                    json!({
+                        "type": ["array", "null"],
+                        "description": "Message parts (for complex messages)",
+                        "items": {},
+                    }),
+                ),
+            ],
+        );
+        message["properties"]["content"] =
+            property(json!(["string", "null"]), "Alias of `text` (read only)");
+        json!({
+            "description": "User message in a chat request",
+            "anyOf": [
+                {"type": "string", "description": "Bare message text"},
+                message,
+            ],
+        })
+    }
+}
+
+impl JsonSchema for TemplateContext {
+    fn schema_name() -> &'static str {
+        "ExportSession"
+    }
+
+    fn json_schema() -> Value {
+        let mut schema = object(
+            "Normalized session, as printed by `export --dump-context` and passed to \
+             `export --template`",
+            vec![
+                (
+                    "title",
+                    property(
+                        json!("string"),
+                        "Session title (custom title or first message)",
+                    ),
+                ),
+                (
+                    "session_id",
+                    property(json!(["string", "null"]), "Session ID, if recorded"),
+                ),
+                (
+                    "workspace",
+                    property(json!("string"), "Project path or workspace hash"),
+                ),
+                (
+                    "messages",
+                    json!({
+                        "type": "array",
+                        "description": "User, tool and assistant messages in order",
+                        "items": TemplateMessage::schema_ref(),
+                    }),
+                ),
+            ],
+        );
+        schema["required"] = json!(["title", "session_id", "workspace", "messages"]);
+        schema
+    }
+}
+
+impl JsonSchema for TemplateMessage {
+    fn schema_name() -> &'static str {
+        "ExportMessage"
+    }
+
+    fn json_schema() -> Value {
+        let mut schema = object(
+            "One message of a normalized session",
+            vec![
+                (
+                    "role",
+                    json!({"type": "string", "enum": ["user", "assistant", "tool"]}),
+                ),
+                ("text", property(json!("string"), "Message text")),
+                (
+                    "timestamp",
+                    property(
+                        json!(["string", "null"]),
+                        "Request time (YYYY-MM-DD HH:MM:SS, UTC), if recorded",
+                    ),
+                ),
+            ],
+        );
+        schema["required"] = json!(["role", "text", "timestamp"]);
+        schema
+    }
+}
+
+/// Add a type's definition under `$defs`
+fn define<T: JsonSchema>(defs: &mut Map<String, Value>) {
+    defs.insert(T::schema_name().to_string(), T::json_schema());
+}
+
+/// Schema document covering the native session file and the normalized
+/// export shape; a document matching either validates
+pub fn session_schema() -> Value {
+    let mut defs = Map::new();
+    define::<ChatSession>(&mut defs);
+    define::<ChatRequest>(&mut defs);
+    define::<ChatBranch>(&mut defs);
+    define::<ChatMessage>(&mut defs);
+    define::<TemplateContext>(&mut defs);
+    define::<TemplateMessage>(&mut defs);
+
+    json!({
+        "$schema": SCHEMA_DIALECT,
+        "title": "chasm chat session",
+        "anyOf": [ChatSession::schema_ref(), TemplateContext::schema_ref()],
+        "$defs": defs,
+    })
+}
@@ -3,10 +3,15 @@
 //! VS Code storage (SQLite database) operations
 
 use crate::error::{CsmError, Result};
-use crate::models::{ChatSession, ChatSessionIndex, ChatSessionIndexEntry};
-use crate::workspace::{get_empty_window_sessions_path, get_workspace_storage_path};
+use crate::models::{ChatSession, ChatSessionIndex, ChatSessionIndexEntry, SessionWithPath};
+use crate::workspace::{
+    get_empty_window_sessions_path, get_workspace_storage_paths, ChannelFilter,
+};
 use regex::Regex;
 use rusqlite::Connection;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 use sysinfo::System;
 
@@ -86,9 +91,98 @@ pub fn parse_session_json(content: &str) -> std::result::Result<ChatSession, ser
     }
 }
 
-/// Get the path to the workspace storage database
+// =============================================================================
+// Atomic File Writes
+// =============================================================================
+
+/// Write a chat session to disk atomically.
+///
+/// The session is serialized into a temporary file in the same directory and then
+/// renamed over `path`, so a crash or serialization failure mid-write never leaves
+/// a truncated session file behind.
+pub fn write_session_atomic(path: &Path, session: &ChatSession) -> Result<()> {
+    write_json_atomic(path, session)
+}
+
+/// Serialize a value as pretty-printed JSON and write it atomically
+pub fn write_json_atomic<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    write_atomic_with(path, |writer| {
+        serde_json::to_writer_pretty(writer, value)?;
+        Ok(())
+    })
+}
+
+/// Write raw bytes to a file atomically
+pub fn write_file_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    write_atomic_with(path, |writer| {
+        writer.write_all(contents)?;
+        Ok(())
+    })
+}
+
+/// Copy a file atomically (the destination is either untouched or fully written)
+pub fn copy_file_atomic(src: &Path, dst: &Path) -> Result<()> {
+    let contents = std::fs::read(src)?;
+    write_file_atomic(dst, &contents)
+}
+
+/// Write to a temporary sibling of `path` and rename it into place on success
+fn write_atomic_with<F>(path: &Path, write: F) -> Result<()>
+where
+    F: FnOnce(&mut BufWriter<File>) -> Result<()>,
+{
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "session".to_string());
+    let temp_path = dir.join(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()));
+
+    let written = File::create(&temp_path)
+        .map_err(CsmError::from)
+        .and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            write(&mut writer)?;
+            let file = writer.into_inner().map_err(|e| e.into_error())?;
+            file.sync_all()?;
+            Ok(())
+        });
+
+    if let Err(e) = written.and_then(|_| replace_file(&temp_path, path).map_err(CsmError::from)) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Rename `from` over `to`, removing the destination first where the platform requires it
+fn replace_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    match std::fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        // Windows can refuse to rename over an existing (e.g. read-only or
+        // briefly locked) file; clear the destination and retry once.
+        #[cfg(windows)]
+        Err(_) if to.exists() => {
+            std::fs::remove_file(to)?;
+            std::fs::rename(from, to)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Get the path to the workspace storage database, in whichever storage
+/// root (Stable or Insiders) holds the workspace
 pub fn get_workspace_storage_db(workspace_id: &str) -> Result<PathBuf> {
-    let storage_path = get_workspace_storage_path()?;
+    let roots = get_workspace_storage_paths(ChannelFilter::All)?;
+    let storage_path = roots
+        .iter()
+        .find(|root| root.join(workspace_id).is_dir())
+        .or(roots.first())
+        .ok_or(CsmError::StorageNotFound)?;
     Ok(storage_path.join(workspace_id).join("state.vscdb"))
 }
 
@@ -165,7 +259,6 @@ pub fn add_session_to_index(
 }
 
 /// Remove a session from the VS Code index
-#[allow(dead_code)]
 pub fn remove_session_from_index(db_path: &Path, session_id: &str) -> Result<bool> {
     let mut index = read_chat_session_index(db_path)?;
     let removed = index.entries.remove(session_id).is_some();
@@ -339,6 +432,15 @@ pub fn is_vscode_running() -> bool {
     false
 }
 
+/// Directory a backup of `workspace_dir`'s sessions taken now would go to
+pub fn workspace_backup_dir(workspace_dir: &Path) -> PathBuf {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    workspace_dir.join(format!("chatSessions-backup-{}", timestamp))
+}
+
 /// Backup workspace sessions to a timestamped directory
 pub fn backup_workspace_sessions(workspace_dir: &Path) -> Result<Option<PathBuf>> {
     let chat_sessions_dir = workspace_dir.join("chatSessions");
@@ -347,12 +449,7 @@ pub fn backup_workspace_sessions(workspace_dir: &Path) -> Result<Option<PathBuf>
         return Ok(None);
     }
 
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-
-    let backup_dir = workspace_dir.join(format!("chatSessions-backup-{}", timestamp));
+    let backup_dir = workspace_backup_dir(workspace_dir);
 
     // Copy directory recursively
     copy_dir_all(&chat_sessions_dir, &backup_dir)?;
@@ -386,6 +483,14 @@ fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
 /// Read all empty window chat sessions (not tied to any workspace)
 /// These appear in VS Code's "ALL SESSIONS" panel
 pub fn read_empty_window_sessions() -> Result<Vec<ChatSession>> {
+    Ok(read_empty_window_sessions_with_paths()?
+        .into_iter()
+        .map(|s| s.session)
+        .collect())
+}
+
+/// Read all empty window chat sessions along with their file paths
+pub fn read_empty_window_sessions_with_paths() -> Result<Vec<SessionWithPath>> {
     let sessions_path = get_empty_window_sessions_path()?;
 
     if !sessions_path.exists() {
@@ -401,14 +506,14 @@ pub fn read_empty_window_sessions() -> Result<Vec<ChatSession>> {
         if path.extension().is_some_and(|e| e == "json") {
             if let Ok(content) = std::fs::read_to_string(&path) {
                 if let Ok(session) = parse_session_json(&content) {
-                    sessions.push(session);
+                    sessions.push(SessionWithPath { path, session });
                 }
             }
         }
     }
 
     // Sort by last message date (most recent first)
-    sessions.sort_by(|a, b| b.last_message_date.cmp(&a.last_message_date));
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.session.last_message_date));
 
     Ok(sessions)
 }
@@ -440,8 +545,7 @@ pub fn write_empty_window_session(session: &ChatSession) -> Result<PathBuf> {
 
     let session_id = session.session_id.as_deref().unwrap_or("unknown");
     let session_path = sessions_path.join(format!("{}.json", session_id));
-    let content = serde_json::to_string_pretty(session)?;
-    std::fs::write(&session_path, content)?;
+    write_session_atomic(&session_path, session)?;
 
     Ok(session_path)
 }
@@ -0,0 +1,139 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! Local session tags
+//!
+//! Tags are stored in a sidecar JSON index under the csm data directory,
+//! keyed by session ID, so VS Code never overwrites them when it rewrites
+//! session files.
+
+use crate::error::Result;
+use crate::storage::write_json_atomic;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Current on-disk format version of the tag index
+const TAG_INDEX_VERSION: u32 = 1;
+
+/// Get the default tag index path
+pub fn get_tag_index_path() -> PathBuf {
+    dirs::data_local_dir()
+        .map(|p| p.join("csm").join("tags.json"))
+        .unwrap_or_else(|| PathBuf::from("tags.json"))
+}
+
+/// Normalize a tag for storage and comparison (trimmed, lowercase)
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// Sidecar index mapping session IDs to their tags
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TagIndex {
+    pub version: u32,
+    /// Tags per session ID
+    pub sessions: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Default for TagIndex {
+    fn default() -> Self {
+        Self {
+            version: TAG_INDEX_VERSION,
+            sessions: BTreeMap::new(),
+        }
+    }
+}
+
+impl TagIndex {
+    /// Load the tag index from the default location
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_tag_index_path())
+    }
+
+    /// Load a tag index from a file, returning an empty index if it doesn't exist
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the tag index to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_tag_index_path())
+    }
+
+    /// Save the tag index to a file
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_json_atomic(path, self)
+    }
+
+    /// Add a tag to a session. Returns false if it was already present.
+    pub fn add(&mut self, session_id: &str, tag: &str) -> bool {
+        let tag = normalize_tag(tag);
+        if tag.is_empty() {
+            return false;
+        }
+        self.sessions
+            .entry(session_id.to_string())
+            .or_default()
+            .insert(tag)
+    }
+
+    /// Remove a tag from a session. Returns false if it wasn't present.
+    pub fn remove(&mut self, session_id: &str, tag: &str) -> bool {
+        let Some(tags) = self.sessions.get_mut(session_id) else {
+            return false;
+        };
+        let removed = tags.remove(&normalize_tag(tag));
+        if tags.is_empty() {
+            self.sessions.remove(session_id);
+        }
+        removed
+    }
+
+    /// Get the tags for a session (sorted)
+    pub fn tags_for(&self, session_id: &str) -> Vec<String> {
+        self.sessions
+            .get(session_id)
+            .map(|tags| tags.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Check whether a session carries a tag
+    pub fn has_tag(&self, session_id: &str, tag: &str) -> bool {
+        self.sessions
+            .get(session_id)
+            .is_some_and(|tags| tags.contains(&normalize_tag(tag)))
+    }
+
+    /// All tags with the number of sessions carrying each
+    pub fn tag_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for tag in self.sessions.values().flatten() {
+            *counts.entry(tag.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Session IDs in the index that are not in `known_ids`
+    pub fn orphaned(&self, known_ids: &BTreeSet<String>) -> Vec<String> {
+        self.sessions
+            .keys()
+            .filter(|id| !known_ids.contains(*id))
+            .cloned()
+            .collect()
+    }
+
+    /// Drop all tags for the given session IDs. Returns the number removed.
+    pub fn remove_sessions(&mut self, session_ids: &[String]) -> usize {
+        session_ids
+            .iter()
+            .filter(|id| self.sessions.remove(*id).is_some())
+            .count()
+    }
+}
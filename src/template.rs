@@ -0,0 +1,379 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! User-supplied export templates
+//!
+//! Used by `export --template` to render sessions into a custom layout. This
+//! is a small subset of the Tera syntax:
+//!
+//! - `{{ title }}`, `{{ message.text | escape }}` (filters: `escape`, `upper`,
+//!   `lower`, `trim`)
+//! - `{% for message in messages %}...{% endfor %}`, with `loop.index`,
+//!   `loop.first` and `loop.last`
+//! - `{% if message.timestamp %}...{% else %}...{% endif %}` (`not` negates)
+//! - `{# comments #}` and `-` whitespace control (`{{-`, `-%}`, ...)
+
+use crate::error::{CsmError, Result};
+use crate::providers::session_format::escape_html;
+use serde_json::{Map, Value};
+
+/// Filters a `{{ ... }}` expression may apply
+const FILTERS: &[&str] = &["escape", "upper", "lower", "trim"];
+
+/// A parsed template, validated at load time
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Var {
+        path: Vec<String>,
+        filters: Vec<String>,
+        line: usize,
+    },
+    For {
+        var: String,
+        path: Vec<String>,
+        body: Vec<Node>,
+        line: usize,
+    },
+    If {
+        path: Vec<String>,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// A lexed piece of template source
+enum Token<'a> {
+    Text(&'a str),
+    Expr(&'a str, usize),
+    Tag(&'a str, usize),
+}
+
+/// Block tags that end a nested body
+enum Terminator {
+    Eof,
+    EndFor,
+    Else,
+    EndIf,
+}
+
+fn template_error(line: usize, message: impl std::fmt::Display) -> CsmError {
+    CsmError::Template(format!("line {}: {}", line, message))
+}
+
+impl Template {
+    /// Parse and validate a template, reporting syntax errors with their line
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut tokens = lex(source)?.into_iter();
+        let (nodes, end) = parse_nodes(&mut tokens)?;
+        match end {
+            (Terminator::Eof, _) => Ok(Self { nodes }),
+            (Terminator::EndFor, line) => Err(template_error(line, "`endfor` without `for`")),
+            (Terminator::Else, line) => Err(template_error(line, "`else` without `if`")),
+            (Terminator::EndIf, line) => Err(template_error(line, "`endif` without `if`")),
+        }
+    }
+
+    /// Load and validate a template file
+    pub fn load(path: &std::path::Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)?;
+        Self::parse(&source)
+            .map_err(|e| CsmError::Template(format!("{}: {}", path.display(), template_message(e))))
+    }
+
+    /// Render the template against a JSON object context
+    pub fn render(&self, context: &Value) -> Result<String> {
+        let mut out = String::new();
+        let mut scope = Vec::new();
+        render_nodes(&self.nodes, context, &mut scope, &mut out)?;
+        Ok(out)
+    }
+}
+
+fn template_message(error: CsmError) -> String {
+    match error {
+        CsmError::Template(message) => message,
+        other => other.to_string(),
+    }
+}
+
+/// Split source into text, `{{ }}` and `{% %}` tokens, applying `-` trimming
+/// and dropping comments
+fn lex(source: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    let mut line = 1;
+    let mut trim_next = false;
+
+    while !rest.is_empty() {
+        let Some(start) = ["{{", "{%", "{#"]
+            .iter()
+            .filter_map(|open| rest.find(open))
+            .min()
+        else {
+            push_text(&mut tokens, rest, trim_next, false);
+            break;
+        };
+
+        let open = &rest[start..start + 2];
+        let close = match open {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+        let body_start = start + 2;
+        let trim_before = rest[body_start..].starts_with('-');
+        push_text(&mut tokens, &rest[..start], trim_next, trim_before);
+        line += rest[..start].matches('\n').count();
+
+        let Some(len) = rest[body_start..].find(close) else {
+            return Err(template_error(line, format!("unclosed `{}`", open)));
+        };
+        let mut body = &rest[body_start..body_start + len];
+        if let Some(stripped) = body.strip_prefix('-') {
+            body = stripped;
+        }
+        trim_next = body.ends_with('-');
+        if trim_next {
+            body = &body[..body.len() - 1];
+        }
+        match open {
+            "{{" => tokens.push(Token::Expr(body.trim(), line)),
+            "{%" => tokens.push(Token::Tag(body.trim(), line)),
+            _ => {}
+        }
+
+        let end = body_start + len + 2;
+        line += rest[start..end].matches('\n').count();
+        rest = &rest[end..];
+    }
+
+    Ok(tokens)
+}
+
+fn push_text<'a>(tokens: &mut Vec<Token<'a>>, text: &'a str, trim_start: bool, trim_end: bool) {
+    let text = if trim_start { text.trim_start() } else { text };
+    let text = if trim_end { text.trim_end() } else { text };
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+}
+
+/// Parse a dotted variable path such as `message.role`
+fn parse_path(expr: &str, line: usize) -> Result<Vec<String>> {
+    let path: Vec<String> = expr.split('.').map(|s| s.trim().to_string()).collect();
+    let valid = path.iter().all(|part| {
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    if !valid {
+        return Err(template_error(
+            line,
+            format!("invalid expression `{}`", expr),
+        ));
+    }
+    Ok(path)
+}
+
+/// Parse nodes until the end of input or a closing block tag, returned with its line
+fn parse_nodes<'a>(
+    tokens: &mut impl Iterator<Item = Token<'a>>,
+) -> Result<(Vec<Node>, (Terminator, usize))> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text.to_string())),
+            Token::Expr(expr, line) => {
+                let mut parts = expr.split('|');
+                let path = parse_path(parts.next().unwrap_or_default(), line)?;
+                let filters = parts
+                    .map(|f| {
+                        let f = f.trim();
+                        if FILTERS.contains(&f) {
+                            Ok(f.to_string())
+                        } else {
+                            Err(template_error(line, format!("unknown filter `{}`", f)))
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                nodes.push(Node::Var {
+                    path,
+                    filters,
+                    line,
+                });
+            }
+            Token::Tag(tag, line) => {
+                let words: Vec<&str> = tag.split_whitespace().collect();
+                match words.as_slice() {
+                    ["for", var, "in", path] => {
+                        let var = parse_path(var, line)?;
+                        if var.len() != 1 {
+                            return Err(template_error(line, "loop variable must be a plain name"));
+                        }
+                        let path = parse_path(path, line)?;
+                        let (body, end) = parse_nodes(tokens)?;
+                        if !matches!(end.0, Terminator::EndFor) {
+                            return Err(template_error(line, "`for` is missing its `endfor`"));
+                        }
+                        nodes.push(Node::For {
+                            var: var.into_iter().next().unwrap_or_default(),
+                            path,
+                            body,
+                            line,
+                        });
+                    }
+                    ["if", rest @ ..] if !rest.is_empty() => {
+                        let (negate, expr) = match rest {
+                            ["not", expr] => (true, *expr),
+                            [expr] => (false, *expr),
+                            _ => {
+                                return Err(template_error(
+                                    line,
+                                    format!("unsupported condition `{}`", rest.join(" ")),
+                                ))
+                            }
+                        };
+                        let path = parse_path(expr, line)?;
+                        let (then, end) = parse_nodes(tokens)?;
+                        let otherwise = match end.0 {
+                            Terminator::EndIf => Vec::new(),
+                            Terminator::Else => {
+                                let (otherwise, end) = parse_nodes(tokens)?;
+                                if !matches!(end.0, Terminator::EndIf) {
+                                    return Err(template_error(
+                                        line,
+                                        "`if` is missing its `endif`",
+                                    ));
+                                }
+                                otherwise
+                            }
+                            _ => return Err(template_error(line, "`if` is missing its `endif`")),
+                        };
+                        nodes.push(Node::If {
+                            path,
+                            negate,
+                            then,
+                            otherwise,
+                        });
+                    }
+                    ["endfor"] => return Ok((nodes, (Terminator::EndFor, line))),
+                    ["else"] => return Ok((nodes, (Terminator::Else, line))),
+                    ["endif"] => return Ok((nodes, (Terminator::EndIf, line))),
+                    _ => return Err(template_error(line, format!("unknown tag `{}`", tag))),
+                }
+            }
+        }
+    }
+
+    Ok((nodes, (Terminator::Eof, 0)))
+}
+
+/// Resolve a path against loop variables first, then the root context
+fn lookup<'v>(
+    path: &[String],
+    context: &'v Value,
+    scope: &'v [(String, Value)],
+) -> Option<&'v Value> {
+    let (first, rest) = path.split_first()?;
+    let mut value = scope
+        .iter()
+        .rev()
+        .find(|(name, _)| name == first)
+        .map(|(_, v)| v)
+        .or_else(|| context.get(first))?;
+    for key in rest {
+        value = value.get(key)?;
+    }
+    Some(value)
+}
+
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(map)) => !map.is_empty(),
+        Some(Value::Number(n)) => n.as_f64() != Some(0.0),
+    }
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    context: &Value,
+    scope: &mut Vec<(String, Value)>,
+    out: &mut String,
+) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var {
+                path,
+                filters,
+                line,
+            } => {
+                let value = lookup(path, context, scope).ok_or_else(|| {
+                    template_error(*line, format!("variable `{}` not found", path.join(".")))
+                })?;
+                let mut text = match value {
+                    Value::Null => String::new(),
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                for filter in filters {
+                    text = match filter.as_str() {
+                        "escape" => escape_html(&text),
+                        "upper" => text.to_uppercase(),
+                        "lower" => text.to_lowercase(),
+                        _ => text.trim().to_string(),
+                    };
+                }
+                out.push_str(&text);
+            }
+            Node::For {
+                var,
+                path,
+                body,
+                line,
+            } => {
+                let items = match lookup(path, context, scope) {
+                    Some(Value::Array(items)) => items.clone(),
+                    Some(Value::Null) | None => Vec::new(),
+                    Some(_) => {
+                        return Err(template_error(
+                            *line,
+                            format!("`{}` is not a list", path.join(".")),
+                        ))
+                    }
+                };
+                let len = items.len();
+                for (i, item) in items.into_iter().enumerate() {
+                    let mut loop_info = Map::new();
+                    loop_info.insert("index".to_string(), (i + 1).into());
+                    loop_info.insert("first".to_string(), (i == 0).into());
+                    loop_info.insert("last".to_string(), (i + 1 == len).into());
+                    scope.push(("loop".to_string(), Value::Object(loop_info)));
+                    scope.push((var.clone(), item));
+                    let result = render_nodes(body, context, scope, out);
+                    scope.truncate(scope.len() - 2);
+                    result?;
+                }
+            }
+            Node::If {
+                path,
+                negate,
+                then,
+                otherwise,
+            } => {
+                let truthy = is_truthy(lookup(path, context, scope)) != *negate;
+                render_nodes(if truthy { then } else { otherwise }, context, scope, out)?;
+            }
+        }
+    }
+    Ok(())
+}
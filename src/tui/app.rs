@@ -3,8 +3,14 @@
 //! Application state for the TUI
 
 use crate::models::{ChatSession, Workspace};
-use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace};
+use crate::workspace::{discover_workspaces, get_chat_sessions_from_workspace, DiscoveryOptions};
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
 use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Number of session transcripts kept by the detail view cache
+const DETAIL_CACHE_CAPACITY: usize = 16;
 
 /// Current view mode in the TUI
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,21 +21,299 @@ pub enum AppMode {
     Sessions,
     /// Viewing details of a session
     SessionDetail,
+    /// Picking a workspace to limit the session table to
+    WorkspacePicker,
     /// Help overlay
     Help,
 }
 
+/// How recently a session was modified, used to color session rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionAge {
+    /// Modified today (UTC)
+    Today,
+    /// Modified within the recent window
+    Recent,
+    /// Modified before the recent window, or unknown
+    Older,
+}
+
+/// Tunable thresholds for the session table color coding
+#[derive(Debug, Clone, Copy)]
+pub struct ColorThresholds {
+    /// Sessions modified within this many days count as recent
+    pub recent_days: i64,
+    /// Sessions with at least this many messages get a highlighted count
+    pub high_message_count: usize,
+}
+
+impl Default for ColorThresholds {
+    fn default() -> Self {
+        Self {
+            recent_days: 7,
+            high_message_count: 50,
+        }
+    }
+}
+
+impl ColorThresholds {
+    /// Classify a modification time relative to `now`
+    pub fn age_of(&self, modified: Option<DateTime<Utc>>, now: DateTime<Utc>) -> SessionAge {
+        match modified {
+            Some(t) if t.date_naive() == now.date_naive() => SessionAge::Today,
+            Some(t) if now.signed_duration_since(t).num_days() < self.recent_days => {
+                SessionAge::Recent
+            }
+            _ => SessionAge::Older,
+        }
+    }
+
+    /// Whether a message count should be highlighted
+    pub fn is_high_message_count(&self, count: usize) -> bool {
+        count >= self.high_message_count
+    }
+}
+
+/// Column the session table is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionSortKey {
+    Title,
+    Messages,
+    Modified,
+}
+
+impl SessionSortKey {
+    /// The next key in the `s` cycle
+    pub fn next(self) -> Self {
+        match self {
+            SessionSortKey::Modified => SessionSortKey::Title,
+            SessionSortKey::Title => SessionSortKey::Messages,
+            SessionSortKey::Messages => SessionSortKey::Modified,
+        }
+    }
+
+    /// Column header this key sorts by
+    pub fn header(self) -> &'static str {
+        match self {
+            SessionSortKey::Title => "Title",
+            SessionSortKey::Messages => "Messages",
+            SessionSortKey::Modified => "Modified",
+        }
+    }
+}
+
+/// Active sort for the session table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionSort {
+    pub key: SessionSortKey,
+    pub descending: bool,
+}
+
+impl Default for SessionSort {
+    /// Newest first
+    fn default() -> Self {
+        Self {
+            key: SessionSortKey::Modified,
+            descending: true,
+        }
+    }
+}
+
+impl SessionSort {
+    /// Sort sessions in place; ties keep their load order
+    pub fn sort(&self, sessions: &mut [SessionInfo]) {
+        sessions.sort_by(|a, b| {
+            let ordering = match self.key {
+                SessionSortKey::Title => a
+                    .session
+                    .title()
+                    .to_lowercase()
+                    .cmp(&b.session.title().to_lowercase()),
+                SessionSortKey::Messages => a.message_count.cmp(&b.message_count),
+                SessionSortKey::Modified => a.modified.cmp(&b.modified),
+            };
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
+
+/// Whether colors are off, per `--color` (which also covers `NO_COLOR` and `TERM=dumb`)
+fn monochrome_terminal() -> bool {
+    !colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
 /// Session info for display
+///
+/// The parsed session is shared, so re-sorting the table never copies it.
 #[derive(Debug, Clone)]
 pub struct SessionInfo {
     pub filename: String,
-    #[allow(dead_code)]
     pub path: PathBuf,
-    pub session: ChatSession,
+    /// Project path of the workspace the session belongs to
+    pub workspace: Option<String>,
+    pub session: Rc<ChatSession>,
     pub last_modified: String,
+    /// File modification time, if known
+    pub modified: Option<DateTime<Utc>>,
     pub message_count: usize,
 }
 
+impl SessionInfo {
+    /// Session ID, falling back to the file name for sessions without one
+    pub fn id(&self) -> &str {
+        self.session.session_id.as_deref().unwrap_or(&self.filename)
+    }
+}
+
+/// Session rows for every session file in a workspace
+fn workspace_sessions(ws: &Workspace) -> Vec<SessionInfo> {
+    let Ok(session_list) = get_chat_sessions_from_workspace(&ws.workspace_path) else {
+        return Vec::new();
+    };
+
+    session_list
+        .into_iter()
+        .map(|swp| {
+            let modified_at: Option<DateTime<Utc>> = swp
+                .path
+                .metadata()
+                .ok()
+                .and_then(|m| m.modified().ok())
+                .map(Into::into);
+            let modified = modified_at
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+
+            let msg_count = swp.session.request_count();
+            let filename = swp
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+
+            SessionInfo {
+                filename,
+                path: swp.path,
+                workspace: ws.project_path.clone(),
+                session: Rc::new(swp.session),
+                last_modified: modified,
+                modified: modified_at,
+                message_count: msg_count,
+            }
+        })
+        .collect()
+}
+
+/// Distinct project paths of the workspaces that have sessions, sorted, for
+/// the workspace picker
+pub fn workspace_picker_paths(workspaces: &[Workspace]) -> Vec<String> {
+    let mut paths: Vec<String> = workspaces
+        .iter()
+        .filter(|ws| ws.has_chat_sessions)
+        .filter_map(|ws| ws.project_path.clone())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// One line of the session detail transcript
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetailLine {
+    /// Formatted request timestamp
+    Timestamp(String),
+    /// Heading for the Nth (1-based) user message
+    User(usize),
+    /// A line of message text
+    Text(String),
+    Blank,
+}
+
+/// Build the detail transcript for a session
+pub fn detail_lines(session: &ChatSession) -> Vec<DetailLine> {
+    let mut lines = Vec::new();
+
+    for (i, req) in session.requests.iter().enumerate() {
+        if let Some(ts) = req.timestamp {
+            let dt = DateTime::from_timestamp_millis(ts)
+                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            lines.push(DetailLine::Timestamp(dt));
+        }
+
+        if let Some(msg) = &req.message {
+            lines.push(DetailLine::User(i + 1));
+            lines.extend(
+                msg.get_text()
+                    .lines()
+                    .map(|l| DetailLine::Text(l.to_string())),
+            );
+        }
+
+        lines.push(DetailLine::Blank);
+    }
+
+    lines
+}
+
+/// Least-recently-used cache of detail transcripts, keyed by session ID
+#[derive(Debug, Default)]
+pub struct DetailCache {
+    /// Most recently used last
+    entries: VecDeque<(String, Rc<[DetailLine]>)>,
+}
+
+impl DetailCache {
+    /// Get the cached transcript for `id`, building and caching it on a miss
+    pub fn get_or_insert_with(
+        &mut self,
+        id: &str,
+        build: impl FnOnce() -> Vec<DetailLine>,
+    ) -> Rc<[DetailLine]> {
+        let entry = match self.entries.iter().position(|(key, _)| key == id) {
+            Some(pos) => self.entries.remove(pos).unwrap_or_default(),
+            None => {
+                if self.entries.len() >= DETAIL_CACHE_CAPACITY {
+                    self.entries.pop_front();
+                }
+                (id.to_string(), build().into())
+            }
+        };
+        let lines = Rc::clone(&entry.1);
+        self.entries.push_back(entry);
+        lines
+    }
+
+    /// Drop every cached transcript
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// What the status bar shows for the list being browsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusSummary {
+    /// Where the selection is, e.g. "session 3 of 120"
+    pub position: String,
+    /// Size of the listed items, e.g. "842 messages"
+    pub totals: String,
+    /// Active filters as (label, value), e.g. `("workspace", "/home/u/web")`
+    pub filters: Vec<(&'static str, String)>,
+}
+
+/// "`noun` X of N" for a 0-based selection, "no `noun`s" for an empty list
+fn position_label(noun: &str, index: usize, len: usize) -> String {
+    if len == 0 {
+        format!("no {}s", noun)
+    } else {
+        format!("{} {} of {}", noun, index.min(len - 1) + 1, len)
+    }
+}
+
 /// Application state
 pub struct App {
     /// Current mode/view
@@ -40,12 +324,31 @@ pub struct App {
     pub workspaces: Vec<Workspace>,
     /// Currently selected workspace index
     pub workspace_index: usize,
-    /// Sessions for the currently selected workspace
+    /// Sessions for the currently selected workspace (or every workspace,
+    /// see `all_workspaces`), in load order
+    pub all_sessions: Vec<SessionInfo>,
+    /// `all_sessions` limited to `workspace_filter` and ordered by
+    /// `session_sort`, as displayed
     pub sessions: Vec<SessionInfo>,
+    /// Whether `all_sessions` holds every workspace's sessions, loaded for
+    /// the workspace picker, rather than the selected workspace's
+    pub all_workspaces: bool,
+    /// Project path the session table is limited to, picked with `w`
+    pub workspace_filter: Option<String>,
+    /// Project paths offered by the workspace picker
+    pub picker_paths: Vec<String>,
+    /// Currently selected picker entry
+    pub picker_index: usize,
+    /// Active sort for the session table
+    pub session_sort: SessionSort,
     /// Currently selected session index
     pub session_index: usize,
     /// Scroll offset for session detail view
     pub detail_scroll: usize,
+    /// Transcript shown by the session detail view
+    pub detail: Rc<[DetailLine]>,
+    /// Recently viewed transcripts, so revisiting a session is instant
+    pub detail_cache: DetailCache,
     /// Search/filter query
     pub filter_query: String,
     /// Is filter input active
@@ -54,29 +357,52 @@ pub struct App {
     pub filtered_indices: Vec<usize>,
     /// Status message to display
     pub status_message: Option<String>,
+    /// Color coding thresholds for the session table
+    pub thresholds: ColorThresholds,
+    /// Use text attributes instead of colors for the color coding
+    pub monochrome: bool,
+    /// How workspaces are discovered, on load and on refresh
+    pub discovery: DiscoveryOptions,
 }
 
 impl App {
     /// Create a new App instance and load workspaces
-    pub fn new() -> anyhow::Result<Self> {
-        let workspaces = discover_workspaces()?;
+    pub fn new(discovery: DiscoveryOptions) -> anyhow::Result<Self> {
+        let workspaces = discover_workspaces(&discovery)?;
+        Ok(Self {
+            discovery,
+            ..Self::with_workspaces(workspaces)
+        })
+    }
+
+    /// Create an App over already discovered workspaces
+    fn with_workspaces(workspaces: Vec<Workspace>) -> Self {
         let filtered_indices: Vec<usize> = (0..workspaces.len()).collect();
 
-        let app = Self {
+        Self {
             mode: AppMode::Workspaces,
             previous_mode: AppMode::Workspaces,
             workspaces,
             workspace_index: 0,
+            all_sessions: Vec::new(),
             sessions: Vec::new(),
+            all_workspaces: false,
+            workspace_filter: None,
+            picker_paths: Vec::new(),
+            picker_index: 0,
+            session_sort: SessionSort::default(),
             session_index: 0,
             detail_scroll: 0,
+            detail: Rc::new([]),
+            detail_cache: DetailCache::default(),
             filter_query: String::new(),
             filter_active: false,
             filtered_indices,
             status_message: None,
-        };
-
-        Ok(app)
+            thresholds: ColorThresholds::default(),
+            monochrome: monochrome_terminal(),
+            discovery: DiscoveryOptions::default(),
+        }
     }
 
     /// Get the currently selected workspace (if any)
@@ -94,45 +420,128 @@ impl App {
     }
 
     /// Load sessions for the currently selected workspace
+    ///
+    /// This is the only place session files are read; rendering works from
+    /// the loaded state.
     pub fn load_sessions_for_current_workspace(&mut self) {
-        self.sessions.clear();
-        self.session_index = 0;
+        self.all_workspaces = false;
+        self.workspace_filter = None;
+        self.all_sessions = self
+            .current_workspace()
+            .map(workspace_sessions)
+            .unwrap_or_default();
 
-        if let Some(ws) = self.current_workspace() {
-            if let Ok(session_list) = get_chat_sessions_from_workspace(&ws.workspace_path) {
-                for swp in session_list {
-                    let modified = swp
-                        .path
-                        .metadata()
-                        .ok()
-                        .and_then(|m| m.modified().ok())
-                        .map(|t| {
-                            let datetime: chrono::DateTime<chrono::Utc> = t.into();
-                            datetime.format("%Y-%m-%d %H:%M").to_string()
-                        })
-                        .unwrap_or_else(|| "unknown".to_string());
-
-                    let msg_count = swp.session.request_count();
-                    let filename = swp
-                        .path
-                        .file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "unknown".to_string());
-
-                    self.sessions.push(SessionInfo {
-                        filename,
-                        path: swp.path,
-                        session: swp.session,
-                        last_modified: modified,
-                        message_count: msg_count,
-                    });
-                }
-            }
+        self.apply_session_sort();
+    }
+
+    /// Load the sessions of every workspace, for filtering by workspace
+    pub fn load_all_sessions(&mut self) {
+        self.all_workspaces = true;
+        self.all_sessions = self
+            .workspaces
+            .iter()
+            .filter(|ws| ws.has_chat_sessions)
+            .flat_map(workspace_sessions)
+            .collect();
+
+        self.apply_session_sort();
+    }
+
+    /// Re-derive the displayed session list from `all_sessions`
+    ///
+    /// Keeps the selected session selected when it is still present.
+    pub fn apply_session_sort(&mut self) {
+        let selected = self.current_session().map(|s| s.path.clone());
+
+        let mut sessions: Vec<SessionInfo> = match &self.workspace_filter {
+            Some(filter) => self
+                .all_sessions
+                .iter()
+                .filter(|s| s.workspace.as_ref() == Some(filter))
+                .cloned()
+                .collect(),
+            None => self.all_sessions.clone(),
+        };
+        self.session_sort.sort(&mut sessions);
+        self.sessions = sessions;
+
+        self.session_index = selected
+            .and_then(|path| self.sessions.iter().position(|s| s.path == path))
+            .unwrap_or(0);
+    }
+
+    /// Label for the sessions being shown: the filtered or selected
+    /// workspace's project path, or every workspace
+    pub fn session_scope_label(&self) -> Option<&str> {
+        match (&self.workspace_filter, self.all_workspaces) {
+            (Some(filter), _) => Some(filter),
+            (None, true) => None,
+            (None, false) => self
+                .current_workspace()
+                .map(|ws| ws.project_path.as_deref().unwrap_or("(none)")),
+        }
+    }
+
+    /// Open the workspace picker over the current view
+    pub fn open_workspace_picker(&mut self) {
+        self.picker_paths = workspace_picker_paths(&self.workspaces);
+        if self.picker_paths.is_empty() {
+            self.status_message = Some("No workspaces with sessions".to_string());
+            return;
         }
+
+        let current = self
+            .session_scope_label()
+            .map(str::to_string)
+            .or_else(|| self.current_workspace()?.project_path.clone());
+        self.picker_index = current
+            .and_then(|path| self.picker_paths.iter().position(|p| *p == path))
+            .unwrap_or(0);
+        self.previous_mode = self.mode;
+        self.mode = AppMode::WorkspacePicker;
+    }
+
+    /// Limit the session table to the picked workspace
+    fn pick_workspace(&mut self) {
+        let Some(path) = self.picker_paths.get(self.picker_index).cloned() else {
+            return;
+        };
+        if !self.all_workspaces {
+            self.load_all_sessions();
+        }
+        self.workspace_filter = Some(path);
+        self.apply_session_sort();
+        self.session_index = 0;
+        self.mode = AppMode::Sessions;
+    }
+
+    /// Sort the session table by the next column
+    pub fn cycle_session_sort(&mut self) {
+        self.session_sort.key = self.session_sort.key.next();
+        self.apply_session_sort();
+    }
+
+    /// Flip the session table sort direction
+    pub fn toggle_session_sort_direction(&mut self) {
+        self.session_sort.descending = !self.session_sort.descending;
+        self.apply_session_sort();
     }
 
     /// Apply filter to workspaces
     pub fn apply_filter(&mut self) {
+        self.update_filtered_indices();
+
+        // Reset selection if out of bounds
+        if self.workspace_index >= self.filtered_indices.len() {
+            self.workspace_index = 0;
+        }
+
+        // Reload sessions for new selection
+        self.load_sessions_for_current_workspace();
+    }
+
+    /// Recompute `filtered_indices` from `filter_query`
+    fn update_filtered_indices(&mut self) {
         if self.filter_query.is_empty() {
             self.filtered_indices = (0..self.workspaces.len()).collect();
         } else {
@@ -151,14 +560,16 @@ impl App {
                 .map(|(i, _)| i)
                 .collect();
         }
+    }
 
-        // Reset selection if out of bounds
-        if self.workspace_index >= self.filtered_indices.len() {
-            self.workspace_index = 0;
-        }
-
-        // Reload sessions for new selection
-        self.load_sessions_for_current_workspace();
+    /// Show the selected session's transcript, from the cache when possible
+    fn load_detail(&mut self) {
+        let Some(session) = self.sessions.get(self.session_index) else {
+            return;
+        };
+        self.detail = self
+            .detail_cache
+            .get_or_insert_with(session.id(), || detail_lines(&session.session));
     }
 
     /// Navigate up in the current list
@@ -179,6 +590,9 @@ impl App {
                     self.detail_scroll -= 1;
                 }
             }
+            AppMode::WorkspacePicker => {
+                self.picker_index = self.picker_index.saturating_sub(1);
+            }
             AppMode::Help => {}
         }
     }
@@ -199,6 +613,11 @@ impl App {
             AppMode::SessionDetail => {
                 self.detail_scroll += 1;
             }
+            AppMode::WorkspacePicker => {
+                if self.picker_index + 1 < self.picker_paths.len() {
+                    self.picker_index += 1;
+                }
+            }
             AppMode::Help => {}
         }
     }
@@ -215,6 +634,9 @@ impl App {
             AppMode::SessionDetail => {
                 self.detail_scroll = self.detail_scroll.saturating_sub(10);
             }
+            AppMode::WorkspacePicker => {
+                self.picker_index = self.picker_index.saturating_sub(10);
+            }
             AppMode::Help => {}
         }
     }
@@ -233,6 +655,10 @@ impl App {
             AppMode::SessionDetail => {
                 self.detail_scroll += 10;
             }
+            AppMode::WorkspacePicker => {
+                let max = self.picker_paths.len().saturating_sub(1);
+                self.picker_index = (self.picker_index + 10).min(max);
+            }
             AppMode::Help => {}
         }
     }
@@ -249,6 +675,9 @@ impl App {
             AppMode::SessionDetail => {
                 self.detail_scroll = 0;
             }
+            AppMode::WorkspacePicker => {
+                self.picker_index = 0;
+            }
             AppMode::Help => {}
         }
     }
@@ -266,6 +695,9 @@ impl App {
                 // Will be clamped by scroll logic
                 self.detail_scroll = usize::MAX;
             }
+            AppMode::WorkspacePicker => {
+                self.picker_index = self.picker_paths.len().saturating_sub(1);
+            }
             AppMode::Help => {}
         }
     }
@@ -285,10 +717,12 @@ impl App {
             }
             AppMode::Sessions => {
                 if self.current_session().is_some() {
+                    self.load_detail();
                     self.mode = AppMode::SessionDetail;
                     self.detail_scroll = 0;
                 }
             }
+            AppMode::WorkspacePicker => self.pick_workspace(),
             AppMode::SessionDetail | AppMode::Help => {}
         }
     }
@@ -300,12 +734,20 @@ impl App {
                 // Already at top level
             }
             AppMode::Sessions => {
-                self.mode = AppMode::Workspaces;
+                if self.workspace_filter.take().is_some() {
+                    // Back to every workspace's sessions
+                    self.apply_session_sort();
+                } else {
+                    if self.all_workspaces {
+                        self.load_sessions_for_current_workspace();
+                    }
+                    self.mode = AppMode::Workspaces;
+                }
             }
             AppMode::SessionDetail => {
                 self.mode = AppMode::Sessions;
             }
-            AppMode::Help => {
+            AppMode::WorkspacePicker | AppMode::Help => {
                 self.mode = self.previous_mode;
             }
         }
@@ -355,13 +797,52 @@ impl App {
         self.apply_filter();
     }
 
-    /// Refresh data
+    /// Reload workspaces and the open workspace's sessions from disk
+    ///
+    /// Keeps the selected workspace and session, and any workspace filter,
+    /// when they still exist.
     pub fn refresh(&mut self) {
-        if let Ok(workspaces) = discover_workspaces() {
-            self.workspaces = workspaces;
-            self.apply_filter();
-            self.status_message = Some("Refreshed workspace data".to_string());
+        let workspaces = match discover_workspaces(&self.discovery) {
+            Ok(workspaces) => workspaces,
+            Err(e) => {
+                self.status_message = Some(format!("Reload failed: {}", e));
+                return;
+            }
+        };
+
+        let selected = self.current_workspace().map(|ws| ws.hash.clone());
+        let open_session = (self.mode == AppMode::SessionDetail)
+            .then(|| self.current_session().map(|s| s.id().to_string()))
+            .flatten();
+        self.workspaces = workspaces;
+        self.update_filtered_indices();
+        self.workspace_index = selected
+            .and_then(|hash| {
+                self.filtered_indices
+                    .iter()
+                    .position(|&i| self.workspaces[i].hash == hash)
+            })
+            .unwrap_or(0);
+
+        self.detail_cache.clear();
+        if self.all_workspaces {
+            self.load_all_sessions();
+        } else {
+            self.load_sessions_for_current_workspace();
+        }
+        if matches!(self.mode, AppMode::Sessions | AppMode::SessionDetail) {
+            if self.sessions.is_empty() {
+                self.load_sessions_for_current_workspace();
+                self.mode = AppMode::Workspaces;
+            } else if self.mode == AppMode::SessionDetail {
+                if self.current_session().map(SessionInfo::id) == open_session.as_deref() {
+                    self.load_detail();
+                } else {
+                    self.mode = AppMode::Sessions;
+                }
+            }
         }
+        self.status_message = Some("Reloaded from disk".to_string());
     }
 
     /// Get count of workspaces with chats
@@ -376,4 +857,286 @@ impl App {
     pub fn total_sessions(&self) -> usize {
         self.workspaces.iter().map(|w| w.chat_session_count).sum()
     }
+
+    /// Position, totals and filters of the list the current view browses
+    pub fn status_summary(&self) -> StatusSummary {
+        let mut filters = Vec::new();
+        let (position, totals) = match self.mode {
+            AppMode::Sessions | AppMode::SessionDetail => {
+                let messages: usize = self.sessions.iter().map(|s| s.message_count).sum();
+                (
+                    position_label("session", self.session_index, self.sessions.len()),
+                    format!("{} messages", messages),
+                )
+            }
+            AppMode::WorkspacePicker => (
+                position_label("workspace", self.picker_index, self.picker_paths.len()),
+                format!("{} sessions", self.total_sessions()),
+            ),
+            AppMode::Workspaces | AppMode::Help => {
+                if !self.filter_query.is_empty() {
+                    filters.push(("filter", format!("\"{}\"", self.filter_query)));
+                }
+                let sessions: usize = self
+                    .filtered_indices
+                    .iter()
+                    .filter_map(|&i| self.workspaces.get(i))
+                    .map(|ws| ws.chat_session_count)
+                    .sum();
+                (
+                    position_label(
+                        "workspace",
+                        self.workspace_index,
+                        self.filtered_indices.len(),
+                    ),
+                    format!("{} sessions", sessions),
+                )
+            }
+        };
+        if let (Some(path), AppMode::Sessions | AppMode::SessionDetail) =
+            (&self.workspace_filter, self.mode)
+        {
+            filters.push(("workspace", path.clone()));
+        }
+
+        StatusSummary {
+            position,
+            totals,
+            filters,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_session_age_buckets() {
+        let thresholds = ColorThresholds::default();
+        let now = Utc.with_ymd_and_hms(2024, 5, 20, 12, 0, 0).unwrap();
+
+        assert_eq!(thresholds.age_of(Some(now), now), SessionAge::Today);
+        assert_eq!(
+            thresholds.age_of(Some(now - Duration::hours(13)), now),
+            SessionAge::Recent
+        );
+        assert_eq!(
+            thresholds.age_of(Some(now - Duration::days(6)), now),
+            SessionAge::Recent
+        );
+        assert_eq!(
+            thresholds.age_of(Some(now - Duration::days(7)), now),
+            SessionAge::Older
+        );
+        assert_eq!(thresholds.age_of(None, now), SessionAge::Older);
+
+        assert!(!thresholds.is_high_message_count(49));
+        assert!(thresholds.is_high_message_count(50));
+    }
+
+    fn session_info(title: &str, messages: usize, day: u32) -> SessionInfo {
+        let session: ChatSession = serde_json::from_value(serde_json::json!({
+            "version": 3,
+            "customTitle": title,
+            "requests": []
+        }))
+        .unwrap();
+        SessionInfo {
+            filename: format!("{}.json", title),
+            path: PathBuf::from(format!("{}.json", title)),
+            workspace: None,
+            session: Rc::new(session),
+            last_modified: String::new(),
+            modified: Some(Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap()),
+            message_count: messages,
+        }
+    }
+
+    #[test]
+    fn test_session_sort() {
+        let titles = |sessions: &[SessionInfo]| -> Vec<String> {
+            sessions.iter().map(|s| s.session.title()).collect()
+        };
+        let mut sessions = vec![
+            session_info("beta", 5, 2),
+            session_info("Alpha", 20, 1),
+            session_info("gamma", 1, 3),
+        ];
+
+        let mut sort = SessionSort::default();
+        sort.sort(&mut sessions);
+        assert_eq!(titles(&sessions), ["gamma", "beta", "Alpha"]);
+
+        sort.key = sort.key.next();
+        assert_eq!(sort.key, SessionSortKey::Title);
+        sort.descending = false;
+        sort.sort(&mut sessions);
+        assert_eq!(titles(&sessions), ["Alpha", "beta", "gamma"]);
+
+        sort.key = sort.key.next();
+        sort.descending = true;
+        sort.sort(&mut sessions);
+        assert_eq!(titles(&sessions), ["Alpha", "beta", "gamma"]);
+        assert_eq!(sort.key.next(), SessionSortKey::Modified);
+    }
+
+    fn workspace(hash: &str, path: &str, sessions: usize) -> Workspace {
+        Workspace {
+            hash: hash.to_string(),
+            storage_root: None,
+            channel: Default::default(),
+            remote: false,
+            project_path: Some(path.to_string()),
+            workspace_path: PathBuf::from(hash),
+            chat_sessions_path: PathBuf::from(hash).join("chatSessions"),
+            chat_session_count: sessions,
+            has_chat_sessions: sessions > 0,
+            last_modified: None,
+        }
+    }
+
+    #[test]
+    fn test_workspace_picker_paths() {
+        let workspaces = [
+            workspace("a", "/home/u/web", 2),
+            workspace("b", "/home/u/api", 1),
+            workspace("c", "/home/u/web", 4),
+            workspace("d", "/home/u/empty", 0),
+        ];
+        assert_eq!(
+            workspace_picker_paths(&workspaces),
+            ["/home/u/api", "/home/u/web"]
+        );
+    }
+
+    #[test]
+    fn test_workspace_filter() {
+        let titles =
+            |app: &App| -> Vec<String> { app.sessions.iter().map(|s| s.session.title()).collect() };
+        let mut app = App::with_workspaces(Vec::new());
+        app.all_workspaces = true;
+        app.all_sessions = [
+            ("web-1", "/web", 1),
+            ("api-1", "/api", 2),
+            ("web-2", "/web", 3),
+        ]
+        .into_iter()
+        .map(|(title, ws, day)| SessionInfo {
+            workspace: Some(ws.to_string()),
+            ..session_info(title, 1, day)
+        })
+        .collect();
+        app.picker_paths = vec!["/api".to_string(), "/web".to_string()];
+        app.picker_index = 1;
+        app.mode = AppMode::WorkspacePicker;
+        app.previous_mode = AppMode::Sessions;
+
+        app.enter();
+        assert_eq!(app.mode, AppMode::Sessions);
+        assert_eq!(app.session_scope_label(), Some("/web"));
+        assert_eq!(titles(&app), ["web-2", "web-1"]);
+
+        // Esc drops the filter before leaving the session table
+        app.back();
+        assert_eq!(app.mode, AppMode::Sessions);
+        assert_eq!(app.workspace_filter, None);
+        assert_eq!(titles(&app), ["web-2", "api-1", "web-1"]);
+        assert_eq!(app.session_scope_label(), None);
+
+        app.back();
+        assert_eq!(app.mode, AppMode::Workspaces);
+        assert!(!app.all_workspaces);
+    }
+
+    #[test]
+    fn test_status_summary() {
+        let mut app = App::with_workspaces(vec![
+            workspace("a", "/home/u/web", 2),
+            workspace("b", "/home/u/api", 3),
+        ]);
+        app.workspace_index = 1;
+        assert_eq!(
+            app.status_summary(),
+            StatusSummary {
+                position: "workspace 2 of 2".to_string(),
+                totals: "5 sessions".to_string(),
+                filters: Vec::new(),
+            }
+        );
+
+        app.filter_query = "api".to_string();
+        app.apply_filter();
+        let summary = app.status_summary();
+        assert_eq!(summary.position, "workspace 1 of 1");
+        assert_eq!(summary.totals, "3 sessions");
+        assert_eq!(summary.filters, [("filter", "\"api\"".to_string())]);
+
+        app.mode = AppMode::Sessions;
+        app.workspace_filter = Some("/web".to_string());
+        app.sessions = vec![session_info("x", 4, 1), session_info("y", 6, 2)];
+        app.session_index = 1;
+        let summary = app.status_summary();
+        assert_eq!(summary.position, "session 2 of 2");
+        assert_eq!(summary.totals, "10 messages");
+        assert_eq!(summary.filters, [("workspace", "/web".to_string())]);
+
+        app.sessions.clear();
+        assert_eq!(app.status_summary().position, "no sessions");
+    }
+
+    #[test]
+    fn test_detail_lines() {
+        let session: ChatSession = serde_json::from_value(serde_json::json!({
+            "version": 3,
+            "requests": [
+                {"timestamp": 0, "message": {"text": "first\nsecond"}},
+                {"message": {"text": "again"}}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            detail_lines(&session),
+            [
+                DetailLine::Timestamp("1970-01-01 00:00:00".to_string()),
+                DetailLine::User(1),
+                DetailLine::Text("first".to_string()),
+                DetailLine::Text("second".to_string()),
+                DetailLine::Blank,
+                DetailLine::User(2),
+                DetailLine::Text("again".to_string()),
+                DetailLine::Blank,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detail_cache_evicts_least_recently_used() {
+        let cached = |cache: &DetailCache, id: &str| cache.entries.iter().any(|(key, _)| key == id);
+        let mut cache = DetailCache::default();
+        let mut builds = 0;
+        for i in 0..DETAIL_CACHE_CAPACITY {
+            cache.get_or_insert_with(&i.to_string(), || {
+                builds += 1;
+                vec![DetailLine::Blank]
+            });
+        }
+
+        // A hit doesn't rebuild, and marks the entry as recently used
+        cache.get_or_insert_with("0", || {
+            builds += 1;
+            Vec::new()
+        });
+        assert_eq!(builds, DETAIL_CACHE_CAPACITY);
+
+        cache.get_or_insert_with("new", Vec::new);
+        assert!(cached(&cache, "0"));
+        assert!(!cached(&cache, "1"));
+        assert!(cached(&cache, "new"));
+
+        cache.clear();
+        assert!(!cached(&cache, "0"));
+    }
 }
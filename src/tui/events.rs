@@ -3,6 +3,7 @@
 //! Event handling and main TUI loop
 
 use std::io;
+use std::time::Duration;
 
 use anyhow::Result;
 use crossterm::{
@@ -16,9 +17,15 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 
 use super::app::{App, AppMode};
 use super::ui;
+use super::watch::StorageWatcher;
+use crate::workspace::DiscoveryOptions;
 
-/// Run the TUI application
-pub fn run_tui() -> Result<()> {
+/// How long to wait for input before checking the watcher
+const WATCH_INPUT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Run the TUI application over the workspaces `discovery` finds,
+/// reloading when sessions change on disk if `watch` is set
+pub fn run_tui(watch: bool, discovery: &DiscoveryOptions) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -27,10 +34,11 @@ pub fn run_tui() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app state
-    let mut app = App::new()?;
+    let mut app = App::new(discovery.clone())?;
+    let watcher = watch.then(|| StorageWatcher::start(discovery.clone()));
 
     // Main loop
-    let res = run_app(&mut terminal, &mut app);
+    let res = run_app(&mut terminal, &mut app, watcher.as_ref());
 
     // Restore terminal
     disable_raw_mode()?;
@@ -49,13 +57,35 @@ pub fn run_tui() -> Result<()> {
 }
 
 /// Main application loop
-fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+fn run_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    watcher: Option<&StorageWatcher>,
+) -> Result<()> {
     // Initial draw
     terminal.draw(|f| ui::render(f, app))?;
 
     loop {
-        // Block waiting for input - no polling delay, instant response
-        if let Event::Key(key) = event::read()? {
+        let event = match watcher {
+            // Alternate between input and file changes; changes wait while
+            // the user is typing a filter or picking a workspace
+            Some(watcher) => {
+                if !event::poll(WATCH_INPUT_TIMEOUT)? {
+                    let busy = app.filter_active
+                        || matches!(app.mode, AppMode::Help | AppMode::WorkspacePicker);
+                    if !busy && watcher.changed() {
+                        app.refresh();
+                        terminal.draw(|f| ui::render(f, app))?;
+                    }
+                    continue;
+                }
+                event::read()?
+            }
+            // Block waiting for input - no polling delay, instant response
+            None => event::read()?,
+        };
+
+        if let Event::Key(key) = event {
             // Only handle key press events, ignore release/repeat to prevent double-triggering
             if key.kind != KeyEventKind::Press {
                 continue;
@@ -122,9 +152,20 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                 KeyCode::Char('/') if app.mode == AppMode::Workspaces => {
                     app.start_filter();
                 }
-                KeyCode::Char('r') => {
+                KeyCode::Char('w')
+                    if matches!(app.mode, AppMode::Workspaces | AppMode::Sessions) =>
+                {
+                    app.open_workspace_picker();
+                }
+                KeyCode::Char('r') if app.mode != AppMode::WorkspacePicker => {
                     app.refresh();
                 }
+                KeyCode::Char('s') if app.mode == AppMode::Sessions => {
+                    app.cycle_session_sort();
+                }
+                KeyCode::Char('S') if app.mode == AppMode::Sessions => {
+                    app.toggle_session_sort_direction();
+                }
                 _ => continue, // No redraw needed for unhandled keys
             }
 
@@ -7,5 +7,6 @@
 mod app;
 mod events;
 mod ui;
+mod watch;
 
 pub use events::run_tui;
@@ -4,16 +4,16 @@
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
     widgets::{
-        Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Scrollbar,
+        Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Scrollbar,
         ScrollbarOrientation, ScrollbarState, Table, TableState, Wrap,
     },
     Frame,
 };
 
-use super::app::{App, AppMode};
+use super::app::{App, AppMode, DetailLine, SessionAge};
 
 /// Color scheme for the TUI (Ayu Monokai)
 #[allow(dead_code)]
@@ -51,7 +51,7 @@ pub fn render(frame: &mut Frame, app: &App) {
         .constraints([
             Constraint::Length(3), // Header
             Constraint::Min(0),    // Main content
-            Constraint::Length(3), // Footer/status
+            Constraint::Length(4), // Footer/status
         ])
         .split(frame.area());
 
@@ -59,9 +59,11 @@ pub fn render(frame: &mut Frame, app: &App) {
     render_main_content(frame, app, chunks[1]);
     render_footer(frame, app, chunks[2]);
 
-    // Render help overlay if active
-    if app.mode == AppMode::Help {
-        render_help_overlay(frame);
+    // Render overlays if active
+    match app.mode {
+        AppMode::Help => render_help_overlay(frame),
+        AppMode::WorkspacePicker => render_workspace_picker(frame, app),
+        _ => {}
     }
 }
 
@@ -71,6 +73,7 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
         AppMode::Workspaces => " CCSM - Workspaces ",
         AppMode::Sessions => " CCSM - Sessions ",
         AppMode::SessionDetail => " CCSM - Session Details ",
+        AppMode::WorkspacePicker => " CCSM - Pick Workspace ",
         AppMode::Help => " CCSM - Help ",
     };
 
@@ -102,6 +105,11 @@ fn render_main_content(frame: &mut Frame, app: &App, area: Rect) {
         AppMode::Workspaces => render_workspaces_view(frame, app, area),
         AppMode::Sessions => render_sessions_view(frame, app, area),
         AppMode::SessionDetail => render_session_detail_view(frame, app, area),
+        // Show the view the picker was opened from behind it
+        AppMode::WorkspacePicker if app.previous_mode == AppMode::Sessions => {
+            render_sessions_view(frame, app, area)
+        }
+        AppMode::WorkspacePicker => render_workspaces_view(frame, app, area),
         AppMode::Help => render_workspaces_view(frame, app, area), // Show workspaces behind help
     }
 }
@@ -273,22 +281,42 @@ fn render_sessions_view(frame: &mut Frame, app: &App, area: Rect) {
     render_message_preview(frame, app, chunks[1]);
 }
 
+/// Style for a session row based on how recently it was modified
+///
+/// Monochrome terminals get bold/normal/dim instead of colors.
+fn session_age_style(age: SessionAge, monochrome: bool) -> Style {
+    match (age, monochrome) {
+        (SessionAge::Today, false) => Style::default().fg(Colors::SUCCESS),
+        (SessionAge::Recent, false) => Style::default().fg(Colors::ACCENT),
+        (SessionAge::Older, false) => Style::default().fg(Colors::TEXT_DIM),
+        (SessionAge::Today, true) => Style::default().add_modifier(Modifier::BOLD),
+        (SessionAge::Recent, true) => Style::default(),
+        (SessionAge::Older, true) => Style::default().add_modifier(Modifier::DIM),
+    }
+}
+
 /// Render session table
 fn render_session_table(frame: &mut Frame, app: &App, area: Rect) {
     let ws_name = app
-        .current_workspace()
-        .and_then(|ws| ws.project_path.as_ref())
+        .session_scope_label()
         .map(|p| truncate_path(p, 30))
-        .unwrap_or_else(|| "(none)".to_string());
+        .unwrap_or_else(|| "All workspaces".to_string());
 
-    let header_cells = ["#", "Title", "Messages", "Modified"]
-        .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(Colors::ACCENT).bold()));
+    let sort = app.session_sort;
+    let header_cells = ["#", "Title", "Messages", "Modified"].iter().map(|h| {
+        let label = if *h == sort.key.header() {
+            format!("{} {}", h, if sort.descending { "v" } else { "^" })
+        } else {
+            h.to_string()
+        };
+        Cell::from(label).style(Style::default().fg(Colors::ACCENT).bold())
+    });
 
     let header = Row::new(header_cells)
         .style(Style::default().bg(Colors::HEADER_BG))
         .height(1);
 
+    let now = chrono::Utc::now();
     let rows: Vec<Row> = app
         .sessions
         .iter()
@@ -296,20 +324,35 @@ fn render_session_table(frame: &mut Frame, app: &App, area: Rect) {
         .map(|(i, s)| {
             let is_selected = i == app.session_index;
             let title = s.session.title();
+            let age_style =
+                session_age_style(app.thresholds.age_of(s.modified, now), app.monochrome);
 
             let row_style = if is_selected {
                 Style::default()
                     .bg(Colors::SELECTED_BG)
                     .fg(Colors::SELECTED_FG)
             } else {
-                Style::default().fg(Colors::TEXT)
+                age_style
+            };
+
+            let high_count = app.thresholds.is_high_message_count(s.message_count);
+            let count_style = match (high_count, app.monochrome) {
+                (true, true) => Style::default().add_modifier(Modifier::BOLD),
+                (true, false) => Style::default().fg(Colors::WARNING).bold(),
+                (false, true) => Style::default(),
+                (false, false) => Style::default().fg(Colors::INFO),
+            };
+            let count = if high_count {
+                format!("{} +", s.message_count)
+            } else {
+                s.message_count.to_string()
             };
 
             Row::new(vec![
                 Cell::from(format!("{}", i + 1)),
                 Cell::from(truncate_string(&title, 35)),
-                Cell::from(format!("{}", s.message_count)).style(Style::default().fg(Colors::INFO)),
-                Cell::from(s.last_modified.clone()).style(Style::default().fg(Colors::TEXT_DIM)),
+                Cell::from(count).style(count_style),
+                Cell::from(s.last_modified.clone()).style(age_style),
             ])
             .style(row_style)
             .height(1)
@@ -456,37 +499,26 @@ fn render_session_detail_view(frame: &mut Frame, app: &App, area: Rect) {
             Style::default().fg(Colors::ACCENT),
         ));
 
-    let mut lines: Vec<Line> = Vec::new();
-
-    for (i, req) in session.session.requests.iter().enumerate() {
-        // Timestamp
-        if let Some(ts) = req.timestamp {
-            let dt = chrono::DateTime::from_timestamp_millis(ts)
-                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
-                .unwrap_or_else(|| "unknown".to_string());
-            lines.push(Line::from(Span::styled(
+    // Transcript is built once per session by the app and cached
+    let lines: Vec<Line> = app
+        .detail
+        .iter()
+        .map(|line| match line {
+            DetailLine::Timestamp(dt) => Line::from(Span::styled(
                 format!("--- {} ---", dt),
                 Style::default().fg(Colors::TEXT_DIM),
-            )));
-        }
-
-        // User message
-        if let Some(msg) = &req.message {
-            let text = msg.get_text();
-            lines.push(Line::from(vec![Span::styled(
-                format!("[{}] User: ", i + 1),
+            )),
+            DetailLine::User(n) => Line::from(Span::styled(
+                format!("[{}] User: ", n),
                 Style::default().fg(Colors::SUCCESS).bold(),
-            )]));
-            for line in text.lines() {
-                lines.push(Line::from(Span::styled(
-                    format!("    {}", line),
-                    Style::default().fg(Colors::TEXT),
-                )));
-            }
-        }
-
-        lines.push(Line::raw(""));
-    }
+            )),
+            DetailLine::Text(text) => Line::from(Span::styled(
+                format!("    {}", text),
+                Style::default().fg(Colors::TEXT),
+            )),
+            DetailLine::Blank => Line::raw(""),
+        })
+        .collect();
 
     let paragraph = Paragraph::new(lines)
         .block(msg_block)
@@ -500,7 +532,7 @@ fn render_session_detail_view(frame: &mut Frame, app: &App, area: Rect) {
         .begin_symbol(Some("^"))
         .end_symbol(Some("v"));
 
-    let total_lines = session.session.requests.len() * 5; // Approximate
+    let total_lines = app.detail.len();
     let mut scrollbar_state = ScrollbarState::new(total_lines).position(app.detail_scroll);
 
     frame.render_stateful_widget(
@@ -523,25 +555,57 @@ fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
                     app.filter_query
                 )
             } else {
-                "[j/k] navigate | [Enter] view sessions | [/] filter | [r] refresh | [?] help | [q] quit".to_string()
+                "[j/k] navigate | [Enter] view sessions | [/] filter | [w] workspace | [r] reload | [?] help | [q] quit".to_string()
             }
         }
+        AppMode::Sessions if app.workspace_filter.is_some() => {
+            "[j/k] navigate | [Enter] view details | [s/S] sort | [w] workspace | [Esc] all sessions | [?] help | [q] quit"
+                .to_string()
+        }
         AppMode::Sessions => {
-            "[j/k] navigate | [Enter] view details | [Esc] back | [?] help | [q] quit".to_string()
+            "[j/k] navigate | [Enter] view details | [s/S] sort | [w] workspace | [r] reload | [Esc] back | [?] help | [q] quit"
+                .to_string()
+        }
+        AppMode::SessionDetail => {
+            "[j/k] scroll | [r] reload | [Esc] back | [?] help | [q] quit".to_string()
+        }
+        AppMode::WorkspacePicker => {
+            "[j/k] navigate | [Enter] show its sessions | [Esc] cancel".to_string()
         }
-        AppMode::SessionDetail => "[j/k] scroll | [Esc] back | [?] help | [q] quit".to_string(),
         AppMode::Help => "Press any key to close help".to_string(),
     };
 
-    let status = app.status_message.as_deref().unwrap_or("");
+    let summary = app.status_summary();
+    let mut status = vec![
+        Span::raw(" "),
+        Span::styled(summary.position, Style::default().fg(Colors::ACCENT).bold()),
+        Span::styled(" | ", Style::default().fg(Colors::TEXT_DIM)),
+        Span::styled(summary.totals, Style::default().fg(Colors::TEXT)),
+    ];
+    for (label, value) in &summary.filters {
+        status.push(Span::styled(" | ", Style::default().fg(Colors::TEXT_DIM)));
+        status.push(Span::styled(
+            format!("{}: {}", label, truncate_path(value, 40)),
+            Style::default().fg(Colors::PURPLE),
+        ));
+    }
+    if let Some(message) = &app.status_message {
+        status.push(Span::raw("  "));
+        status.push(Span::styled(
+            message.as_str(),
+            Style::default().fg(Colors::WARNING),
+        ));
+    }
 
-    let footer = Paragraph::new(Line::from(vec![
-        Span::styled(" ", Style::default()),
-        Span::styled(mode_hint, Style::default().fg(Colors::TEXT_DIM)),
-        Span::styled("  ", Style::default()),
-        Span::styled(status, Style::default().fg(Colors::WARNING)),
-    ]))
-    .block(
+    let lines = vec![
+        Line::from(status),
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled(mode_hint, Style::default().fg(Colors::TEXT_DIM)),
+        ]),
+    ];
+
+    let footer = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Colors::BORDER))
@@ -607,9 +671,26 @@ fn render_help_overlay(frame: &mut Frame) {
                 Style::default().fg(Colors::TEXT),
             ),
         ]),
+        Line::from(vec![
+            Span::styled("  w           ", Style::default().fg(Colors::PURPLE)),
+            Span::styled(
+                "Pick a workspace to show sessions from (Esc shows all)",
+                Style::default().fg(Colors::TEXT),
+            ),
+        ]),
         Line::from(vec![
             Span::styled("  r           ", Style::default().fg(Colors::PURPLE)),
-            Span::styled("Refresh data", Style::default().fg(Colors::TEXT)),
+            Span::styled(
+                "Reload workspaces and sessions from disk",
+                Style::default().fg(Colors::TEXT),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  s / S       ", Style::default().fg(Colors::PURPLE)),
+            Span::styled(
+                "Cycle sort column / direction (sessions view)",
+                Style::default().fg(Colors::TEXT),
+            ),
         ]),
         Line::raw(""),
         Line::from(vec![Span::styled(
@@ -647,6 +728,49 @@ fn render_help_overlay(frame: &mut Frame) {
     frame.render_widget(help, area);
 }
 
+/// Render the workspace picker overlay
+fn render_workspace_picker(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = app
+        .picker_paths
+        .iter()
+        .map(|path| {
+            let style = if app.workspace_filter.as_ref() == Some(path) {
+                Style::default().fg(Colors::ACCENT)
+            } else {
+                Style::default().fg(Colors::TEXT)
+            };
+            ListItem::new(Span::styled(truncate_path(path, 60), style))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" Workspaces ({}) ", app.picker_paths.len()),
+                    Style::default().fg(Colors::ACCENT).bold(),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Colors::BORDER_FOCUSED))
+                .style(Style::default().bg(Color::Rgb(24, 24, 37))),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Colors::SELECTED_BG)
+                .fg(Colors::SELECTED_FG),
+        )
+        .highlight_symbol(">> ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.picker_index));
+
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
 /// Helper to create a centered rectangle
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
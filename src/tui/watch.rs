@@ -0,0 +1,96 @@
+// Copyright (c) 2024-2026 Nervosys LLC
+// SPDX-License-Identifier: Apache-2.0
+//! `run tui --watch`: notice changes under `workspaceStorage`
+//!
+//! A background thread polls the workspace directories and their
+//! `chatSessions` files, and sends one message down a channel once a change
+//! has settled. Polling keeps this dependency-free and behaves the same on
+//! network drives and WSL mounts, where native file events are unreliable.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::workspace::{storage_workspace_dirs, DiscoveryOptions};
+
+/// How often the storage tree is checked
+pub const POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Watches `workspaceStorage` from a background thread
+///
+/// Once the watcher is dropped, the thread exits the next time it has a
+/// change to report.
+pub struct StorageWatcher {
+    changes: Receiver<()>,
+}
+
+impl StorageWatcher {
+    /// Start watching the workspace directories `discovery` finds
+    pub fn start(discovery: DiscoveryOptions) -> Self {
+        let (tx, changes) = mpsc::channel();
+        thread::spawn(move || {
+            let mut last = storage_fingerprint(&discovery);
+            let mut pending = false;
+            loop {
+                thread::sleep(POLL_INTERVAL);
+                let current = storage_fingerprint(&discovery);
+                if current != last {
+                    // Still changing (VS Code writes in bursts); wait for quiet
+                    last = current;
+                    pending = true;
+                } else if pending {
+                    pending = false;
+                    if tx.send(()).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Self { changes }
+    }
+
+    /// Whether anything changed since the last call, collapsing any number
+    /// of queued changes into one
+    pub fn changed(&self) -> bool {
+        let mut changed = false;
+        while self.changes.try_recv().is_ok() {
+            changed = true;
+        }
+        changed
+    }
+}
+
+/// Hash of every workspace directory and chat session file with its size
+/// and modification time; unreadable entries simply drop out
+pub fn storage_fingerprint(discovery: &DiscoveryOptions) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    if let Ok(dirs) = storage_workspace_dirs(discovery, &mut Vec::new()) {
+        for (_, dir) in dirs {
+            dir.hash(&mut hasher);
+            hash_entry(&dir.join("workspace.json"), &mut hasher);
+            let Ok(entries) = std::fs::read_dir(dir.join("chatSessions")) else {
+                continue;
+            };
+            let mut files: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+            files.sort();
+            for file in files {
+                file.hash(&mut hasher);
+                hash_entry(&file, &mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+fn hash_entry(path: &Path, hasher: &mut DefaultHasher) {
+    if let Ok(meta) = path.metadata() {
+        meta.len().hash(hasher);
+        meta.modified()
+            .ok()
+            .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .hash(hasher);
+    }
+}
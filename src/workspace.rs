@@ -5,26 +5,526 @@
 use crate::error::{CsmError, Result};
 use crate::models::{SessionWithPath, Workspace, WorkspaceJson};
 use crate::storage::parse_session_json;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use urlencoding::decode;
 
 /// Type alias for workspace info tuple (hash, path, project_path, modified_time)
 pub type WorkspaceInfo = (String, PathBuf, Option<String>, std::time::SystemTime);
 
-/// Get the VS Code workspaceStorage path based on the operating system
+/// Environment variable overriding the workspaceStorage root
+pub const STORAGE_PATH_ENV: &str = "CSM_STORAGE_PATH";
+
+/// What workspace discovery reads and skips, from `--depth`,
+/// `--follow-symlinks`, `--channel`, `--exclude` and `.csmignore`. The
+/// default scans a flat workspaceStorage of both VS Code builds and skips
+/// nothing.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveryOptions {
+    /// Nested directory levels walked below each storage root. 0 scans a
+    /// single flat workspaceStorage directory; higher values find per-user
+    /// trees such as `<root>/alice/.config/Code/User/workspaceStorage/<hash>`.
+    pub depth: usize,
+    /// Whether to descend into symlinked directories, e.g. workspaces kept on
+    /// another drive
+    pub follow_symlinks: bool,
+    /// Which VS Code builds (Stable, Insiders or both) to read
+    pub channel: ChannelFilter,
+    /// Project path globs whose workspaces are dropped, e.g. `/tmp/*`
+    pub exclude: Vec<glob::Pattern>,
+    /// Project path or session ID globs loaded from `.csmignore` files
+    pub ignore: Vec<glob::Pattern>,
+}
+
+impl DiscoveryOptions {
+    /// Add the globs (from `--exclude` and the `exclude_globs` config key)
+    /// whose matching project paths are dropped
+    pub fn with_exclude_globs(mut self, globs: &[String]) -> Result<Self> {
+        for g in globs {
+            let pattern = glob::Pattern::new(g.trim_start_matches('/'))
+                .map_err(|e| CsmError::InvalidExcludeGlob(format!("'{}': {}", g, e)))?;
+            self.exclude.push(pattern);
+        }
+        Ok(self)
+    }
+
+    /// Add the patterns of every `.csmignore` in [`ignore_file_paths`];
+    /// missing files are skipped
+    pub fn with_ignore_files(mut self) -> Result<Self> {
+        for path in ignore_file_paths() {
+            match std::fs::read_to_string(&path) {
+                Ok(content) => self.ignore.extend(parse_ignore_patterns(&content, &path)?),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(self)
+    }
+
+    /// The same walk (depth and symlinks) with no channel, exclude or
+    /// `.csmignore` filtering, for checks that must see every session on disk
+    pub fn unfiltered(&self) -> Self {
+        Self {
+            depth: self.depth,
+            follow_symlinks: self.follow_symlinks,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a workspace with this project path is excluded
+    pub fn is_excluded(&self, project_path: Option<&str>) -> bool {
+        project_path.is_some_and(|path| matches_any(&self.exclude, path))
+    }
+
+    /// Whether a workspace with this project path is hidden by `.csmignore`
+    pub fn is_ignored(&self, project_path: Option<&str>) -> bool {
+        project_path.is_some_and(|path| matches_any(&self.ignore, path))
+    }
+
+    /// Whether a session ID is hidden by `.csmignore`
+    pub fn is_session_ignored(&self, session_id: &str) -> bool {
+        matches_any(&self.ignore, session_id)
+    }
+}
+
+/// Whether any of `patterns` matches `value`. Both sides are compared
+/// without a leading slash, so `/tmp/*` and `tmp/*` match alike.
+fn matches_any(patterns: &[glob::Pattern], value: &str) -> bool {
+    let value = value.replace('\\', "/");
+    let value = value.trim_start_matches('/');
+    patterns.iter().any(|p| p.matches(value))
+}
+
+/// VS Code build whose storage a workspace was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VsCodeChannel {
+    #[default]
+    Stable,
+    Insiders,
+}
+
+impl VsCodeChannel {
+    pub const ALL: [VsCodeChannel; 2] = [VsCodeChannel::Stable, VsCodeChannel::Insiders];
+
+    /// Folder the build keeps its user data in (under the OS config directory)
+    pub fn app_dir(self) -> &'static str {
+        match self {
+            VsCodeChannel::Stable => "Code",
+            VsCodeChannel::Insiders => "Code - Insiders",
+        }
+    }
+
+    /// Folder the build's VS Code Server (Remote - SSH, WSL, dev
+    /// containers) keeps its data in, under the remote user's home
+    pub fn server_dir(self) -> &'static str {
+        match self {
+            VsCodeChannel::Stable => ".vscode-server",
+            VsCodeChannel::Insiders => ".vscode-server-insiders",
+        }
+    }
+
+    /// Channel of a workspace directory: Insiders when it sits under the
+    /// Insiders app folder or server folder, which also holds for nested
+    /// `--depth` scans
+    pub fn of_path(path: &Path) -> Self {
+        let insiders = [
+            VsCodeChannel::Insiders.app_dir(),
+            VsCodeChannel::Insiders.server_dir(),
+        ];
+        if path
+            .components()
+            .any(|c| insiders.iter().any(|dir| c.as_os_str() == *dir))
+        {
+            VsCodeChannel::Insiders
+        } else {
+            VsCodeChannel::Stable
+        }
+    }
+}
+
+impl std::fmt::Display for VsCodeChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VsCodeChannel::Stable => write!(f, "stable"),
+            VsCodeChannel::Insiders => write!(f, "insiders"),
+        }
+    }
+}
+
+/// `--channel`: which VS Code builds discovery reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChannelFilter {
+    Stable,
+    Insiders,
+    #[default]
+    All,
+}
+
+impl ChannelFilter {
+    /// Names accepted on the command line
+    pub const VALID_NAMES: &'static [&'static str] = &["stable", "insiders", "all"];
+
+    pub fn includes(self, channel: VsCodeChannel) -> bool {
+        match self {
+            ChannelFilter::Stable => channel == VsCodeChannel::Stable,
+            ChannelFilter::Insiders => channel == VsCodeChannel::Insiders,
+            ChannelFilter::All => true,
+        }
+    }
+}
+
+impl std::str::FromStr for ChannelFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "stable" => Ok(ChannelFilter::Stable),
+            "insiders" => Ok(ChannelFilter::Insiders),
+            "all" => Ok(ChannelFilter::All),
+            _ => Err(format!(
+                "unknown channel '{}' (valid channels: {})",
+                s,
+                Self::VALID_NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// Ignore file listing project path or session ID globs to hide, one per line
+pub const IGNORE_FILE_NAME: &str = ".csmignore";
+
+/// Parse `.csmignore` content: one glob per line, with blank lines and `#`
+/// comments skipped. `source` names the file in errors.
+pub fn parse_ignore_patterns(content: &str, source: &Path) -> Result<Vec<glob::Pattern>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            glob::Pattern::new(line.trim_start_matches('/')).map_err(|e| {
+                CsmError::InvalidIgnorePattern(format!("{}: '{}': {}", source.display(), line, e))
+            })
+        })
+        .collect()
+}
+
+/// Where `.csmignore` is looked for: the current directory, then the csm
+/// config directory (next to `config.json`)
+pub fn ignore_file_paths() -> Vec<PathBuf> {
+    let cwd = std::env::current_dir().ok();
+    let config = dirs::config_dir().map(|p| p.join("csm"));
+    cwd.into_iter()
+        .chain(config)
+        .map(|dir| dir.join(IGNORE_FILE_NAME))
+        .collect()
+}
+
+/// Find workspace directories (those containing `workspace.json` or
+/// `chatSessions`) under `root`, descending at most `depth` levels below its
+/// direct children. Workspace directories themselves are never descended into.
+#[allow(dead_code)]
+pub fn find_workspace_dirs(root: &Path, depth: usize) -> Vec<PathBuf> {
+    find_workspace_dirs_reporting(root, depth, &mut Vec::new())
+}
+
+/// [`find_workspace_dirs`], recording directories that couldn't be read in
+/// `errors` instead of silently leaving them out. Symlinked workspaces are
+/// listed, but symlinked directories are not descended into.
+pub fn find_workspace_dirs_reporting(
+    root: &Path,
+    depth: usize,
+    errors: &mut Vec<DiscoveryError>,
+) -> Vec<PathBuf> {
+    find_workspace_dirs_with(root, depth, false, errors)
+}
+
+/// [`find_workspace_dirs_reporting`] with explicit symlink handling
+///
+/// A symlinked workspace directory is always listed, as VS Code storage kept
+/// on another drive often is. Without `follow_symlinks`, other symlinked
+/// directories are not descended into. With it, they are resolved and walked
+/// like real directories; the canonical path of every directory visited is
+/// remembered, so a link back up the tree (or a second link to the same
+/// target) is walked only once rather than looping or listing the same
+/// workspace twice.
+pub fn find_workspace_dirs_with(
+    root: &Path,
+    depth: usize,
+    follow_symlinks: bool,
+    errors: &mut Vec<DiscoveryError>,
+) -> Vec<PathBuf> {
+    let mut visited = HashSet::new();
+    if follow_symlinks {
+        if let Ok(canonical) = root.canonicalize() {
+            visited.insert(canonical);
+        }
+    }
+    walk_workspace_dirs(root, depth, follow_symlinks, &mut visited, errors)
+}
+
+fn walk_workspace_dirs(
+    root: &Path,
+    depth: usize,
+    follow_symlinks: bool,
+    visited: &mut HashSet<PathBuf>,
+    errors: &mut Vec<DiscoveryError>,
+) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) => {
+            errors.push(DiscoveryError::skipped(root, DiscoveryIssue::from(e)));
+            return Vec::new();
+        }
+    };
+
+    let mut dirs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_symlink = entry.file_type().is_ok_and(|t| t.is_symlink());
+        if !path.is_dir() {
+            continue;
+        }
+        if follow_symlinks {
+            // Canonical paths see through links, so cycles end here
+            match path.canonicalize() {
+                Ok(canonical) => {
+                    if !visited.insert(canonical) {
+                        continue;
+                    }
+                }
+                Err(e) => {
+                    errors.push(DiscoveryError::skipped(&path, DiscoveryIssue::from(e)));
+                    continue;
+                }
+            }
+        }
+        // A directory we may list but not look into fails here, not above
+        match path.join("workspace.json").try_exists() {
+            Err(e) => errors.push(DiscoveryError::skipped(&path, DiscoveryIssue::from(e))),
+            Ok(true) => dirs.push(path),
+            Ok(false) if path.join("chatSessions").is_dir() => dirs.push(path),
+            Ok(false) if depth > 0 && (follow_symlinks || !is_symlink) => dirs.extend(
+                walk_workspace_dirs(&path, depth - 1, follow_symlinks, visited, errors),
+            ),
+            Ok(false) => {}
+        }
+    }
+    dirs
+}
+
+/// What went wrong reading a workspace directory during discovery
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscoveryIssue {
+    /// The directory or a file in it isn't readable by this user
+    PermissionDenied,
+    /// `workspace.json` is not valid JSON
+    InvalidJson(String),
+    /// There is a `chatSessions` directory but no `workspace.json`
+    MissingWorkspaceJson,
+    /// `workspace.json` has neither a `folder` nor a `workspace` entry
+    MissingFolder,
+    /// Any other I/O error
+    Io(String),
+}
+
+impl From<std::io::Error> for DiscoveryIssue {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::PermissionDenied => DiscoveryIssue::PermissionDenied,
+            _ => DiscoveryIssue::Io(e.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for DiscoveryIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiscoveryIssue::PermissionDenied => write!(f, "permission denied"),
+            DiscoveryIssue::InvalidJson(e) => write!(f, "invalid workspace.json ({})", e),
+            DiscoveryIssue::MissingWorkspaceJson => write!(f, "missing workspace.json"),
+            DiscoveryIssue::MissingFolder => write!(f, "workspace.json has no folder"),
+            DiscoveryIssue::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// A directory workspace discovery couldn't fully read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryError {
+    pub path: PathBuf,
+    pub issue: DiscoveryIssue,
+    /// Whether the workspace was left out, rather than listed with what
+    /// could be read (no project path, or no sessions)
+    pub skipped: bool,
+}
+
+impl DiscoveryError {
+    fn skipped(path: &Path, issue: DiscoveryIssue) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            issue,
+            skipped: true,
+        }
+    }
+
+    fn partial(path: &Path, issue: DiscoveryIssue) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            issue,
+            skipped: false,
+        }
+    }
+}
+
+impl std::fmt::Display for DiscoveryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let action = if self.skipped {
+            "Skipped"
+        } else {
+            "Partly read"
+        };
+        write!(f, "{} {}: {}", action, self.path.display(), self.issue)
+    }
+}
+
+/// Everything a workspace scan found: the readable workspaces, how many
+/// the exclude globs and `.csmignore` dropped, and the directories it had
+/// trouble with
+#[derive(Debug, Default)]
+pub struct WorkspaceScan {
+    pub workspaces: Vec<Workspace>,
+    pub excluded: usize,
+    pub ignored: usize,
+    pub errors: Vec<DiscoveryError>,
+}
+
+/// Workspace hash of a workspace directory (its folder name)
+fn workspace_hash(workspace_dir: &Path) -> String {
+    workspace_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Directory a nested-scan workspace was found in, relative to the storage
+/// root (e.g. `alice/.config/Code/User/workspaceStorage`); `None` when the
+/// workspace sits directly in the root
+pub fn storage_root_label(storage_path: &Path, workspace_dir: &Path) -> Option<String> {
+    let relative = workspace_dir.parent()?.strip_prefix(storage_path).ok()?;
+    if relative.as_os_str().is_empty() {
+        return None;
+    }
+    Some(relative.to_string_lossy().replace('\\', "/"))
+}
+
+/// Get the VS Code workspaceStorage path based on the operating system,
+/// or `CSM_STORAGE_PATH` when set. This is the Stable build's; see
+/// [`get_workspace_storage_paths`] for every root discovery reads.
 pub fn get_workspace_storage_path() -> Result<PathBuf> {
+    if let Some(path) = std::env::var_os(STORAGE_PATH_ENV).filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    default_workspace_storage_path()
+}
+
+/// Every workspaceStorage root discovery reads: `CSM_STORAGE_PATH` when set,
+/// else the Stable and Insiders paths `channel` allows, followed by their
+/// [remote server](remote_storage_paths) paths. Roots that don't exist are
+/// included; callers skip them.
+pub fn get_workspace_storage_paths(channel: ChannelFilter) -> Result<Vec<PathBuf>> {
+    if let Some(path) = std::env::var_os(STORAGE_PATH_ENV).filter(|p| !p.is_empty()) {
+        return Ok(vec![PathBuf::from(path)]);
+    }
+    let mut paths = VsCodeChannel::ALL
+        .into_iter()
+        .filter(|&build| channel.includes(build))
+        .map(channel_storage_path)
+        .collect::<Result<Vec<_>>>()?;
+    paths.extend(remote_storage_paths(channel));
+    Ok(paths)
+}
+
+/// Folder Cursor's remote server keeps its data in; it mirrors VS Code
+/// Server's layout and counts as the Stable channel
+pub const CURSOR_SERVER_DIR: &str = ".cursor-server";
+
+/// workspaceStorage paths of the VS Code Server (and Cursor server) builds
+/// `channel` allows, as found when running on the remote host of a
+/// Remote - SSH, WSL or dev container session
+pub fn remote_storage_paths(channel: ChannelFilter) -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    VsCodeChannel::ALL
+        .into_iter()
+        .filter(|&build| channel.includes(build))
+        .map(VsCodeChannel::server_dir)
+        .chain(
+            channel
+                .includes(VsCodeChannel::Stable)
+                .then_some(CURSOR_SERVER_DIR),
+        )
+        .map(|dir| home.join(dir).join("data/User/workspaceStorage"))
+        .collect()
+}
+
+/// Whether a workspace directory sits in a VS Code Server or Cursor server
+/// layout, i.e. belongs to a remote session rather than a desktop install
+pub fn is_remote_storage(path: &Path) -> bool {
+    let servers = [
+        VsCodeChannel::Stable.server_dir(),
+        VsCodeChannel::Insiders.server_dir(),
+        CURSOR_SERVER_DIR,
+    ];
+    path.components()
+        .any(|c| servers.iter().any(|dir| c.as_os_str() == *dir))
+}
+
+/// VS Code's own workspaceStorage path for this OS, ignoring `CSM_STORAGE_PATH`
+pub fn default_workspace_storage_path() -> Result<PathBuf> {
+    channel_storage_path(VsCodeChannel::Stable)
+}
+
+/// A VS Code build's workspaceStorage path for this OS
+pub fn channel_storage_path(channel: VsCodeChannel) -> Result<PathBuf> {
+    let app = channel.app_dir();
     let path = if cfg!(target_os = "windows") {
-        dirs::config_dir().map(|p| p.join("Code").join("User").join("workspaceStorage"))
+        dirs::config_dir().map(|p| p.join(app).join("User").join("workspaceStorage"))
     } else if cfg!(target_os = "macos") {
-        dirs::home_dir().map(|p| p.join("Library/Application Support/Code/User/workspaceStorage"))
+        dirs::home_dir().map(|p| {
+            p.join("Library/Application Support")
+                .join(app)
+                .join("User/workspaceStorage")
+        })
     } else {
         // Linux
-        dirs::home_dir().map(|p| p.join(".config/Code/User/workspaceStorage"))
+        dirs::home_dir().map(|p| p.join(".config").join(app).join("User/workspaceStorage"))
     };
 
     path.ok_or(CsmError::StorageNotFound)
 }
 
+/// Workspace directories under every storage root (see
+/// [`get_workspace_storage_paths`]) with the root each was found in, walked
+/// as `options` says and leaving out those of a channel it excludes
+pub fn storage_workspace_dirs(
+    options: &DiscoveryOptions,
+    errors: &mut Vec<DiscoveryError>,
+) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut dirs = Vec::new();
+    for root in get_workspace_storage_paths(options.channel)? {
+        if !root.exists() {
+            continue;
+        }
+        for dir in find_workspace_dirs_with(&root, options.depth, options.follow_symlinks, errors) {
+            if options.channel.includes(VsCodeChannel::of_path(&dir)) {
+                dirs.push((root.clone(), dir));
+            }
+        }
+    }
+    Ok(dirs)
+}
+
 /// Get the VS Code globalStorage path based on the operating system
 pub fn get_global_storage_path() -> Result<PathBuf> {
     let path = if cfg!(target_os = "windows") {
@@ -47,14 +547,15 @@ pub fn get_empty_window_sessions_path() -> Result<PathBuf> {
 }
 
 /// Decode a workspace folder URI to a path
+///
+/// `file:///home/u/proj` keeps its leading slash, so the result is absolute;
+/// `file:///c%3A/Users/u` becomes `c:\Users\u` on Windows.
 pub fn decode_workspace_folder(folder_uri: &str) -> String {
     let mut folder = folder_uri.to_string();
 
     // Remove file:// prefix
-    if folder.starts_with("file:///") {
-        folder = folder[8..].to_string();
-    } else if folder.starts_with("file://") {
-        folder = folder[7..].to_string();
+    if let Some(rest) = folder.strip_prefix("file://") {
+        folder = rest.to_string();
     }
 
     // URL decode
@@ -62,6 +563,13 @@ pub fn decode_workspace_folder(folder_uri: &str) -> String {
         folder = decoded.into_owned();
     }
 
+    // A drive letter follows the URI's root slash: /C:/Users -> C:/Users
+    if let [b'/', drive, b':', ..] = folder.as_bytes() {
+        if drive.is_ascii_alphabetic() {
+            folder.remove(0);
+        }
+    }
+
     // On Windows, convert forward slashes to backslashes
     if cfg!(target_os = "windows") {
         folder = folder.replace('/', "\\");
@@ -83,57 +591,62 @@ pub fn normalize_path(path: &str) -> String {
 }
 
 /// Discover all VS Code workspaces
-pub fn discover_workspaces() -> Result<Vec<Workspace>> {
-    let storage_path = get_workspace_storage_path()?;
-
-    if !storage_path.exists() {
-        return Ok(Vec::new());
-    }
-
-    let mut workspaces = Vec::new();
-
-    for entry in std::fs::read_dir(&storage_path)? {
-        let entry = entry?;
-        let workspace_dir = entry.path();
+pub fn discover_workspaces(options: &DiscoveryOptions) -> Result<Vec<Workspace>> {
+    scan_workspaces(options).map(|scan| scan.workspaces)
+}
 
-        if !workspace_dir.is_dir() {
-            continue;
-        }
+/// Discover all VS Code workspaces, also reporting how many were dropped by
+/// the exclude globs and which directories couldn't be fully read
+pub fn scan_workspaces(options: &DiscoveryOptions) -> Result<WorkspaceScan> {
+    let mut scan = WorkspaceScan::default();
 
+    for (storage_path, workspace_dir) in storage_workspace_dirs(options, &mut scan.errors)? {
         let workspace_json_path = workspace_dir.join("workspace.json");
         if !workspace_json_path.exists() {
+            scan.errors.push(DiscoveryError::skipped(
+                &workspace_dir,
+                DiscoveryIssue::MissingWorkspaceJson,
+            ));
             continue;
         }
 
-        // Parse workspace.json
-        let project_path = match std::fs::read_to_string(&workspace_json_path) {
-            Ok(content) => match serde_json::from_str::<WorkspaceJson>(&content) {
-                Ok(ws_json) => ws_json.folder.map(|f| decode_workspace_folder(&f)),
-                Err(_) => None,
-            },
-            Err(_) => None,
+        // Parse workspace.json; a workspace whose project path can't be read
+        // is still listed, by hash
+        let project_path = std::fs::read_to_string(&workspace_json_path)
+            .map_err(DiscoveryIssue::from)
+            .and_then(|content| {
+                serde_json::from_str::<WorkspaceJson>(&content)
+                    .map_err(|e| DiscoveryIssue::InvalidJson(e.to_string()))
+            })
+            .and_then(|ws_json| ws_json.project_path().ok_or(DiscoveryIssue::MissingFolder));
+        let project_path = match project_path {
+            Ok(path) => Some(path),
+            Err(issue) => {
+                scan.errors
+                    .push(DiscoveryError::partial(&workspace_dir, issue));
+                None
+            }
         };
+        if options.is_excluded(project_path.as_deref()) {
+            scan.excluded += 1;
+            continue;
+        }
+        if options.is_ignored(project_path.as_deref()) {
+            scan.ignored += 1;
+            continue;
+        }
 
         let chat_sessions_path = workspace_dir.join("chatSessions");
-        let has_chat_sessions = chat_sessions_path.exists();
+        if chat_sessions_path.is_dir() {
+            if let Err(e) = std::fs::read_dir(&chat_sessions_path) {
+                scan.errors
+                    .push(DiscoveryError::partial(&chat_sessions_path, e.into()));
+            }
+        }
 
-        let chat_session_count = if has_chat_sessions {
-            std::fs::read_dir(&chat_sessions_path)
-                .map(|entries| {
-                    entries
-                        .filter_map(|e| e.ok())
-                        .filter(|e| {
-                            e.path()
-                                .extension()
-                                .map(|ext| ext == "json")
-                                .unwrap_or(false)
-                        })
-                        .count()
-                })
-                .unwrap_or(0)
-        } else {
-            0
-        };
+        // Count with the same enumeration get_chat_sessions_from_workspace uses
+        let chat_session_count = list_session_files(&chat_sessions_path).len();
+        let has_chat_sessions = chat_session_count > 0;
 
         // Get last modified time
         let last_modified = if has_chat_sessions {
@@ -151,8 +664,11 @@ pub fn discover_workspaces() -> Result<Vec<Workspace>> {
             None
         };
 
-        workspaces.push(Workspace {
-            hash: entry.file_name().to_string_lossy().to_string(),
+        scan.workspaces.push(Workspace {
+            hash: workspace_hash(&workspace_dir),
+            storage_root: storage_root_label(&storage_path, &workspace_dir),
+            channel: VsCodeChannel::of_path(&workspace_dir),
+            remote: is_remote_storage(&workspace_dir),
             project_path,
             workspace_path: workspace_dir.clone(),
             chat_sessions_path,
@@ -162,20 +678,23 @@ pub fn discover_workspaces() -> Result<Vec<Workspace>> {
         });
     }
 
-    Ok(workspaces)
+    Ok(scan)
 }
 
 /// Find a workspace by its hash
-pub fn get_workspace_by_hash(hash: &str) -> Result<Option<Workspace>> {
-    let workspaces = discover_workspaces()?;
+pub fn get_workspace_by_hash(hash: &str, options: &DiscoveryOptions) -> Result<Option<Workspace>> {
+    let workspaces = discover_workspaces(options)?;
     Ok(workspaces
         .into_iter()
         .find(|w| w.hash == hash || w.hash.starts_with(hash)))
 }
 
 /// Find a workspace by project path
-pub fn get_workspace_by_path(project_path: &str) -> Result<Option<Workspace>> {
-    let workspaces = discover_workspaces()?;
+pub fn get_workspace_by_path(
+    project_path: &str,
+    options: &DiscoveryOptions,
+) -> Result<Option<Workspace>> {
+    let workspaces = discover_workspaces(options)?;
     let target_path = normalize_path(project_path);
 
     Ok(workspaces.into_iter().find(|w| {
@@ -190,24 +709,12 @@ pub fn get_workspace_by_path(project_path: &str) -> Result<Option<Workspace>> {
 /// When multiple workspaces match the same path, returns the most recently modified one.
 pub fn find_workspace_by_path(
     project_path: &str,
+    options: &DiscoveryOptions,
 ) -> Result<Option<(String, PathBuf, Option<String>)>> {
-    let storage_path = get_workspace_storage_path()?;
-
-    if !storage_path.exists() {
-        return Ok(None);
-    }
-
     let target_path = normalize_path(project_path);
     let mut matches: Vec<(String, PathBuf, Option<String>, std::time::SystemTime)> = Vec::new();
 
-    for entry in std::fs::read_dir(&storage_path)? {
-        let entry = entry?;
-        let workspace_dir = entry.path();
-
-        if !workspace_dir.is_dir() {
-            continue;
-        }
-
+    for (_, workspace_dir) in storage_workspace_dirs(options, &mut Vec::new())? {
         let workspace_json_path = workspace_dir.join("workspace.json");
         if !workspace_json_path.exists() {
             continue;
@@ -215,8 +722,7 @@ pub fn find_workspace_by_path(
 
         if let Ok(content) = std::fs::read_to_string(&workspace_json_path) {
             if let Ok(ws_json) = serde_json::from_str::<WorkspaceJson>(&content) {
-                if let Some(folder) = &ws_json.folder {
-                    let folder_path = decode_workspace_folder(folder);
+                if let Some(folder_path) = ws_json.project_path() {
                     if normalize_path(&folder_path) == target_path {
                         // Get the most recent modification time from chatSessions or workspace dir
                         let chat_sessions_dir = workspace_dir.join("chatSessions");
@@ -244,7 +750,7 @@ pub fn find_workspace_by_path(
                         };
 
                         matches.push((
-                            entry.file_name().to_string_lossy().to_string(),
+                            workspace_hash(&workspace_dir),
                             workspace_dir,
                             Some(folder_path),
                             last_modified,
@@ -256,7 +762,7 @@ pub fn find_workspace_by_path(
     }
 
     // Sort by last modified (newest first) and return the most recent
-    matches.sort_by(|a, b| b.3.cmp(&a.3));
+    matches.sort_by_key(|m| std::cmp::Reverse(m.3));
 
     Ok(matches
         .into_iter()
@@ -265,24 +771,14 @@ pub fn find_workspace_by_path(
 }
 
 /// Find all workspaces for a project (by name matching)
-pub fn find_all_workspaces_for_project(project_name: &str) -> Result<Vec<WorkspaceInfo>> {
-    let storage_path = get_workspace_storage_path()?;
-
-    if !storage_path.exists() {
-        return Ok(Vec::new());
-    }
-
+pub fn find_all_workspaces_for_project(
+    project_name: &str,
+    options: &DiscoveryOptions,
+) -> Result<Vec<WorkspaceInfo>> {
     let project_name_lower = project_name.to_lowercase();
     let mut workspaces = Vec::new();
 
-    for entry in std::fs::read_dir(&storage_path)? {
-        let entry = entry?;
-        let workspace_dir = entry.path();
-
-        if !workspace_dir.is_dir() {
-            continue;
-        }
-
+    for (_, workspace_dir) in storage_workspace_dirs(options, &mut Vec::new())? {
         let workspace_json_path = workspace_dir.join("workspace.json");
         if !workspace_json_path.exists() {
             continue;
@@ -290,9 +786,7 @@ pub fn find_all_workspaces_for_project(project_name: &str) -> Result<Vec<Workspa
 
         if let Ok(content) = std::fs::read_to_string(&workspace_json_path) {
             if let Ok(ws_json) = serde_json::from_str::<WorkspaceJson>(&content) {
-                if let Some(folder) = &ws_json.folder {
-                    let folder_path = decode_workspace_folder(folder);
-
+                if let Some(folder_path) = ws_json.project_path() {
                     if folder_path.to_lowercase().contains(&project_name_lower) {
                         let chat_sessions_dir = workspace_dir.join("chatSessions");
 
@@ -320,7 +814,7 @@ pub fn find_all_workspaces_for_project(project_name: &str) -> Result<Vec<Workspa
                         };
 
                         workspaces.push((
-                            entry.file_name().to_string_lossy().to_string(),
+                            workspace_hash(&workspace_dir),
                             workspace_dir,
                             Some(folder_path),
                             last_modified,
@@ -332,35 +826,95 @@ pub fn find_all_workspaces_for_project(project_name: &str) -> Result<Vec<Workspa
     }
 
     // Sort by last modified (newest first)
-    workspaces.sort_by(|a, b| b.3.cmp(&a.3));
+    workspaces.sort_by_key(|w| std::cmp::Reverse(w.3));
 
     Ok(workspaces)
 }
 
-/// Get all chat sessions from a workspace directory
-pub fn get_chat_sessions_from_workspace(workspace_dir: &Path) -> Result<Vec<SessionWithPath>> {
-    let chat_sessions_dir = workspace_dir.join("chatSessions");
+/// List session files (`*.json`) in a chatSessions directory, sorted by path.
+///
+/// Hidden files (such as in-progress atomic-write temp files) are skipped.
+/// Both workspace session counts and session loading use this enumeration.
+pub fn list_session_files(chat_sessions_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(chat_sessions_dir) else {
+        return Vec::new();
+    };
 
-    if !chat_sessions_dir.exists() {
-        return Ok(Vec::new());
-    }
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+                && !path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        })
+        .collect();
+    files.sort();
+    files
+}
 
-    let mut sessions = Vec::new();
+/// Total size in bytes of the files in a `chatSessions` directory
+///
+/// Counts every file (not just session JSON), so it matches what deleting
+/// the directory would reclaim. Files are stat'ed in parallel.
+pub fn chat_sessions_size(chat_sessions_dir: &Path) -> u64 {
+    use rayon::prelude::*;
 
-    for entry in std::fs::read_dir(&chat_sessions_dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    let Ok(entries) = std::fs::read_dir(chat_sessions_dir) else {
+        return 0;
+    };
+    let paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    paths
+        .par_iter()
+        .filter_map(|path| path.metadata().ok())
+        .filter(|meta| meta.is_file())
+        .map(|meta| meta.len())
+        .sum()
+}
 
-        if path.extension().map(|e| e == "json").unwrap_or(false) {
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if let Ok(session) = parse_session_json(&content) {
-                    sessions.push(SessionWithPath { path, session });
-                }
-            }
+/// A session file that was left out because it couldn't be read or parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedSessionFile {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+impl std::fmt::Display for SkippedSessionFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.reason)
+    }
+}
+
+/// All chat sessions from a workspace directory, plus the files that had to
+/// be skipped, so one corrupt file can't hide the rest
+pub fn load_chat_sessions_from_workspace(
+    workspace_dir: &Path,
+) -> (Vec<SessionWithPath>, Vec<SkippedSessionFile>) {
+    let mut sessions = Vec::new();
+    let mut skipped = Vec::new();
+
+    for path in list_session_files(&workspace_dir.join("chatSessions")) {
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| parse_session_json(&content).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(session) => sessions.push(SessionWithPath { path, session }),
+            Err(reason) => skipped.push(SkippedSessionFile { path, reason }),
         }
     }
 
-    Ok(sessions)
+    (sessions, skipped)
+}
+
+/// Get all chat sessions from a workspace directory, skipping files that
+/// can't be read or parsed (see [`load_chat_sessions_from_workspace`] to
+/// learn which)
+pub fn get_chat_sessions_from_workspace(workspace_dir: &Path) -> Result<Vec<SessionWithPath>> {
+    Ok(load_chat_sessions_from_workspace(workspace_dir).0)
 }
 
 use chrono::Utc;
@@ -0,0 +1,260 @@
+//! Tests for the in-tree gzip and tar writers behind `export --gzip`,
+//! `export --archive` and `.gz` output paths
+//!
+//! This file covers:
+//! - CRC-32 against the standard check value
+//! - DEFLATE and gzip output for empty input, byte for byte
+//! - Round trips through a reference decoder: empty, incompressible and
+//!   highly repetitive input
+//! - ustar headers, checksums, padding and long paths
+
+use chasm_cli::commands::{crc32, deflate, gzip, OutputSink, TarBuilder};
+
+/// Length and distance tables from RFC 1951, for the decoder below
+const LENGTH_BASE: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn bit(&mut self) -> u32 {
+        let bit = (self.data[self.pos / 8] >> (self.pos % 8)) & 1;
+        self.pos += 1;
+        bit as u32
+    }
+
+    fn bits(&mut self, n: u32) -> u32 {
+        (0..n).fold(0, |value, i| value | self.bit() << i)
+    }
+
+    /// A Huffman code of `n` bits, stored most significant bit first
+    fn code(&mut self, n: u32) -> u32 {
+        (0..n).fold(0, |value, _| value << 1 | self.bit())
+    }
+
+    /// A literal/length symbol in the fixed code
+    fn symbol(&mut self) -> u32 {
+        let code = self.code(7);
+        if code <= 0b0010111 {
+            return code + 256;
+        }
+        let code = code << 1 | self.bit();
+        if (0x30..=0xBF).contains(&code) {
+            return code - 0x30;
+        }
+        if (0xC0..=0xC7).contains(&code) {
+            return code - 0xC0 + 280;
+        }
+        (code << 1 | self.bit()) - 0x190 + 144
+    }
+}
+
+/// Decode a raw DEFLATE stream made of fixed-Huffman blocks
+fn inflate(data: &[u8]) -> Vec<u8> {
+    let mut reader = BitReader { data, pos: 0 };
+    let mut out = Vec::new();
+    loop {
+        let last = reader.bit() == 1;
+        assert_eq!(reader.bits(2), 1, "expected a fixed Huffman block");
+        loop {
+            let symbol = reader.symbol() as usize;
+            match symbol {
+                0..=255 => out.push(symbol as u8),
+                256 => break,
+                _ => {
+                    let i = symbol - 257;
+                    let len = LENGTH_BASE[i] + reader.bits(LENGTH_EXTRA[i]) as usize;
+                    let d = reader.code(5) as usize;
+                    let dist = DISTANCE_BASE[d] + reader.bits(DISTANCE_EXTRA[d]) as usize;
+                    let start = out.len() - dist;
+                    for k in 0..len {
+                        out.push(out[start + k]);
+                    }
+                }
+            }
+        }
+        if last {
+            return out;
+        }
+    }
+}
+
+fn gunzip(data: &[u8]) -> Vec<u8> {
+    assert_eq!(
+        &data[..3],
+        &[0x1f, 0x8b, 8],
+        "gzip magic and deflate method"
+    );
+    assert_eq!(data[3], 0, "no optional header fields");
+    let body = &data[10..data.len() - 8];
+    let out = inflate(body);
+    let trailer = &data[data.len() - 8..];
+    assert_eq!(trailer[..4], crc32(&out).to_le_bytes());
+    assert_eq!(trailer[4..], (out.len() as u32).to_le_bytes());
+    out
+}
+
+fn sample_inputs() -> Vec<Vec<u8>> {
+    let transcript = "## User\n\nHow do I read a file?\n\n## Assistant\n\n```rust\n\
+                      let text = std::fs::read_to_string(path)?;\n```\n\n"
+        .repeat(500);
+    // Pseudo-random bytes, which find few matches
+    let noise: Vec<u8> = (0..70_000u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect();
+    vec![
+        Vec::new(),
+        b"a".to_vec(),
+        b"abcabcabcabcabcabcabc".to_vec(),
+        vec![b'x'; 100_000],
+        transcript.into_bytes(),
+        noise,
+    ]
+}
+
+#[test]
+fn test_crc32_check_value() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(
+        crc32(b"The quick brown fox jumps over the lazy dog"),
+        0x414F_A339
+    );
+}
+
+#[test]
+fn test_empty_input_vectors() {
+    // A final fixed-Huffman block holding only end-of-block
+    assert_eq!(deflate(b""), [0x03, 0x00]);
+    assert_eq!(
+        gzip(b""),
+        [0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 255, 0x03, 0x00, 0, 0, 0, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(inflate(&deflate(b"")), b"");
+}
+
+#[test]
+fn test_deflate_incompressible_input() {
+    // Bytes from a xorshift generator repeat no three-byte run nearby, so
+    // nearly every byte is a literal of 8 or 9 bits
+    let mut state = 0x2545_F491u32;
+    let input: Vec<u8> = (0..50_000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect();
+    let compressed = deflate(&input);
+    assert!(compressed.len() < input.len() * 9 / 8 + 8);
+    assert_eq!(inflate(&compressed), input);
+}
+
+#[test]
+fn test_deflate_repetitive_input() {
+    // One literal, then 40 matches of the longest length (258) at
+    // distance 1, 13 bits each
+    let input = vec![b'a'; 258 * 40 + 1];
+    let compressed = deflate(&input);
+    assert!(compressed.len() <= 68, "{} bytes", compressed.len());
+    assert_eq!(inflate(&compressed), input);
+}
+
+#[test]
+fn test_deflate_round_trips() {
+    for input in sample_inputs() {
+        assert_eq!(inflate(&deflate(&input)), input, "{} bytes", input.len());
+    }
+}
+
+#[test]
+fn test_deflate_compresses_repetition() {
+    let input = "the same line of a transcript\n".repeat(1000);
+    assert!(deflate(input.as_bytes()).len() < input.len() / 20);
+}
+
+#[test]
+fn test_gzip_framing() {
+    for input in sample_inputs() {
+        let gz = gzip(&input);
+        assert_eq!(gz[9], 255, "OS unknown");
+        assert_eq!(gunzip(&gz), input);
+    }
+}
+
+#[test]
+fn test_tar_entries() {
+    let mut tar = TarBuilder::new();
+    tar.append("out/a.md", b"hello\n").unwrap();
+    tar.append("out/empty.txt", b"").unwrap();
+    let bytes = tar.into_bytes();
+    // Header, one data block, header, then the two closing zero blocks
+    assert_eq!(bytes.len(), 512 * 5);
+    assert!(bytes[512 * 3..].iter().all(|&b| b == 0));
+
+    let header = &bytes[..512];
+    assert_eq!(&header[..9], b"out/a.md\0");
+    assert_eq!(&header[124..136], b"00000000006\0");
+    assert_eq!(header[156], b'0');
+    assert_eq!(&header[257..265], b"ustar\x0000");
+    let stored = std::str::from_utf8(&header[148..154]).unwrap();
+    let mut blank = header.to_vec();
+    blank[148..156].fill(b' ');
+    let sum: u32 = blank.iter().map(|&b| b as u32).sum();
+    assert_eq!(u32::from_str_radix(stored, 8).unwrap(), sum);
+    assert_eq!(&bytes[512..518], b"hello\n");
+    assert_eq!(&bytes[1024..1037], b"out/empty.txt");
+}
+
+#[test]
+fn test_tar_long_paths() {
+    let dir = "d".repeat(120);
+    let path = format!("{}/session.md", dir);
+    let mut tar = TarBuilder::new();
+    tar.append(&path, b"x").unwrap();
+    let bytes = tar.into_bytes();
+    assert_eq!(&bytes[..11], b"session.md\0");
+    assert_eq!(&bytes[345..465], dir.as_bytes());
+
+    let name = "n".repeat(101);
+    assert!(TarBuilder::new().append(&name, b"x").is_err());
+}
+
+#[test]
+fn test_output_sink_gzips_gz_paths() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let text = "# Export\n\nhello\n";
+    let path = |name: &str| dir.path().join(name).to_string_lossy().to_string();
+    OutputSink::open(Some(&path("out.md")))
+        .unwrap()
+        .write_all_and_finish(text)
+        .unwrap();
+    assert_eq!(
+        std::fs::read(dir.path().join("out.md")).unwrap(),
+        text.as_bytes()
+    );
+
+    OutputSink::open(Some(&path("out.md.gz")))
+        .unwrap()
+        .write_all_and_finish(text)
+        .unwrap();
+    let gz = std::fs::read(dir.path().join("out.md.gz")).unwrap();
+    assert_eq!(gunzip(&gz), text.as_bytes());
+}
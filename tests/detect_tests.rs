@@ -221,7 +221,7 @@ mod provider_detection_tests {
 // ============================================================================
 
 mod workspace_detection_tests {
-    use chasm_cli::workspace::{find_workspace_by_path, normalize_path};
+    use chasm_cli::workspace::{find_workspace_by_path, normalize_path, DiscoveryOptions};
 
     #[test]
     fn test_normalize_path_removes_trailing_slash() {
@@ -255,7 +255,10 @@ mod workspace_detection_tests {
 
     #[test]
     fn test_find_workspace_nonexistent_path() {
-        let result = find_workspace_by_path("/definitely/nonexistent/path/12345");
+        let result = find_workspace_by_path(
+            "/definitely/nonexistent/path/12345",
+            &DiscoveryOptions::default(),
+        );
         // Should not error, just return None
         assert!(result.is_ok());
         // May or may not find a workspace depending on system state
@@ -263,14 +266,15 @@ mod workspace_detection_tests {
 
     #[test]
     fn test_find_workspace_empty_path() {
-        let result = find_workspace_by_path("");
+        let result = find_workspace_by_path("", &DiscoveryOptions::default());
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_find_workspace_current_dir() {
         let current = std::env::current_dir().unwrap();
-        let result = find_workspace_by_path(current.to_str().unwrap());
+        let result =
+            find_workspace_by_path(current.to_str().unwrap(), &DiscoveryOptions::default());
         assert!(result.is_ok());
     }
 }
@@ -700,12 +704,12 @@ mod filter_tests {
 // ============================================================================
 
 mod error_handling_tests {
-    use chasm_cli::workspace::find_workspace_by_path;
+    use chasm_cli::workspace::{find_workspace_by_path, DiscoveryOptions};
 
     #[test]
     fn test_invalid_path_does_not_panic() {
         // Should not panic on invalid paths
-        let result = find_workspace_by_path("\0invalid");
+        let result = find_workspace_by_path("\0invalid", &DiscoveryOptions::default());
         // Either succeeds with None or returns an error, but no panic
         let _ = result; // Result is either Ok or Err, both are fine
     }
@@ -713,7 +717,7 @@ mod error_handling_tests {
     #[test]
     fn test_very_long_path_handled() {
         let long_path = "a".repeat(10000);
-        let result = find_workspace_by_path(&long_path);
+        let result = find_workspace_by_path(&long_path, &DiscoveryOptions::default());
         // Should handle gracefully
         assert!(result.is_ok() || result.is_err());
     }
@@ -721,7 +725,7 @@ mod error_handling_tests {
     #[test]
     fn test_unicode_path_handled() {
         let unicode_path = "/home/user/project/test";
-        let result = find_workspace_by_path(unicode_path);
+        let result = find_workspace_by_path(unicode_path, &DiscoveryOptions::default());
         assert!(result.is_ok());
     }
 }
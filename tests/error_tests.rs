@@ -83,6 +83,14 @@ mod error_variant_tests {
         let msg = format!("{}", err);
         assert!(msg.contains("--hash") || msg.contains("--path"));
     }
+
+    #[test]
+    fn test_invalid_redact_pattern_error() {
+        let err = CsmError::InvalidRedactPattern("unclosed group".to_string());
+        let msg = format!("{}", err);
+        assert!(msg.contains("Invalid redaction pattern"));
+        assert!(msg.contains("unclosed group"));
+    }
 }
 
 // ============================================================================
@@ -5,9 +5,11 @@
 //! - Git log command
 //! - Git diff command
 //! - Metadata storage and retrieval
+//! - Session to repository auto-linking
 //! - Error handling for git operations
 
 use std::fs;
+use std::path::Path;
 use std::process::Command;
 use tempfile::TempDir;
 
@@ -242,6 +244,25 @@ mod git_status_tests {
         let result = parse_git_status_line("x");
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_parse_porcelain_line_reads_both_columns() {
+        use chasm_cli::commands::{parse_porcelain_line, FileStatus as Status};
+
+        let parse = |line: &str| parse_porcelain_line(line).map(|(status, _)| status);
+        assert_eq!(parse("A  new.rs"), Some(Status::Added));
+        assert_eq!(parse("?? untracked.rs"), Some(Status::Added));
+        // Unstaged changes leave the index column blank
+        assert_eq!(parse(" M src/lib.rs"), Some(Status::Modified));
+        assert_eq!(parse(" D gone.rs"), Some(Status::Deleted));
+        assert_eq!(parse("MM both.rs"), Some(Status::Modified));
+        assert_eq!(
+            parse_porcelain_line("R  old.rs -> new.rs"),
+            Some((Status::Renamed, "old.rs -> new.rs".to_string()))
+        );
+        assert_eq!(parse_porcelain_line("x"), None);
+        assert_eq!(parse_porcelain_line(" M "), None);
+    }
 }
 
 // ============================================================================
@@ -695,6 +716,106 @@ mod link_command_tests {
     }
 }
 
+// ============================================================================
+// Auto-Link Tests
+// ============================================================================
+
+mod auto_link_tests {
+    use super::*;
+    use chasm_cli::links::{find_git_root, LinkIndex};
+
+    #[test]
+    fn test_find_git_root_walks_up() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        let nested = repo.join("crates").join("core");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_git_root(&repo), Some(repo.clone()));
+        assert_eq!(find_git_root(&nested), Some(repo));
+    }
+
+    #[test]
+    fn test_find_git_root_accepts_gitfile() {
+        // Worktrees and submodules use a `.git` file instead of a directory
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".git"), "gitdir: ../main/.git/worktrees/wt").unwrap();
+
+        assert_eq!(find_git_root(dir.path()), Some(dir.path().to_path_buf()));
+    }
+
+    #[test]
+    fn test_find_git_root_outside_repo() {
+        let dir = TempDir::new().unwrap();
+        let root = find_git_root(dir.path());
+        // The temp dir itself is never a repository root
+        assert_ne!(root.as_deref(), Some(dir.path()));
+    }
+
+    #[test]
+    fn test_find_git_root_refuses_relative_paths() {
+        // Tests run inside the crate's own checkout, where a relative path
+        // would resolve against the current directory's repository
+        assert_eq!(find_git_root(Path::new("")), None);
+        assert_eq!(find_git_root(Path::new("src")), None);
+        assert_eq!(find_git_root(Path::new("home/u/proj")), None);
+    }
+
+    #[test]
+    fn test_link_index_reports_new_links() {
+        let mut index = LinkIndex::default();
+
+        assert!(index.link("session-123", "/repo/a"));
+        assert!(!index.link("session-123", "/repo/a"));
+        assert!(index.link("session-123", "/repo/b"));
+        assert_eq!(
+            index.sessions.get("session-123").map(String::as_str),
+            Some("/repo/b")
+        );
+    }
+
+    #[test]
+    fn test_link_index_unlink() {
+        let mut index = LinkIndex::default();
+        index.link("session-123", "/repo/a");
+
+        assert_eq!(index.repo_for("session-123"), Some("/repo/a"));
+        assert_eq!(index.unlink("session-123").as_deref(), Some("/repo/a"));
+        assert_eq!(index.unlink("session-123"), None);
+        assert_eq!(index.repo_for("session-123"), None);
+    }
+
+    #[test]
+    fn test_link_index_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("csm").join("links.json");
+        assert_eq!(LinkIndex::load_from(&path).unwrap(), LinkIndex::default());
+
+        let mut index = LinkIndex::default();
+        index.link("session-123", "/repo/a");
+        index.save_to(&path).unwrap();
+
+        assert_eq!(LinkIndex::load_from(&path).unwrap(), index);
+    }
+
+    #[test]
+    fn test_sessions_for_repo() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        fs::create_dir_all(&repo).unwrap();
+
+        let mut index = LinkIndex::default();
+        index.link("session-a", &repo.to_string_lossy());
+        index.link("session-b", "/elsewhere");
+
+        assert_eq!(index.sessions_for_repo(&repo), vec!["session-a"]);
+        assert!(index
+            .sessions_for_repo(std::path::Path::new("/nowhere"))
+            .is_empty());
+    }
+}
+
 // ============================================================================
 // Error Case Tests
 // ============================================================================
@@ -148,6 +148,8 @@ mod model_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -228,6 +230,8 @@ mod model_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
         // Title should be first message text, truncated
@@ -241,7 +245,7 @@ mod model_tests {
 // =============================================================================
 
 mod cli_tests {
-    use chasm_cli::cli::{Cli, Commands};
+    use chasm_cli::cli::{Cli, Commands, FindCommands};
     use clap::Parser;
 
     #[test]
@@ -250,12 +254,411 @@ mod cli_tests {
         assert!(matches!(cli.command, Commands::Run { .. }));
     }
 
+    #[test]
+    fn test_cli_run_tui_watch() {
+        use chasm_cli::cli::RunCommands;
+
+        let cli = Cli::try_parse_from(["csm", "run", "tui", "--watch"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Run {
+                command: RunCommands::Tui { watch: true }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cli_detect_providers_json_require() {
+        use chasm_cli::cli::DetectCommands;
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "detect",
+            "providers",
+            "--json",
+            "--require",
+            "ollama,vllm",
+            "--timeout",
+            "1",
+            "--retries",
+            "3",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Detect {
+                command:
+                    Some(DetectCommands::Providers {
+                        json,
+                        require,
+                        timeout,
+                        retries,
+                        ..
+                    }),
+                ..
+            } => {
+                assert!(json);
+                assert_eq!(require, vec!["ollama", "vllm"]);
+                assert_eq!(timeout, 1);
+                assert_eq!(retries, 3);
+            }
+            _ => panic!("expected detect providers"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["csm", "detect", "providers", "--json", "--with-sessions"])
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_cli_list_workspaces_command() {
         let cli = Cli::try_parse_from(["csm", "list", "workspaces"]).unwrap();
         assert!(matches!(cli.command, Commands::List { .. }));
     }
 
+    #[test]
+    fn test_cli_list_workspaces_sort() {
+        use chasm_cli::cli::ListCommands;
+        use chasm_cli::commands::WorkspaceSort;
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "list",
+            "workspaces",
+            "--sort",
+            "sessions",
+            "--reverse",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::List {
+                command:
+                    Some(ListCommands::Workspaces {
+                        sort,
+                        reverse,
+                        size,
+                        verbose,
+                        ..
+                    }),
+            } => {
+                assert_eq!(sort, WorkspaceSort::Sessions);
+                assert!(reverse);
+                assert!(!size);
+                assert!(!verbose);
+            }
+            _ => panic!("expected list workspaces command"),
+        }
+
+        assert!(Cli::try_parse_from(["csm", "list", "workspaces", "--sort", "size"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_global_depth_flag() {
+        let cli = Cli::try_parse_from(["csm", "list", "workspaces"]).unwrap();
+        assert_eq!(cli.depth, 0);
+
+        // Global, so it is accepted after the subcommand too
+        let cli = Cli::try_parse_from(["csm", "list", "workspaces", "--depth", "2"]).unwrap();
+        assert_eq!(cli.depth, 2);
+
+        assert!(Cli::try_parse_from(["csm", "--depth", "-1", "list"]).is_err());
+
+        assert!(!cli.follow_symlinks);
+        let cli = Cli::try_parse_from(["csm", "list", "workspaces", "--follow-symlinks"]).unwrap();
+        assert!(cli.follow_symlinks);
+    }
+
+    #[test]
+    fn test_cli_global_color_flag() {
+        use chasm_cli::cli::ColorChoice;
+
+        let cli = Cli::try_parse_from(["csm", "list", "workspaces"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Auto);
+
+        let cli = Cli::try_parse_from(["csm", "list", "workspaces", "--color", "always"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Always);
+        let cli = Cli::try_parse_from(["csm", "--color", "never", "list"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Never);
+
+        assert!(Cli::try_parse_from(["csm", "--color", "sometimes", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_global_channel_flag() {
+        use chasm_cli::workspace::ChannelFilter;
+
+        let cli = Cli::try_parse_from(["csm", "list", "workspaces"]).unwrap();
+        assert_eq!(cli.channel, ChannelFilter::All);
+        let cli =
+            Cli::try_parse_from(["csm", "list", "workspaces", "--channel", "insiders"]).unwrap();
+        assert_eq!(cli.channel, ChannelFilter::Insiders);
+        assert!(Cli::try_parse_from(["csm", "--channel", "beta", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_global_quiet_flag() {
+        assert!(
+            !Cli::try_parse_from(["csm", "list", "sessions"])
+                .unwrap()
+                .quiet
+        );
+        assert!(
+            Cli::try_parse_from(["csm", "-q", "list", "sessions"])
+                .unwrap()
+                .quiet
+        );
+        assert!(
+            Cli::try_parse_from(["csm", "find", "session", "docker", "--quiet"])
+                .unwrap()
+                .quiet
+        );
+    }
+
+    #[test]
+    fn test_cli_find_session_multiple_patterns() {
+        let cli =
+            Cli::try_parse_from(["csm", "find", "session", "docker", "compose", "--all"]).unwrap();
+        match cli.command {
+            Commands::Find {
+                command: Some(FindCommands::Session { patterns, all, .. }),
+            } => {
+                assert_eq!(patterns, vec!["docker", "compose"]);
+                assert!(all);
+            }
+            _ => panic!("expected find session command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["csm", "find", "session", "a", "b", "--all", "--any"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_find_save_and_run() {
+        let cli = Cli::try_parse_from([
+            "csm", "find", "save", "weekly", "auth", "login", "-w", "api", "--after", "7d", "-c",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Find {
+                command: Some(FindCommands::Save { name, query }),
+            } => {
+                assert_eq!(name, "weekly");
+                let saved = query.into_saved_search();
+                assert_eq!(saved.patterns, vec!["auth", "login"]);
+                assert_eq!(saved.workspace.as_deref(), Some("api"));
+                assert_eq!(saved.after.as_deref(), Some("7d"));
+                assert!(saved.content);
+                assert_eq!(saved.limit, None);
+            }
+            _ => panic!("expected find save command"),
+        }
+
+        let cli = Cli::try_parse_from(["csm", "find", "run", "weekly", "--after", "14d", "--json"])
+            .unwrap();
+        match cli.command {
+            Commands::Find {
+                command:
+                    Some(FindCommands::Run {
+                        name,
+                        overrides,
+                        json,
+                        ..
+                    }),
+            } => {
+                assert_eq!(name, "weekly");
+                assert!(overrides.patterns.is_empty());
+                assert_eq!(overrides.after.as_deref(), Some("14d"));
+                assert!(json);
+            }
+            _ => panic!("expected find run command"),
+        }
+
+        assert!(matches!(
+            Cli::try_parse_from(["csm", "find", "saved"])
+                .unwrap()
+                .command,
+            Commands::Find {
+                command: Some(FindCommands::Saved)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cli_find_session_max_bytes() {
+        let max_bytes = |args: &[&str]| match Cli::try_parse_from(args).unwrap().command {
+            Commands::Find {
+                command: Some(FindCommands::Session { max_bytes, .. }),
+            } => max_bytes,
+            _ => panic!("expected find session command"),
+        };
+        assert_eq!(
+            max_bytes(&["csm", "find", "session", "x", "--content"]),
+            chasm_cli::commands::DEFAULT_MAX_SESSION_BYTES
+        );
+        assert_eq!(
+            max_bytes(&["csm", "find", "session", "x", "--max-bytes", "0"]),
+            0
+        );
+    }
+
+    #[test]
+    fn test_cli_find_session_context() {
+        let cli = Cli::try_parse_from([
+            "csm",
+            "find",
+            "session",
+            "panic",
+            "--content",
+            "--context",
+            "2",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Find {
+                command: Some(FindCommands::Session { context, .. }),
+            } => assert_eq!(context, Some(2)),
+            _ => panic!("expected find session command"),
+        }
+
+        // Only content matches have surrounding lines
+        assert!(Cli::try_parse_from(["csm", "find", "session", "x", "--context", "2"]).is_err());
+        assert!(Cli::try_parse_from([
+            "csm",
+            "find",
+            "session",
+            "x",
+            "--content",
+            "--context",
+            "2",
+            "--json",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_global_exclude_flag() {
+        let cli = Cli::try_parse_from(["csm", "list", "workspaces"]).unwrap();
+        assert!(cli.exclude.is_empty());
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "find",
+            "workspace",
+            "proj",
+            "--exclude",
+            "/tmp/*",
+            "--exclude",
+            "**/scratch",
+        ])
+        .unwrap();
+        assert_eq!(cli.exclude, vec!["/tmp/*", "**/scratch"]);
+    }
+
+    #[test]
+    fn test_cli_harvest_git_auto_link() {
+        use chasm_cli::cli::{HarvestCommands, HarvestGitCommands};
+
+        let cli = Cli::try_parse_from(["csm", "harvest", "git", "--auto-link"]).unwrap();
+        match cli.command {
+            Commands::Harvest {
+                command: HarvestCommands::Git { auto_link, command },
+            } => {
+                assert!(auto_link);
+                assert!(command.is_none());
+            }
+            _ => panic!("expected harvest git command"),
+        }
+
+        let cli = Cli::try_parse_from(["csm", "harvest", "git", "init"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Harvest {
+                command: HarvestCommands::Git {
+                    auto_link: false,
+                    command: Some(HarvestGitCommands::Init { .. }),
+                },
+            }
+        ));
+
+        assert!(Cli::try_parse_from(["csm", "harvest", "git", "--auto-link", "init"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_harvest_run_since_commit() {
+        use chasm_cli::cli::HarvestCommands;
+
+        let cli =
+            Cli::try_parse_from(["csm", "harvest", "run", "--since-commit", "v1.2.0"]).unwrap();
+        match cli.command {
+            Commands::Harvest {
+                command: HarvestCommands::Run { since_commit, .. },
+            } => assert_eq!(since_commit.as_deref(), Some("v1.2.0")),
+            _ => panic!("expected harvest run command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_harvest_export_to_sqlite() {
+        use chasm_cli::cli::HarvestCommands;
+
+        let cli = Cli::try_parse_from(["csm", "harvest", "export", "--to", "history.db"]).unwrap();
+        match cli.command {
+            Commands::Harvest {
+                command: HarvestCommands::Export { output, to, .. },
+            } => {
+                assert_eq!(output, None);
+                assert_eq!(to.as_deref(), Some("history.db"));
+            }
+            _ => panic!("expected harvest export command"),
+        }
+
+        // A file (stdout without one) or a database, never both
+        assert!(Cli::try_parse_from(["csm", "harvest", "export"]).is_ok());
+        assert!(
+            Cli::try_parse_from(["csm", "harvest", "export", "out.json", "--to", "h.db"]).is_err()
+        );
+        assert!(Cli::try_parse_from([
+            "csm", "harvest", "export", "--to", "h.db", "--format", "md"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_find_date_filter_parsing() {
+        use chasm_cli::commands::parse_date_filter_from;
+        use chrono::NaiveDate;
+
+        let today = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(
+            parse_date_filter_from("2024-01-15", today).unwrap(),
+            date(2024, 1, 15)
+        );
+        assert_eq!(
+            parse_date_filter_from("7d", today).unwrap(),
+            date(2024, 3, 24)
+        );
+        assert_eq!(
+            parse_date_filter_from("2w", today).unwrap(),
+            date(2024, 3, 17)
+        );
+        assert_eq!(
+            parse_date_filter_from("1mo", today).unwrap(),
+            date(2024, 2, 29)
+        );
+        assert_eq!(
+            parse_date_filter_from("1y", today).unwrap(),
+            date(2023, 3, 31)
+        );
+
+        for bad in ["", "7", "d", "7x", "-3d", "2024-13-01", "yesterday"] {
+            let err = parse_date_filter_from(bad, today).unwrap_err();
+            assert!(err.to_string().contains("Invalid date"), "{}", bad);
+        }
+    }
+
     #[test]
     fn test_cli_list_sessions_command() {
         let cli = Cli::try_parse_from(["csm", "list", "sessions", "--project-path", "/test/path"])
@@ -263,6 +666,56 @@ mod cli_tests {
         assert!(matches!(cli.command, Commands::List { .. }));
     }
 
+    #[test]
+    fn test_cli_list_sessions_csv_format() {
+        use chasm_cli::cli::ListCommands;
+        use chasm_cli::commands::OutputFormat;
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "list",
+            "sessions",
+            "--format",
+            "csv",
+            "--columns",
+            "title,workspace",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::List {
+                command:
+                    Some(ListCommands::Sessions {
+                        format, columns, ..
+                    }),
+            } => {
+                assert_eq!(format, OutputFormat::Csv);
+                assert_eq!(columns, vec!["title", "workspace"]);
+            }
+            _ => panic!("expected list sessions command"),
+        }
+
+        assert!(Cli::try_parse_from(["csm", "list", "sessions", "--format", "xml"]).is_err());
+        assert!(
+            Cli::try_parse_from(["csm", "find", "session", "x", "--format", "csv", "--json"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_harvest_scan_json() {
+        use chasm_cli::cli::HarvestCommands;
+
+        let cli = Cli::try_parse_from(["csm", "harvest", "scan", "--json", "--web"]).unwrap();
+        match cli.command {
+            Commands::Harvest {
+                command: HarvestCommands::Scan { json, web, .. },
+            } => assert!(json && web),
+            _ => panic!("expected harvest scan command"),
+        }
+        // Browser debug output would corrupt the JSON
+        assert!(Cli::try_parse_from(["csm", "harvest", "scan", "--json", "--verbose"]).is_err());
+    }
+
     #[test]
     fn test_cli_find_workspace_command() {
         let cli = Cli::try_parse_from(["csm", "find", "workspace", "my_project"]).unwrap();
@@ -293,6 +746,114 @@ mod cli_tests {
         assert!(matches!(cli.command, Commands::Merge { .. }));
     }
 
+    #[test]
+    fn test_cli_merge_sessions_output() {
+        use chasm_cli::cli::MergeCommands;
+
+        let cli =
+            Cli::try_parse_from(["csm", "merge", "sessions", "a", "b", "-o", "merged-id"]).unwrap();
+        match cli.command {
+            Commands::Merge {
+                command: Some(MergeCommands::Sessions { output, .. }),
+                ..
+            } => assert_eq!(output.as_deref(), Some("merged-id")),
+            _ => panic!("Expected merge sessions command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_merge_sessions_from_file() {
+        use chasm_cli::cli::MergeCommands;
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "merge",
+            "sessions",
+            "--from-file",
+            "groups.txt",
+            "--atomic",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Merge {
+                command:
+                    Some(MergeCommands::Sessions {
+                        sessions,
+                        from_file,
+                        atomic,
+                        ..
+                    }),
+                ..
+            } => {
+                assert!(sessions.is_empty());
+                assert_eq!(from_file.as_deref(), Some("groups.txt"));
+                assert!(atomic);
+            }
+            _ => panic!("Expected merge sessions command"),
+        }
+
+        // Sessions come from the list or the command line, not both
+        assert!(Cli::try_parse_from(["csm", "merge", "sessions"]).is_err());
+        assert!(
+            Cli::try_parse_from(["csm", "merge", "sessions", "a", "--from-file", "g.txt"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["csm", "merge", "sessions", "a", "b", "--atomic"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_merge_preview() {
+        use chasm_cli::cli::MergeCommands;
+        use chasm_cli::commands::MergePreviewFormat;
+
+        let cli =
+            Cli::try_parse_from(["csm", "merge", "preview", "a", "b", "--format", "diff"]).unwrap();
+        match cli.command {
+            Commands::Merge {
+                command:
+                    Some(MergeCommands::Preview {
+                        sessions, format, ..
+                    }),
+                ..
+            } => {
+                assert_eq!(sessions, ["a", "b"]);
+                assert_eq!(format, MergePreviewFormat::Diff);
+            }
+            _ => panic!("Expected merge preview command"),
+        }
+
+        // A merge needs two sessions
+        assert!(Cli::try_parse_from(["csm", "merge", "preview", "a"]).is_err());
+        assert!(
+            Cli::try_parse_from(["csm", "merge", "preview", "a", "b", "--format", "html"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_serve_defaults_to_localhost() {
+        let cli = Cli::try_parse_from(["csm", "serve"]).unwrap();
+        match cli.command {
+            Commands::Serve { host, port } => {
+                assert_eq!(host, "127.0.0.1");
+                assert_eq!(port, 8080);
+            }
+            _ => panic!("Expected Serve command"),
+        }
+        assert!(Cli::try_parse_from(["csm", "api", "serve"]).is_ok());
+    }
+
+    #[test]
+    fn test_cli_stats_command() {
+        let cli = Cli::try_parse_from(["csm", "stats", "abc123", "--by-day"]).unwrap();
+        match cli.command {
+            Commands::Stats { session_id, by_day } => {
+                assert_eq!(session_id, "abc123");
+                assert!(by_day);
+            }
+            _ => panic!("Expected Stats command"),
+        }
+        assert!(Cli::try_parse_from(["csm", "stats"]).is_err());
+    }
+
     #[test]
     fn test_cli_show_workspace_command() {
         let cli = Cli::try_parse_from(["csm", "show", "workspace", "test-project"]).unwrap();
@@ -305,6 +866,179 @@ mod cli_tests {
         assert!(matches!(cli.command, Commands::Show { .. }));
     }
 
+    #[test]
+    fn test_cli_show_session_scriptable_output() {
+        use chasm_cli::cli::ShowCommands;
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "show",
+            "session",
+            "abc",
+            "--json",
+            "--preview-count",
+            "10",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Show {
+                command:
+                    Some(ShowCommands::Session {
+                        plain,
+                        json,
+                        preview_count,
+                        ..
+                    }),
+            } => {
+                assert!(!plain);
+                assert!(json);
+                assert_eq!(preview_count, 10);
+            }
+            _ => panic!("expected show session command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["csm", "show", "session", "abc", "--plain", "--json"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_show_session_first_last() {
+        use chasm_cli::cli::ShowCommands;
+
+        let parse = |args: &[&str]| -> Result<(usize, Option<usize>), clap::Error> {
+            let cli = Cli::try_parse_from([&["csm", "show", "session", "abc"], args].concat())?;
+            match cli.command {
+                Commands::Show {
+                    command:
+                        Some(ShowCommands::Session {
+                            preview_count,
+                            last,
+                            ..
+                        }),
+                } => Ok((preview_count, last)),
+                _ => panic!("expected show session command"),
+            }
+        };
+
+        assert_eq!(parse(&[]).unwrap(), (3, None));
+        assert_eq!(parse(&["--first", "5"]).unwrap(), (5, None));
+        assert_eq!(parse(&["--last", "2"]).unwrap().1, Some(2));
+        // A bare --last shows the default number of turns
+        assert_eq!(parse(&["--last"]).unwrap().1, Some(3));
+        assert!(parse(&["--first", "1", "--last", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_find_session_sort() {
+        use chasm_cli::commands::FindSort;
+
+        let sort = |args: &[&str]| -> Result<FindSort, clap::Error> {
+            let cli = Cli::try_parse_from([&["csm", "find", "session", "x"], args].concat())?;
+            match cli.command {
+                Commands::Find {
+                    command: Some(FindCommands::Session { sort, .. }),
+                } => Ok(sort),
+                _ => panic!("expected find session command"),
+            }
+        };
+        assert_eq!(sort(&[]).unwrap(), FindSort::Modified);
+        assert_eq!(sort(&["--sort", "relevance"]).unwrap(), FindSort::Relevance);
+        assert!(sort(&["--sort", "size"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_json_layout_flags() {
+        let cli =
+            Cli::try_parse_from(["csm", "find", "session", "x", "--json", "--pretty"]).unwrap();
+        assert!(cli.pretty);
+        assert!(!cli.compact);
+        assert!(Cli::try_parse_from(["csm", "--pretty", "--compact", "doctor"]).is_err());
+
+        let cli = Cli::try_parse_from(["csm", "find", "session", "x", "--ndjson"]).unwrap();
+        match cli.command {
+            Commands::Find {
+                command: Some(FindCommands::Session { ndjson, json, .. }),
+            } => assert!(ndjson && !json),
+            _ => panic!("expected find session command"),
+        }
+        assert!(
+            Cli::try_parse_from(["csm", "find", "session", "x", "--ndjson", "--json"]).is_err()
+        );
+        assert!(Cli::try_parse_from([
+            "csm", "find", "session", "x", "--ndjson", "--format", "csv"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_find_session_exclude_content() {
+        let cli = Cli::try_parse_from([
+            "csm",
+            "find",
+            "session",
+            "error",
+            "--content",
+            "--exclude-content",
+            "resolved",
+            "--exclude-content",
+            "wontfix",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Find {
+                command:
+                    Some(FindCommands::Session {
+                        exclude_content, ..
+                    }),
+            } => assert_eq!(exclude_content, vec!["resolved", "wontfix"]),
+            _ => panic!("expected find session command"),
+        }
+        // Excluding needs the content that only --content reads
+        assert!(Cli::try_parse_from([
+            "csm",
+            "find",
+            "session",
+            "error",
+            "--exclude-content",
+            "resolved"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_show_session_full_messages() {
+        use chasm_cli::cli::ShowCommands;
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "show",
+            "session",
+            "abc",
+            "--messages",
+            "2-4",
+            "--full",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Show {
+                command: Some(ShowCommands::Session { messages, full, .. }),
+            } => {
+                assert_eq!(messages.map(|m| m.to_string()), Some("2-4".to_string()));
+                assert!(full);
+            }
+            _ => panic!("expected show session command"),
+        }
+
+        let parse = |args: &[&str]| {
+            Cli::try_parse_from([&["csm", "show", "session", "abc"], args].concat())
+        };
+        assert!(parse(&["--messages", "2", "--last", "1"]).is_err());
+        assert!(parse(&["--messages", "2", "--first", "1"]).is_err());
+        assert!(parse(&["--full", "--json"]).is_err());
+        assert!(parse(&["--messages", "0"]).is_err());
+    }
+
     #[test]
     fn test_cli_export_sessions_command() {
         let cli = Cli::try_parse_from([
@@ -319,6 +1053,400 @@ mod cli_tests {
         assert!(matches!(cli.command, Commands::Export { .. }));
     }
 
+    #[test]
+    fn test_cli_export_format_flag() {
+        let cli = Cli::try_parse_from([
+            "csm",
+            "export",
+            "sessions",
+            "/dest/path",
+            "session1",
+            "--format",
+            "md",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Export { format, .. } => {
+                assert_eq!(format, chasm_cli::commands::ExportFormat::Markdown)
+            }
+            _ => panic!("expected export command"),
+        }
+
+        let err = Cli::try_parse_from([
+            "csm",
+            "export",
+            "--format",
+            "pdf",
+            "sessions",
+            "/dest/path",
+            "session1",
+        ])
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("json, md, html, txt"));
+    }
+
+    #[test]
+    fn test_cli_export_messages_range() {
+        use chasm_cli::commands::MessageRange;
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "export",
+            "sessions",
+            "/dest/path",
+            "session1",
+            "--messages",
+            "3-7",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Export { messages, .. } => assert_eq!(
+                messages,
+                Some(MessageRange {
+                    start: 3,
+                    end: Some(7)
+                })
+            ),
+            _ => panic!("expected export command"),
+        }
+
+        assert_eq!(
+            "10-".parse::<MessageRange>().unwrap(),
+            MessageRange {
+                start: 10,
+                end: None
+            }
+        );
+        assert_eq!("5".parse::<MessageRange>().unwrap().to_string(), "5");
+        for bad in ["0", "7-3", "a-b", "-4", ""] {
+            assert!(bad.parse::<MessageRange>().is_err(), "{} parsed", bad);
+        }
+    }
+
+    #[test]
+    fn test_cli_flatten_threads() {
+        use chasm_cli::cli::ShowCommands;
+        use chasm_cli::commands::FlattenThreads;
+
+        let cli = Cli::try_parse_from(["csm", "export", "all", "/dest"]).unwrap();
+        match cli.command {
+            Commands::Export {
+                flatten_threads, ..
+            } => assert_eq!(flatten_threads, FlattenThreads::Latest),
+            _ => panic!("expected export command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["csm", "show", "session", "abc", "--flatten-threads", "all"])
+                .unwrap();
+        match cli.command {
+            Commands::Show {
+                command:
+                    Some(ShowCommands::Session {
+                        flatten_threads, ..
+                    }),
+            } => assert_eq!(flatten_threads, FlattenThreads::All),
+            _ => panic!("expected show session command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["csm", "export", "all", "/d", "--flatten-threads", "x"]).is_err()
+        );
+        assert_eq!(FlattenThreads::All.to_string(), "all");
+    }
+
+    #[test]
+    fn test_cli_export_layout() {
+        use chasm_cli::commands::{sanitize_dir_name, ExportLayout};
+
+        let cli =
+            Cli::try_parse_from(["csm", "export", "all", "/dest", "--layout", "by-date"]).unwrap();
+        match cli.command {
+            Commands::Export { layout, .. } => assert_eq!(layout, ExportLayout::ByDate),
+            _ => panic!("expected export command"),
+        }
+        assert!(
+            Cli::try_parse_from(["csm", "export", "all", "/dest", "--layout", "tree"]).is_err()
+        );
+        assert_eq!(ExportLayout::ByWorkspace.to_string(), "by-workspace");
+
+        assert_eq!(sanitize_dir_name("my:proj?"), "my_proj_");
+        assert_eq!(sanitize_dir_name(".."), "workspace");
+    }
+
+    #[test]
+    fn test_cli_export_obsidian() {
+        use chasm_cli::cli::ExportCommands;
+        use chasm_cli::commands::obsidian_tag;
+
+        let cli = Cli::try_parse_from([
+            "csm", "export", "obsidian", "--output", "/vault", "--redact",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Export {
+                redact,
+                command: Some(ExportCommands::Obsidian { output }),
+                ..
+            } => {
+                assert!(redact);
+                assert_eq!(output, "/vault");
+            }
+            _ => panic!("expected export obsidian command"),
+        }
+        assert!(Cli::try_parse_from(["csm", "export", "obsidian"]).is_err());
+
+        assert_eq!(obsidian_tag("My Project.v2"), "my-project-v2");
+        assert_eq!(obsidian_tag("2024"), "ws-2024");
+    }
+
+    #[test]
+    fn test_cli_export_date_range() {
+        use chasm_cli::cli::ExportCommands;
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "export",
+            "--format",
+            "md",
+            "workspace",
+            "./jan",
+            "abc123",
+            "--since",
+            "2024-01-01",
+            "--until",
+            "2024-01-31",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Export {
+                command: Some(ExportCommands::Workspace { since, until, .. }),
+                ..
+            } => {
+                assert_eq!(since.as_deref(), Some("2024-01-01"));
+                assert_eq!(until.as_deref(), Some("2024-01-31"));
+            }
+            _ => panic!("expected export workspace command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_export_stdin_ids() {
+        let cli =
+            Cli::try_parse_from(["csm", "export", "--stdin-ids", "--output", "./out"]).unwrap();
+        match cli.command {
+            Commands::Export {
+                stdin_ids, output, ..
+            } => {
+                assert!(stdin_ids);
+                assert_eq!(output.as_deref(), Some("./out"));
+            }
+            _ => panic!("expected export command"),
+        }
+        assert!(Cli::try_parse_from(["csm", "export", "--stdin-ids"]).is_err());
+        assert!(Cli::try_parse_from(["csm", "export", "--output", "./out"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_session_id_list() {
+        use chasm_cli::commands::parse_session_id_list;
+
+        assert_eq!(
+            parse_session_id_list("abc123\n\n  \"def456\"\r\nghi.JSON\n"),
+            vec!["abc123", "def456", "ghi"]
+        );
+        assert!(parse_session_id_list("\n \n").is_empty());
+    }
+
+    #[test]
+    fn test_cli_export_combine() {
+        use chasm_cli::cli::ExportCommands;
+
+        let cli = Cli::try_parse_from([
+            "csm",
+            "export",
+            "--format",
+            "md",
+            "workspace",
+            "out.md",
+            "abc123",
+            "--combine",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Export {
+                command:
+                    Some(ExportCommands::Workspace {
+                        destination,
+                        combine,
+                        ..
+                    }),
+                ..
+            } => {
+                assert_eq!(destination, "out.md");
+                assert!(combine);
+            }
+            _ => panic!("expected export workspace command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_tag_commands() {
+        let cli = Cli::try_parse_from(["csm", "tag", "add", "abc123", "work", "rust"]).unwrap();
+        match cli.command {
+            Commands::Tag {
+                command:
+                    chasm_cli::cli::TagCommands::Add {
+                        session_id,
+                        tags,
+                        dry_run,
+                    },
+            } => {
+                assert_eq!(session_id, "abc123");
+                assert_eq!(tags, vec!["work", "rust"]);
+                assert!(!dry_run);
+            }
+            _ => panic!("expected tag add command"),
+        }
+
+        let cli = Cli::try_parse_from(["csm", "tag", "rm", "abc123", "work"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Tag {
+                command: chasm_cli::cli::TagCommands::Remove { .. }
+            }
+        ));
+
+        assert!(Cli::try_parse_from(["csm", "tag", "add", "abc123"]).is_err());
+        assert!(Cli::try_parse_from(["csm", "find", "session", "x", "--tag", "work"]).is_ok());
+    }
+
+    #[test]
+    fn test_cli_purge_command() {
+        let cli =
+            Cli::try_parse_from(["csm", "purge", "--empty", "--older-than", "30d", "-y"]).unwrap();
+        match cli.command {
+            Commands::Purge {
+                empty,
+                older_than,
+                delete,
+                yes,
+                dry_run,
+            } => {
+                assert!(empty);
+                assert_eq!(older_than.as_deref(), Some("30d"));
+                assert!(!delete);
+                assert!(yes);
+                assert!(!dry_run);
+            }
+            _ => panic!("expected purge command"),
+        }
+
+        // --empty is the only purge target, so it is required
+        assert!(Cli::try_parse_from(["csm", "purge", "--yes"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_dedupe_command() {
+        let cli = Cli::try_parse_from(["csm", "dedupe", "--remove", "-y"]).unwrap();
+        match cli.command {
+            Commands::Dedupe { remove, yes } => {
+                assert!(remove);
+                assert!(yes);
+            }
+            _ => panic!("expected dedupe command"),
+        }
+        assert!(matches!(
+            Cli::try_parse_from(["csm", "dedupe"]).unwrap().command,
+            Commands::Dedupe {
+                remove: false,
+                yes: false
+            }
+        ));
+        assert!(Cli::try_parse_from(["csm", "dedupe", "--yes"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_dry_run_flags() {
+        use chasm_cli::cli::TagCommands;
+
+        let dry_run = |args: &[&str]| {
+            let cli = Cli::try_parse_from(args).unwrap();
+            match cli.command {
+                Commands::Purge { dry_run, .. }
+                | Commands::Merge { dry_run, .. }
+                | Commands::Import { dry_run, .. }
+                | Commands::Move { dry_run, .. }
+                | Commands::Link { dry_run, .. }
+                | Commands::Unlink { dry_run, .. }
+                | Commands::Fetch { dry_run, .. }
+                | Commands::Register { dry_run, .. }
+                | Commands::Tag {
+                    command:
+                        TagCommands::Add { dry_run, .. }
+                        | TagCommands::Remove { dry_run, .. }
+                        | TagCommands::Gc { dry_run },
+                } => dry_run,
+                _ => panic!("expected a mutating command: {:?}", args),
+            }
+        };
+
+        assert!(dry_run(&["csm", "purge", "--empty", "--dry-run"]));
+        assert!(dry_run(&["csm", "tag", "add", "abc", "work", "--dry-run"]));
+        assert!(dry_run(&["csm", "tag", "rm", "abc", "work", "--dry-run"]));
+        assert!(dry_run(&["csm", "tag", "gc", "--dry-run"]));
+        assert!(dry_run(&["csm", "link", "abc", "/repo", "--dry-run"]));
+        assert!(dry_run(&["csm", "unlink", "abc", "--dry-run"]));
+        // Global on merge, import, move, fetch and register, so it goes
+        // before or after the subcommand
+        assert!(dry_run(&[
+            "csm",
+            "merge",
+            "sessions",
+            "a",
+            "b",
+            "--dry-run"
+        ]));
+        assert!(dry_run(&["csm", "merge", "--dry-run", "workspaces", "x"]));
+        assert!(dry_run(&["csm", "import", "path", "/src", "--dry-run"]));
+        assert!(dry_run(&["csm", "move", "path", "/a", "/b", "--dry-run"]));
+        assert!(!dry_run(&["csm", "move", "path", "/a", "/b"]));
+        assert!(dry_run(&["csm", "fetch", "session", "abc", "--dry-run"]));
+        assert!(dry_run(&["csm", "register", "--dry-run", "all"]));
+        assert!(dry_run(&["csm", "register", "session", "abc", "--dry-run"]));
+
+        // --list only reads the link index
+        assert!(Cli::try_parse_from(["csm", "link", "--list", "--dry-run"]).is_err());
+
+        // A dry run never deletes
+        assert!(Cli::try_parse_from(["csm", "purge", "--empty", "--dry-run", "--yes"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_open_session_command() {
+        use chasm_cli::cli::OpenCommands;
+
+        let cli =
+            Cli::try_parse_from(["csm", "open", "session", "abc123", "--editor-cmd", "cursor"])
+                .unwrap();
+        match cli.command {
+            Commands::Open {
+                command:
+                    OpenCommands::Session {
+                        session_id,
+                        editor_cmd,
+                    },
+            } => {
+                assert_eq!(session_id, "abc123");
+                assert_eq!(editor_cmd.as_deref(), Some("cursor"));
+            }
+            _ => panic!("expected open session command"),
+        }
+
+        assert!(Cli::try_parse_from(["csm", "open", "session"]).is_err());
+    }
+
     #[test]
     fn test_cli_import_sessions_command() {
         let cli =
@@ -338,12 +1466,54 @@ mod cli_tests {
         assert!(matches!(cli.command, Commands::Git { .. }));
     }
 
+    #[test]
+    fn test_cli_git_init_link_session() {
+        use chasm_cli::cli::GitCommands;
+
+        let cli = Cli::try_parse_from(["csm", "git", "init", "--link-session", "abc", "--commit"])
+            .unwrap();
+        match cli.command {
+            Commands::Git {
+                command:
+                    GitCommands::Init {
+                        path,
+                        link_session,
+                        commit,
+                    },
+            } => {
+                assert_eq!(path, None);
+                assert_eq!(link_session.as_deref(), Some("abc"));
+                assert!(commit);
+            }
+            _ => panic!("expected git init command"),
+        }
+
+        // A path is required without --link-session, and --commit needs it
+        assert!(Cli::try_parse_from(["csm", "git", "init"]).is_err());
+        assert!(Cli::try_parse_from(["csm", "git", "init", "/p", "--commit"]).is_err());
+    }
+
     #[test]
     fn test_cli_git_add_command() {
         let cli = Cli::try_parse_from(["csm", "git", "add", "/path/to/project"]).unwrap();
         assert!(matches!(cli.command, Commands::Git { .. }));
     }
 
+    #[test]
+    fn test_cli_git_track_amend() {
+        let parse = |args: &[&str]| {
+            let mut argv = vec!["csm", "git", "track", "/p"];
+            argv.extend_from_slice(args);
+            Cli::try_parse_from(argv)
+        };
+        assert!(parse(&["--amend", "--session", "abc"]).is_ok());
+        assert!(parse(&["--amend", "--force"]).is_ok());
+        // --amend only rewrites the message, so it takes no other changes
+        assert!(parse(&["--amend", "-m", "msg"]).is_err());
+        assert!(parse(&["--amend", "--all"]).is_err());
+        assert!(parse(&["--force"]).is_err());
+    }
+
     #[test]
     fn test_cli_git_status_command() {
         let cli = Cli::try_parse_from(["csm", "git", "status", "/path/to/project"]).unwrap();
@@ -364,6 +1534,46 @@ mod cli_tests {
         assert!(matches!(cli.command, Commands::Git { .. }));
     }
 
+    #[test]
+    fn test_cli_link_commands() {
+        let cli = Cli::try_parse_from(["csm", "link", "abc123", "/path/to/repo"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Link {
+                session_id: Some(_),
+                repo_path: Some(_),
+                list: false,
+                dry_run: false,
+            }
+        ));
+        let cli = Cli::try_parse_from(["csm", "link", "--list"]).unwrap();
+        assert!(matches!(cli.command, Commands::Link { list: true, .. }));
+        assert!(Cli::try_parse_from(["csm", "link", "abc123"]).is_err());
+        assert!(Cli::try_parse_from(["csm", "link", "abc123", "/repo", "--list"]).is_err());
+
+        let cli = Cli::try_parse_from(["csm", "unlink", "abc123"]).unwrap();
+        assert!(matches!(cli.command, Commands::Unlink { .. }));
+    }
+
+    #[test]
+    fn test_cli_git_diff_session_command() {
+        let cli =
+            Cli::try_parse_from(["csm", "git", "diff", "/path/to/project", "--session", "abc"])
+                .unwrap();
+        assert!(matches!(cli.command, Commands::Git { .. }));
+        assert!(Cli::try_parse_from([
+            "csm",
+            "git",
+            "diff",
+            "/path",
+            "--session",
+            "abc",
+            "--from",
+            "HEAD~1",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_cli_migration_create_command() {
         let cli =
@@ -391,6 +1601,27 @@ mod cli_tests {
         assert!(matches!(cli.command, Commands::Provider { .. }));
     }
 
+    #[test]
+    fn test_cli_provider_info_all() {
+        for args in [
+            &["csm", "provider", "info"][..],
+            &["csm", "provider", "info", "--all", "--probe", "--json"],
+            &[
+                "csm",
+                "provider",
+                "info",
+                "ollama",
+                "--probe",
+                "--timeout",
+                "2",
+            ],
+        ] {
+            assert!(Cli::try_parse_from(args).is_ok(), "{:?}", args);
+        }
+        assert!(Cli::try_parse_from(["csm", "provider", "info", "ollama", "--all"]).is_err());
+        assert!(Cli::try_parse_from(["csm", "provider", "info", "--timeout", "2"]).is_err());
+    }
+
     #[test]
     fn test_cli_provider_config_command() {
         let cli = Cli::try_parse_from([
@@ -478,6 +1709,192 @@ mod workspace_tests {
 mod session_tests {
     use super::*;
 
+    #[test]
+    fn test_split_session_parts() {
+        use chasm_cli::commands::split_session;
+        use chasm_cli::storage::parse_session_json;
+
+        let session = parse_session_json(
+            r#"{"version":3,"sessionId":"s","creationDate":1,"lastMessageDate":9,"requests":[
+                {"timestamp":100,"message":{"text":"a"}},
+                {"timestamp":200,"message":{"text":"b"}},
+                {"message":{"text":"c"}}]}"#,
+        )
+        .unwrap();
+
+        let parts = split_session(&session, &[2, 1]).unwrap();
+        let sizes: Vec<usize> = parts.iter().map(|p| p.requests.len()).collect();
+        assert_eq!(sizes, [1, 1, 1]);
+        assert_eq!(
+            (parts[1].creation_date, parts[1].last_message_date),
+            (200, 200)
+        );
+        // Without timestamps a part keeps the session's dates
+        assert_eq!((parts[2].creation_date, parts[2].last_message_date), (1, 9));
+        assert_eq!(parts[1].session_id.as_deref(), Some("s"));
+
+        assert!(split_session(&session, &[0]).is_err());
+        assert!(split_session(&session, &[3]).is_err());
+        assert!(split_session(&session, &[1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_text_matcher_case_and_word() {
+        use chasm_cli::commands::TextMatcher;
+
+        let find = |matcher: TextMatcher, haystack: &str, needle: &str| {
+            matcher.find(&matcher.normalize(haystack), &matcher.normalize(needle))
+        };
+        let default = TextMatcher::default();
+        let word = TextMatcher {
+            whole_word: true,
+            ..Default::default()
+        };
+        let case = TextMatcher {
+            case_sensitive: true,
+            ..Default::default()
+        };
+
+        assert_eq!(find(default, "audio", "IO"), Some(3));
+        assert_eq!(find(word, "audio", "IO"), None);
+        assert_eq!(find(word, "audio / IO layer", "io"), Some(8));
+        assert_eq!(find(word, "snake_io", "io"), None);
+        assert_eq!(find(default, "prefix", "Fix"), Some(3));
+        assert_eq!(find(case, "prefix", "Fix"), None);
+        assert_eq!(find(case, "prefix. Fix it", "Fix"), Some(8));
+        // Offsets are in bytes
+        assert_eq!(find(word, "Ünïcode-io", "io"), Some(10));
+    }
+
+    #[test]
+    fn test_text_matcher_count_and_relevance() {
+        use chasm_cli::commands::{relevance_score, TextMatcher, TITLE_MATCH_WEIGHT};
+
+        let default = TextMatcher::default();
+        let word = TextMatcher {
+            whole_word: true,
+            ..Default::default()
+        };
+        assert_eq!(default.count("io audio io_x io", "io"), (4, 2));
+        assert_eq!(word.count("io audio io_x io", "io"), (2, 2));
+        assert_eq!(default.count("nothing", "io"), (0, 0));
+
+        let needles = vec!["parser".to_string()];
+        let score = |title: &str, content: Option<&str>| {
+            relevance_score(default, &needles, "id-1", title, content)
+        };
+        // A whole-word title hit outranks the same hit in content
+        assert_eq!(score("fix the parser", None), 2 * TITLE_MATCH_WEIGHT);
+        assert_eq!(score("untitled", Some("the parser")), 2);
+        // More occurrences and whole words each add
+        assert!(score("untitled", Some("parser parser")) > score("untitled", Some("parser")));
+        assert!(score("untitled", Some("parser")) > score("untitled", Some("parsers")));
+        assert_eq!(score("untitled", Some("no match")), 0);
+    }
+
+    #[test]
+    fn test_similarity_terms_and_ranking() {
+        use chasm_cli::commands::{count_terms, Similarity, TermCounts};
+
+        let terms = |text: &str| {
+            let mut counts = TermCounts::new();
+            count_terms(text, &mut counts);
+            counts
+        };
+        // Short words, numbers and filler are dropped; case is folded
+        let counts = terms("The Docker docker build is on port 8080 for_real");
+        assert_eq!(counts.get("docker"), Some(&2));
+        assert_eq!(counts.get("build"), Some(&1));
+        assert_eq!(counts.get("for_real"), Some(&1));
+        for dropped in ["the", "is", "on", "8080"] {
+            assert!(!counts.contains_key(dropped), "{}", dropped);
+        }
+
+        let reference = terms("docker compose volumes networking");
+        let close = terms("docker compose networking bridge");
+        let far = terms("rust borrow checker lifetimes");
+        let shared = terms("networking lunch plans");
+        let similarity = Similarity::new(&reference, &[&close, &far, &shared]);
+        assert!((similarity.score(&reference) - 1.0).abs() < 1e-9);
+        assert!(similarity.score(&close) > similarity.score(&shared));
+        assert_eq!(similarity.percent(&far), 0);
+        // Words the other sessions also use weigh less
+        assert_eq!(similarity.keywords(2), vec!["volumes", "compose"]);
+    }
+
+    #[test]
+    fn test_output_sink_targets_and_bom() {
+        use chasm_cli::commands::{export_dir, is_stdout_target, OutputSink};
+        use std::io::Write;
+
+        assert!(is_stdout_target(None));
+        assert!(is_stdout_target(Some("-")));
+        assert!(!is_stdout_target(Some("out.md")));
+        assert!(export_dir("-").is_err());
+        assert!(export_dir("out").is_ok());
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("nested/out.md");
+        let mut sink = OutputSink::open(path.to_str()).unwrap();
+        assert!(!sink.is_stdout());
+        sink.write_all("\u{feff}# Title\n".as_bytes()).unwrap();
+        // Only a leading BOM is dropped
+        sink.write_all("\u{feff}body".as_bytes()).unwrap();
+        sink.finish().unwrap();
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            "# Title\n\u{feff}body".as_bytes()
+        );
+        assert!(OutputSink::open(Some("-")).unwrap().is_stdout());
+    }
+
+    #[test]
+    fn test_csv_output_columns_and_escaping() {
+        use chasm_cli::commands::{select_columns, strip_ansi, to_csv, CsvColumn};
+
+        struct Row(&'static str, usize);
+        let columns = [
+            CsvColumn::<Row> {
+                key: "title",
+                header: "Title",
+                value: |r| r.0.to_string(),
+            },
+            CsvColumn::<Row> {
+                key: "messages",
+                header: "Messages",
+                value: |r| r.1.to_string(),
+            },
+        ];
+
+        let defaults = select_columns(&columns, &["title"], &[]).unwrap();
+        assert_eq!(defaults.len(), 1);
+        let picked = select_columns(
+            &columns,
+            &[],
+            &["Messages".to_string(), "title".to_string()],
+        )
+        .unwrap();
+        let Err(err) = select_columns(&columns, &[], &["size".to_string()]) else {
+            panic!("expected an unknown column error");
+        };
+        assert!(err.to_string().contains("valid columns: title, messages"));
+
+        let rows = [
+            Row("Fix \"parser\", again", 3),
+            Row("\x1b[1;32mbold\x1b[0m", 1),
+        ];
+        assert_eq!(
+            to_csv(&rows, &picked).unwrap(),
+            "Messages,Title\n3,\"Fix \"\"parser\"\", again\"\n1,bold\n"
+        );
+
+        assert_eq!(strip_ansi("\x1b[31mred\x1b[0m plain"), "red plain");
+        assert_eq!(
+            strip_ansi("\x1b]8;;https://x.dev\x1b\\link\x1b]8;;\x07"),
+            "link"
+        );
+    }
+
     #[test]
     fn test_create_session_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -505,6 +1922,74 @@ mod session_tests {
         assert_eq!(session["requests"].as_array().unwrap().len(), 2);
     }
 
+    #[test]
+    fn test_session_activity_date_uses_newest_timestamp() {
+        use chasm_cli::commands::session_activity_date;
+        use chrono::NaiveDate;
+
+        let temp_dir = TempDir::new().unwrap();
+        let sessions_dir = temp_dir.path().join("chatSessions");
+        fs::create_dir_all(&sessions_dir).unwrap();
+
+        // 2023-11-14 and 2024-01-15 (UTC)
+        create_test_session(
+            &sessions_dir,
+            "dated",
+            "Dated",
+            vec![("old", 1700000000000), ("new", 1705320000000)],
+        );
+        assert_eq!(
+            session_activity_date(&sessions_dir.join("dated.json")),
+            NaiveDate::from_ymd_opt(2024, 1, 15)
+        );
+
+        // No timestamps in the content: falls back to the file modification time
+        create_test_session(&sessions_dir, "undated", "Undated", vec![]);
+        assert_eq!(
+            session_activity_date(&sessions_dir.join("undated.json")),
+            Some(chrono::Utc::now().date_naive())
+        );
+    }
+
+    #[test]
+    fn test_year_activity_counts_messages_in_year() {
+        use chasm_cli::commands::{calendar_shade, YearActivity};
+        use chasm_cli::models::ChatSession;
+        use chrono::NaiveDate;
+
+        // 2023-12-31 23:00, 2024-01-01 10:00 (twice) and one untimed message (UTC)
+        let session: ChatSession = serde_json::from_str(
+            r#"{"version":3,"requests":[
+                {"timestamp":1704063600000,"message":{"text":"a"}},
+                {"timestamp":1704103200000,"message":{"text":"b"}},
+                {"timestamp":1704103500000,"message":{"text":"c"}},
+                {"message":{"text":"d"}}
+            ]}"#,
+        )
+        .unwrap();
+        let day = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+
+        let mut activity = YearActivity::new(2024);
+        activity.add_session(&session, Some(day(3, 5)));
+        assert_eq!(activity.by_day.get(&day(1, 1)), Some(&2));
+        assert_eq!(activity.by_day.get(&day(3, 5)), Some(&1));
+        assert_eq!(activity.total(), 3);
+        assert_eq!(activity.approximate_messages, 1);
+        assert_eq!(activity.approximate_sessions, 1);
+        assert_eq!(activity.busiest_day(), Some((day(1, 1), 2)));
+
+        // A modification day outside the year drops the untimed messages
+        let mut other = YearActivity::new(2024);
+        other.add_session(&session, NaiveDate::from_ymd_opt(2025, 2, 1));
+        assert_eq!((other.total(), other.approximate_messages), (2, 0));
+        assert_eq!(activity.merge(other).by_day.get(&day(1, 1)), Some(&4));
+
+        assert_eq!(calendar_shade(0, 8), 0);
+        assert_eq!(calendar_shade(1, 8), 1);
+        assert_eq!(calendar_shade(3, 8), 2);
+        assert_eq!(calendar_shade(8, 8), 4);
+    }
+
     #[test]
     fn test_session_timestamps_ordering() {
         let temp_dir = TempDir::new().unwrap();
@@ -209,6 +209,56 @@ mod session_merge_logic_tests {
         let single = create_session("single", "Single Session", &[1000]);
         assert_eq!(single.requests.len(), 1);
     }
+
+    #[test]
+    fn test_interleave_by_time_orders_across_sessions() {
+        use chasm_cli::commands::interleave_by_time;
+
+        let a = create_session("a", "A", &[1000, 3000, 5000]);
+        let b = create_session("b", "B", &[2000, 3000, 4000]);
+
+        let timeline = interleave_by_time([&a, &b]);
+        let order: Vec<String> = timeline
+            .requests
+            .iter()
+            .map(|r| r.request_id.clone().unwrap())
+            .collect();
+        // The 3000 tie goes to the session listed first
+        assert_eq!(
+            order,
+            ["req-a-0", "req-b-0", "req-a-1", "req-b-1", "req-b-2", "req-a-2"]
+        );
+        assert_eq!(timeline.untimed, 0);
+        assert_eq!(timeline.first_timestamp(), 1000);
+        assert_eq!(timeline.last_timestamp(), 5000);
+        assert_eq!(timeline.requests[1].source_session.as_deref(), Some("B"));
+        assert_eq!(timeline.sources, [0, 1, 0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_interleave_by_time_keeps_session_order_and_appends_untimed() {
+        use chasm_cli::commands::interleave_by_time;
+
+        // B's clock runs slightly behind: its second request is stamped
+        // before its first, but must not jump ahead of it
+        let a = create_session("a", "A", &[1000, 4000]);
+        let mut b = create_session("b", "B", &[3000, 2500, 9000]);
+        b.requests[2].timestamp = None;
+
+        let timeline = interleave_by_time([&a, &b]);
+        let order: Vec<String> = timeline
+            .requests
+            .iter()
+            .map(|r| r.request_id.clone().unwrap())
+            .collect();
+        assert_eq!(
+            order,
+            ["req-a-0", "req-b-0", "req-b-1", "req-a-1", "req-b-2"]
+        );
+        assert_eq!(timeline.untimed, 1);
+        assert_eq!(timeline.sources, [0, 1, 1, 0, 1]);
+        assert_eq!(timeline.last_timestamp(), 4000);
+    }
 }
 
 // ============================================================================
@@ -331,6 +381,39 @@ mod merge_filter_tests {
     }
 }
 
+// ============================================================================
+// Merge List Tests
+// ============================================================================
+
+mod merge_list_tests {
+    use chasm_cli::commands::{parse_merge_groups, MergeGroup};
+
+    #[test]
+    fn test_parse_merge_groups() {
+        let groups = parse_merge_groups(
+            "# reorganize auth work\naaaa-1 bbbb-2\n\n  cccc-3,dddd-4 , eeee-5.json  # three\n,\n",
+        );
+        assert_eq!(
+            groups,
+            vec![
+                MergeGroup {
+                    line: 2,
+                    session_ids: vec!["aaaa-1".to_string(), "bbbb-2".to_string()],
+                },
+                MergeGroup {
+                    line: 4,
+                    session_ids: vec![
+                        "cccc-3".to_string(),
+                        "dddd-4".to_string(),
+                        "eeee-5.json".to_string()
+                    ],
+                },
+            ]
+        );
+        assert!(parse_merge_groups("# nothing\n\n").is_empty());
+    }
+}
+
 // ============================================================================
 // Provider List Parsing Tests
 // ============================================================================
@@ -694,3 +777,85 @@ mod output_limit_tests {
         assert_eq!(displayed.len(), 5);
     }
 }
+
+// ============================================================================
+// Workspace Merge Plan Tests
+// ============================================================================
+
+mod workspace_merge_plan_tests {
+    use super::*;
+    use chasm_cli::commands::{plan_workspace_merge, MergeAction};
+
+    fn ws_info(hash: &str, dir: PathBuf) -> chasm_cli::workspace::WorkspaceInfo {
+        (hash.to_string(), dir, None, std::time::SystemTime::now())
+    }
+
+    #[test]
+    fn test_plan_skips_collisions_and_empty_sessions() {
+        let base = TempDir::new().unwrap();
+        let ws_a = create_test_workspace_dir(&base, "aaaa1111", "/home/u/projA");
+        let ws_b = create_test_workspace_dir(&base, "bbbb2222", "/home/u/projB");
+
+        create_test_session_file(
+            &ws_a.join("chatSessions"),
+            "shared",
+            "Shared",
+            &[("q", "a", 1_700_000_000_000)],
+        );
+        create_test_session_file(
+            &ws_b.join("chatSessions"),
+            "shared",
+            "Shared copy",
+            &[("q", "a", 1_700_000_000_000)],
+        );
+        create_test_session_file(&ws_b.join("chatSessions"), "empty", "Empty", &[]);
+        // Untimed messages are merged at the end of the timeline, not skipped
+        fs::write(
+            ws_b.join("chatSessions").join("untimed.json"),
+            r#"{"version":3,"sessionId":"untimed","customTitle":"Untimed",
+                "requests":[{"message":{"text":"q"},"response":{"value":[{"value":"a"}]}}]}"#,
+        )
+        .unwrap();
+
+        let plan =
+            plan_workspace_merge(&[ws_info("aaaa1111", ws_a), ws_info("bbbb2222", ws_b)]).unwrap();
+
+        assert_eq!(plan.entries.len(), 4);
+        assert_eq!(plan.sessions.len(), 2);
+
+        let action_for = |title: &str| {
+            plan.entries
+                .iter()
+                .find(|e| e.title == title)
+                .map(|e| e.action.clone())
+                .unwrap()
+        };
+        assert_eq!(action_for("Shared"), MergeAction::Copy);
+        assert_eq!(action_for("Untimed"), MergeAction::Copy);
+        assert_eq!(
+            action_for("Shared copy"),
+            MergeAction::Skip("collision".to_string())
+        );
+        assert_eq!(
+            action_for("Empty"),
+            MergeAction::Skip("no messages".to_string())
+        );
+    }
+
+    #[test]
+    fn test_plan_does_not_modify_workspaces() {
+        let base = TempDir::new().unwrap();
+        let ws = create_test_workspace_dir(&base, "cccc3333", "/home/u/projC");
+        create_test_session_file(
+            &ws.join("chatSessions"),
+            "s1",
+            "Only",
+            &[("q", "a", 1_700_000_000_000)],
+        );
+
+        plan_workspace_merge(&[ws_info("cccc3333", ws.clone())]).unwrap();
+
+        let files: Vec<_> = fs::read_dir(ws.join("chatSessions")).unwrap().collect();
+        assert_eq!(files.len(), 1);
+    }
+}
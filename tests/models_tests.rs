@@ -151,6 +151,8 @@ mod chat_request_tests {
             code_citations: None,
             response_markdown_info: None,
             source_session: None,
+            branches: Vec::new(),
+            selected_branch: None,
         }
     }
 
@@ -208,6 +210,8 @@ mod chat_request_tests {
             code_citations: Some(vec![serde_json::json!({"license": "MIT"})]),
             response_markdown_info: Some(vec![serde_json::json!({"rendered": true})]),
             source_session: Some("source-session-123".to_string()),
+            branches: Vec::new(),
+            selected_branch: None,
         };
 
         let json = serde_json::to_string(&req).unwrap();
@@ -257,10 +261,40 @@ mod chat_request_tests {
             code_citations: None,
             response_markdown_info: None,
             source_session: None,
+            branches: Vec::new(),
+            selected_branch: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("_sourceSession"));
     }
+
+    #[test]
+    fn test_chat_request_referenced_files() {
+        let req: ChatRequest = serde_json::from_value(serde_json::json!({
+            "variableData": {"variables": [
+                {"id": "vscode.implicit.file", "value": {"fsPath": "/src/main.rs", "path": "/src/main.rs"}},
+                {"id": "selection", "value": {"uri": {"fsPath": "/src/lib.rs"}, "range": {}}}
+            ]},
+            "contentReferences": [
+                {"kind": "reference", "reference": {"fsPath": "/src/main.rs"}},
+                {"kind": "reference", "reference": {"fsPath": ""}}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            req.referenced_files(),
+            ["/src/main.rs", "/src/lib.rs", "/src/main.rs"]
+        );
+
+        let session = ChatSession {
+            requests: vec![req.clone(), req],
+            ..serde_json::from_str(r#"{"version":3,"requests":[]}"#).unwrap()
+        };
+        assert_eq!(
+            session.referenced_files().into_iter().collect::<Vec<_>>(),
+            ["/src/lib.rs", "/src/main.rs"]
+        );
+    }
 }
 
 // ============================================================================
@@ -303,6 +337,8 @@ mod chat_session_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
                 ChatRequest {
                     timestamp: Some(1700000010000),
@@ -323,6 +359,8 @@ mod chat_session_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
             ],
         }
@@ -764,13 +802,17 @@ mod workspace_json_tests {
     fn test_workspace_json_with_folder() {
         let ws = WorkspaceJson {
             folder: Some("file:///home/user/project".to_string()),
+            workspace: None,
         };
         assert_eq!(ws.folder, Some("file:///home/user/project".to_string()));
     }
 
     #[test]
     fn test_workspace_json_no_folder() {
-        let ws = WorkspaceJson { folder: None };
+        let ws = WorkspaceJson {
+            folder: None,
+            workspace: None,
+        };
         assert!(ws.folder.is_none());
     }
 
@@ -778,6 +820,7 @@ mod workspace_json_tests {
     fn test_workspace_json_serialization() {
         let ws = WorkspaceJson {
             folder: Some("file:///test".to_string()),
+            workspace: None,
         };
         let json = serde_json::to_string(&ws).unwrap();
         assert!(json.contains("\"folder\":\"file:///test\""));
@@ -790,6 +833,24 @@ mod workspace_json_tests {
         assert_eq!(ws.folder, Some("file:///C:/Users/test/project".to_string()));
     }
 
+    #[test]
+    fn test_workspace_json_multi_root_project_path() {
+        let json = r#"{"workspace": "file:///home/user/myproj.code-workspace"}"#;
+        let ws: WorkspaceJson = serde_json::from_str(json).unwrap();
+        assert!(ws.folder.is_none());
+        let path = ws.project_path().unwrap();
+        assert!(path.ends_with("myproj.code-workspace"));
+        assert!(path.contains("myproj"));
+
+        // A single folder takes precedence and `workspace` isn't written back
+        let ws = WorkspaceJson {
+            folder: Some("file:///home/user/project".to_string()),
+            workspace: None,
+        };
+        assert!(ws.project_path().unwrap().ends_with("project"));
+        assert!(!serde_json::to_string(&ws).unwrap().contains("workspace"));
+    }
+
     #[test]
     fn test_workspace_json_deserialization_no_folder() {
         let json = r#"{}"#;
@@ -801,6 +862,7 @@ mod workspace_json_tests {
     fn test_workspace_json_clone() {
         let ws = WorkspaceJson {
             folder: Some("test".to_string()),
+            workspace: None,
         };
         let cloned = ws.clone();
         assert_eq!(cloned.folder, ws.folder);
@@ -817,6 +879,9 @@ mod workspace_struct_tests {
     fn create_test_workspace() -> Workspace {
         Workspace {
             hash: "abc123def456".to_string(),
+            storage_root: None,
+            channel: Default::default(),
+            remote: false,
             project_path: Some("/home/user/myproject".to_string()),
             workspace_path: PathBuf::from("/vscode/workspaceStorage/abc123def456"),
             chat_sessions_path: PathBuf::from("/vscode/workspaceStorage/abc123def456/chatSessions"),
@@ -934,6 +999,8 @@ mod edge_case_tests {
             code_citations: None,
             response_markdown_info: None,
             source_session: None,
+            branches: Vec::new(),
+            selected_branch: None,
         });
 
         assert!(session.title().len() <= 53);
@@ -976,6 +1043,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             });
         }
 
@@ -1015,6 +1084,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -1054,6 +1125,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -1092,6 +1165,8 @@ mod edge_case_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
                 ChatRequest {
                     timestamp: None, // No timestamp
@@ -1109,6 +1184,8 @@ mod edge_case_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
                 ChatRequest {
                     timestamp: Some(300),
@@ -1126,6 +1203,8 @@ mod edge_case_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
             ],
         };
@@ -1137,3 +1216,167 @@ mod edge_case_tests {
         assert_eq!(max, 300);
     }
 }
+
+// ============================================================================
+// Session Schema Compatibility Tests
+// ============================================================================
+
+mod session_schema_tests {
+    use super::*;
+    use chasm_cli::providers::session_format::session_to_text;
+    use chasm_cli::storage::parse_session_json;
+
+    const LEGACY: &str = include_str!("fixtures/session_legacy.json");
+    const CURRENT: &str = include_str!("fixtures/session_current.json");
+    const TOOL_CALLS: &str = include_str!("fixtures/session_tool_calls.json");
+    const MODELS: &str = include_str!("fixtures/session_models.json");
+
+    #[test]
+    fn test_legacy_schema() {
+        let session = parse_session_json(LEGACY).unwrap();
+        assert_eq!(session.request_count(), 2);
+        assert_eq!(session.title(), "Legacy layout");
+
+        let txt = session_to_text(&session);
+        assert!(txt.contains("User: And the tests?"));
+        assert!(txt.contains("There are none yet."));
+    }
+
+    #[test]
+    fn test_current_schema() {
+        let session = parse_session_json(CURRENT).unwrap();
+        assert_eq!(session.request_count(), 2);
+        // No customTitle: the title comes from the first message's parts
+        assert_eq!(session.title(), "Explain #file:main.rs");
+
+        let first = session.requests[0].message.as_ref().unwrap();
+        assert_eq!(first.get_text(), "Explain #file:main.rs");
+        assert_eq!(first.parts.as_ref().map(Vec::len), Some(2));
+
+        let second = session.requests[1].message.as_ref().unwrap();
+        assert_eq!(second.get_text(), "And the tests?");
+
+        // `responseMessage` lands in `response`, and array responses render
+        let txt = session_to_text(&session);
+        assert!(txt.contains("It prints hello."));
+        assert!(txt.contains("There are none yet."));
+    }
+
+    #[test]
+    fn test_tool_call_parts() {
+        use chasm_cli::models::ToolCall;
+
+        let session = parse_session_json(TOOL_CALLS).unwrap();
+        assert_eq!(
+            session.requests[0].tool_calls(),
+            vec![ToolCall {
+                name: "read_file".to_string(),
+                arguments: Some("Reading src/main.rs".to_string()),
+                result: Some("Read src/main.rs".to_string()),
+            }]
+        );
+
+        // A turn with only a tool part still has content
+        let second = &session.requests[1];
+        assert!(second.message.as_ref().unwrap().text.is_none());
+        let calls = second.tool_calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(
+            calls[0].summary(),
+            r#"run_tests({"filter":"parser"}) -> 3 passed"#
+        );
+
+        // Plain response values are not tool calls
+        assert!(ToolCall::from_part(&serde_json::json!({ "value": "hi" })).is_none());
+
+        let txt = session_to_text(&session);
+        assert!(txt.contains("Tool: read_file(Reading src/main.rs) -> Read src/main.rs"));
+        assert!(txt.contains("It prints hello."));
+        assert!(txt.contains("Tool: run_tests("));
+    }
+
+    #[test]
+    fn test_string_message_round_trips_as_object() {
+        let msg: ChatMessage = serde_json::from_str(r#""plain text""#).unwrap();
+        assert_eq!(msg.text.as_deref(), Some("plain text"));
+
+        let json = serde_json::to_string(&msg).unwrap();
+        let reparsed: ChatMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(reparsed.text.as_deref(), Some("plain text"));
+    }
+
+    #[test]
+    fn test_model_ids_drop_vendor_prefix() {
+        use chasm_cli::models::DominantModel;
+
+        let session = parse_session_json(MODELS).unwrap();
+        let models: Vec<Option<&str>> = session.requests.iter().map(|r| r.model()).collect();
+        assert_eq!(
+            models,
+            vec![
+                Some("claude-3.5-sonnet"),
+                Some("gpt-4o"),
+                Some("o3-mini"),
+                Some("gpt-4.1"),
+            ]
+        );
+        // The raw identifier is kept as stored
+        assert_eq!(
+            session.requests[0].model_id.as_deref(),
+            Some("copilot/claude-3.5-sonnet")
+        );
+        // Four models, one request each: no majority
+        assert_eq!(session.dominant_model(), DominantModel::Mixed);
+        assert_eq!(session.dominant_model().to_string(), "mixed");
+    }
+
+    #[test]
+    fn test_agent_ids() {
+        let session = parse_session_json(MODELS).unwrap();
+        let agents: Vec<Option<&str>> = session.requests.iter().map(|r| r.agent_id()).collect();
+        assert_eq!(
+            agents,
+            vec![
+                Some("github.copilot.default"),
+                Some("github.copilot.editsAgent"),
+                Some("github.copilot.default"),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dominant_model() {
+        use chasm_cli::models::DominantModel;
+
+        let current = parse_session_json(CURRENT).unwrap();
+        assert_eq!(
+            current.dominant_model(),
+            DominantModel::Model("gpt-4o".to_string())
+        );
+        let legacy = parse_session_json(LEGACY).unwrap();
+        assert_eq!(legacy.dominant_model().to_string(), "gpt-4");
+
+        // A strict majority wins; requests without a model don't count
+        let mut session = parse_session_json(MODELS).unwrap();
+        session.requests[2].model_id = Some("copilot/gpt-4o".to_string());
+        session.requests[3].model_id = None;
+        assert_eq!(
+            session.dominant_model(),
+            DominantModel::Model("gpt-4o".to_string())
+        );
+        assert_eq!(
+            session.model_counts(),
+            vec![
+                ("gpt-4o".to_string(), 2),
+                ("claude-3.5-sonnet".to_string(), 1)
+            ]
+        );
+
+        for request in &mut session.requests {
+            request.model_id = None;
+        }
+        assert_eq!(session.dominant_model(), DominantModel::Unknown);
+        assert_eq!(session.dominant_model().to_string(), "—");
+    }
+}
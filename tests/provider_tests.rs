@@ -321,6 +321,70 @@ mod csm_config_tests {
         assert!(config.auto_discover);
     }
 
+    #[test]
+    fn test_saved_search_overrides_and_round_trip() {
+        use chasm_cli::providers::SavedSearch;
+
+        let saved = SavedSearch {
+            patterns: vec!["auth".to_string()],
+            workspace: Some("api".to_string()),
+            after: Some("7d".to_string()),
+            content: true,
+            ..Default::default()
+        };
+        let run = saved.with_overrides(&SavedSearch {
+            after: Some("14d".to_string()),
+            word: true,
+            ..Default::default()
+        });
+        assert_eq!(run.patterns, vec!["auth"]);
+        assert_eq!(run.workspace.as_deref(), Some("api"));
+        assert_eq!(run.after.as_deref(), Some("14d"));
+        assert!(run.content && run.word);
+        assert_eq!(
+            run.flags(),
+            vec!["--workspace api", "--after 14d", "--content", "--word"]
+        );
+        let repatterned = saved.with_overrides(&SavedSearch {
+            patterns: vec!["login".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(repatterned.patterns, vec!["login"]);
+
+        let mut config = CsmConfig::default();
+        assert!(!serde_json::to_string(&config)
+            .unwrap()
+            .contains("saved_searches"));
+        config
+            .saved_searches
+            .insert("weekly".to_string(), saved.clone());
+        let json = serde_json::to_string(&config).unwrap();
+        // Unset options are left out of the file
+        assert!(!json.contains("title_only"));
+        let loaded: CsmConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.saved_searches.get("weekly"), Some(&saved));
+    }
+
+    #[test]
+    fn test_editor_cmd_resolution() {
+        use chasm_cli::commands::{resolve_editor_cmd, DEFAULT_EDITOR_CMD};
+
+        let mut config = CsmConfig::default();
+        assert_eq!(resolve_editor_cmd(None, &config), DEFAULT_EDITOR_CMD);
+
+        config.editor_cmd = Some("codium".to_string());
+        assert_eq!(resolve_editor_cmd(None, &config), "codium");
+        assert_eq!(resolve_editor_cmd(Some("cursor"), &config), "cursor");
+
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: CsmConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.editor_cmd.as_deref(), Some("codium"));
+
+        // Older configs without the key still load
+        let loaded: CsmConfig = serde_json::from_str(r#"{"providers": []}"#).unwrap();
+        assert!(loaded.editor_cmd.is_none());
+    }
+
     #[test]
     fn test_csm_config_serialization() {
         let mut config = CsmConfig {
@@ -383,6 +447,65 @@ mod csm_config_tests {
             .find(|p| p.provider_type == ProviderType::Cursor);
         assert!(cursor.is_none());
     }
+
+    #[test]
+    fn test_csm_config_custom_providers() {
+        let mut config = CsmConfig::default();
+        config.set_provider(ProviderConfig::new(ProviderType::Ollama));
+        config.set_custom_provider(ProviderConfig::custom(
+            "gpu-box",
+            "http://gpu-box:8000",
+            Some("GPU_BOX_KEY"),
+        ));
+        config.set_custom_provider(ProviderConfig::custom("other", "http://other:8000", None));
+
+        // Re-adding by name replaces the entry
+        config.set_custom_provider(ProviderConfig::custom(
+            "GPU-BOX",
+            "http://gpu-box:9000",
+            None,
+        ));
+        assert_eq!(config.custom_providers().count(), 2);
+        assert_eq!(
+            config
+                .get_custom_provider("gpu-box")
+                .unwrap()
+                .endpoint
+                .as_deref(),
+            Some("http://gpu-box:9000")
+        );
+
+        assert!(config.remove_custom_provider("other"));
+        assert!(!config.remove_custom_provider("other"));
+        assert_eq!(config.custom_providers().count(), 1);
+        assert!(config.get_provider(ProviderType::Ollama).is_some());
+    }
+
+    #[test]
+    fn test_validate_base_url() {
+        use chasm_cli::providers::config::validate_base_url;
+
+        assert_eq!(
+            validate_base_url("http://localhost:8000/").unwrap(),
+            "http://localhost:8000"
+        );
+        assert!(validate_base_url("https://example.com/v1").is_ok());
+        assert!(validate_base_url("ftp://example.com").is_err());
+        assert!(validate_base_url("not a url").is_err());
+    }
+
+    #[test]
+    fn test_builtin_names_recognized() {
+        assert_eq!(
+            ProviderType::from_name("Ollama"),
+            Some(ProviderType::Ollama)
+        );
+        assert_eq!(
+            ProviderType::from_name("lmstudio"),
+            Some(ProviderType::LmStudio)
+        );
+        assert_eq!(ProviderType::from_name("my-vllm"), None);
+    }
 }
 
 // ============================================================================
@@ -492,6 +615,8 @@ mod session_format_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
                 ChatRequest {
                     timestamp: Some(1700000001000),
@@ -514,6 +639,8 @@ mod session_format_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
             ],
         }
@@ -683,9 +810,39 @@ mod session_format_tests {
 
 mod openai_compat_tests {
     use super::*;
-    use chasm_cli::providers::openai_compat::{OpenAIChatMessage, OpenAICompatProvider};
+    use chasm_cli::providers::openai_compat::{
+        models_url, OpenAIChatMessage, OpenAICompatProvider,
+    };
     use chasm_cli::providers::ChatProvider;
 
+    #[test]
+    fn test_models_url() {
+        assert_eq!(
+            models_url("http://localhost:8000"),
+            "http://localhost:8000/v1/models"
+        );
+        assert_eq!(
+            models_url("http://localhost:8000/v1/"),
+            "http://localhost:8000/v1/models"
+        );
+    }
+
+    #[test]
+    fn test_unreachable_custom_provider_fails_only_when_probed() {
+        let config = ProviderConfig::custom("nowhere", "http://127.0.0.1:1", None);
+        let provider = OpenAICompatProvider::custom(&config);
+
+        assert_eq!(provider.provider_type(), ProviderType::Custom);
+        assert_eq!(provider.name(), "nowhere");
+        assert_eq!(provider.endpoint(), Some("http://127.0.0.1:1"));
+        // Building the provider sends nothing; it's configured, so available
+        assert!(provider.is_available());
+        assert!(provider.list_models().unwrap().is_empty());
+
+        let probe = provider.fetch_models(std::time::Duration::from_secs(1));
+        assert!(matches!(probe, Some(Err(_))));
+    }
+
     #[test]
     fn test_openai_chat_message_creation() {
         let msg = OpenAIChatMessage {
@@ -790,6 +947,8 @@ mod openai_compat_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -981,6 +1140,96 @@ mod discovery_tests {
             let _ = ProviderRegistry::new();
         }
     }
+
+    #[test]
+    fn test_probe_endpoint_reachable() {
+        use chasm_cli::providers::discovery::probe_endpoint;
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+        use std::time::Duration;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            // Any HTTP status counts as reachable
+            stream
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+        });
+
+        assert!(probe_endpoint(&url, Duration::from_secs(5)).is_ok());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_probe_endpoint_unreachable() {
+        use chasm_cli::providers::discovery::probe_endpoint;
+        use std::net::TcpListener;
+        use std::time::Duration;
+
+        // Grab a free port, then close it so nothing is listening
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let url = format!("http://{}", addr);
+
+        assert!(probe_endpoint(&url, Duration::from_secs(2)).is_err());
+    }
+
+    #[test]
+    fn test_probe_retries_until_the_server_answers() {
+        use chasm_cli::providers::discovery::probe_endpoint_with_retries;
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+        use std::time::Duration;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            // Still "loading": hang up on the first request without answering
+            for (i, stream) in listener.incoming().flatten().enumerate() {
+                let mut stream = stream;
+                let _ = stream.read(&mut [0u8; 1024]);
+                if i > 0 {
+                    let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+                }
+            }
+        });
+
+        let (result, attempts) = probe_endpoint_with_retries(&url, Duration::from_secs(2), 3);
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(attempts, 2);
+    }
+
+    #[test]
+    fn test_probe_retries_stay_within_budget() {
+        use chasm_cli::providers::discovery::{probe_endpoint_with_retries, PROBE_BACKOFF};
+        use std::net::TcpListener;
+        use std::time::{Duration, Instant};
+
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let url = format!("http://{}", addr);
+
+        let (result, attempts) = probe_endpoint_with_retries(&url, Duration::from_secs(2), 0);
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+
+        // A 300 ms timeout with 5 retries allows 1.8 s: room for the 250 ms,
+        // 500 ms and 1 s waits would run out, so fewer attempts are made
+        let timeout = Duration::from_millis(300);
+        let start = Instant::now();
+        let (result, attempts) = probe_endpoint_with_retries(&url, timeout, 5);
+        assert!(result.is_err());
+        assert!((2..6).contains(&attempts), "{} attempts", attempts);
+        assert!(start.elapsed() <= timeout * 6 + PROBE_BACKOFF);
+    }
 }
 
 // ============================================================================
@@ -1028,6 +1277,8 @@ mod integration_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -1085,6 +1336,8 @@ mod integration_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
                 ChatRequest {
                     timestamp: Some(1700000001000),
@@ -1107,6 +1360,8 @@ mod integration_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
                 ChatRequest {
                     timestamp: Some(1700000002000),
@@ -1129,6 +1384,8 @@ mod integration_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
             ],
         };
@@ -1239,6 +1496,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -1282,6 +1541,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -1332,6 +1593,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -1366,6 +1629,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             });
         }
 
@@ -0,0 +1,126 @@
+//! Tests for the session JSON Schema
+//!
+//! This file covers:
+//! - Document layout and definitions
+//! - Schema properties matching what serde reads and writes
+
+use chasm_cli::models::ChatSession;
+use chasm_cli::providers::session_format::TemplateContext;
+use chasm_cli::schema::session_schema;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+/// Property names of a `$defs` entry
+fn schema_properties(name: &str) -> BTreeSet<String> {
+    let schema = session_schema();
+    let def = &schema["$defs"][name];
+    let object = def["anyOf"]
+        .as_array()
+        .and_then(|variants| variants.iter().find(|v| v["type"] == "object"))
+        .unwrap_or(def);
+    object["properties"]
+        .as_object()
+        .unwrap_or_else(|| panic!("{} has no properties", name))
+        .keys()
+        .cloned()
+        .collect()
+}
+
+fn keys(value: &Value) -> BTreeSet<String> {
+    value.as_object().unwrap().keys().cloned().collect()
+}
+
+/// A session with every field set, round-tripped through the model
+fn full_session() -> ChatSession {
+    serde_json::from_value(json!({
+        "version": 3,
+        "sessionId": "sess-1",
+        "customTitle": "Schema",
+        "requests": [{
+            "timestamp": 1720000000000i64,
+            "message": {"text": "hello", "parts": []},
+            "response": {"value": [{"value": "hi"}]},
+            "_sourceSession": "other",
+            "branches": [{
+                "timestamp": 1719999990000i64,
+                "message": {"text": "helo", "parts": []},
+                "response": {"value": [{"value": "hi?"}]},
+                "modelId": "copilot/gpt-4o"
+            }],
+            "selectedBranch": 0
+        }]
+    }))
+    .unwrap()
+}
+
+// ============================================================================
+// Document Tests
+// ============================================================================
+
+mod schema_document_tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_document() {
+        let schema = session_schema();
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        for name in [
+            "ChatSession",
+            "ChatRequest",
+            "ChatBranch",
+            "ChatMessage",
+            "ExportSession",
+            "ExportMessage",
+        ] {
+            assert!(schema["$defs"][name].is_object(), "missing {}", name);
+        }
+
+        // Every $ref points at a definition
+        let text = serde_json::to_string(&schema).unwrap();
+        for reference in text.split("\"$ref\":\"#/$defs/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schema["$defs"][name].is_object(), "dangling $ref {}", name);
+        }
+    }
+}
+
+// ============================================================================
+// Model Drift Tests
+// ============================================================================
+
+mod schema_drift_tests {
+    use super::*;
+
+    #[test]
+    fn test_native_schema_matches_model() {
+        let session = serde_json::to_value(full_session()).unwrap();
+        assert_eq!(keys(&session), schema_properties("ChatSession"));
+
+        let request = &session["requests"][0];
+        assert!(request.get("_sourceSession").is_some());
+        assert_eq!(keys(request), schema_properties("ChatRequest"));
+        assert_eq!(
+            keys(&request["branches"][0]),
+            schema_properties("ChatBranch")
+        );
+
+        let mut message = keys(&request["message"]);
+        message.insert("content".to_string());
+        assert_eq!(message, schema_properties("ChatMessage"));
+    }
+
+    #[test]
+    fn test_export_schema_matches_context() {
+        let context =
+            serde_json::to_value(TemplateContext::new(&full_session(), "/home/u/proj")).unwrap();
+        assert_eq!(keys(&context), schema_properties("ExportSession"));
+        assert!(schema_properties("ExportSession").contains("session_id"));
+        assert_eq!(
+            keys(&context["messages"][0]),
+            schema_properties("ExportMessage")
+        );
+    }
+}
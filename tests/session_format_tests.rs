@@ -6,11 +6,15 @@
 //! - ChatSession to GenericSession conversion
 //! - GenericSession to ChatSession conversion
 //! - Markdown export/import
+//! - Combined multi-session documents
+//! - Content hashes used by `dedupe`
+//! - Code block extraction for `export --split-by-code`
 //! - Response text extraction
 
 use chasm_cli::models::{ChatMessage, ChatRequest, ChatSession};
 use chasm_cli::providers::session_format::{
-    markdown_to_session, session_to_markdown, GenericMessage, GenericSession,
+    markdown_to_session, session_to_markdown, sessions_to_combined_html,
+    sessions_to_combined_markdown, sessions_to_combined_text, GenericMessage, GenericSession,
 };
 
 // ============================================================================
@@ -296,6 +300,8 @@ mod chat_session_to_generic_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         }
     }
@@ -608,6 +614,8 @@ mod session_to_markdown_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         }
     }
@@ -689,6 +697,8 @@ mod session_to_markdown_tests {
             code_citations: None,
             response_markdown_info: None,
             source_session: None,
+            branches: Vec::new(),
+            selected_branch: None,
         });
 
         let md = session_to_markdown(&session);
@@ -887,6 +897,8 @@ mod roundtrip_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
                 ChatRequest {
                     timestamp: Some(1700000010000),
@@ -909,6 +921,8 @@ mod roundtrip_tests {
                     code_citations: None,
                     response_markdown_info: None,
                     source_session: None,
+                    branches: Vec::new(),
+                    selected_branch: None,
                 },
             ],
         }
@@ -958,10 +972,209 @@ mod roundtrip_tests {
     }
 }
 
+// ============================================================================
+// Combined Document Tests
+// ============================================================================
+
+mod combined_document_tests {
+    use super::*;
+
+    fn titled_session(title: &str, created: i64, question: &str) -> ChatSession {
+        serde_json::from_value(serde_json::json!({
+            "version": 3,
+            "sessionId": format!("id-{}", created),
+            "creationDate": created,
+            "lastMessageDate": created,
+            "customTitle": title,
+            "requests": [{
+                "timestamp": created,
+                "message": {"text": question},
+                "response": {"value": [{"value": "An answer"}]}
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_combined_markdown_toc_links_each_section() {
+        let sessions = vec![
+            titled_session("Setup CI", 1704067200000, "How do I add CI?"),
+            titled_session("Setup CI", 1704153600000, "And caching?"),
+            titled_session("Fix: the *parser*", 1704240000000, "Why does it fail?"),
+        ];
+        let md = sessions_to_combined_markdown("proj", Some("3 session(s)"), &sessions);
+
+        assert!(md.starts_with("# proj\n\n3 session(s)\n\n## Contents\n"));
+        assert!(md.contains("1. [Setup CI](#setup-ci) (2024-01-01, 1 messages)"));
+        assert!(md.contains("2. [Setup CI](#setup-ci-2) (2024-01-02, 1 messages)"));
+        assert!(md.contains("3. [Fix: the *parser*](#fix-the-parser)"));
+        for anchor in ["setup-ci", "setup-ci-2", "fix-the-parser"] {
+            assert!(md.contains(&format!("<a id=\"{}\"></a>", anchor)));
+        }
+        // Each session's own H1 sits one level down, under the document's
+        assert_eq!(md.matches("\n# ").count(), 0);
+        assert_eq!(md.matches("## Setup CI\n").count(), 2);
+        assert!(md.find("How do I add CI?").unwrap() < md.find("And caching?").unwrap());
+    }
+
+    #[test]
+    fn test_combined_markdown_leaves_code_blocks_alone() {
+        let session = titled_session("Shell", 1704067200000, "```sh\n# a comment\n```");
+        let md = sessions_to_combined_markdown("proj", None, &[session]);
+        assert!(md.contains("\n# a comment\n"));
+        assert!(md.contains("## Shell\n"));
+    }
+
+    #[test]
+    fn test_combined_html_sections_and_text_contents() {
+        let sessions = vec![
+            titled_session("First <one>", 1704067200000, "q1"),
+            titled_session("", 1704153600000, "q2"),
+        ];
+        let html = sessions_to_combined_html("proj", None, &sessions);
+        assert_eq!(html.matches("<html").count(), 1);
+        assert!(html.contains("<h1>proj</h1>"));
+        assert!(html.contains("<a href=\"#first-one\">First &lt;one&gt;</a>"));
+        assert!(html.contains("<h2 id=\"first-one\">First &lt;one&gt;</h2>"));
+        assert_eq!(html.matches("<section>").count(), 2);
+
+        let txt = sessions_to_combined_text("proj", None, &sessions);
+        assert!(txt.starts_with("proj\n"));
+        assert!(txt.contains("  1. First <one> (2024-01-01, 1 messages)"));
+        assert!(txt.contains("\n1. Title: First <one>"));
+    }
+}
+
 // ============================================================================
 // Edge Case Tests
 // ============================================================================
 
+mod content_hash_tests {
+    use super::*;
+    use chasm_cli::commands::session_content_hash;
+
+    fn session(id: &str, title: &str, turns: &[(&str, &str)]) -> ChatSession {
+        let requests: Vec<serde_json::Value> = turns
+            .iter()
+            .map(|(q, a)| {
+                serde_json::json!({
+                    "message": {"text": q},
+                    "response": {"value": [{"value": a}]}
+                })
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "version": 3,
+            "sessionId": id,
+            "creationDate": 1704067200000i64,
+            "lastMessageDate": 1704067200000i64,
+            "customTitle": title,
+            "requests": requests
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hash_ignores_ids_titles_and_line_endings() {
+        let a = session("one", "First", &[("Hi", "Hello\nthere"), ("Bye", "Later")]);
+        let b = session(
+            "two",
+            "Copy",
+            &[("Hi \r\n", "Hello\r\nthere"), ("Bye", "Later")],
+        );
+        let hash = session_content_hash(&a).unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(session_content_hash(&b), Some(hash));
+    }
+
+    #[test]
+    fn test_hash_tells_conversations_apart() {
+        let base = session("a", "t", &[("Hi", "Hello")]);
+        let others = [
+            session("a", "t", &[("Hi", "Hello!")]),
+            session("a", "t", &[("Hi", "Hello"), ("More", "Sure")]),
+            // Text moved between prompt and response
+            session("a", "t", &[("HiH", "ello")]),
+        ];
+        for other in &others {
+            assert_ne!(session_content_hash(&base), session_content_hash(other));
+        }
+    }
+
+    #[test]
+    fn test_sessions_without_text_have_no_hash() {
+        assert_eq!(session_content_hash(&session("a", "t", &[])), None);
+        assert_eq!(session_content_hash(&session("a", "t", &[(" ", "")])), None);
+    }
+}
+
+// ============================================================================
+// Code Block Extraction Tests
+// ============================================================================
+
+mod code_block_tests {
+    use chasm_cli::providers::session_format::{
+        code_extension, extract_response_text, set_response_text, split_code_blocks,
+    };
+
+    #[test]
+    fn test_code_extension_from_fence_language() {
+        assert_eq!(code_extension("rust"), "rs");
+        assert_eq!(code_extension("Python"), "py");
+        assert_eq!(code_extension("c++"), "cpp");
+        assert_eq!(code_extension("typescript"), "ts");
+        assert_eq!(code_extension(""), "txt");
+        // Unknown but plausible hints are kept; anything else is text
+        assert_eq!(code_extension("nim"), "nim");
+        assert_eq!(code_extension("not/a-language"), "txt");
+    }
+
+    #[test]
+    fn test_split_code_blocks() {
+        let text = "Try:\n```rust title=\"main\"\nfn main() {}\n```\n~~~\nplain\n~~~\n````md\n```\nnested\n```\n````\nDone";
+        let mut blocks = Vec::new();
+        let split = split_code_blocks(text, |language, body| {
+            blocks.push((language.to_string(), body.to_string()));
+            format!("<{}>", blocks.len())
+        });
+        assert_eq!(split, "Try:\n<1>\n<2>\n<3>\nDone");
+        assert_eq!(
+            blocks,
+            vec![
+                ("rust".to_string(), "fn main() {}".to_string()),
+                (String::new(), "plain".to_string()),
+                ("md".to_string(), "```\nnested\n```".to_string()),
+            ]
+        );
+
+        // Unclosed fences and inline code stay as written
+        let unclosed = "```python\nprint(1)";
+        assert_eq!(split_code_blocks(unclosed, |_, _| unreachable!()), unclosed);
+        let inline = "use ```x``` here";
+        assert_eq!(split_code_blocks(inline, |_, _| unreachable!()), inline);
+    }
+
+    #[test]
+    fn test_set_response_text_keeps_other_parts() {
+        let mut response = serde_json::json!({"value": [
+            {"kind": "toolInvocationSerialized", "toolId": "run"},
+            {"value": "first "},
+            {"value": "second"}
+        ]});
+        set_response_text(&mut response, "replaced".to_string());
+        assert_eq!(
+            extract_response_text(&response).as_deref(),
+            Some("replaced")
+        );
+        assert_eq!(response["value"].as_array().unwrap().len(), 2);
+        assert_eq!(response["value"][0]["toolId"], "run");
+
+        let mut response = serde_json::json!({"text": "old"});
+        set_response_text(&mut response, "new".to_string());
+        assert_eq!(response, serde_json::json!({"text": "new"}));
+    }
+}
+
 mod edge_case_tests {
     use super::*;
 
@@ -1000,6 +1213,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -1040,6 +1255,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -1082,6 +1299,8 @@ mod edge_case_tests {
                 code_citations: None,
                 response_markdown_info: None,
                 source_session: None,
+                branches: Vec::new(),
+                selected_branch: None,
             }],
         };
 
@@ -1013,7 +1013,7 @@ mod empty_window_sessions_tests {
         ];
 
         // Sort by last_message_date descending (most recent first)
-        sessions.sort_by(|a, b| b.last_message_date.cmp(&a.last_message_date));
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_message_date));
 
         assert_eq!(sessions[0].session_id, Some("new".to_string()));
         assert_eq!(sessions[1].session_id, Some("mid".to_string()));
@@ -1118,3 +1118,61 @@ mod empty_window_sessions_tests {
         assert!(invalid_session.is_err());
     }
 }
+
+// ============================================================================
+// Atomic Write Tests
+// ============================================================================
+
+mod atomic_write_tests {
+    use chasm_cli::models::ChatSession;
+    use chasm_cli::storage::{write_json_atomic, write_session_atomic};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// A value whose serialization always fails partway through
+    struct FailingValue;
+
+    impl serde::Serialize for FailingValue {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::{Error, SerializeMap};
+            let mut map = serializer.serialize_map(None)?;
+            map.serialize_entry("partial", "data")?;
+            Err(S::Error::custom("serialization failed"))
+        }
+    }
+
+    fn create_test_session(session_id: &str) -> ChatSession {
+        serde_json::from_str(&format!(
+            r#"{{"version": 3, "sessionId": "{}", "requests": []}}"#,
+            session_id
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_write_session_atomic_replaces_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("session.json");
+        fs::write(&path, "old content").unwrap();
+
+        write_session_atomic(&path, &create_test_session("new-session")).unwrap();
+
+        let restored: ChatSession =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(restored.session_id, Some("new-session".to_string()));
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_failed_serialization_leaves_original_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("session.json");
+        fs::write(&path, "original content").unwrap();
+
+        assert!(write_json_atomic(&path, &FailingValue).is_err());
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "original content");
+        // The temporary file must be cleaned up as well
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,109 @@
+//! Tests for the local session tag index
+//!
+//! This file covers:
+//! - Adding and removing tags
+//! - Tag normalization
+//! - Persistence of the sidecar index
+//! - Orphaned tag detection
+
+use chasm_cli::tags::{normalize_tag, TagIndex};
+use std::collections::BTreeSet;
+use tempfile::TempDir;
+
+// ============================================================================
+// Tag Index Tests
+// ============================================================================
+
+mod tag_index_tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_remove_tags() {
+        let mut index = TagIndex::default();
+
+        assert!(index.add("s1", "Backend"));
+        assert!(!index.add("s1", "backend"));
+        assert!(index.add("s1", "auth"));
+        assert_eq!(index.tags_for("s1"), vec!["auth", "backend"]);
+        assert!(index.has_tag("s1", "AUTH"));
+
+        assert!(index.remove("s1", "auth"));
+        assert!(!index.remove("s1", "auth"));
+        assert!(index.remove("s1", "backend"));
+
+        // Sessions without tags are dropped from the index
+        assert!(index.sessions.is_empty());
+    }
+
+    #[test]
+    fn test_empty_tag_is_ignored() {
+        let mut index = TagIndex::default();
+        assert!(!index.add("s1", "   "));
+        assert!(index.sessions.is_empty());
+        assert_eq!(normalize_tag("  Work "), "work");
+    }
+
+    #[test]
+    fn test_tag_counts() {
+        let mut index = TagIndex::default();
+        index.add("s1", "work");
+        index.add("s2", "work");
+        index.add("s2", "rust");
+
+        let counts = index.tag_counts();
+        assert_eq!(counts.get("work"), Some(&2));
+        assert_eq!(counts.get("rust"), Some(&1));
+    }
+}
+
+// ============================================================================
+// Persistence Tests
+// ============================================================================
+
+mod tag_persistence_tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_index_loads_empty() {
+        let dir = TempDir::new().unwrap();
+        let index = TagIndex::load_from(&dir.path().join("tags.json")).unwrap();
+        assert_eq!(index, TagIndex::default());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("tags.json");
+
+        let mut index = TagIndex::default();
+        index.add("s1", "work");
+        index.add("s2", "home");
+        index.save_to(&path).unwrap();
+
+        let loaded = TagIndex::load_from(&path).unwrap();
+        assert_eq!(loaded, index);
+    }
+}
+
+// ============================================================================
+// Orphaned Tag Tests
+// ============================================================================
+
+mod orphaned_tag_tests {
+    use super::*;
+
+    #[test]
+    fn test_orphaned_sessions_detected_and_removed() {
+        let mut index = TagIndex::default();
+        index.add("alive", "work");
+        index.add("gone", "work");
+
+        let known: BTreeSet<String> = ["alive".to_string()].into_iter().collect();
+        let orphaned = index.orphaned(&known);
+        assert_eq!(orphaned, vec!["gone".to_string()]);
+
+        assert_eq!(index.remove_sessions(&orphaned), 1);
+        assert!(index.orphaned(&known).is_empty());
+        assert_eq!(index.tags_for("alive"), vec!["work"]);
+    }
+}
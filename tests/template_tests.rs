@@ -0,0 +1,107 @@
+//! Tests for user-supplied export templates
+//!
+//! This file covers:
+//! - Variables, filters, loops and conditionals
+//! - Whitespace control and comments
+//! - Line-numbered syntax and render errors
+
+use chasm_cli::template::Template;
+use serde_json::json;
+
+fn context() -> serde_json::Value {
+    json!({
+        "title": "Auth refactor",
+        "session_id": "sess-1",
+        "workspace": "/home/u/proj",
+        "messages": [
+            {"role": "user", "text": "Fix <login>", "timestamp": "2024-07-03 09:46:40"},
+            {"role": "assistant", "text": "Done.", "timestamp": null}
+        ]
+    })
+}
+
+// ============================================================================
+// Rendering Tests
+// ============================================================================
+
+mod template_render_tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_variables_and_filters() {
+        let template = Template::parse("# {{ title | upper }} ({{ session_id }})").unwrap();
+        assert_eq!(
+            template.render(&context()).unwrap(),
+            "# AUTH REFACTOR (sess-1)"
+        );
+    }
+
+    #[test]
+    fn test_renders_loops_and_conditionals() {
+        let template = Template::parse(
+            "{% for m in messages %}{{ loop.index }}:{{ m.role }}\
+             {% if m.timestamp %}@{{ m.timestamp }}{% else %}@?{% endif %}\
+             {% if not loop.last %}, {% endif %}{% endfor %}",
+        )
+        .unwrap();
+        assert_eq!(
+            template.render(&context()).unwrap(),
+            "1:user@2024-07-03 09:46:40, 2:assistant@?"
+        );
+    }
+
+    #[test]
+    fn test_escape_filter_and_whitespace_control() {
+        let template = Template::parse(
+            "<ul>{# one item per message #}\n{%- for m in messages %}\n  <li>{{ m.text | escape }}</li>\n{%- endfor %}\n</ul>",
+        )
+        .unwrap();
+        assert_eq!(
+            template.render(&context()).unwrap(),
+            "<ul>\n  <li>Fix &lt;login&gt;</li>\n  <li>Done.</li>\n</ul>"
+        );
+    }
+
+    #[test]
+    fn test_unknown_variable_fails_with_line() {
+        let template = Template::parse("ok\n{{ missing }}").unwrap();
+        let err = template.render(&context()).unwrap_err().to_string();
+        assert!(err.contains("line 2"), "{}", err);
+        assert!(err.contains("missing"), "{}", err);
+    }
+}
+
+// ============================================================================
+// Syntax Error Tests
+// ============================================================================
+
+mod template_syntax_tests {
+    use super::*;
+
+    fn parse_error(source: &str) -> String {
+        Template::parse(source).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_unclosed_expression_reports_line() {
+        let err = parse_error("# {{ title }}\n\n{{ session_id }\n");
+        assert!(err.contains("line 3"), "{}", err);
+        assert!(err.contains("unclosed"), "{}", err);
+    }
+
+    #[test]
+    fn test_unbalanced_blocks_report_line() {
+        let err = parse_error("a\n{% for m in messages %}\n{{ m.text }}\n");
+        assert!(err.contains("line 2"), "{}", err);
+        assert!(err.contains("endfor"), "{}", err);
+
+        let err = parse_error("a\nb\n{% endif %}");
+        assert!(err.contains("line 3"), "{}", err);
+    }
+
+    #[test]
+    fn test_unknown_tag_and_filter_rejected() {
+        assert!(parse_error("{% include \"x\" %}").contains("unknown tag"));
+        assert!(parse_error("{{ title | shout }}").contains("unknown filter"));
+    }
+}
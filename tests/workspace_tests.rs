@@ -25,6 +25,26 @@ mod decode_workspace_folder_tests {
         assert!(decoded.contains("home") || decoded.contains("user"));
     }
 
+    #[test]
+    fn test_decode_keeps_root_slash() {
+        let decoded = decode_workspace_folder("file:///home/user/my%20project");
+        if cfg!(target_os = "windows") {
+            assert_eq!(decoded, "\\home\\user\\my project");
+        } else {
+            assert_eq!(decoded, "/home/user/my project");
+        }
+    }
+
+    #[test]
+    fn test_decode_drive_letter_drops_root_slash() {
+        let decoded = decode_workspace_folder("file:///c%3A/Users/test");
+        if cfg!(target_os = "windows") {
+            assert_eq!(decoded, "c:\\Users\\test");
+        } else {
+            assert_eq!(decoded, "c:/Users/test");
+        }
+    }
+
     #[test]
     fn test_decode_windows_path() {
         let uri = "file:///C:/Users/test/project";
@@ -212,11 +232,11 @@ mod workspace_storage_path_tests {
 // ============================================================================
 
 mod workspace_discovery_tests {
-    use chasm_cli::workspace::discover_workspaces;
+    use chasm_cli::workspace::{discover_workspaces, DiscoveryOptions};
 
     #[test]
     fn test_discover_workspaces_returns_vec() {
-        let result = discover_workspaces();
+        let result = discover_workspaces(&DiscoveryOptions::default());
         assert!(result.is_ok());
         // Returns a vector (may be empty if no workspaces exist)
         let workspaces = result.unwrap();
@@ -225,7 +245,7 @@ mod workspace_discovery_tests {
 
     #[test]
     fn test_discovered_workspace_fields() {
-        let result = discover_workspaces();
+        let result = discover_workspaces(&DiscoveryOptions::default());
         if let Ok(workspaces) = result {
             for ws in &workspaces {
                 // Hash should not be empty
@@ -237,6 +257,248 @@ mod workspace_discovery_tests {
             }
         }
     }
+
+    #[test]
+    fn test_discovery_options_are_independent() {
+        let excluding = DiscoveryOptions::default()
+            .with_exclude_globs(&["/tmp/*".to_string()])
+            .unwrap();
+        assert!(excluding.is_excluded(Some("/tmp/scratch")));
+        assert!(excluding.is_excluded(Some("tmp/scratch")));
+        assert!(!excluding.is_excluded(Some("/home/u/proj")));
+        assert!(!excluding.is_excluded(None));
+
+        // A second set of options in the same process sees its own globs
+        let default = DiscoveryOptions::default();
+        assert!(!default.is_excluded(Some("/tmp/scratch")));
+        let other = DiscoveryOptions::default()
+            .with_exclude_globs(&["/home/*".to_string()])
+            .unwrap();
+        assert!(other.is_excluded(Some("/home/u")));
+        assert!(!other.is_excluded(Some("/tmp/scratch")));
+
+        assert!(DiscoveryOptions::default()
+            .with_exclude_globs(&["[".to_string()])
+            .is_err());
+    }
+}
+
+// ============================================================================
+// VS Code Channel Tests
+// ============================================================================
+
+mod channel_tests {
+    use chasm_cli::workspace::{channel_storage_path, ChannelFilter, VsCodeChannel};
+    use std::path::Path;
+
+    #[test]
+    fn test_channel_of_workspace_path() {
+        assert_eq!(
+            VsCodeChannel::of_path(Path::new(
+                "/home/u/.config/Code - Insiders/User/workspaceStorage/abc"
+            )),
+            VsCodeChannel::Insiders
+        );
+        assert_eq!(
+            VsCodeChannel::of_path(Path::new("/home/u/.config/Code/User/workspaceStorage/abc")),
+            VsCodeChannel::Stable
+        );
+        // Only a whole path component counts
+        assert_eq!(
+            VsCodeChannel::of_path(Path::new("/srv/Code - Insiders backup/abc")),
+            VsCodeChannel::Stable
+        );
+    }
+
+    #[test]
+    fn test_channel_storage_paths_differ_by_app_folder() {
+        let stable = channel_storage_path(VsCodeChannel::Stable).unwrap();
+        let insiders = channel_storage_path(VsCodeChannel::Insiders).unwrap();
+        assert!(stable.ends_with("Code/User/workspaceStorage"));
+        assert!(insiders.ends_with("Code - Insiders/User/workspaceStorage"));
+        assert_eq!(VsCodeChannel::of_path(&insiders), VsCodeChannel::Insiders);
+    }
+
+    #[test]
+    fn test_remote_server_storage() {
+        use chasm_cli::workspace::{is_remote_storage, remote_storage_paths};
+
+        let server = Path::new("/home/u/.vscode-server/data/User/workspaceStorage/abc");
+        assert!(is_remote_storage(server));
+        assert_eq!(VsCodeChannel::of_path(server), VsCodeChannel::Stable);
+        let insiders = Path::new("/home/u/.vscode-server-insiders/data/User/workspaceStorage/abc");
+        assert!(is_remote_storage(insiders));
+        assert_eq!(VsCodeChannel::of_path(insiders), VsCodeChannel::Insiders);
+        assert!(is_remote_storage(Path::new(
+            "/home/u/.cursor-server/data/User/workspaceStorage/abc"
+        )));
+        assert!(!is_remote_storage(Path::new(
+            "/home/u/.config/Code/User/workspaceStorage/abc"
+        )));
+
+        let paths = remote_storage_paths(ChannelFilter::All);
+        assert!(paths
+            .iter()
+            .any(|p| p.ends_with(".vscode-server/data/User/workspaceStorage")));
+        assert!(paths
+            .iter()
+            .any(|p| p.ends_with(".cursor-server/data/User/workspaceStorage")));
+    }
+
+    #[test]
+    fn test_channel_filter() {
+        let parse = |s: &str| s.parse::<ChannelFilter>();
+        assert_eq!(parse("Insiders"), Ok(ChannelFilter::Insiders));
+        assert_eq!(ChannelFilter::default(), ChannelFilter::All);
+        assert!(parse("beta").unwrap_err().contains("stable, insiders, all"));
+
+        assert!(ChannelFilter::All.includes(VsCodeChannel::Insiders));
+        assert!(ChannelFilter::Stable.includes(VsCodeChannel::Stable));
+        assert!(!ChannelFilter::Stable.includes(VsCodeChannel::Insiders));
+        assert!(!ChannelFilter::Insiders.includes(VsCodeChannel::Stable));
+    }
+}
+
+// ============================================================================
+// Nested Storage Scan Tests
+// ============================================================================
+
+mod nested_storage_tests {
+    use super::*;
+    use chasm_cli::workspace::{find_workspace_dirs, storage_root_label};
+
+    fn make_workspace(dir: &std::path::Path) {
+        fs::create_dir_all(dir.join("chatSessions")).unwrap();
+        fs::write(dir.join("workspace.json"), r#"{"folder":"file:///p"}"#).unwrap();
+    }
+
+    #[test]
+    fn test_flat_scan_ignores_nested_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        make_workspace(&temp_dir.path().join("hash1"));
+        make_workspace(&temp_dir.path().join("alice").join("hash2"));
+
+        let dirs = find_workspace_dirs(temp_dir.path(), 0);
+        assert_eq!(dirs, vec![temp_dir.path().join("hash1")]);
+    }
+
+    #[test]
+    fn test_depth_scan_finds_per_user_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        make_workspace(&root.join("alice").join("hash1"));
+        make_workspace(&root.join("bob").join("hash1"));
+        make_workspace(&root.join("carol").join("nested").join("hash2"));
+
+        let mut dirs = find_workspace_dirs(root, 1);
+        dirs.sort();
+        assert_eq!(
+            dirs,
+            vec![
+                root.join("alice").join("hash1"),
+                root.join("bob").join("hash1")
+            ]
+        );
+
+        assert_eq!(find_workspace_dirs(root, 2).len(), 3);
+    }
+
+    #[test]
+    fn test_workspace_dirs_are_not_descended_into() {
+        let temp_dir = TempDir::new().unwrap();
+        let ws = temp_dir.path().join("hash1");
+        make_workspace(&ws);
+
+        // chatSessions must not be mistaken for a nested storage root
+        let dirs = find_workspace_dirs(temp_dir.path(), 3);
+        assert_eq!(dirs, vec![ws]);
+    }
+
+    #[test]
+    fn test_reporting_scan_records_unreadable_root() {
+        use chasm_cli::workspace::{find_workspace_dirs_reporting, DiscoveryIssue};
+
+        let temp_dir = TempDir::new().unwrap();
+        make_workspace(&temp_dir.path().join("hash1"));
+        let missing = temp_dir.path().join("missing");
+
+        let mut errors = Vec::new();
+        assert!(find_workspace_dirs_reporting(&missing, 0, &mut errors).is_empty());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, missing);
+        assert!(errors[0].skipped);
+        assert!(matches!(errors[0].issue, DiscoveryIssue::Io(_)));
+
+        errors.clear();
+        let dirs = find_workspace_dirs_reporting(temp_dir.path(), 1, &mut errors);
+        assert_eq!(dirs, vec![temp_dir.path().join("hash1")]);
+        assert!(errors.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_follow_symlinks_with_cycle_guard() {
+        use chasm_cli::workspace::find_workspace_dirs_with;
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("storage");
+        let external = temp_dir.path().join("external");
+        make_workspace(&root.join("hash1"));
+        make_workspace(&external.join("hash2"));
+        symlink(external.join("hash2"), root.join("hash2")).unwrap();
+        symlink(&external, root.join("drive")).unwrap();
+        // A link back to the storage root must not be walked again
+        symlink(&root, external.join("loop")).unwrap();
+
+        let mut errors = Vec::new();
+        let mut dirs = find_workspace_dirs_with(&root, 3, false, &mut errors);
+        dirs.sort();
+        // The linked workspace is listed, but drive/ isn't descended into
+        assert_eq!(dirs, vec![root.join("hash1"), root.join("hash2")]);
+
+        let dirs = find_workspace_dirs_with(&root, 3, true, &mut errors);
+        // hash2 is reachable twice (directly and via drive/) but listed once
+        assert_eq!(dirs.len(), 2);
+        assert!(dirs.contains(&root.join("hash1")));
+        assert_eq!(dirs.iter().filter(|d| d.ends_with("hash2")).count(), 1);
+        assert!(errors.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_workspace_listed_by_default() {
+        use chasm_cli::workspace::find_workspace_dirs_reporting;
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("storage");
+        let external = temp_dir.path().join("external");
+        make_workspace(&root.join("hash1"));
+        make_workspace(&external.join("hash2"));
+        symlink(external.join("hash2"), root.join("hash2")).unwrap();
+
+        let mut errors = Vec::new();
+        let mut dirs = find_workspace_dirs_reporting(&root, 0, &mut errors);
+        dirs.sort();
+        assert_eq!(dirs, vec![root.join("hash1"), root.join("hash2")]);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_storage_root_label() {
+        let root = PathBuf::from("/srv/profiles");
+
+        assert_eq!(storage_root_label(&root, &root.join("hash1")), None);
+        assert_eq!(
+            storage_root_label(&root, &root.join("alice").join("hash1")),
+            Some("alice".to_string())
+        );
+        assert_eq!(
+            storage_root_label(&root, &root.join("carol").join("nested").join("hash2")),
+            Some("carol/nested".to_string())
+        );
+    }
 }
 
 // ============================================================================
@@ -244,18 +506,18 @@ mod workspace_discovery_tests {
 // ============================================================================
 
 mod get_workspace_by_hash_tests {
-    use chasm_cli::workspace::get_workspace_by_hash;
+    use chasm_cli::workspace::{get_workspace_by_hash, DiscoveryOptions};
 
     #[test]
     fn test_get_workspace_nonexistent_hash() {
-        let result = get_workspace_by_hash("nonexistent_hash_12345");
+        let result = get_workspace_by_hash("nonexistent_hash_12345", &DiscoveryOptions::default());
         assert!(result.is_ok());
         assert!(result.unwrap().is_none());
     }
 
     #[test]
     fn test_get_workspace_empty_hash() {
-        let result = get_workspace_by_hash("");
+        let result = get_workspace_by_hash("", &DiscoveryOptions::default());
         assert!(result.is_ok());
         // Empty hash should not match any workspace
     }
@@ -263,7 +525,7 @@ mod get_workspace_by_hash_tests {
     #[test]
     fn test_get_workspace_partial_hash() {
         // Test that partial hashes can match (starts_with behavior)
-        let result = get_workspace_by_hash("a");
+        let result = get_workspace_by_hash("a", &DiscoveryOptions::default());
         assert!(result.is_ok());
         // May or may not find a workspace starting with 'a'
     }
@@ -274,24 +536,24 @@ mod get_workspace_by_hash_tests {
 // ============================================================================
 
 mod get_workspace_by_path_tests {
-    use chasm_cli::workspace::get_workspace_by_path;
+    use chasm_cli::workspace::{get_workspace_by_path, DiscoveryOptions};
 
     #[test]
     fn test_get_workspace_nonexistent_path() {
-        let result = get_workspace_by_path("/nonexistent/path/12345");
+        let result = get_workspace_by_path("/nonexistent/path/12345", &DiscoveryOptions::default());
         assert!(result.is_ok());
         assert!(result.unwrap().is_none());
     }
 
     #[test]
     fn test_get_workspace_empty_path() {
-        let result = get_workspace_by_path("");
+        let result = get_workspace_by_path("", &DiscoveryOptions::default());
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_get_workspace_relative_path() {
-        let result = get_workspace_by_path("relative/path");
+        let result = get_workspace_by_path("relative/path", &DiscoveryOptions::default());
         assert!(result.is_ok());
     }
 
@@ -305,7 +567,7 @@ mod get_workspace_by_path_tests {
     #[cfg(not(target_os = "windows"))]
     #[test]
     fn test_get_workspace_unix_path() {
-        let result = get_workspace_by_path("/home/user/project");
+        let result = get_workspace_by_path("/home/user/project", &DiscoveryOptions::default());
         assert!(result.is_ok());
     }
 }
@@ -315,18 +577,19 @@ mod get_workspace_by_path_tests {
 // ============================================================================
 
 mod find_workspace_by_path_tests {
-    use chasm_cli::workspace::find_workspace_by_path;
+    use chasm_cli::workspace::{find_workspace_by_path, DiscoveryOptions};
 
     #[test]
     fn test_find_workspace_nonexistent() {
-        let result = find_workspace_by_path("/definitely/nonexistent/path");
+        let result =
+            find_workspace_by_path("/definitely/nonexistent/path", &DiscoveryOptions::default());
         assert!(result.is_ok());
         assert!(result.unwrap().is_none());
     }
 
     #[test]
     fn test_find_workspace_returns_tuple() {
-        let result = find_workspace_by_path("/some/path");
+        let result = find_workspace_by_path("/some/path", &DiscoveryOptions::default());
         assert!(result.is_ok());
         // If found, should return (hash, path, project_path)
         if let Some((hash, dir, _project)) = result.unwrap() {
@@ -341,12 +604,15 @@ mod find_workspace_by_path_tests {
 // ============================================================================
 
 mod find_all_workspaces_for_project_tests {
-    use chasm_cli::workspace::find_all_workspaces_for_project;
+    use chasm_cli::workspace::{find_all_workspaces_for_project, DiscoveryOptions};
 
     #[test]
     fn test_find_all_workspaces_by_name() {
         // Search for a project name that probably doesn't exist
-        let result = find_all_workspaces_for_project("very_unique_project_name_12345");
+        let result = find_all_workspaces_for_project(
+            "very_unique_project_name_12345",
+            &DiscoveryOptions::default(),
+        );
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
     }
@@ -354,15 +620,15 @@ mod find_all_workspaces_for_project_tests {
     #[test]
     fn test_find_all_workspaces_empty_name() {
         // Empty string should match nothing or everything
-        let result = find_all_workspaces_for_project("");
+        let result = find_all_workspaces_for_project("", &DiscoveryOptions::default());
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_find_all_workspaces_case_insensitive() {
         // Search is case-insensitive
-        let result1 = find_all_workspaces_for_project("TEST");
-        let result2 = find_all_workspaces_for_project("test");
+        let result1 = find_all_workspaces_for_project("TEST", &DiscoveryOptions::default());
+        let result2 = find_all_workspaces_for_project("test", &DiscoveryOptions::default());
         assert!(result1.is_ok());
         assert!(result2.is_ok());
     }
@@ -372,6 +638,28 @@ mod find_all_workspaces_for_project_tests {
 // Get Chat Sessions From Workspace Tests
 // ============================================================================
 
+mod chat_sessions_size_tests {
+    use super::*;
+    use chasm_cli::workspace::chat_sessions_size;
+
+    #[test]
+    fn test_size_sums_every_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let chat_sessions = temp_dir.path().join("chatSessions");
+        fs::create_dir_all(chat_sessions.join("nested")).unwrap();
+        fs::write(chat_sessions.join("a.json"), vec![b'x'; 1000]).unwrap();
+        fs::write(chat_sessions.join("b.json.bak"), vec![b'x'; 24]).unwrap();
+
+        assert_eq!(chat_sessions_size(&chat_sessions), 1024);
+    }
+
+    #[test]
+    fn test_size_of_missing_dir_is_zero() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(chat_sessions_size(&temp_dir.path().join("chatSessions")), 0);
+    }
+}
+
 mod get_chat_sessions_from_workspace_tests {
     use super::*;
     use chasm_cli::workspace::get_chat_sessions_from_workspace;
@@ -429,6 +717,28 @@ mod get_chat_sessions_from_workspace_tests {
         assert!(result.unwrap().is_empty()); // Invalid files should be skipped
     }
 
+    #[test]
+    fn test_load_sessions_reports_skipped_files() {
+        use chasm_cli::workspace::load_chat_sessions_from_workspace;
+
+        let temp_dir = TempDir::new().unwrap();
+        let chat_sessions = temp_dir.path().join("chatSessions");
+        fs::create_dir(&chat_sessions).unwrap();
+        fs::write(
+            chat_sessions.join("good.json"),
+            r#"{"version": 3, "requests": []}"#,
+        )
+        .unwrap();
+        fs::write(chat_sessions.join("broken.json"), "not valid json").unwrap();
+
+        let (sessions, skipped) = load_chat_sessions_from_workspace(temp_dir.path());
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].path, chat_sessions.join("good.json"));
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].path, chat_sessions.join("broken.json"));
+        assert!(!skipped[0].reason.is_empty());
+    }
+
     #[test]
     fn test_get_sessions_with_mixed_files() {
         let temp_dir = TempDir::new().unwrap();
@@ -480,6 +790,61 @@ mod get_chat_sessions_from_workspace_tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 5);
     }
+
+    #[test]
+    fn test_list_session_files_matches_loaded_sessions() {
+        use chasm_cli::workspace::list_session_files;
+
+        let temp_dir = TempDir::new().unwrap();
+        let chat_sessions = temp_dir.path().join("chatSessions");
+        fs::create_dir(&chat_sessions).unwrap();
+
+        let session_json = r#"{
+            "version": 3,
+            "creationDate": 1700000000000,
+            "lastMessageDate": 1700000000000,
+            "requests": []
+        }"#;
+        fs::write(chat_sessions.join("b.json"), session_json).unwrap();
+        fs::write(chat_sessions.join("a.JSON"), session_json).unwrap();
+        // Leftover atomic-write temp file and unrelated files are not sessions
+        fs::write(chat_sessions.join(".b.json.1234.tmp"), "{").unwrap();
+        fs::write(chat_sessions.join(".hidden.json"), session_json).unwrap();
+        fs::write(chat_sessions.join("notes.txt"), "text").unwrap();
+
+        let files = list_session_files(&chat_sessions);
+        assert_eq!(
+            files,
+            vec![chat_sessions.join("a.JSON"), chat_sessions.join("b.json")]
+        );
+
+        let sessions = get_chat_sessions_from_workspace(temp_dir.path()).unwrap();
+        assert_eq!(sessions.len(), files.len());
+    }
+
+    #[test]
+    fn test_count_session_requests_ignores_nested_message_keys() {
+        use chasm_cli::commands::count_session_requests;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("session.json");
+        // Tool output embedding its own "message": keys must not inflate the count
+        let session_json = r#"{
+            "version": 3,
+            "requests": [
+                {"message": {"text": "first"}, "response": [{"message": "tool log"}]},
+                {"message": {"text": "second"}, "response": [{"message": "a"}, {"message": "b"}]}
+            ]
+        }"#;
+        fs::write(&path, session_json).unwrap();
+        assert_eq!(count_session_requests(&path).unwrap(), 2);
+
+        fs::write(&path, r#"{"version": 3, "requests": []}"#).unwrap();
+        assert_eq!(count_session_requests(&path).unwrap(), 0);
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(count_session_requests(&path).is_err());
+    }
 }
 
 // ============================================================================